@@ -14,7 +14,7 @@ const COLOURS: [&str; 2] = ["#000000FF", "#FFFFFFFF"];
 /// Example of loading a scene, camera, and assets from JSON files,
 /// and rendering a light map using ray tracing.
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let assets = SerializedAssets::<Precision>::load("./inputs/assets.json")?.build()?;
+    let assets = SerializedAssets::<Precision>::load_and_build("./inputs/assets.json")?;
     let scene = SerializedScene::<Precision>::load("./inputs/scene.json")?.build(&assets)?;
     let camera = SerializedCamera::load("./inputs/camera.json")?.build()?;
 
@@ -36,13 +36,13 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             let light_value = if let Some(hit) = scene.intersect(&ray)? {
                 // Calculate light contribution
                 let ambient = 0.1;
-                let hit_position = ray.origin + ray.direction.scale(hit.distance - 0.01);
+                let hit_position = ray.origin + ray.direction.scale(hit.distance);
                 let light_dir = Unit::new_normalize(sun - hit_position);
                 let diffuse = (hit.geometric_normal.dot(&light_dir)).max(0.0);
 
-                // Check for shadows
+                // Check for shadows, skipping the scene ray bias at each end of the shadow ray
                 let shadow_ray = Ray::new(hit_position, light_dir);
-                let shadow = if scene.intersect_any(&shadow_ray, 100.0)? {
+                let shadow = if scene.intersect_any(&shadow_ray, (sun - hit_position).norm())? {
                     0.0 // In shadow
                 } else {
                     1.0 // Not in shadow
@@ -7,19 +7,29 @@ fn main() -> Result<(), Box<dyn Error>> {
     let camera = SerializedCamera {
         projection: SerializedProjection::Perspective(90.0_f32), // camera projection mode
         position: [10.0, 10.0, 10.0],                            // view point
-        look_at: [0.0, 0.0, 3.0],                                // target point
+        look_at: Some([0.0, 0.0, 3.0]),                          // target point
+        orientation: None,                                       // or a [x, y, z, w] quaternion, fixing the roll
         resolution: [6000, 8000],                                // [height, width]
+        lens_shift: None,                                        // optional [vertical, horizontal] shift
+        tilt: None,                                              // optional perspective tilt (degrees)
+        metadata: None,                                          // optional shutter, exposure and clip hints
     };
     camera.save("./inputs/camera.json")?;
 
     // Assets
     let assets = SerializedAssets::<f32> {
         bvh_config: Some(BvhConfig::default()),
+        mesh_config: None,
         meshes: vec![
-            ("circle".to_string(), "./assets/meshes/circle.obj".into()),
-            ("cube".to_string(), "./assets/meshes/cube.obj".into()),
-            ("tree".to_string(), "./assets/meshes/tree.obj".into()),
+            (
+                "circle".to_string(),
+                SerializedMesh::File("./assets/meshes/circle.obj".into()),
+            ),
+            ("cube".to_string(), SerializedMesh::File("./assets/meshes/cube.obj".into())),
+            ("tree".to_string(), SerializedMesh::File("./assets/meshes/tree.obj".into())),
         ],
+        search_paths: vec![],
+        memory_budget: None,
     };
     assets.save("./inputs/assets.json")?;
 
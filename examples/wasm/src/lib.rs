@@ -0,0 +1,67 @@
+//! Example of tracing a `geodesic` scene in the browser.
+//!
+//! The scene files and any mesh files they reference are handed over from JavaScript as strings and bytes,
+//! and served to `geodesic` through a `MemorySource`, so nothing touches a filesystem.
+//! See `index.html` for the JavaScript side, and the README for build instructions.
+
+use geodesic::prelude::*;
+use wasm_bindgen::prelude::*;
+
+type Precision = f32;
+
+/// In-browser renderer of serialized scenes.
+#[wasm_bindgen]
+#[derive(Debug, Default)]
+pub struct Viewer {
+    /// Mesh files referenced by the assets, keyed by the path used in the assets JSON.
+    files: MemorySource,
+}
+
+#[wasm_bindgen]
+impl Viewer {
+    /// Construct a new `Viewer` with no files.
+    #[wasm_bindgen(constructor)]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a mesh file, under the path by which the assets JSON refers to it.
+    pub fn add_file(&mut self, path: &str, contents: Vec<u8>) {
+        let _previous = self.files.insert(path, contents);
+    }
+
+    /// Render the shading normals of a scene as RGBA pixels, in row-major order, ready for a canvas `ImageData`.
+    /// The image has the resolution of the camera, and pixels which miss the scene are transparent.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of the JSON is invalid, or the scene cannot be built or traced.
+    pub fn render(&self, assets: &str, scene: &str, camera: &str) -> Result<Vec<u8>, JsError> {
+        let assets = SerializedAssets::<Precision>::from_json(assets)?.build_from(&self.files)?;
+        let scene = SerializedScene::<Precision>::from_json(scene)?.build(&assets)?;
+        let camera = SerializedCamera::<Precision>::from_json(camera)?.build()?;
+
+        let buffer = GBuffer::render(&scene, &camera)?;
+        Ok(buffer
+            .samples()
+            .iter()
+            .flat_map(|sample| {
+                sample.as_ref().map_or([0; 4], |sample| {
+                    let [x, y, z] = sample.interpolated_normal.map(to_byte).into();
+                    [x, y, z, u8::MAX]
+                })
+            })
+            .collect())
+    }
+}
+
+/// Map a normal component from [-1, 1] to a colour channel byte.
+#[expect(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    reason = "The value is clamped to the range of a byte."
+)]
+fn to_byte(component: Precision) -> u8 {
+    (component.mul_add(0.5, 0.5).clamp(0.0, 1.0) * 255.0).round() as u8
+}
@@ -0,0 +1,308 @@
+//! # `geodesic-render`
+//!
+//! Render a serialized scene to a PNG image, without writing a Rust program first.
+//!
+//! ```text
+//! geodesic-render --assets assets.json --scene scene.json --camera camera.json --output image.png
+//!     [--mode shaded|depth|normals] [--samples N] [--seed S] [--threads N] [--light X,Y,Z] [--near N] [--far F]
+//! ```
+
+use flate2::{Compression, write::ZlibEncoder};
+use geodesic::prelude::*;
+use nalgebra::{Point3, Unit};
+use rayon::prelude::*;
+use std::{
+    env::args,
+    error::Error,
+    fs::write,
+    io::{Write, stderr},
+    process::ExitCode,
+    str::FromStr,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+type Precision = f32;
+
+const USAGE: &str = "Usage: geodesic-render --assets <FILE> --scene <FILE> --camera <FILE> --output <FILE.png>
+    [--mode shaded|depth|normals]  Image to render (default: shaded)
+    [--samples <N>]                Jittered samples per pixel of a shaded image (default: 1)
+    [--seed <S>]                   Seed of the jittered samples (default: 0)
+    [--threads <N>]                Number of render threads (default: one per core)
+    [--light <X,Y,Z>]              Position of the point light of a shaded image (default: the camera position)
+    [--near <N>] [--far <F>]       Depth range of a depth image (default: 0.1 to 100)";
+
+/// Ambient light level of a shaded image, so surfaces in shadow are still visible.
+const AMBIENT: Precision = 0.1;
+
+/// Image rendered by the tool.
+#[derive(Debug, Clone, Copy)]
+enum Mode {
+    /// Diffuse shading from a point light, with hard shadows.
+    Shaded,
+    /// Linear depth along the camera's forward axis.
+    Depth,
+    /// Shading normals, mapped from [-1, 1] to colour channels.
+    Normals,
+}
+
+/// Command line settings.
+#[derive(Debug)]
+struct Settings {
+    /// Path of the `SerializedAssets` file.
+    assets: String,
+    /// Path of the `SerializedScene` file.
+    scene: String,
+    /// Path of the `SerializedCamera` file.
+    camera: String,
+    /// Path of the image to write.
+    output: String,
+    /// Image to render.
+    mode: Mode,
+    /// Jittered samples per pixel of a shaded image.
+    samples: u16,
+    /// Seed of the jittered samples.
+    seed: u64,
+    /// Threads on which to render a shaded image.
+    threads: RenderThreads,
+    /// Position of the point light, if not at the camera.
+    light: Option<Point3<Precision>>,
+    /// Near clipping distance of a depth image.
+    near: Precision,
+    /// Far clipping distance of a depth image.
+    far: Precision,
+}
+
+fn main() -> ExitCode {
+    let settings = match Settings::parse(args().skip(1)) {
+        Ok(settings) => settings,
+        Err(message) => {
+            eprintln!("{message}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+    match render(&settings) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("Error: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+impl Settings {
+    /// Parse the command line arguments, following the program name.
+    fn parse<I: Iterator<Item = String>>(mut args: I) -> Result<Self, String> {
+        let (mut assets, mut scene, mut camera, mut output) = (None, None, None, None);
+        let mut settings = Self {
+            assets: String::new(),
+            scene: String::new(),
+            camera: String::new(),
+            output: String::new(),
+            mode: Mode::Shaded,
+            samples: 1,
+            seed: 0,
+            threads: RenderThreads::Current,
+            light: None,
+            near: 0.1,
+            far: 100.0,
+        };
+
+        while let Some(flag) = args.next() {
+            let value = args.next().ok_or_else(|| format!("Missing value for {flag}"))?;
+            match flag.as_str() {
+                "--assets" => assets = Some(value),
+                "--scene" => scene = Some(value),
+                "--camera" => camera = Some(value),
+                "--output" => output = Some(value),
+                "--mode" => {
+                    settings.mode = match value.as_str() {
+                        "shaded" => Mode::Shaded,
+                        "depth" => Mode::Depth,
+                        "normals" => Mode::Normals,
+                        _ => return Err(format!("Unknown mode: {value}")),
+                    };
+                }
+                "--samples" => settings.samples = parse_value(&flag, &value)?,
+                "--seed" => settings.seed = parse_value(&flag, &value)?,
+                "--threads" => settings.threads = RenderThreads::Count(parse_value(&flag, &value)?),
+                "--light" => {
+                    let coords = value
+                        .split(',')
+                        .map(|coord| parse_value(&flag, coord.trim()))
+                        .collect::<Result<Vec<Precision>, _>>()?;
+                    let [x, y, z] = coords[..] else {
+                        return Err(format!("Expected three comma-separated coordinates for {flag}"));
+                    };
+                    settings.light = Some(Point3::new(x, y, z));
+                }
+                "--near" => settings.near = parse_value(&flag, &value)?,
+                "--far" => settings.far = parse_value(&flag, &value)?,
+                _ => return Err(format!("Unknown option: {flag}")),
+            }
+        }
+        if settings.samples == 0 {
+            return Err("--samples must be at least one".to_string());
+        }
+
+        settings.assets = assets.ok_or("Missing --assets")?;
+        settings.scene = scene.ok_or("Missing --scene")?;
+        settings.camera = camera.ok_or("Missing --camera")?;
+        settings.output = output.ok_or("Missing --output")?;
+        Ok(settings)
+    }
+}
+
+/// Load the serialized scene, render the requested image, and write it out.
+fn render(settings: &Settings) -> Result<(), Box<dyn Error>> {
+    eprintln!("Loading {}", settings.assets);
+    let assets = SerializedAssets::<Precision>::load_and_build(&settings.assets)?;
+    eprintln!("Loading {}", settings.scene);
+    let scene = SerializedScene::<Precision>::load(&settings.scene)?.build(&assets)?;
+    let camera = SerializedCamera::<Precision>::load(&settings.camera)?.build()?;
+    let [height, width] = *camera.resolution();
+    eprintln!("Rendering {width}x{height} pixels");
+
+    let (channels, pixels) = match settings.mode {
+        Mode::Shaded => {
+            let light = settings.light.unwrap_or_else(|| *camera.position());
+            let pixels = settings
+                .threads
+                .install(|| shade(&scene, &camera, &light, settings.samples, settings.seed))??;
+            (1, pixels)
+        }
+        Mode::Depth => {
+            let depth = DepthMap::render(&scene, &camera, settings.near, settings.far, DepthEncoding::Linear)?;
+            (1, depth.values().iter().map(|&depth| to_byte(1.0 - depth)).collect())
+        }
+        Mode::Normals => {
+            let buffer = GBuffer::render(&scene, &camera)?;
+            let pixels = buffer
+                .samples()
+                .iter()
+                .flat_map(|sample| {
+                    sample.as_ref().map_or([0; 3], |sample| {
+                        sample.interpolated_normal.map(|n| to_byte(n.mul_add(0.5, 0.5))).into()
+                    })
+                })
+                .collect();
+            (3, pixels)
+        }
+    };
+
+    write(&settings.output, encode_png(width, height, channels, &pixels)?)?;
+    eprintln!("Wrote {}", settings.output);
+    Ok(())
+}
+
+/// Shade every pixel by the diffuse light from a point light, averaged over jittered samples, reporting progress.
+fn shade(
+    scene: &Scene<'_, Precision>,
+    camera: &Camera<Precision>,
+    light: &Point3<Precision>,
+    samples: u16,
+    seed: u64,
+) -> Result<Vec<u8>, GeodesicError> {
+    let [height, width] = *camera.resolution();
+    let rows_done = AtomicUsize::new(0);
+    let rows = (0..height)
+        .into_par_iter()
+        .map(|row| {
+            let values = (0..width)
+                .map(|col| {
+                    let mut total = 0.0;
+                    for sample in 0..usize::from(samples) {
+                        let mut rng = Pcg32::for_sample(seed, [row, col], sample);
+                        let ray = camera.generate_jittered_ray([row, col], &mut rng)?;
+                        total += radiance(scene, &ray, light)?;
+                    }
+                    Ok(to_byte(total / Precision::from(samples)))
+                })
+                .collect::<Result<Vec<_>, GeodesicError>>()?;
+
+            // Only report when the whole percentage changes, to keep the output readable for tall images
+            let done = rows_done.fetch_add(1, Ordering::Relaxed) + 1;
+            if (done * 100) / height != ((done - 1) * 100) / height {
+                eprint!("\rRendered {}%", (done * 100) / height);
+                let _flushed = stderr().flush();
+            }
+            Ok(values)
+        })
+        .collect::<Result<Vec<_>, GeodesicError>>()?;
+    eprintln!();
+    Ok(rows.concat())
+}
+
+/// Light arriving along a camera `Ray`: ambient light, plus diffuse light from the point light if it is visible.
+fn radiance(scene: &Scene<'_, Precision>, ray: &Ray<Precision>, light: &Point3<Precision>) -> Result<Precision, GeodesicError> {
+    let Some(hit) = scene.intersect(ray)? else {
+        return Ok(0.0);
+    };
+    let position = ray.at(hit.distance);
+    let offset = light - position;
+    let direction = Unit::new_normalize(offset);
+    let normal = if hit.interpolated_normal.dot(&ray.direction) > 0.0 {
+        -hit.interpolated_normal
+    } else {
+        hit.interpolated_normal
+    };
+    let diffuse = normal.dot(&direction).max(0.0);
+    if diffuse <= 0.0 || scene.intersect_any(&Ray::new(position, direction), offset.norm())? {
+        return Ok(AMBIENT);
+    }
+    Ok((1.0 - AMBIENT).mul_add(diffuse, AMBIENT))
+}
+
+// == Utility functions ==
+
+/// Parse the value of an option.
+fn parse_value<V: FromStr>(flag: &str, value: &str) -> Result<V, String> {
+    value.parse().map_err(|_| format!("Invalid value for {flag}: {value}"))
+}
+
+/// Convert a value in the unit range to a byte, clamping values outside it.
+fn to_byte(value: Precision) -> u8 {
+    #[expect(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        reason = "The value is clamped to the range of a byte."
+    )]
+    let byte = (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+    byte
+}
+
+/// Encode 8-bit greyscale (one channel) or RGB (three channel) pixels, in row-major order, as a PNG file.
+fn encode_png(width: usize, height: usize, channels: usize, pixels: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
+    let colour_type = if channels == 3 { 2 } else { 0 };
+    let mut header = Vec::with_capacity(13);
+    header.extend_from_slice(&u32::try_from(width)?.to_be_bytes());
+    header.extend_from_slice(&u32::try_from(height)?.to_be_bytes());
+    header.extend_from_slice(&[8, colour_type, 0, 0, 0]);
+
+    // Each scanline is preceded by its filter type, which is always none
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    for row in pixels.chunks(width * channels) {
+        encoder.write_all(&[0])?;
+        encoder.write_all(row)?;
+    }
+    let data = encoder.finish()?;
+
+    let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+    for (kind, contents) in [(b"IHDR", header.as_slice()), (b"IDAT", &data), (b"IEND", &[])] {
+        png.extend_from_slice(&u32::try_from(contents.len())?.to_be_bytes());
+        let start = png.len();
+        png.extend_from_slice(kind);
+        png.extend_from_slice(contents);
+        let checksum = crc32(&png[start..]);
+        png.extend_from_slice(&checksum.to_be_bytes());
+    }
+    Ok(png)
+}
+
+/// CRC-32 checksum of a PNG chunk, as specified by ISO 3309.
+fn crc32(bytes: &[u8]) -> u32 {
+    !bytes.iter().fold(u32::MAX, |crc, &byte| {
+        (0..8).fold(crc ^ u32::from(byte), |crc, _| {
+            if crc & 1 == 1 { (crc >> 1) ^ 0xEDB8_8320 } else { crc >> 1 }
+        })
+    })
+}
@@ -1,51 +1,131 @@
 //! Bounding Volume Hierarchy node structure with Surface Area Heuristic.
 
-use nalgebra::RealField;
-use num_traits::ToPrimitive;
-use std::borrow::Cow;
+use serde::{Deserialize, Serialize};
+use std::{marker::PhantomData, ops::Range};
 
 use crate::{
     bvh::{BvhBuilder, BvhConfig},
-    error::{BvhError, Result},
+    error::{BvhError, GeometryError, Result},
     geometry::Aabb,
     rt::{Hit, Ray},
-    traits::{Bounded, FallibleNumeric, Traceable},
+    scene::MemoryUsage,
+    traits::{Bounded, BoundingVolume, Bounds, FallibleNumeric, Scalar, Traceable},
 };
 
-/// Bounding volume hierarchy node.
-#[derive(Debug, Clone)]
-pub struct BvhNode<T: RealField + Copy> {
-    /// Bounding box.
-    pub aabb: Aabb<T>,
+/// Bounding volume hierarchy node, bounded by an `Aabb` unless another `BoundingVolume` is chosen.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BvhNode<T: Scalar, V = Aabb<T>> {
+    /// Bounding volume.
+    #[serde(alias = "aabb")]
+    pub volume: V,
     /// Left child node index. Right child node index is `left_child + 1`.
     pub left_child: usize,
     /// Number of objects contained in this node.
     pub count: usize,
+    /// Scalar type of the bounding volume.
+    #[serde(skip)]
+    scalar: PhantomData<T>,
+}
+
+impl<T: Scalar> BvhNode<T, Aabb<T>> {
+    /// Get the `Aabb` bounding the node, which the `volume` field holds for `Aabb`-bounded hierarchies.
+    #[must_use]
+    pub const fn aabb(&self) -> &Aabb<T> {
+        &self.volume
+    }
+}
+
+impl<T: Scalar, V> BvhNode<T, V> {
+    /// Construct a new `BvhNode` instance.
+    /// A `count` of zero marks an internal node, whose children are at `left_child` and `left_child + 1`;
+    /// otherwise the node is a leaf, holding the shapes in the `count` slots from `left_child`.
+    pub const fn new(volume: V, left_child: usize, count: usize) -> Self {
+        Self {
+            volume,
+            left_child,
+            count,
+            scalar: PhantomData,
+        }
+    }
+
+    /// Check if the node is a leaf, holding shapes rather than child nodes.
+    #[must_use]
+    pub const fn is_leaf(&self) -> bool {
+        self.count > 0
+    }
+
+    /// Get the indices of the left and right child nodes, or `None` for a leaf.
+    #[must_use]
+    pub const fn children(&self) -> Option<[usize; 2]> {
+        if self.is_leaf() {
+            None
+        } else {
+            Some([self.left_child, self.left_child + 1])
+        }
+    }
+
+    /// Get the range of leaf slots (positions in the `Bvh` indices) holding the shapes of the node.
+    /// The range is empty for an internal node.
+    #[must_use]
+    pub const fn slots(&self) -> Range<usize> {
+        if self.is_leaf() {
+            self.left_child..self.left_child + self.count
+        } else {
+            0..0
+        }
+    }
 }
 
 /// Bounding Volume Hierarchy (BVH) structure used to accelerate ray tracing by reducing the number of intersection tests required.
-#[derive(Debug)]
-pub struct Bvh<T: RealField + Copy> {
+///
+/// Nodes are bounded by `Aabb`s by default. Another `BoundingVolume`, such as a `Kdop`,
+/// can be fitted to the topology of an `Aabb` hierarchy with `from_bvh`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Bvh<T: Scalar, V = Aabb<T>> {
     /// Indices of objects contained in this node.
     indices: Vec<usize>,
     /// List of nodes.
-    nodes: Vec<BvhNode<T>>,
+    nodes: Vec<BvhNode<T, V>>,
     /// Depth of the tree.
     depth: usize,
 }
 
-impl<T: RealField + Copy + ToPrimitive> Bvh<T> {
+impl<T: Scalar> Bvh<T> {
     /// Construct a new `Bvh` instance using a builder and a collection of `Bounded` shapes.
+    /// The same shapes and `BvhConfig` always build a bit-identical `Bvh`, so cached hierarchies stay valid.
+    /// Unbounded shapes are left out of the hierarchy, and must be tested separately, as a `Scene` does.
     ///
     /// # Errors
     ///
     /// Returns an error if:
-    /// - The shapes slice is empty
+    /// - The shapes slice contains no finite shapes
     /// - BVH construction fails due to invalid configuration
     /// - Mathematical operations fail during construction
     pub fn new<B: Bounded<T>>(config: &BvhConfig<T>, shapes: &[B]) -> Result<Self> {
         BvhBuilder::new(config).build(shapes)
     }
+}
+
+impl<T: Scalar, V: BoundingVolume<T>> Bvh<T, V> {
+    /// Construct a `Bvh` with the same topology as an `Aabb` hierarchy, fitting a bounding volume to every node.
+    /// The `Aabb` hierarchy is typically built for the same shapes with `Bvh::new`, which chooses splits by their boxes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - A shape referenced by the `Bvh` is missing from the shapes slice, or is unbounded
+    /// - A bounding volume cannot be computed
+    pub fn from_bvh<B: Bounded<T>>(bvh: &Bvh<T>, shapes: &[B]) -> Result<Self> {
+        let (indices, nodes, depth) = bvh.clone().into_parts();
+        let placeholder = V::enclose(&nodes[0].volume)?.ok_or(BvhError::MismatchedGeometry)?;
+        let nodes = nodes
+            .into_iter()
+            .map(|node| BvhNode::new(placeholder.clone(), node.left_child, node.count))
+            .collect();
+        let mut fitted = Self { indices, nodes, depth };
+        fitted.refit(shapes)?;
+        Ok(fitted)
+    }
 
     /// Construct a new `Bvh` instance directly.
     ///
@@ -58,7 +138,7 @@ impl<T: RealField + Copy + ToPrimitive> Bvh<T> {
     /// # Panics
     ///
     /// Panics if `indices` or `nodes` are empty.
-    pub fn construct_directly(indices: Vec<usize>, nodes: Vec<BvhNode<T>>, depth: usize) -> Result<Self> {
+    pub fn construct_directly(indices: Vec<usize>, nodes: Vec<BvhNode<T, V>>, depth: usize) -> Result<Self> {
         if indices.is_empty() {
             return Err(BvhError::EmptyGeometry.into());
         }
@@ -68,6 +148,240 @@ impl<T: RealField + Copy + ToPrimitive> Bvh<T> {
         Ok(Self { indices, nodes, depth })
     }
 
+    /// Decompose the `Bvh` into its shape indices, nodes and depth, as accepted by `construct_directly`.
+    #[must_use]
+    pub fn into_parts(self) -> (Vec<usize>, Vec<BvhNode<T, V>>, usize) {
+        (self.indices, self.nodes, self.depth)
+    }
+
+    /// Check if the `Bvh` correctly bounds a collection of `Bounded` shapes.
+    /// Every finite shape must be referenced by exactly one reachable leaf, unbounded shapes by none,
+    /// and every node must enclose its contents.
+    /// Used to validate a previously saved `Bvh` before reuse.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bounding box of any shape cannot be computed.
+    pub fn fits<B: Bounded<T>>(&self, shapes: &[B]) -> Result<bool> {
+        if self.nodes.is_empty() || self.indices.len() > shapes.len() {
+            return Ok(false);
+        }
+
+        let mut referenced = vec![false; shapes.len()];
+        let mut stack = vec![0];
+        while let Some(node_index) = stack.pop() {
+            let node = &self.nodes[node_index];
+            if node.count > 0 {
+                let Some(slots) = self.indices.get(node.left_child..node.left_child + node.count) else {
+                    return Ok(false);
+                };
+                for &shape_index in slots {
+                    if shape_index >= shapes.len() || referenced[shape_index] {
+                        return Ok(false);
+                    }
+                    let Some(volume) = V::enclose(&shapes[shape_index])? else {
+                        return Ok(false);
+                    };
+                    if !node.volume.contains(&volume) {
+                        return Ok(false);
+                    }
+                    referenced[shape_index] = true;
+                }
+            } else {
+                // Children must come after their parent, which also rules out cycles
+                let (left, right) = (node.left_child, node.left_child + 1);
+                if left <= node_index || right >= self.nodes.len() {
+                    return Ok(false);
+                }
+                if !node.volume.contains(&self.nodes[left].volume) || !node.volume.contains(&self.nodes[right].volume) {
+                    return Ok(false);
+                }
+                stack.extend([left, right]);
+            }
+        }
+
+        for (shape, seen) in shapes.iter().zip(referenced) {
+            if !seen && shape.aabb()?.is_finite() {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// Recompute the bounds of every node for a collection of `Bounded` shapes, keeping the tree topology.
+    /// Much cheaper than rebuilding when shapes have moved, at the cost of possibly looser bounds.
+    /// The shapes must be in the same order as those the `Bvh` was built for.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - A shape referenced by the `Bvh` is missing from the shapes slice
+    /// - A shape referenced by the `Bvh` has become unbounded
+    /// - The bounding box of any shape cannot be computed
+    pub fn refit<B: Bounded<T>>(&mut self, shapes: &[B]) -> Result<()> {
+        if self.indices.iter().any(|&shape_index| shape_index >= shapes.len()) {
+            return Err(BvhError::MismatchedGeometry.into());
+        }
+
+        // Children are always visited after their parent, so refit in reverse visiting order
+        let mut order = Vec::with_capacity(self.nodes.len());
+        let mut stack = vec![0];
+        while let Some(node_index) = stack.pop() {
+            order.push(node_index);
+            let node = &self.nodes[node_index];
+            if node.count == 0 {
+                stack.extend([node.left_child, node.left_child + 1]);
+            }
+        }
+
+        for node_index in order.into_iter().rev() {
+            let node = &self.nodes[node_index];
+            let volume = if node.count > 0 {
+                let slots = &self.indices[node.left_child..node.left_child + node.count];
+                let mut volume = enclose_finite::<T, V, B>(&shapes[slots[0]])?;
+                for &shape_index in &slots[1..] {
+                    volume = volume.merge(&enclose_finite(&shapes[shape_index])?)?;
+                }
+                volume
+            } else {
+                self.nodes[node.left_child]
+                    .volume
+                    .merge(&self.nodes[node.left_child + 1].volume)?
+            };
+            self.nodes[node_index].volume = volume;
+        }
+        Ok(())
+    }
+
+    /// Collapse subtrees which the Surface Area Heuristic expects to be slower to traverse than a single leaf,
+    /// along with subtrees holding no more than `max_shapes_per_node` shapes, such as tiny sibling leaves.
+    /// A greedy top-down build only compares each split with the leaf it replaces, so it cannot undo
+    /// splits which only pay off deeper down; this pass costs each subtree bottom-up and can.
+    /// Subtrees are only collapsed when their leaf slots are contiguous, as they always are in a built `Bvh`.
+    /// The remaining nodes are renumbered depth first, so the shape indices are left untouched.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The surface area of a node cannot be computed
+    /// - Mathematical operations fail when costing the tree
+    pub fn collapse(&mut self, config: &BvhConfig<T>) -> Result<()> {
+        // Children are always visited after their parent, so cost the tree in reverse visiting order
+        let mut order = Vec::with_capacity(self.nodes.len());
+        let mut stack = vec![0];
+        while let Some(node_index) = stack.pop() {
+            order.push(node_index);
+            if let Some(children) = self.nodes[node_index].children() {
+                stack.extend(children);
+            }
+        }
+
+        // Expected cost of each subtree, and its range of leaf slots if they are contiguous
+        let mut costs = vec![T::zero(); self.nodes.len()];
+        let mut spans: Vec<Option<(usize, usize)>> = vec![None; self.nodes.len()];
+        for node_index in order.into_iter().rev() {
+            let node = &self.nodes[node_index];
+            let Some([left, right]) = node.children() else {
+                costs[node_index] = T::try_from_usize(node.count)? * config.intersect_cost;
+                spans[node_index] = Some((node.left_child, node.count));
+                continue;
+            };
+
+            let area = node.volume.bounding_box()?.surface_area()?;
+            let left_area = self.nodes[left].volume.bounding_box()?.surface_area()?;
+            let right_area = self.nodes[right].volume.bounding_box()?.surface_area()?;
+            let split_cost = if area > T::zero() {
+                config.traverse_cost + (left_area * costs[left] + right_area * costs[right]) / area
+            } else {
+                config.traverse_cost + costs[left] + costs[right]
+            };
+
+            let span = match (spans[left], spans[right]) {
+                (Some((left_start, left_count)), Some((right_start, right_count)))
+                    if left_start + left_count == right_start || right_start + right_count == left_start =>
+                {
+                    Some((left_start.min(right_start), left_count + right_count))
+                }
+                _ => None,
+            };
+            spans[node_index] = span;
+            costs[node_index] = split_cost;
+
+            if let Some((start, count)) = span {
+                let leaf_cost = T::try_from_usize(count)? * config.intersect_cost;
+                if leaf_cost <= split_cost || count <= config.max_shapes_per_node {
+                    let node = &mut self.nodes[node_index];
+                    node.left_child = start;
+                    node.count = count;
+                    costs[node_index] = leaf_cost;
+                }
+            }
+        }
+
+        // Renumber the reachable nodes depth first, keeping each pair of children adjacent
+        let mut nodes = Vec::with_capacity(self.nodes.len());
+        nodes.push(self.nodes[0].clone());
+        let mut depth = 0;
+        let mut stack = vec![(0, 0, 0)];
+        while let Some((old_index, new_index, node_depth)) = stack.pop() {
+            depth = depth.max(node_depth);
+            if let Some([left, right]) = self.nodes[old_index].children() {
+                let first = nodes.len();
+                nodes.extend([self.nodes[left].clone(), self.nodes[right].clone()]);
+                nodes[new_index].left_child = first;
+                stack.extend([(right, first + 1, node_depth + 1), (left, first, node_depth + 1)]);
+            }
+        }
+        nodes.shrink_to_fit();
+        self.nodes = nodes;
+        self.depth = depth;
+        Ok(())
+    }
+
+    /// Get the flattened nodes of the `Bvh`, starting with the root.
+    #[must_use]
+    pub fn nodes(&self) -> &[BvhNode<T, V>] {
+        &self.nodes
+    }
+
+    /// Get the shape indices referenced by the leaf nodes.
+    #[must_use]
+    pub fn indices(&self) -> &[usize] {
+        &self.indices
+    }
+
+    /// Get the number of bytes held by the nodes and shape indices of the `Bvh`.
+    #[must_use]
+    pub const fn memory_usage(&self) -> MemoryUsage {
+        MemoryUsage {
+            triangle_bytes: 0,
+            node_bytes: self.nodes.len() * size_of::<BvhNode<T, V>>(),
+            index_bytes: self.indices.len() * size_of::<usize>(),
+            other_bytes: 0,
+        }
+    }
+
+    /// Get the indices of the shapes held by a node, which are empty for an internal node.
+    #[must_use]
+    pub fn leaf_shapes(&self, node: &BvhNode<T, V>) -> &[usize] {
+        &self.indices[node.slots()]
+    }
+
+    /// Walk the nodes of the `Bvh` depth first, calling `visitor` with each node and its depth (zero at the root).
+    /// Children are visited left before right, and only if `visitor` returns `true` for their parent,
+    /// so custom queries can prune the walk as the built-in traversals do.
+    pub fn visit<F: FnMut(&BvhNode<T, V>, usize) -> bool>(&self, mut visitor: F) {
+        let mut stack = vec![(0, 0)];
+        while let Some((node_index, depth)) = stack.pop() {
+            let node = &self.nodes[node_index];
+            if visitor(node, depth)
+                && let Some([left, right]) = node.children()
+            {
+                stack.extend([(right, depth + 1), (left, depth + 1)]);
+            }
+        }
+    }
+
     /// Get the depth of the `Bvh` tree.
     #[must_use]
     pub const fn depth(&self) -> usize {
@@ -87,7 +401,102 @@ impl<T: RealField + Copy + ToPrimitive> Bvh<T> {
     where
         B: Bounded<T> + Traceable<T>,
     {
-        self.intersect_recursive(ray, shapes, 0)
+        self.intersect_within(ray, shapes, T::try_max_value()?)
+    }
+
+    /// Test for intersections between a `Ray` and geometries in the `Bvh` nearer than `max_distance`.
+    /// Returns the closest intersection within the bound if any.
+    /// Nodes lying beyond the bound are skipped, and the bound is passed on to the shapes,
+    /// so nested hierarchies are pruned too.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - Ray-shape intersection tests fail
+    /// - Mathematical operations fail during traversal
+    /// - Invalid node indices are encountered
+    pub fn intersect_within<B>(&self, ray: &Ray<T>, shapes: &[B], max_distance: T) -> Result<Option<(usize, Hit<T>)>>
+    where
+        B: Bounded<T> + Traceable<T>,
+    {
+        if !ray.is_finite() {
+            return Ok(None);
+        }
+        self.intersect_recursive(ray, shapes, 0, max_distance)
+    }
+
+    /// Test for intersections between a `Ray` and geometries in the `Bvh`.
+    /// Returns the distance to the closest intersection if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - Ray-shape intersection tests fail
+    /// - Mathematical operations fail during traversal
+    /// - Invalid node indices are encountered
+    pub fn intersect_t<B>(&self, ray: &Ray<T>, shapes: &[B]) -> Result<Option<T>>
+    where
+        B: Bounded<T> + Traceable<T>,
+    {
+        Ok(self
+            .intersect_slots(ray, |slot| shapes[self.indices[slot]].intersect_t(ray))?
+            .map(|(_, distance)| distance))
+    }
+
+    /// Test for intersections between a `Ray` and the leaf slots of the `Bvh`.
+    /// The `test` closure returns the distance to the shape held in a slot (a position in `indices`), if it is hit.
+    /// Leaf slots are contiguous, so shape data stored in slot order is read sequentially.
+    /// Returns the slot and distance of the closest intersection if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The `test` closure fails
+    /// - Mathematical operations fail during traversal
+    pub fn intersect_slots<F>(&self, ray: &Ray<T>, test: F) -> Result<Option<(usize, T)>>
+    where
+        F: Fn(usize) -> Result<Option<T>>,
+    {
+        self.intersect_slots_within(ray, T::try_max_value()?, test)
+    }
+
+    /// Test for intersections between a `Ray` and the leaf slots of the `Bvh` nearer than `max_distance`,
+    /// as in `intersect_slots`. Nodes lying beyond the bound are skipped.
+    /// Returns the slot and distance of the closest intersection within the bound if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The `test` closure fails
+    /// - Mathematical operations fail during traversal
+    pub fn intersect_slots_within<F>(&self, ray: &Ray<T>, max_distance: T, test: F) -> Result<Option<(usize, T)>>
+    where
+        F: Fn(usize) -> Result<Option<T>>,
+    {
+        if !ray.is_finite() {
+            return Ok(None);
+        }
+        self.intersect_slots_recursive(ray, &test, 0, max_distance)
+    }
+
+    /// Sweep a sphere of `radius` along a `Ray` through the geometries in the `Bvh`.
+    /// Node bounds are inflated by `radius`, so only nodes the sphere can touch are visited.
+    /// Returns the first contact if any, along with the index of the shape which was touched.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - `radius` is negative
+    /// - Sphere casts against the shapes fail or are unsupported
+    /// - Mathematical operations fail during traversal
+    pub fn sphere_cast<B>(&self, ray: &Ray<T>, radius: T, shapes: &[B]) -> Result<Option<(usize, Hit<T>)>>
+    where
+        B: Bounded<T> + Traceable<T>,
+    {
+        if !ray.is_finite() {
+            return Ok(None);
+        }
+        self.sphere_cast_recursive(ray, radius, shapes, 0, T::try_max_value()?)
     }
 
     /// Test if a `Ray` intersects any geometry in the `Bvh` (shadow ray optimization).
@@ -102,11 +511,102 @@ impl<T: RealField + Copy + ToPrimitive> Bvh<T> {
     where
         B: Bounded<T> + Traceable<T>,
     {
-        self.intersect_any_recursive(ray, shapes, 0, max_distance)
+        self.intersect_any_slots(ray, max_distance, |slot| {
+            shapes[self.indices[slot]].intersect_any(ray, max_distance)
+        })
     }
 
-    /// Recursive helper for `Bvh` traversal.
-    fn intersect_recursive<B>(&self, ray: &Ray<T>, shapes: &[B], node_index: usize) -> Result<Option<(usize, Hit<T>)>>
+    /// Test if a `Ray` intersects any leaf slot of the `Bvh` within `max_distance` (shadow ray optimization).
+    /// The `test` closure reports whether the shape held in a slot (a position in `indices`) is hit within `max_distance`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The `test` closure fails
+    /// - Mathematical operations fail during traversal
+    pub fn intersect_any_slots<F>(&self, ray: &Ray<T>, max_distance: T, test: F) -> Result<bool>
+    where
+        F: Fn(usize) -> Result<bool>,
+    {
+        if !ray.is_finite() {
+            return Ok(false);
+        }
+        self.intersect_any_recursive(ray, &test, 0, max_distance)
+    }
+
+    /// Find every pair of geometries in the `Bvh` whose bounding volumes intersect or touch (broadphase collision).
+    /// The `Bvh` is descended against itself, so only overlapping nodes are compared.
+    /// Returns pairs of shape indices, each with the lower index first, in ascending order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if bounding box calculations fail.
+    pub fn overlapping_pairs<B: Bounded<T>>(&self, shapes: &[B]) -> Result<Vec<(usize, usize)>> {
+        let volumes = shapes.iter().map(V::enclose).collect::<Result<Vec<_>>>()?;
+        let mut pairs = Vec::new();
+        let mut record = |index: usize, other_index: usize| {
+            if let (Some(volume), Some(other_volume)) = (&volumes[index], &volumes[other_index])
+                && volume.overlaps(other_volume)
+            {
+                pairs.push((index.min(other_index), index.max(other_index)));
+            }
+        };
+
+        // Each entry is a pair of nodes, or a node paired with itself to compare the shapes within it
+        let mut stack = vec![(0, 0)];
+        while let Some((a, b)) = stack.pop() {
+            let (node, other) = (&self.nodes[a], &self.nodes[b]);
+            if a == b {
+                if node.count > 0 {
+                    let slots = &self.indices[node.left_child..node.left_child + node.count];
+                    for (n, &index) in slots.iter().enumerate() {
+                        for &other_index in &slots[n + 1..] {
+                            record(index, other_index);
+                        }
+                    }
+                } else {
+                    let (left, right) = (node.left_child, node.left_child + 1);
+                    stack.extend([(left, left), (right, right), (left, right)]);
+                }
+                continue;
+            }
+
+            if !node.volume.overlaps(&other.volume) {
+                continue;
+            }
+            match (node.count > 0, other.count > 0) {
+                (true, true) => {
+                    for &index in &self.indices[node.left_child..node.left_child + node.count] {
+                        for &other_index in &self.indices[other.left_child..other.left_child + other.count] {
+                            record(index, other_index);
+                        }
+                    }
+                }
+                (false, true) => stack.extend([(node.left_child, b), (node.left_child + 1, b)]),
+                (true, false) => stack.extend([(a, other.left_child), (a, other.left_child + 1)]),
+                // Descend into the larger node, so the boxes being compared stay similar in size
+                (false, false) => {
+                    if node.volume.bounding_box()?.surface_area()? >= other.volume.bounding_box()?.surface_area()? {
+                        stack.extend([(node.left_child, b), (node.left_child + 1, b)]);
+                    } else {
+                        stack.extend([(a, other.left_child), (a, other.left_child + 1)]);
+                    }
+                }
+            }
+        }
+
+        pairs.sort_unstable();
+        Ok(pairs)
+    }
+
+    /// Recursive helper for `Bvh` traversal, bounded by `max_distance`.
+    fn intersect_recursive<B>(
+        &self,
+        ray: &Ray<T>,
+        shapes: &[B],
+        node_index: usize,
+        max_distance: T,
+    ) -> Result<Option<(usize, Hit<T>)>>
     where
         B: Bounded<T> + Traceable<T>,
     {
@@ -116,54 +616,145 @@ impl<T: RealField + Copy + ToPrimitive> Bvh<T> {
 
         let node = &self.nodes[node_index];
 
-        // Test ray against node's bounding box
-        if !node.aabb.intersect_any(ray)? {
+        // Test ray against node's bounding volume
+        if node.volume.intersect_within(ray, max_distance).is_none() {
             return Ok(None);
         }
 
         // Leaf node - test against primitives
         if node.count > 0 {
             let mut closest_hit: Option<(usize, Hit<T>)> = None;
-            let mut closest_distance = T::try_max_value()?;
+            let mut closest_distance = max_distance;
 
             for i in 0..node.count {
                 let shape_index = self.indices[node.left_child + i];
-                if let Some(hit) = shapes[shape_index].intersect(ray)? {
-                    if hit.distance < closest_distance {
-                        closest_distance = hit.distance;
-                        closest_hit = Some((shape_index, hit));
-                    }
+                if let Some(hit) = shapes[shape_index].intersect_within(ray, closest_distance)?
+                    && hit.distance < closest_distance
+                {
+                    closest_distance = hit.distance;
+                    closest_hit = Some((shape_index, hit));
                 }
             }
 
             return Ok(closest_hit);
         }
 
-        // Internal node - traverse children
-        let left_child_index = node.left_child;
-        let right_child_index = left_child_index + 1;
+        // Internal node - traverse the nearer child first, so its closest hit prunes the farther child
+        let [near, far] = self.nearer_child_first(ray, node.left_child, max_distance);
+        let near_hit = self.intersect_recursive(ray, shapes, near, max_distance)?;
+        let limit = near_hit.as_ref().map_or(max_distance, |(_, hit)| hit.distance);
+        let far_hit = self.intersect_recursive(ray, shapes, far, limit)?;
 
-        let left_hit = self.intersect_recursive(ray, shapes, left_child_index)?;
-        let right_hit = self.intersect_recursive(ray, shapes, right_child_index)?;
+        // Only hits strictly closer than the limit are returned, so a far hit is the closest
+        Ok(far_hit.or(near_hit))
+    }
 
-        // Return the closest hit
-        Ok(match (left_hit, right_hit) {
-            (Some((left_idx, left_hit)), Some((right_idx, right_hit))) => {
-                if left_hit.distance <= right_hit.distance {
-                    Some((left_idx, left_hit))
-                } else {
-                    Some((right_idx, right_hit))
+    /// Recursive helper for slot-wise `Bvh` traversal, bounded by `max_distance`.
+    fn intersect_slots_recursive<F>(
+        &self,
+        ray: &Ray<T>,
+        test: &F,
+        node_index: usize,
+        max_distance: T,
+    ) -> Result<Option<(usize, T)>>
+    where
+        F: Fn(usize) -> Result<Option<T>>,
+    {
+        if node_index >= self.nodes.len() {
+            return Ok(None);
+        }
+
+        let node = &self.nodes[node_index];
+
+        // Test ray against node's bounding volume
+        if node.volume.intersect_within(ray, max_distance).is_none() {
+            return Ok(None);
+        }
+
+        // Leaf node - test against primitives
+        if node.count > 0 {
+            let mut closest: Option<(usize, T)> = None;
+            for slot in node.left_child..node.left_child + node.count {
+                if let Some(distance) = test(slot)?
+                    && distance < closest.map_or(max_distance, |(_, closest_distance)| closest_distance)
+                {
+                    closest = Some((slot, distance));
+                }
+            }
+            return Ok(closest);
+        }
+
+        // Internal node - traverse the nearer child first, so its closest hit prunes the farther child
+        let [near, far] = self.nearer_child_first(ray, node.left_child, max_distance);
+        let near_hit = self.intersect_slots_recursive(ray, test, near, max_distance)?;
+        let limit = near_hit.map_or(max_distance, |(_, distance)| distance);
+        Ok(self.intersect_slots_recursive(ray, test, far, limit)?.or(near_hit))
+    }
+
+    /// Order the children of an internal node, the first of which is `left_child`, by where the `Ray` enters them.
+    /// A child the `Ray` misses within `max_distance` is ordered last.
+    fn nearer_child_first(&self, ray: &Ray<T>, left_child: usize, max_distance: T) -> [usize; 2] {
+        let entry = |index: usize| {
+            self.nodes
+                .get(index)
+                .and_then(|node| node.volume.intersect_within(ray, max_distance))
+        };
+        match (entry(left_child), entry(left_child + 1)) {
+            (Some(left), Some(right)) if right < left => [left_child + 1, left_child],
+            (None, Some(_)) => [left_child + 1, left_child],
+            _ => [left_child, left_child + 1],
+        }
+    }
+
+    /// Recursive helper for sphere casts, bounded by the largest finite distance `unbounded`.
+    fn sphere_cast_recursive<B>(
+        &self,
+        ray: &Ray<T>,
+        radius: T,
+        shapes: &[B],
+        node_index: usize,
+        unbounded: T,
+    ) -> Result<Option<(usize, Hit<T>)>>
+    where
+        B: Bounded<T> + Traceable<T>,
+    {
+        if node_index >= self.nodes.len() {
+            return Ok(None);
+        }
+
+        let node = &self.nodes[node_index];
+
+        // Test ray against node's bounding volume, grown to cover every position of the sphere touching it
+        if node.volume.expand(radius)?.intersect_within(ray, unbounded).is_none() {
+            return Ok(None);
+        }
+
+        // Leaf node - sweep against primitives
+        if node.count > 0 {
+            let mut closest: Option<(usize, Hit<T>)> = None;
+            for &shape_index in &self.indices[node.left_child..node.left_child + node.count] {
+                if let Some(hit) = shapes[shape_index].sphere_cast(ray, radius)?
+                    && closest.as_ref().is_none_or(|(_, closest)| hit.distance < closest.distance)
+                {
+                    closest = Some((shape_index, hit));
                 }
             }
-            (Some(hit), None) | (None, Some(hit)) => Some(hit),
-            (None, None) => None,
+            return Ok(closest);
+        }
+
+        // Internal node - traverse children and return the first contact
+        let left = self.sphere_cast_recursive(ray, radius, shapes, node.left_child, unbounded)?;
+        let right = self.sphere_cast_recursive(ray, radius, shapes, node.left_child + 1, unbounded)?;
+        Ok(match (left, right) {
+            (Some(left), Some(right)) => Some(if left.1.distance <= right.1.distance { left } else { right }),
+            (hit, None) | (None, hit) => hit,
         })
     }
 
     /// Recursive helper for shadow ray testing.
-    fn intersect_any_recursive<B>(&self, ray: &Ray<T>, shapes: &[B], node_index: usize, max_distance: T) -> Result<bool>
+    fn intersect_any_recursive<F>(&self, ray: &Ray<T>, test: &F, node_index: usize, max_distance: T) -> Result<bool>
     where
-        B: Bounded<T> + Traceable<T>,
+        F: Fn(usize) -> Result<bool>,
     {
         if node_index >= self.nodes.len() {
             return Ok(false);
@@ -171,23 +762,16 @@ impl<T: RealField + Copy + ToPrimitive> Bvh<T> {
 
         let node = &self.nodes[node_index];
 
-        // Test ray against node's bounding box
-        if let Some(distance) = node.aabb.intersect_distance(ray)? {
-            if distance > max_distance {
-                return Ok(false);
-            }
-        } else {
+        // Test ray against node's bounding volume
+        if node.volume.intersect_within(ray, max_distance).is_none() {
             return Ok(false);
         }
 
         // Leaf node - test against primitives
         if node.count > 0 {
-            for i in 0..node.count {
-                let shape_index = self.indices[node.left_child + i];
-                if let Some(hit) = shapes[shape_index].intersect(ray)? {
-                    if hit.distance <= max_distance {
-                        return Ok(true);
-                    }
+            for slot in node.left_child..node.left_child + node.count {
+                if test(slot)? {
+                    return Ok(true);
                 }
             }
             return Ok(false);
@@ -197,13 +781,100 @@ impl<T: RealField + Copy + ToPrimitive> Bvh<T> {
         let left_child_index = node.left_child;
         let right_child_index = left_child_index + 1;
 
-        Ok(self.intersect_any_recursive(ray, shapes, left_child_index, max_distance)?
-            || self.intersect_any_recursive(ray, shapes, right_child_index, max_distance)?)
+        Ok(self.intersect_any_recursive(ray, test, left_child_index, max_distance)?
+            || self.intersect_any_recursive(ray, test, right_child_index, max_distance)?)
+    }
+}
+
+impl<T: Scalar, V: BoundingVolume<T>> Bounded<T> for Bvh<T, V> {
+    fn aabb(&self) -> Result<Bounds<'_, T>> {
+        Ok(Bounds::Finite(self.nodes[0].volume.bounding_box()?))
     }
 }
 
-impl<T: RealField + Copy + ToPrimitive> Bounded<T> for Bvh<T> {
-    fn aabb(&self) -> Result<Cow<Aabb<T>>> {
-        Ok(Cow::Borrowed(&self.nodes[0].aabb))
+// == Utility functions ==
+
+/// Construct the bounding volume of a shape which must be bounded.
+fn enclose_finite<T: Scalar, V: BoundingVolume<T>, B: Bounded<T>>(shape: &B) -> Result<V> {
+    V::enclose(shape)?.ok_or_else(|| GeometryError::UnboundedGeometry.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use nalgebra::{Point3, Unit, Vector3};
+
+    use crate::{
+        bvh::{Bvh, BvhConfig},
+        error::Result,
+        geometry::{Aabb, Triangle},
+        rt::Ray,
+        testing::SceneGenerator,
+        traits::Traceable,
+    };
+
+    #[test]
+    fn traversal_finds_the_closest_hit_of_a_linear_search() -> Result<()> {
+        let bounds = Aabb::new(Point3::new(-1.0, -1.0, -1.0), Point3::new(1.0, 1.0, 1.0))?;
+        for (seed, count, max_shapes_per_node) in [(1, 1, 1), (2, 50, 2), (3, 500, 4)] {
+            let bvh_config = BvhConfig {
+                max_shapes_per_node,
+                ..BvhConfig::default()
+            };
+            let generator = SceneGenerator::<f64>::new(bounds.clone(), seed);
+            let triangles = generator.triangle_soup(&bvh_config, count, 0.3)?.triangles().to_vec();
+            let bvh = Bvh::new(&bvh_config, &triangles)?;
+            for ray in generator.rays(300)? {
+                let mut closest: Option<f64> = None;
+                for triangle in &triangles {
+                    if let Some(distance) = triangle.intersect_t(&ray)? {
+                        closest = Some(closest.map_or(distance, |closest| closest.min(distance)));
+                    }
+                }
+                assert_eq!(bvh.intersect_t(&ray, &triangles)?, closest, "intersect_t of {ray:?}");
+                assert_eq!(
+                    bvh.intersect(&ray, &triangles)?.map(|(_, hit)| hit.distance),
+                    closest,
+                    "intersect of {ray:?}"
+                );
+                for max_distance in [0.5, 2.0] {
+                    assert_eq!(
+                        bvh.intersect_within(&ray, &triangles, max_distance)?
+                            .map(|(_, hit)| hit.distance),
+                        closest.filter(|&distance| distance < max_distance),
+                        "intersect_within of {ray:?} within {max_distance}"
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn non_finite_rays_and_bounds_miss() -> Result<()> {
+        let normal = Vector3::z_axis();
+        let triangle = |z: f64| {
+            let vertices = [[0.0, 0.0], [1.0, 0.0], [0.0, 1.0]].map(|[x, y]| Point3::new(x, y, z));
+            Triangle::new(vertices, [normal; 3])
+        };
+        let triangles = vec![triangle(1.0), triangle(f64::NAN), triangle(2.0)];
+        let bvh = Bvh::new(&BvhConfig::default(), &triangles)?;
+        let direction = Vector3::z_axis();
+
+        let ray = Ray::new(Point3::new(0.25, 0.25, 0.0), direction);
+        assert_eq!(bvh.intersect(&ray, &triangles)?.map(|(index, _)| index), Some(0));
+
+        for ray in [
+            Ray::new(Point3::new(f64::NAN, 0.25, 0.0), direction),
+            Ray::new(Point3::new(0.25, 0.25, f64::NEG_INFINITY), direction),
+            Ray::new(
+                Point3::new(0.25, 0.25, 0.0),
+                Unit::new_unchecked(Vector3::new(f64::NAN, 0.0, 1.0)),
+            ),
+        ] {
+            assert!(bvh.intersect(&ray, &triangles)?.is_none(), "intersect of {ray:?}");
+            assert!(bvh.intersect_t(&ray, &triangles)?.is_none(), "intersect_t of {ray:?}");
+            assert!(!bvh.intersect_any(&ray, &triangles, f64::MAX)?, "intersect_any of {ray:?}");
+        }
+        Ok(())
     }
 }
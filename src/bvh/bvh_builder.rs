@@ -1,135 +1,232 @@
 //! Bounding Volume Hierarchy node structure with Surface Area Heuristic.
 
-use nalgebra::RealField;
-use num_traits::ToPrimitive;
+use std::mem::take;
 
 use crate::{
-    bvh::{Bvh, BvhConfig, BvhNode},
+    bvh::{Bvh, BvhConfig, BvhNode, ConfigSplit},
     error::{BvhError, Result},
     geometry::Aabb,
-    traits::{Bounded, FallibleNumeric},
+    traits::{Bounded, Scalar, SplitStrategy},
 };
 
-/// Internal transient structure used for surface area heuristic (SAH) evaluation.
-struct SplitCandidate<T: RealField + Copy> {
-    /// Axis along which the split is evaluated. 0 = x-axis, 1 = y-axis, 2 = z-axis.
-    axis: usize,
-    /// Position along the axis where the split occurs.
-    position: T,
-    /// Cost of the split, calculated using SAH.
-    cost: T,
+/// Reusable buffers for `BvhBuilder::build_into`, so repeated `Bvh` rebuilds (e.g. in dynamic scenes) reuse
+/// their allocations rather than requesting fresh ones from the global allocator each time.
+///
+/// Buffers are lent to each build, and handed back to the arena by `recycle` once the `Bvh` is no longer needed.
+#[derive(Debug)]
+pub struct BvhArena<T: Scalar> {
+    /// Shape index buffer.
+    indices: Vec<usize>,
+    /// Node buffer.
+    nodes: Vec<BvhNode<T>>,
+    /// Shape bounding box scratch buffer.
+    boxes: Vec<Aabb<T>>,
+    /// Partition scratch buffer.
+    partition: Vec<usize>,
 }
 
 /// Transient structure used in building a Bounding Volume Hierarchy (BVH).
+///
+/// Nodes are split top down, each by the plane chosen by a `SplitStrategy`:
+/// the `SplitMethod` of the `BvhConfig` unless another strategy is supplied.
+/// If the `BvhConfig` asks for it, the finished tree is then tidied up with `Bvh::collapse`.
+/// Builds are deterministic for a deterministic strategy: the same shapes and `BvhConfig` always produce
+/// a bit-identical `Bvh`, on any platform. Shapes are partitioned stably, so each child keeps its shapes in input order.
+/// No hashed collections or threads are involved.
 #[derive(Debug)]
-pub struct BvhBuilder<T: RealField + Copy> {
+pub struct BvhBuilder<T: Scalar, S = ConfigSplit<T>> {
     /// Configuration parameters for the BVH.
     config: BvhConfig<T>,
+    /// Strategy choosing the plane splitting each node.
+    strategy: S,
     /// Indices of shapes contained in this node.
     indices: Vec<usize>,
     /// List of nodes.
     nodes: Vec<BvhNode<T>>,
     /// Current number of nodes used.
     nodes_used: usize,
+    /// Scratch buffer holding the bounding boxes of the shapes of the node being split, reused across nodes.
+    boxes: Vec<Aabb<T>>,
+    /// Scratch buffer holding the right-hand shapes during partitioning, reused across nodes.
+    partition: Vec<usize>,
+}
+
+impl<T: Scalar> BvhArena<T> {
+    /// Construct a new empty `BvhArena` instance.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            indices: Vec::new(),
+            nodes: Vec::new(),
+            boxes: Vec::new(),
+            partition: Vec::new(),
+        }
+    }
+
+    /// Hand the buffers of a `Bvh` which is no longer needed back to the arena, for reuse by the next build.
+    pub fn recycle(&mut self, bvh: Bvh<T>) {
+        let (indices, nodes, _) = bvh.into_parts();
+        if indices.capacity() > self.indices.capacity() {
+            self.indices = indices;
+        }
+        if nodes.capacity() > self.nodes.capacity() {
+            self.nodes = nodes;
+        }
+    }
+
+    /// Get the number of `BvhNode`s the arena can hold without reallocating.
+    #[must_use]
+    pub const fn node_capacity(&self) -> usize {
+        self.nodes.capacity()
+    }
 }
 
-impl<T: RealField + Copy + ToPrimitive> BvhBuilder<T> {
-    /// Construct a new `BvhBuilder` instance.
+impl<T: Scalar> Default for BvhArena<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Scalar> BvhBuilder<T> {
+    /// Construct a new `BvhBuilder` instance, splitting nodes by the `SplitMethod` of the `BvhConfig`.
     pub fn new(config: &BvhConfig<T>) -> Self {
         Self {
             config: config.clone(),
+            strategy: ConfigSplit::new(),
             indices: Vec::new(),
             nodes: Vec::new(),
             nodes_used: 0,
+            boxes: Vec::new(),
+            partition: Vec::new(),
+        }
+    }
+}
+
+impl<T: Scalar, S: SplitStrategy<T>> BvhBuilder<T, S> {
+    /// Replace the `SplitStrategy` choosing the plane splitting each node.
+    pub fn with_strategy<R: SplitStrategy<T>>(self, strategy: R) -> BvhBuilder<T, R> {
+        BvhBuilder {
+            config: self.config,
+            strategy,
+            indices: self.indices,
+            nodes: self.nodes,
+            nodes_used: self.nodes_used,
+            boxes: self.boxes,
+            partition: self.partition,
         }
     }
 
     /// Construct a `Bvh` from a collection of `Bounded` shapes.
+    /// Unbounded shapes are left out of the hierarchy.
     ///
     /// # Errors
     ///
     /// Returns an error if:
-    /// - The shapes slice is empty
+    /// - The shapes slice contains no finite shapes
     /// - Bounding box calculations fail
     /// - Mathematical operations fail during subdivision
-    pub fn build<B: Bounded<T>>(mut self, shapes: &[B]) -> Result<Bvh<T>> {
-        if shapes.is_empty() {
+    pub fn build<B: Bounded<T>>(self, shapes: &[B]) -> Result<Bvh<T>> {
+        let (indices, mut nodes, depth) = self.build_into(&mut BvhArena::new(), shapes)?.into_parts();
+        nodes.shrink_to_fit();
+        Bvh::construct_directly(indices, nodes, depth)
+    }
+
+    /// Construct a `Bvh` from a collection of `Bounded` shapes, building into the buffers of a `BvhArena`.
+    /// The `Bvh` takes ownership of the buffers, which can be returned to the arena with `BvhArena::recycle`.
+    /// Unbounded shapes are left out of the hierarchy.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The shapes slice contains no finite shapes
+    /// - Bounding box calculations fail
+    /// - Mathematical operations fail during subdivision
+    pub fn build_into<B: Bounded<T>>(mut self, arena: &mut BvhArena<T>, shapes: &[B]) -> Result<Bvh<T>> {
+        self.indices = take(&mut arena.indices);
+        self.indices.clear();
+        for (index, shape) in shapes.iter().enumerate() {
+            if shape.aabb()?.is_finite() {
+                self.indices.push(index);
+            }
+        }
+        let count = self.indices.len();
+        if count == 0 {
+            arena.indices = take(&mut self.indices);
             return Err(BvhError::EmptyGeometry.into());
         }
 
-        self.indices = (0..shapes.len()).collect();
-        self.nodes = vec![
-            BvhNode {
-                aabb: Aabb::empty()?,
-                left_child: 0,
-                count: 0,
-            };
-            (shapes.len() * 2) - 1
-        ];
+        self.nodes = take(&mut arena.nodes);
+        self.nodes.clear();
+        self.nodes.resize((count * 2) - 1, BvhNode::new(Aabb::empty()?, 0, 0));
+
+        self.boxes = take(&mut arena.boxes);
+        self.partition = take(&mut arena.partition);
 
         self.nodes[0].left_child = 0;
-        self.nodes[0].count = shapes.len();
+        self.nodes[0].count = count;
         self.nodes_used = 1;
 
         self.update_bounds(0, shapes)?;
         let depth = self.subdivide(0, shapes, 0)?;
 
         self.nodes.truncate(self.nodes_used);
-        self.nodes.shrink_to_fit();
+        arena.boxes = take(&mut self.boxes);
+        arena.partition = take(&mut self.partition);
 
-        Bvh::construct_directly(self.indices, self.nodes, depth)
+        let mut bvh = Bvh::construct_directly(self.indices, self.nodes, depth)?;
+        if self.config.collapse_leaves {
+            bvh.collapse(&self.config)?;
+        }
+        Ok(bvh)
     }
 
     /// Expand the bounding box of a `BvhNode` to include all geometries contained within the node.
     fn update_bounds<B: Bounded<T>>(&mut self, index: usize, shapes: &[B]) -> Result<()> {
-        let start = self.nodes[index].aabb.clone();
-        let new_aabb = (0..self.nodes[index].count)
+        let start = self.nodes[index].volume.clone();
+        let new_volume = (0..self.nodes[index].count)
             .map(|i| shapes[self.indices[self.nodes[index].left_child + i]].aabb())
-            .try_fold(start, |acc, aabb| acc.merge(&*aabb?))?;
-        self.nodes[index].aabb = new_aabb;
+            .try_fold(start, |acc, bounds| acc.merge(&*bounds?.try_finite()?))?;
+        self.nodes[index].volume = new_volume;
         Ok(())
     }
 
-    /// Subdivide a `BvhNode` using Surface Area Heuristic (SAH).
+    /// Subdivide a `BvhNode` at the plane chosen by the `SplitStrategy`.
     fn subdivide<B: Bounded<T>>(&mut self, index: usize, shapes: &[B], current_depth: usize) -> Result<usize> {
         // Termination criteria
         if (self.nodes[index].count <= self.config.max_shapes_per_node) || (current_depth >= self.config.max_depth) {
             return Ok(current_depth);
         }
 
-        // Find the best split using SAH
-        let Ok(Some(best_split)) = self.find_best_split(index, shapes) else {
-            return Ok(current_depth);
-        };
-
-        // Calculate cost of not splitting (leaf node cost)
-        let leaf_cost = T::try_from_usize(self.nodes[index].count)? * self.config.intersect_cost;
-
-        // If splitting is not beneficial, create a leaf
-        if best_split.cost >= leaf_cost {
-            return Ok(current_depth);
+        // Gather the bounding boxes of the shapes, in node order, for the strategy
+        let start = self.nodes[index].left_child;
+        let end = start + self.nodes[index].count;
+        self.boxes.clear();
+        for &shape_index in &self.indices[start..end] {
+            self.boxes.push(shapes[shape_index].aabb()?.try_finite()?.into_owned());
         }
 
-        // Partition primitives based on the best split
-        let mut i = self.nodes[index].left_child;
-        let mut j = i + self.nodes[index].count - 1;
+        // A node the strategy declines to split becomes a leaf
+        let Some(split) = self.strategy.split(&self.config, &self.nodes[index].volume, &self.boxes)? else {
+            return Ok(current_depth);
+        };
 
-        while i <= j {
-            let shape_index = self.indices[i];
-            let centroid = shapes[shape_index].aabb()?.centre()?;
+        // Stably partition primitives based on the split, keeping their relative order on each side
+        self.partition.clear();
+        let mut i = start;
+        for j in start..end {
+            let shape_index = self.indices[j];
+            let centroid = self.boxes[j - start].centre()?;
 
-            if centroid[best_split.axis] < best_split.position {
+            if centroid[split.axis] < split.position {
+                self.indices[i] = shape_index;
                 i += 1;
             } else {
-                self.indices.swap(i, j);
-                if j == 0 {
-                    return Ok(current_depth);
-                }
-                j -= 1;
+                self.partition.push(shape_index);
             }
         }
+        self.indices[i..end].copy_from_slice(&self.partition);
 
-        let left_count = i - self.nodes[index].left_child;
+        let left_count = i - start;
 
         // Fallback to prevent degenerate splits
         if (left_count == 0) || (left_count == self.nodes[index].count) {
@@ -160,106 +257,4 @@ impl<T: RealField + Copy + ToPrimitive> BvhBuilder<T> {
 
         Ok(left_depth.max(right_depth))
     }
-
-    /// Find the best split using Surface Area Heuristic (SAH).
-    fn find_best_split<B: Bounded<T>>(&self, node_index: usize, shapes: &[B]) -> Result<Option<SplitCandidate<T>>> {
-        let node = &self.nodes[node_index];
-        let node_surface_area = node.aabb.surface_area()?;
-
-        if node_surface_area <= T::zero() {
-            return Ok(None);
-        }
-
-        let mut best_split: Option<SplitCandidate<T>> = None;
-
-        // Try all three axes
-        for axis in 0..3 {
-            let extent = node.aabb.maxs[axis] - node.aabb.mins[axis];
-            if extent <= T::zero() {
-                continue;
-            }
-
-            // Create buckets for this axis
-            let mut buckets = vec![(0, Aabb::empty()?); self.config.sah_buckets];
-
-            // Assign primitives to buckets
-            for i in 0..node.count {
-                let shape_index = self.indices[node.left_child + i];
-                let shape_aabb = shapes[shape_index].aabb()?;
-                let centroid = shape_aabb.centre()?;
-
-                let bucket_index = ((centroid[axis] - node.aabb.mins[axis]) / extent
-                    * T::try_from_usize(self.config.sah_buckets)?)
-                .floor()
-                .to_usize()
-                .unwrap_or(0)
-                .min(self.config.sah_buckets - 1);
-
-                buckets[bucket_index].0 += 1;
-                if buckets[bucket_index].0 == 1 {
-                    buckets[bucket_index].1 = shape_aabb.into_owned();
-                } else {
-                    buckets[bucket_index].1 = buckets[bucket_index].1.merge(&shape_aabb)?;
-                }
-            }
-
-            // Evaluate splits between buckets
-            for split_bucket in 1..self.config.sah_buckets {
-                // Calculate left side
-                let mut left_count = 0;
-                let mut left_aabb: Option<Aabb<T>> = None;
-                for bucket in &buckets[..split_bucket] {
-                    if bucket.0 > 0 {
-                        left_count += bucket.0;
-                        left_aabb = Some(left_aabb.map_or_else(|| Ok(bucket.1.clone()), |aabb| aabb.merge(&bucket.1))?);
-                    }
-                }
-
-                // Calculate right side
-                let mut right_count = 0;
-                let mut right_aabb: Option<Aabb<T>> = None;
-                for bucket in &buckets[split_bucket..] {
-                    if bucket.0 > 0 {
-                        right_count += bucket.0;
-                        right_aabb = Some(right_aabb.map_or_else(|| Ok(bucket.1.clone()), |aabb| aabb.merge(&bucket.1))?);
-                    }
-                }
-
-                // Skip invalid splits
-                if left_count == 0 || right_count == 0 {
-                    continue;
-                }
-
-                // Calculate SAH cost
-                let left_surface_area = left_aabb
-                    .as_ref()
-                    .map(Aabb::surface_area)
-                    .transpose()?
-                    .unwrap_or_else(T::zero);
-
-                let right_surface_area = right_aabb
-                    .as_ref()
-                    .map(Aabb::surface_area)
-                    .transpose()?
-                    .unwrap_or_else(T::zero);
-
-                let cost = self.config.traverse_cost
-                    + (left_surface_area / node_surface_area) * T::try_from_usize(left_count)? * self.config.intersect_cost
-                    + (right_surface_area / node_surface_area) * T::try_from_usize(right_count)? * self.config.intersect_cost;
-
-                let split_position = node.aabb.mins[axis]
-                    + extent * T::try_from_usize(split_bucket)? / T::try_from_usize(self.config.sah_buckets)?;
-
-                if best_split.as_ref().is_none_or(|best| cost < best.cost) {
-                    best_split = Some(SplitCandidate {
-                        axis,
-                        position: split_position,
-                        cost,
-                    });
-                }
-            }
-        }
-
-        Ok(best_split)
-    }
 }
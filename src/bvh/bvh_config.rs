@@ -1,18 +1,19 @@
 //! Bounding Volume Hierarchy configuration structure.
 
-use nalgebra::RealField;
 use serde::{Deserialize, Serialize};
 
 use crate::{
+    bvh::SplitMethod,
     config::{
         DEFAULT_INTERSECT_COST, DEFAULT_MAX_DEPTH, DEFAULT_MAX_SHAPES_PER_NODE, DEFAULT_SAH_BUCKETS, DEFAULT_TRAVERSE_COST,
     },
     error::{BvhConfigError, Result},
+    traits::Scalar,
 };
 
 /// Configuration structure for constructing a Bounding Volume Hierarchy (BVH).
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct BvhConfig<T: RealField + Copy> {
+pub struct BvhConfig<T: Scalar> {
     /// Cost of intersecting a primitive.
     pub traverse_cost: T,
     /// Cost of traversing an internal node.
@@ -23,9 +24,15 @@ pub struct BvhConfig<T: RealField + Copy> {
     pub max_shapes_per_node: usize,
     /// Maximum depth of the BVH.
     pub max_depth: usize,
+    /// Heuristic used to split nodes.
+    #[serde(default)]
+    pub split_method: SplitMethod,
+    /// Collapse subtrees which are expected to be slower to traverse than a single leaf once built.
+    #[serde(default)]
+    pub collapse_leaves: bool,
 }
 
-impl<T: RealField + Copy> BvhConfig<T> {
+impl<T: Scalar> BvhConfig<T> {
     /// Construct a new `BvhConfig` instance.
     ///
     /// # Errors
@@ -78,11 +85,27 @@ impl<T: RealField + Copy> BvhConfig<T> {
             sah_buckets,
             max_shapes_per_node,
             max_depth,
+            split_method: SplitMethod::default(),
+            collapse_leaves: false,
         })
     }
+
+    /// Set the heuristic used to split nodes.
+    #[must_use]
+    pub const fn with_split_method(mut self, split_method: SplitMethod) -> Self {
+        self.split_method = split_method;
+        self
+    }
+
+    /// Set whether subtrees expected to be slower to traverse than a single leaf are collapsed once built.
+    #[must_use]
+    pub const fn with_collapse_leaves(mut self, collapse_leaves: bool) -> Self {
+        self.collapse_leaves = collapse_leaves;
+        self
+    }
 }
 
-impl<T: RealField + Copy> Default for BvhConfig<T> {
+impl<T: Scalar> Default for BvhConfig<T> {
     fn default() -> Self {
         Self::new(
             T::from_f64(DEFAULT_TRAVERSE_COST).unwrap(),
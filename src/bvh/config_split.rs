@@ -0,0 +1,38 @@
+//! Configured split strategy.
+
+use crate::{
+    bvh::{BvhConfig, MedianSplit, MiddleSplit, SahSplit, Split, SplitMethod},
+    error::Result,
+    geometry::Aabb,
+    traits::{Scalar, SplitStrategy},
+};
+
+/// Splits nodes by the `SplitMethod` selected in the `BvhConfig`, the default `SplitStrategy` of a `BvhBuilder`.
+#[derive(Debug, Clone, Default)]
+pub struct ConfigSplit<T: Scalar> {
+    /// Strategy used for `SplitMethod::Sah`.
+    sah: SahSplit<T>,
+    /// Strategy used for `SplitMethod::Median`.
+    median: MedianSplit<T>,
+}
+
+impl<T: Scalar> ConfigSplit<T> {
+    /// Construct a new `ConfigSplit` instance.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            sah: SahSplit::new(),
+            median: MedianSplit::new(),
+        }
+    }
+}
+
+impl<T: Scalar> SplitStrategy<T> for ConfigSplit<T> {
+    fn split(&mut self, config: &BvhConfig<T>, bounds: &Aabb<T>, shapes: &[Aabb<T>]) -> Result<Option<Split<T>>> {
+        match config.split_method {
+            SplitMethod::Sah => self.sah.split(config, bounds, shapes),
+            SplitMethod::Median => self.median.split(config, bounds, shapes),
+            SplitMethod::Middle => MiddleSplit.split(config, bounds, shapes),
+        }
+    }
+}
@@ -0,0 +1,27 @@
+//! Spatial middle split strategy.
+
+use crate::{
+    bvh::{BvhConfig, Split},
+    error::Result,
+    geometry::Aabb,
+    traits::{FallibleNumeric, Scalar, SplitStrategy},
+};
+
+/// Splits nodes halfway along the axis over which the shape centres are most spread.
+///
+/// The cheapest `SplitStrategy`: no shapes are sorted and no costs are evaluated, so it suits hierarchies rebuilt
+/// every frame, at the cost of slower traversal than `SahSplit`. Clustered shapes can give unbalanced trees.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MiddleSplit;
+
+impl<T: Scalar> SplitStrategy<T> for MiddleSplit {
+    fn split(&mut self, _config: &BvhConfig<T>, _bounds: &Aabb<T>, shapes: &[Aabb<T>]) -> Result<Option<Split<T>>> {
+        let centres = shapes.iter().map(Aabb::centre).collect::<Result<Vec<_>>>()?;
+        let Some(centre_bounds) = Aabb::from_points(centres) else {
+            return Ok(None);
+        };
+        let axis = centre_bounds.longest_axis();
+        let position = (centre_bounds.mins[axis] + centre_bounds.maxs[axis]) / T::try_from_u8(2)?;
+        Ok(Some(Split::new(axis, position)))
+    }
+}
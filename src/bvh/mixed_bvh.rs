@@ -0,0 +1,250 @@
+//! Mixed precision Bounding Volume Hierarchy structure.
+
+use nalgebra::{Point3, Unit, Vector3};
+use serde::{Deserialize, Serialize};
+use std::any::type_name;
+
+use crate::{
+    bvh::{Bvh, BvhConfig, BvhNode},
+    error::{NumericError, Result},
+    geometry::Aabb,
+    rt::{Hit, Ray},
+    traits::{Bounded, Scalar, Traceable},
+};
+
+/// Margin by which node bounds are inflated during traversal, relative to the magnitude of the coordinates involved.
+/// Covers the rounding of the `Ray` to single precision, and the arithmetic of the slab test.
+const TRAVERSAL_MARGIN: f32 = 8.0 * f32::EPSILON;
+
+/// `Bvh` over geometry held in higher precision (such as `f64`), whose bounds are stored and traversed in `f32`.
+///
+/// Bounds are stored relative to the centre of the hierarchy, so they keep their precision far from the world origin,
+/// as in planetary or geospatial scenes. They are rounded outwards, and inflated during traversal to cover
+/// the rounding of the `Ray`, so no geometry is missed. Shapes are always tested in their own precision,
+/// so `Hit`s are exact, while the hierarchy takes half the memory.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MixedBvh<T: Scalar> {
+    /// Single precision hierarchy, with bounds relative to `origin` enclosing the full precision shapes.
+    bvh: Bvh<f32>,
+    /// Full precision origin of the single precision bounds.
+    origin: Point3<T>,
+}
+
+impl<T: Scalar> MixedBvh<T> {
+    /// Construct a new `MixedBvh` over a collection of `Bounded` shapes.
+    /// The hierarchy is built in full precision, then rounded to single precision.
+    /// Unbounded shapes, such as `Plane`s, are left out of the hierarchy and must be tested separately, as a `Scene` does.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The shapes slice is empty
+    /// - BVH construction fails due to invalid configuration
+    /// - The bounds cannot be converted to single precision
+    pub fn new<B: Bounded<T>>(config: &BvhConfig<T>, shapes: &[B]) -> Result<Self> {
+        Self::from_bvh(&Bvh::new(config, shapes)?)
+    }
+
+    /// Construct a `MixedBvh` from a full precision `Bvh`, rounding every node bound outwards to single precision.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bounds cannot be converted to single precision.
+    pub fn from_bvh(bvh: &Bvh<T>) -> Result<Self> {
+        let origin = bvh.aabb()?.try_finite()?.centre()?;
+        let nodes = bvh
+            .nodes()
+            .iter()
+            .map(|node| {
+                let (mins, maxs) = (node.volume.mins - origin, node.volume.maxs - origin);
+                let mins = Point3::new(
+                    round_down(to_f64(mins.x)?),
+                    round_down(to_f64(mins.y)?),
+                    round_down(to_f64(mins.z)?),
+                );
+                let maxs = Point3::new(
+                    round_up(to_f64(maxs.x)?),
+                    round_up(to_f64(maxs.y)?),
+                    round_up(to_f64(maxs.z)?),
+                );
+                Ok(BvhNode::new(Aabb::new(mins, maxs)?, node.left_child, node.count))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self {
+            bvh: Bvh::construct_directly(bvh.indices().to_vec(), nodes, bvh.depth())?,
+            origin,
+        })
+    }
+
+    /// Get the single precision `Bvh`, whose bounds are relative to the `origin`.
+    #[must_use]
+    pub const fn bvh(&self) -> &Bvh<f32> {
+        &self.bvh
+    }
+
+    /// Get the full precision origin of the single precision bounds.
+    #[must_use]
+    pub const fn origin(&self) -> &Point3<T> {
+        &self.origin
+    }
+
+    /// Test for intersections between a `Ray` and geometries in the `MixedBvh`.
+    /// Returns the closest intersection if any, along with the index of the shape which was hit.
+    /// Unbounded shapes are not referenced by the hierarchy, so are never hit.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - Ray-shape intersection tests fail
+    /// - The `Ray` cannot be converted to single precision
+    pub fn intersect<B: Traceable<T>>(&self, ray: &Ray<T>, shapes: &[B]) -> Result<Option<(usize, Hit<T>)>> {
+        let Some((local, margin)) = self.local_ray(ray)? else {
+            return Ok(None);
+        };
+        let nodes = self.bvh.nodes();
+        let Some(root_entry) = entry(&nodes[0], &local, margin, f32::INFINITY) else {
+            return Ok(None);
+        };
+
+        // Visit nearer children first, so the closest `Hit` found so far prunes more of the hierarchy
+        let mut closest: Option<(usize, Hit<T>)> = None;
+        let mut limit = f32::INFINITY;
+        let mut stack = vec![(0, root_entry)];
+        while let Some((node_index, node_entry)) = stack.pop() {
+            if node_entry > limit {
+                continue;
+            }
+
+            let node = &nodes[node_index];
+            if node.count > 0 {
+                for &shape_index in &self.bvh.indices()[node.left_child..node.left_child + node.count] {
+                    if let Some(hit) = shapes[shape_index].intersect(ray)?
+                        && closest.as_ref().is_none_or(|(_, closest)| hit.distance < closest.distance)
+                    {
+                        limit = round_up(to_f64(hit.distance)?);
+                        closest = Some((shape_index, hit));
+                    }
+                }
+                continue;
+            }
+
+            let (left, right) = (node.left_child, node.left_child + 1);
+            match (
+                entry(&nodes[left], &local, margin, limit),
+                entry(&nodes[right], &local, margin, limit),
+            ) {
+                (Some(left_entry), Some(right_entry)) if left_entry <= right_entry => {
+                    stack.extend([(right, right_entry), (left, left_entry)]);
+                }
+                (Some(left_entry), Some(right_entry)) => stack.extend([(left, left_entry), (right, right_entry)]),
+                (Some(left_entry), None) => stack.push((left, left_entry)),
+                (None, Some(right_entry)) => stack.push((right, right_entry)),
+                (None, None) => {}
+            }
+        }
+        Ok(closest)
+    }
+
+    /// Test if a `Ray` intersects any geometry in the `MixedBvh` within `max_distance` (shadow ray optimization).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - Ray-shape intersection tests fail
+    /// - The `Ray` or `max_distance` cannot be converted to single precision
+    pub fn intersect_any<B: Traceable<T>>(&self, ray: &Ray<T>, shapes: &[B], max_distance: T) -> Result<bool> {
+        let Some((local, margin)) = self.local_ray(ray)? else {
+            return Ok(false);
+        };
+
+        let limit = round_up(to_f64(max_distance)?);
+        let mut stack = vec![0];
+        while let Some(node_index) = stack.pop() {
+            let node = &self.bvh.nodes()[node_index];
+            if entry(node, &local, margin, limit).is_none() {
+                continue;
+            }
+
+            if node.count > 0 {
+                for &shape_index in &self.bvh.indices()[node.left_child..node.left_child + node.count] {
+                    if shapes[shape_index].intersect_any(ray, max_distance)? {
+                        return Ok(true);
+                    }
+                }
+            } else {
+                stack.extend([node.left_child + 1, node.left_child]);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Translate a `Ray` to the `origin` and round it to single precision, returning `None` if it is not finite.
+    /// Also returns the margin by which to inflate node bounds, in proportion to the magnitude of the `Ray` origin
+    /// and the root bounds, which limits the distance between the rounded `Ray` and the true `Ray` within the root.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the `Ray` cannot be converted to `f64`.
+    fn local_ray(&self, ray: &Ray<T>) -> Result<Option<(Ray<f32>, f32)>> {
+        if !ray.is_finite() {
+            return Ok(None);
+        }
+        let origin = ray.origin - self.origin;
+        #[expect(clippy::cast_possible_truncation, reason = "Rounding to single precision is intended.")]
+        let round = |value: T| to_f64(value).map(|value| value as f32);
+        let origin = Point3::new(round(origin.x)?, round(origin.y)?, round(origin.z)?);
+        let direction = Vector3::new(round(ray.direction.x)?, round(ray.direction.y)?, round(ray.direction.z)?);
+        let root = &self.bvh.nodes()[0].volume;
+        let magnitude = origin.coords.amax() + root.mins.coords.amax().max(root.maxs.coords.amax());
+        Ok(Some((
+            Ray::new(origin, Unit::new_normalize(direction)),
+            TRAVERSAL_MARGIN * magnitude,
+        )))
+    }
+}
+
+// == Utility functions ==
+
+/// Convert a value to `f64`.
+///
+/// # Errors
+///
+/// Returns an error if the value cannot be represented as an `f64`.
+fn to_f64<T: Scalar>(value: T) -> Result<f64> {
+    Ok(value.to_f64().ok_or_else(|| NumericError::TypeConversion {
+        from_type: type_name::<T>().to_string(),
+        to_type: "f64".to_string(),
+    })?)
+}
+
+/// Round a value to the nearest `f32` no greater than it.
+fn round_down(value: f64) -> f32 {
+    #[expect(clippy::cast_possible_truncation, reason = "Rounding to single precision is intended.")]
+    let rounded = value as f32;
+    if f64::from(rounded) > value {
+        rounded.next_down()
+    } else {
+        rounded
+    }
+}
+
+/// Round a value to the nearest `f32` no less than it.
+fn round_up(value: f64) -> f32 {
+    #[expect(clippy::cast_possible_truncation, reason = "Rounding to single precision is intended.")]
+    let rounded = value as f32;
+    if f64::from(rounded) < value {
+        rounded.next_up()
+    } else {
+        rounded
+    }
+}
+
+/// Distance along a single precision `Ray` at which it may first reach geometry within a node, if before `limit`.
+/// The node is inflated by `margin` to cover the rounding of the `Ray`.
+fn entry(node: &BvhNode<f32>, ray: &Ray<f32>, margin: f32, limit: f32) -> Option<f32> {
+    let inflated = Aabb {
+        mins: node.volume.mins - Vector3::repeat(margin),
+        maxs: node.volume.maxs + Vector3::repeat(margin),
+    };
+    inflated.intersect_within(ray, limit + margin)
+}
@@ -1,7 +1,21 @@
 mod bvh;
 mod bvh_builder;
 mod bvh_config;
+mod config_split;
+mod median_split;
+mod middle_split;
+mod mixed_bvh;
+mod sah_split;
+mod split;
+mod split_method;
 
 pub use bvh::{Bvh, BvhNode};
-pub use bvh_builder::BvhBuilder;
+pub use bvh_builder::{BvhArena, BvhBuilder};
 pub use bvh_config::BvhConfig;
+pub use config_split::ConfigSplit;
+pub use median_split::MedianSplit;
+pub use middle_split::MiddleSplit;
+pub use mixed_bvh::MixedBvh;
+pub use sah_split::SahSplit;
+pub use split::Split;
+pub use split_method::SplitMethod;
@@ -0,0 +1,136 @@
+//! Surface Area Heuristic split strategy.
+
+use crate::{
+    bvh::{BvhConfig, Split},
+    error::Result,
+    geometry::Aabb,
+    traits::{FallibleNumeric, Scalar, SplitStrategy},
+};
+
+/// Splits nodes by the binned Surface Area Heuristic (SAH), as selected by the default `SplitMethod`.
+///
+/// Shape centres are binned into `sah_buckets` buckets along each axis, and the plane between buckets with the lowest
+/// estimated traversal cost is chosen, provided it is cheaper than testing every shape in a leaf.
+/// Ties between equally costly splits go to the lowest axis then the lowest split position.
+#[derive(Debug, Clone)]
+pub struct SahSplit<T: Scalar> {
+    /// Bucket scratch buffer of shape counts and bounds, reused across nodes and axes.
+    buckets: Vec<(usize, Aabb<T>)>,
+}
+
+impl<T: Scalar> SahSplit<T> {
+    /// Construct a new `SahSplit` instance.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { buckets: Vec::new() }
+    }
+}
+
+impl<T: Scalar> Default for SahSplit<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Scalar> SplitStrategy<T> for SahSplit<T> {
+    fn split(&mut self, config: &BvhConfig<T>, bounds: &Aabb<T>, shapes: &[Aabb<T>]) -> Result<Option<Split<T>>> {
+        let node_surface_area = bounds.surface_area()?;
+
+        if node_surface_area <= T::zero() {
+            return Ok(None);
+        }
+
+        let mut best_split: Option<(Split<T>, T)> = None;
+
+        // Try all three axes
+        for axis in 0..3 {
+            let extent = bounds.maxs[axis] - bounds.mins[axis];
+            if extent <= T::zero() {
+                continue;
+            }
+
+            // Reset the buckets for this axis
+            let buckets = &mut self.buckets;
+            buckets.clear();
+            buckets.resize(config.sah_buckets, (0, Aabb::empty()?));
+
+            // Assign primitives to buckets
+            for shape_aabb in shapes {
+                let centroid = shape_aabb.centre()?;
+
+                let bucket_index = ((centroid[axis] - bounds.mins[axis]) / extent * T::try_from_usize(config.sah_buckets)?)
+                    .floor()
+                    .to_usize()
+                    .unwrap_or(0)
+                    .min(config.sah_buckets - 1);
+
+                buckets[bucket_index].0 += 1;
+                if buckets[bucket_index].0 == 1 {
+                    buckets[bucket_index].1 = shape_aabb.clone();
+                } else {
+                    buckets[bucket_index].1 = buckets[bucket_index].1.merge(shape_aabb)?;
+                }
+            }
+
+            // Evaluate splits between buckets
+            for split_bucket in 1..config.sah_buckets {
+                // Calculate left side
+                let mut left_count = 0;
+                let mut left_aabb: Option<Aabb<T>> = None;
+                for bucket in &buckets[..split_bucket] {
+                    if bucket.0 > 0 {
+                        left_count += bucket.0;
+                        left_aabb = Some(left_aabb.map_or_else(|| Ok(bucket.1.clone()), |aabb| aabb.merge(&bucket.1))?);
+                    }
+                }
+
+                // Calculate right side
+                let mut right_count = 0;
+                let mut right_aabb: Option<Aabb<T>> = None;
+                for bucket in &buckets[split_bucket..] {
+                    if bucket.0 > 0 {
+                        right_count += bucket.0;
+                        right_aabb = Some(right_aabb.map_or_else(|| Ok(bucket.1.clone()), |aabb| aabb.merge(&bucket.1))?);
+                    }
+                }
+
+                // Skip invalid splits
+                if left_count == 0 || right_count == 0 {
+                    continue;
+                }
+
+                // Calculate SAH cost
+                let left_surface_area = left_aabb
+                    .as_ref()
+                    .map(Aabb::surface_area)
+                    .transpose()?
+                    .unwrap_or_else(T::zero);
+
+                let right_surface_area = right_aabb
+                    .as_ref()
+                    .map(Aabb::surface_area)
+                    .transpose()?
+                    .unwrap_or_else(T::zero);
+
+                let cost = config.traverse_cost
+                    + (left_surface_area / node_surface_area) * T::try_from_usize(left_count)? * config.intersect_cost
+                    + (right_surface_area / node_surface_area) * T::try_from_usize(right_count)? * config.intersect_cost;
+
+                let split_position =
+                    bounds.mins[axis] + extent * T::try_from_usize(split_bucket)? / T::try_from_usize(config.sah_buckets)?;
+
+                // Strict comparison keeps the first of equally costly splits, and never selects a NaN cost
+                if best_split
+                    .as_ref()
+                    .map_or_else(|| cost.is_finite(), |(_, best_cost)| cost < *best_cost)
+                {
+                    best_split = Some((Split::new(axis, split_position), cost));
+                }
+            }
+        }
+
+        // Splitting must be cheaper than testing every shape in a leaf
+        let leaf_cost = T::try_from_usize(shapes.len())? * config.intersect_cost;
+        Ok(best_split.and_then(|(split, cost)| (cost < leaf_cost).then_some(split)))
+    }
+}
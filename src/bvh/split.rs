@@ -0,0 +1,20 @@
+//! Bounding Volume Hierarchy node split structure.
+
+use crate::traits::Scalar;
+
+/// Plane splitting a Bounding Volume Hierarchy node, as chosen by a `SplitStrategy`.
+/// Shapes whose bounding box centre lies below `position` along `axis` go to the left child, the rest to the right.
+#[derive(Debug, Clone, Copy)]
+pub struct Split<T: Scalar> {
+    /// Axis normal to the plane. 0 = x-axis, 1 = y-axis, 2 = z-axis.
+    pub axis: usize,
+    /// Position of the plane along the axis.
+    pub position: T,
+}
+
+impl<T: Scalar> Split<T> {
+    /// Construct a new `Split` instance.
+    pub const fn new(axis: usize, position: T) -> Self {
+        Self { axis, position }
+    }
+}
@@ -0,0 +1,16 @@
+//! Bounding Volume Hierarchy split method enumeration.
+
+use serde::{Deserialize, Serialize};
+
+/// Built-in heuristic used to split `Bvh` nodes, selected in the `BvhConfig`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SplitMethod {
+    /// Binned Surface Area Heuristic (`SahSplit`). Slowest to build, but gives the fastest traversal.
+    #[default]
+    Sah,
+    /// Split at the median shape centre (`MedianSplit`), giving balanced trees without evaluating any costs.
+    Median,
+    /// Split at the middle of the shape centres (`MiddleSplit`). Fastest to build,
+    /// for hierarchies rebuilt every frame, such as those of small dynamic scenes.
+    Middle,
+}
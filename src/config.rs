@@ -8,3 +8,19 @@ pub const DEFAULT_SAH_BUCKETS: usize = 16;
 pub const DEFAULT_MAX_SHAPES_PER_NODE: usize = 4;
 /// Maximum depth of the `Bvh`.
 pub const DEFAULT_MAX_DEPTH: usize = 64;
+/// `Scene` ray bias, as a fraction of the diagonal of the `Scene` bounding box.
+pub const DEFAULT_RAY_BIAS_SCALE: f64 = 1e-4;
+/// Directions of the `Ray`s cast to test whether a point lies inside a closed `Mesh`, skewed away from the axes
+/// so they rarely graze the edges of axis-aligned geometry.
+pub const CONTAINMENT_DIRECTIONS: [[f64; 3]; 3] = [
+    [0.871_201, 0.352_113, 0.341_873],
+    [-0.297_315, 0.904_712, 0.305_291],
+    [0.313_497, -0.281_406, 0.906_923],
+];
+/// Boundary crossings along a `Ray` after which the remaining boundaries of a `Medium`-bearing object are ignored,
+/// so a `Ray` trapped by a zero ray bias cannot loop forever.
+pub const MAX_MEDIUM_CROSSINGS: usize = 1024;
+/// Consecutive rejected candidates after which interior sampling gives up, treating the geometry as enclosing no volume.
+pub const INTERIOR_SAMPLE_ATTEMPTS: usize = 10_000;
+/// Maximum number of times a `CurveSegment` is halved when intersected, limiting the cost of very curved or thin segments.
+pub const MAX_CURVE_SUBDIVISIONS: usize = 10;
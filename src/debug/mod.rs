@@ -0,0 +1,5 @@
+//! Debug visualisation of scenes, acceleration structures and traced rays.
+
+mod obj_recorder;
+
+pub use obj_recorder::ObjRecorder;
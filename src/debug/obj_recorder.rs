@@ -0,0 +1,115 @@
+//! Wavefront recording of debug geometry.
+
+use nalgebra::Point3;
+#[cfg(feature = "fs")]
+use std::{fs::write, path::Path};
+
+use crate::{
+    error::Result,
+    geometry::{Aabb, Triangle},
+    serialization::WavefrontWriter,
+    traits::{DebugLogger, Scalar},
+};
+
+/// `DebugLogger` which records geometry as a wavefront (.obj) file, with one object per logged entity.
+///
+/// Points and segments are written as OBJ point and line elements, and boxes as closed polylines,
+/// so the recording opens in any viewer which draws them, such as Blender, `MeshLab` or rerun.io.
+#[derive(Debug, Clone, Default)]
+pub struct ObjRecorder {
+    /// Recorded OBJ statements.
+    writer: WavefrontWriter,
+}
+
+impl ObjRecorder {
+    /// Construct a new empty `ObjRecorder`.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the number of vertices recorded so far.
+    #[must_use]
+    pub const fn vertex_count(&self) -> usize {
+        self.writer.vertex_count()
+    }
+
+    /// Write the recording as a wavefront (.obj) string.
+    #[must_use]
+    pub fn to_wavefront(&self) -> String {
+        self.writer.as_str().to_string()
+    }
+
+    /// Save the recording as a wavefront (.obj) file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    #[cfg(feature = "fs")]
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        Ok(write(path, self.writer.as_str())?)
+    }
+
+    /// Start a new object, and record its vertices.
+    /// Returns the (1-based) OBJ index of the first vertex.
+    fn begin<T: Scalar>(&mut self, entity: &str, vertices: impl IntoIterator<Item = Point3<T>>) -> usize {
+        self.writer.object(entity);
+        let first = self.writer.vertex_count() + 1; // OBJ indices are 1-based
+        for v in vertices {
+            let _unused = self.writer.vertex(&v, None);
+        }
+        first
+    }
+}
+
+impl<T: Scalar> DebugLogger<T> for ObjRecorder {
+    fn log_points(&mut self, entity: &str, points: &[Point3<T>]) -> Result<()> {
+        let first = self.begin(entity, points.iter().copied());
+        for index in first..first + points.len() {
+            self.writer.point(index);
+        }
+        Ok(())
+    }
+
+    fn log_segments(&mut self, entity: &str, segments: &[[Point3<T>; 2]]) -> Result<()> {
+        let first = self.begin(entity, segments.iter().flatten().copied());
+        for index in (first..first + 2 * segments.len()).step_by(2) {
+            self.writer.line(&[index, index + 1]);
+        }
+        Ok(())
+    }
+
+    fn log_boxes(&mut self, entity: &str, boxes: &[Aabb<T>]) -> Result<()> {
+        let corners = boxes.iter().flat_map(|aabb| {
+            let (a, b) = (aabb.mins, aabb.maxs);
+            [
+                Point3::new(a.x, a.y, a.z),
+                Point3::new(b.x, a.y, a.z),
+                Point3::new(b.x, b.y, a.z),
+                Point3::new(a.x, b.y, a.z),
+                Point3::new(a.x, a.y, b.z),
+                Point3::new(b.x, a.y, b.z),
+                Point3::new(b.x, b.y, b.z),
+                Point3::new(a.x, b.y, b.z),
+            ]
+        });
+        let first = self.begin(entity, corners);
+        for i in (first..first + 8 * boxes.len()).step_by(8) {
+            // Bottom and top faces as closed loops, then the four vertical edges
+            self.writer.line(&[i, i + 1, i + 2, i + 3, i]);
+            self.writer.line(&[i + 4, i + 5, i + 6, i + 7, i + 4]);
+            for corner in i..i + 4 {
+                self.writer.line(&[corner, corner + 4]);
+            }
+        }
+        Ok(())
+    }
+
+    fn log_triangles(&mut self, entity: &str, triangles: &[Triangle<T>]) -> Result<()> {
+        let first = self.begin(entity, triangles.iter().flat_map(Triangle::vertices));
+        for index in (first..first + 3 * triangles.len()).step_by(3) {
+            self.writer.face([index, index + 1, index + 2], None, None);
+        }
+        Ok(())
+    }
+}
@@ -7,4 +7,6 @@ pub enum BvhError {
     EmptyGeometry,
     #[error("Bvh must contain at least one node, but found none.")]
     EmptyNodes,
+    #[error("Bvh does not match the geometry it is used with.")]
+    MismatchedGeometry,
 }
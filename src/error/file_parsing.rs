@@ -12,12 +12,27 @@ pub enum FileParsingError {
     #[error("Missing vertex normal data at line {line}")]
     MissingVertexNormal { line: usize },
 
+    #[error("Missing texture coordinate data at line {line}")]
+    MissingTextureCoordinate { line: usize },
+
     #[error("Invalid face data at line {line}: {message}")]
     InvalidFaceData { line: usize, message: String },
 
     #[error("Invalid coordinate value '{value}' at line {line}")]
     InvalidCoordinate { value: String, line: usize },
 
+    #[error("Invalid glTF file format: {message}")]
+    InvalidGltfFormat { message: String },
+
+    #[error("Invalid base64 character '{character}'")]
+    InvalidBase64 { character: char },
+
     #[error("File not found: {path}")]
     FileNotFound { path: String },
+
+    #[error("File is not valid UTF-8: {path}")]
+    InvalidUtf8 { path: String },
+
+    #[error("Unsupported mesh file format: {path}")]
+    UnsupportedFormat { path: String },
 }
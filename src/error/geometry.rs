@@ -19,6 +19,12 @@ pub enum GeometryError {
         max_z: String,
     },
 
+    #[error("Invalid k-DOP bounds: slab {slab} has min={min} greater than max={max}")]
+    InvalidKdopBounds { slab: usize, min: String, max: String },
+
+    #[error("Mismatched k-DOP slab count: expected {expected} slabs, found {count}")]
+    MismatchedKdopSlabs { expected: usize, count: usize },
+
     #[error("Invalid intersection distance: {distance}, must be non-negative")]
     NegativeIntersectionDistance { distance: String },
 
@@ -29,4 +35,52 @@ pub enum GeometryError {
         res_height: usize,
         res_width: usize,
     },
+
+    #[error("Vertex index {index} out of bounds for {count} vertices")]
+    VertexIndexOutOfBounds { index: usize, count: usize },
+
+    #[error("Mismatched vertex normal count: {normals} normals for {positions} positions")]
+    MismatchedNormalCount { positions: usize, normals: usize },
+
+    #[error("Mismatched vertex texture coordinate count: {uvs} texture coordinates for {positions} positions")]
+    MismatchedUvCount { positions: usize, uvs: usize },
+
+    #[error("Mismatched vertex colour count: {colors} colours for {positions} positions")]
+    MismatchedColorCount { positions: usize, colors: usize },
+
+    #[error("Invalid decimation cell size: {size}, must be positive and finite")]
+    InvalidCellSize { size: String },
+
+    #[error("Invalid curve control point count: {count}, must be 3n + 1 for at least one segment")]
+    InvalidCurvePointCount { count: usize },
+
+    #[error("Mismatched curve radius count: {radii} radii for {segments} segments, expected one more radius than segments")]
+    MismatchedCurveRadiusCount { segments: usize, radii: usize },
+
+    #[error("Invalid heightmap resolution: width={width}, height={height}, both must be positive")]
+    InvalidHeightmapResolution { width: usize, height: usize },
+
+    #[error("Mismatched heightmap value count: {values} values, expected {expected}")]
+    MismatchedHeightmapValues { values: usize, expected: usize },
+
+    #[error("Invalid heightmap: every height must be finite")]
+    InvalidHeightmapHeight,
+
+    #[error("Cannot displace triangle {index}, which has no texture coordinates to look up its heights")]
+    MissingDisplacementUvs { index: usize },
+
+    #[error("Cannot sample a surface with zero area")]
+    ZeroArea,
+
+    #[error("Cannot sample the interior of a geometry with zero volume")]
+    ZeroVolume,
+
+    #[error("Sphere casts are not supported by this geometry")]
+    UnsupportedSphereCast,
+
+    #[error("Geometry is unbounded, so has no finite bounding box")]
+    UnboundedGeometry,
+
+    #[error("Invalid ray: origin={origin}, direction={direction}, both must be finite")]
+    NonFiniteRay { origin: String, direction: String },
 }
@@ -0,0 +1,26 @@
+use thiserror::Error;
+
+/// Specific error type for participating `Medium` construction.
+#[derive(Error, Debug)]
+pub enum MediumError {
+    #[error("Invalid extinction: {extinction}, must be non-negative and finite")]
+    InvalidExtinction { extinction: String },
+
+    #[error("Invalid albedo: {albedo}, must lie within [0, 1]")]
+    InvalidAlbedo { albedo: String },
+
+    #[error("Invalid height falloff: {falloff}, must be non-negative and finite")]
+    InvalidFalloff { falloff: String },
+
+    #[error("Invalid density grid resolution [{nx}, {ny}, {nz}], every axis must hold at least one voxel")]
+    InvalidGridResolution { nx: usize, ny: usize, nz: usize },
+
+    #[error("Density grid holds {values} values, but its resolution requires {expected}")]
+    MismatchedGridValues { values: usize, expected: usize },
+
+    #[error("Density grid contains a negative or non-finite density")]
+    InvalidGridDensity,
+
+    #[error("Density grid bounds must have positive extent on every axis")]
+    FlatGridBounds,
+}
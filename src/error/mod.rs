@@ -4,7 +4,9 @@ mod bvh;
 mod bvh_config;
 mod file_parsing;
 mod geometry;
+mod medium;
 mod numeric;
+mod render;
 mod scene;
 mod transformation;
 
@@ -12,7 +14,9 @@ pub use bvh::BvhError;
 pub use bvh_config::BvhConfigError;
 pub use file_parsing::FileParsingError;
 pub use geometry::GeometryError;
+pub use medium::MediumError;
 pub use numeric::NumericError;
+pub use render::RenderError;
 pub use scene::SceneError;
 pub use transformation::TransformationError;
 
@@ -46,6 +50,9 @@ pub enum GeodesicError {
     /// - BVH traverse cost or intersect cost set to zero or negative values
     /// - Invalid SAH bucket counts or maximum depth settings
     /// - Camera resolution set to zero dimensions
+    /// - Depth render near plane not positive or beyond the far plane
+    /// - Export tessellation settings too coarse or non-positive
+    /// - Participating medium with negative extinction or an albedo outside [0, 1]
     #[error("Invalid configuration: {0}")]
     InvalidConfiguration(String),
 
@@ -160,12 +167,24 @@ impl From<GeometryError> for GeodesicError {
     }
 }
 
+impl From<MediumError> for GeodesicError {
+    fn from(err: MediumError) -> Self {
+        Self::InvalidConfiguration(err.to_string())
+    }
+}
+
 impl From<NumericError> for GeodesicError {
     fn from(err: NumericError) -> Self {
         Self::Math(err.to_string())
     }
 }
 
+impl From<RenderError> for GeodesicError {
+    fn from(err: RenderError) -> Self {
+        Self::InvalidConfiguration(err.to_string())
+    }
+}
+
 impl From<SceneError> for GeodesicError {
     fn from(err: SceneError) -> Self {
         Self::SceneConstruction(err.to_string())
@@ -0,0 +1,69 @@
+use thiserror::Error;
+
+/// Specific error type for render pass configuration.
+#[derive(Error, Debug)]
+pub enum RenderError {
+    #[error("Invalid depth range: near={near}, far={far}, near must be positive and less than far")]
+    InvalidDepthRange { near: String, far: String },
+
+    #[error("Invalid tessellation: sphere_segments={segments} must be at least 4 and plane_extent={extent} must be positive")]
+    InvalidTessellation { segments: usize, extent: String },
+
+    #[error("Invalid march distance: far={far}, must be positive and finite")]
+    InvalidMarchDistance { far: String },
+
+    #[error("Mismatched ray bundle: {rays} rays for shape [{rows}, {cols}]")]
+    MismatchedRayBundle { rays: usize, rows: usize, cols: usize },
+
+    #[error("Mismatched film: film of [{rows}, {cols}] pixels for camera of [{camera_rows}, {camera_cols}] pixels")]
+    MismatchedFilm {
+        rows: usize,
+        cols: usize,
+        camera_rows: usize,
+        camera_cols: usize,
+    },
+
+    #[error(
+        "Invalid adaptive sampling: min_samples={min} must be at least 2, batch_samples={batch} positive, \
+         max_samples={max} at least min_samples and threshold={threshold} non-negative"
+    )]
+    InvalidAdaptiveSampling {
+        min: usize,
+        batch: usize,
+        max: usize,
+        threshold: String,
+    },
+
+    #[error("No photons: a photon map must trace at least one photon")]
+    NoPhotons,
+
+    #[error("No occlusion samples: ambient occlusion must be estimated from at least one sample")]
+    NoOcclusionSamples,
+
+    #[error("Missing texture coordinates: no triangle of the mesh has texture coordinates to bake into")]
+    MissingTextureCoordinates,
+
+    #[error("Ambiguous camera orientation: exactly one of look_at and orientation must be given")]
+    AmbiguousCameraOrientation,
+
+    #[error("Camera with ID '{id}' not found")]
+    CameraNotFound { id: String },
+
+    #[error("Camera with ID '{id}' already exists")]
+    DuplicateCameraId { id: String },
+
+    #[error("Invalid clip range: near={near}, far={far}, near must be positive and less than far")]
+    InvalidClipRange { near: String, far: String },
+
+    #[error("Invalid bake distance: max_distance={distance}, must be positive and finite")]
+    InvalidBakeDistance { distance: String },
+
+    #[error("Invalid gather radius: radius={radius}, must be positive and finite")]
+    InvalidGatherRadius { radius: String },
+
+    #[error("Invalid render settings: tile_size={tile_size} and sample_batch={sample_batch} must be positive")]
+    InvalidRenderSettings { tile_size: usize, sample_batch: usize },
+
+    #[error("Thread pool error: {message}")]
+    ThreadPool { message: String },
+}
@@ -12,6 +12,36 @@ pub enum SceneError {
     #[error("Asset with ID '{id}' already exists")]
     DuplicateAssetId { id: String },
 
+    #[error("Asset with ID '{id}' requires {required} bytes, but only {available} bytes of the memory budget remain")]
+    MemoryBudgetExceeded { id: String, required: usize, available: usize },
+
     #[error("Asset with ID '{id}' not found")]
     AssetNotFound { id: String },
+
+    #[error("Instance references a mesh which is not registered in the assets")]
+    UnregisteredMesh,
+
+    #[error("Scene object cannot be serialized: {reason}")]
+    UnserializableObject { reason: String },
+
+    #[error("Patch references object {index}, but the scene contains {count} objects")]
+    PatchIndexOutOfBounds { index: usize, count: usize },
+
+    #[error("Patch retransforms object {index}, which is not an instance")]
+    NotAnInstance { index: usize },
+
+    #[error("Form factor estimation requires at least one sample")]
+    NoFormFactorSamples,
+
+    #[error("Medium attached to object {index}, but the scene contains {count} objects")]
+    MediumIndexOutOfBounds { index: usize, count: usize },
+
+    #[error("Medium attached to object {index}, which does not enclose a volume")]
+    OpenMediumBoundary { index: usize },
+
+    #[error("Single scattering estimation requires at least one step")]
+    NoScatteringSteps,
+
+    #[error("Payload attached to object {index}, but the scene contains {count} objects")]
+    PayloadIndexOutOfBounds { index: usize, count: usize },
 }
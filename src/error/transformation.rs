@@ -8,4 +8,13 @@ pub enum TransformationError {
 
     #[error("Invalid transformation matrix")]
     InvalidMatrix,
+
+    #[error("Transformation must be a rotation, translation and uniform scale to preserve this shape")]
+    NonUniformScale,
+
+    #[error("Rotation quaternion or axis must have a non-zero length")]
+    DegenerateRotation,
+
+    #[error("Transformation matrix cannot be combined with translation, rotation or scale components")]
+    ConflictingComponents,
 }
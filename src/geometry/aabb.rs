@@ -1,24 +1,25 @@
 //! Axis-aligned bounding box structure.
 
-use nalgebra::{Matrix4, Point3, RealField};
+use nalgebra::{Matrix4, Point3, Vector3};
+use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
 
 use crate::{
     error::{GeometryError, Result},
     rt::Ray,
-    traits::{Bounded, FallibleNumeric},
+    traits::{Bounded, BoundingVolume, Bounds, DistanceField, FallibleNumeric, Scalar, Transformable},
 };
 
 /// Axis-aligned bounding box.
-#[derive(Debug, Clone)]
-pub struct Aabb<T: RealField + Copy> {
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Aabb<T: Scalar> {
     /// Minimum corner.
     pub mins: Point3<T>,
     /// Maximum corner.
     pub maxs: Point3<T>,
 }
 
-impl<T: RealField + Copy> Aabb<T> {
+impl<T: Scalar> Aabb<T> {
     /// Construct a new `Aabb` instance.
     ///
     /// # Errors
@@ -39,6 +40,15 @@ impl<T: RealField + Copy> Aabb<T> {
         Ok(Self { mins, maxs })
     }
 
+    /// Construct the smallest `Aabb` enclosing a collection of points.
+    /// Returns `None` if there are no points.
+    pub fn from_points<I: IntoIterator<Item = Point3<T>>>(points: I) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        let (mins, maxs) = points.fold((first, first), |(mins, maxs), point| (mins.inf(&point), maxs.sup(&point)));
+        Some(Self { mins, maxs })
+    }
+
     /// Create an 'empty' `Aabb` with extreme bounds.
     ///
     /// # Errors
@@ -67,28 +77,40 @@ impl<T: RealField + Copy> Aabb<T> {
         ))
     }
 
+    /// Calculate the side lengths of the `Aabb` along each axis.
+    pub fn extents(&self) -> Vector3<T> {
+        self.maxs - self.mins
+    }
+
+    /// Get the axis along which the `Aabb` is longest. 0 = x-axis, 1 = y-axis, 2 = z-axis.
+    /// Ties go to the lowest axis.
+    pub fn longest_axis(&self) -> usize {
+        self.extents().imax()
+    }
+
+    /// Get the interval [min, max] of the dot products of the points of the `Aabb` with a direction.
+    #[must_use]
+    pub fn span(&self, direction: &Vector3<T>) -> [T; 2] {
+        (0..3).fold([T::zero(); 2], |[min, max], i| {
+            let (a, b) = (self.mins[i] * direction[i], self.maxs[i] * direction[i]);
+            [min + a.min(b), max + a.max(b)]
+        })
+    }
+
     /// Calculate the surface area of an `Aabb`.
     ///
     /// # Errors
     ///
     /// Returns an error if numeric type conversion fails (e.g., converting 2 to type `T`).
     pub fn surface_area(&self) -> Result<T> {
-        let extent = [
-            self.maxs[0] - self.mins[0],
-            self.maxs[1] - self.mins[1],
-            self.maxs[2] - self.mins[2],
-        ];
+        let extent = self.extents();
         let two = T::try_from_u8(2)?;
         Ok(two * ((extent[0] * extent[1]) + (extent[1] * extent[2]) + (extent[2] * extent[0])))
     }
 
     /// Calculate the volume of an `Aabb`.
     pub fn volume(&self) -> T {
-        let extent = [
-            self.maxs[0] - self.mins[0],
-            self.maxs[1] - self.mins[1],
-            self.maxs[2] - self.mins[2],
-        ];
+        let extent = self.extents();
         extent[0] * extent[1] * extent[2]
     }
 
@@ -111,6 +133,56 @@ impl<T: RealField + Copy> Aabb<T> {
         Self::new(new_mins, new_maxs)
     }
 
+    /// Return the region shared by this `Aabb` and another `Aabb`.
+    /// Returns `None` if they do not overlap. `Aabb`s which only touch share a flat region.
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let mins = self.mins.sup(&other.mins);
+        let maxs = self.maxs.inf(&other.maxs);
+        (mins.x <= maxs.x && mins.y <= maxs.y && mins.z <= maxs.z).then_some(Self { mins, maxs })
+    }
+
+    /// Return a copy of the `Aabb` grown by `margin` on every side. A negative `margin` shrinks it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a negative `margin` would invert the bounds.
+    pub fn expand(&self, margin: T) -> Result<Self> {
+        let margin = Vector3::repeat(margin);
+        Self::new(self.mins - margin, self.maxs + margin)
+    }
+
+    /// Check if a point lies inside or on the surface of this `Aabb`.
+    #[must_use]
+    pub fn contains_point(&self, point: &Point3<T>) -> bool {
+        self.mins.x <= point.x
+            && self.mins.y <= point.y
+            && self.mins.z <= point.z
+            && self.maxs.x >= point.x
+            && self.maxs.y >= point.y
+            && self.maxs.z >= point.z
+    }
+
+    /// Squared distance from a point to the nearest point of the `Aabb`, which is zero inside it.
+    /// Cheap enough to prune hierarchy nodes in nearest-surface searches.
+    #[must_use]
+    pub fn distance_squared(&self, point: &Point3<T>) -> T {
+        let below = self.mins - point;
+        let above = point - self.maxs;
+        below.sup(&above).sup(&Vector3::zeros()).norm_squared()
+    }
+
+    /// Check if this `Aabb` fully encloses another `Aabb`.
+    #[must_use]
+    pub fn contains_aabb(&self, other: &Self) -> bool {
+        self.mins.x <= other.mins.x
+            && self.mins.y <= other.mins.y
+            && self.mins.z <= other.mins.z
+            && self.maxs.x >= other.maxs.x
+            && self.maxs.y >= other.maxs.y
+            && self.maxs.z >= other.maxs.z
+    }
+
     /// Apply a transformation to the `Aabb`.
     ///
     /// # Errors
@@ -158,10 +230,27 @@ impl<T: RealField + Copy> Aabb<T> {
     ///
     /// # Errors
     ///
-    /// Returns an error if mathematical operations fail or if numeric bounds cannot be determined.
+    /// Returns an error if numeric bounds cannot be determined.
     pub fn intersect_any(&self, ray: &Ray<T>) -> Result<bool> {
+        Ok(self.intersect_within(ray, T::try_max_value()?).is_some())
+    }
+
+    /// Test for an intersection between a `Ray` and the `Aabb`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if numeric bounds cannot be determined.
+    pub fn intersect_distance(&self, ray: &Ray<T>) -> Result<Option<T>> {
+        Ok(self.intersect_within(ray, T::try_max_value()?))
+    }
+
+    /// Distance along the `Ray` at which it enters the `Aabb`, if it does so within `max_distance`.
+    /// A `Ray` starting inside the `Aabb` enters at zero.
+    /// Unlike `intersect_distance` this cannot fail, so per-ray traversal loops carry no error handling.
+    #[must_use]
+    pub fn intersect_within(&self, ray: &Ray<T>, max_distance: T) -> Option<T> {
         let mut t_min = T::zero();
-        let mut t_max = T::try_max_value()?;
+        let mut t_max = max_distance;
 
         // Use pre-computed inverse directions from Ray struct
         for i in 0..3 {
@@ -170,10 +259,10 @@ impl<T: RealField + Copy> Aabb<T> {
             let box_min_i = self.mins[i];
             let box_max_i = self.maxs[i];
 
-            // Check for parallel ray (inv_direction will be inf/-inf)
+            // Check for parallel ray (inv_direction will be inf/-inf, or NaN for a NaN direction)
             if !inv_dir_i.is_finite() {
-                if ray_origin_i < box_min_i || ray_origin_i > box_max_i {
-                    return Ok(false);
+                if inv_dir_i.partial_cmp(&T::zero()).is_none() || !(box_min_i..=box_max_i).contains(&ray_origin_i) {
+                    return None;
                 }
                 continue;
             }
@@ -182,6 +271,11 @@ impl<T: RealField + Copy> Aabb<T> {
             let t0 = (box_min_i - ray_origin_i) * inv_dir_i;
             let t1 = (box_max_i - ray_origin_i) * inv_dir_i;
 
+            // NaN origins or bounds give unordered distances, which never intersect
+            if t0.is_nan() || t1.is_nan() {
+                return None;
+            }
+
             // Use ray.sign for branchless min/max
             let t_near = if ray.sign[i] == 0 { t0 } else { t1 };
             let t_far = if ray.sign[i] == 0 { t1 } else { t0 };
@@ -191,69 +285,65 @@ impl<T: RealField + Copy> Aabb<T> {
 
             // Early exit if no intersection
             if t_min > t_max {
-                return Ok(false);
+                return None;
             }
         }
 
-        // If the maximum distance is negative, the box is behind the ray
-        Ok(match t_max.partial_cmp(&T::zero()) {
-            Some(std::cmp::Ordering::Less) => false,
-            Some(_) => true,
-            None => unimplemented!("t_max is NaN, cannot determine intersection"),
-        })
+        Some(t_min)
     }
+}
 
-    /// Test for an intersection between a `Ray` and the `Aabb`.
-    ///
-    /// # Errors
-    ///
-    /// Returns an error if mathematical operations fail or if numeric bounds cannot be determined.
-    pub fn intersect_distance(&self, ray: &Ray<T>) -> Result<Option<T>> {
-        let mut t_min = T::zero();
-        let mut t_max = T::try_max_value()?;
+impl<T: Scalar> Bounded<T> for Aabb<T> {
+    fn aabb(&self) -> Result<Bounds<'_, T>> {
+        Ok(Bounds::Finite(Cow::Borrowed(self)))
+    }
+}
 
-        // Use pre-computed inverse directions from Ray struct
-        for i in 0..3 {
-            let ray_origin_i = ray.origin[i];
-            let inv_dir_i = ray.inv_direction[i];
-            let box_min_i = self.mins[i];
-            let box_max_i = self.maxs[i];
+impl<T: Scalar> BoundingVolume<T> for Aabb<T> {
+    fn enclose<B: Bounded<T> + ?Sized>(shape: &B) -> Result<Option<Self>> {
+        Ok(shape.aabb()?.finite().map(Cow::into_owned))
+    }
 
-            // Check for parallel ray (inv_direction will be inf/-inf)
-            if !inv_dir_i.is_finite() {
-                if ray_origin_i < box_min_i || ray_origin_i > box_max_i {
-                    return Ok(None);
-                }
-                continue;
-            }
+    fn merge(&self, other: &Self) -> Result<Self> {
+        Self::merge(self, other)
+    }
 
-            // Use pre-computed inverse direction
-            let t0 = (box_min_i - ray_origin_i) * inv_dir_i;
-            let t1 = (box_max_i - ray_origin_i) * inv_dir_i;
+    fn expand(&self, margin: T) -> Result<Self> {
+        Self::expand(self, margin)
+    }
 
-            // Use ray.sign for branchless min/max
-            let t_near = if ray.sign[i] == 0 { t0 } else { t1 };
-            let t_far = if ray.sign[i] == 0 { t1 } else { t0 };
+    fn contains(&self, other: &Self) -> bool {
+        self.contains_aabb(other)
+    }
 
-            t_min = t_min.max(t_near);
-            t_max = t_max.min(t_far);
+    fn overlaps(&self, other: &Self) -> bool {
+        self.intersection(other).is_some()
+    }
 
-            // Early exit if no intersection
-            if t_min > t_max {
-                return Ok(None);
-            }
-        }
+    fn intersect_within(&self, ray: &Ray<T>, max_distance: T) -> Option<T> {
+        Self::intersect_within(self, ray, max_distance)
+    }
 
-        if t_max < T::zero() {
-            return Ok(None);
-        }
+    fn bounding_box(&self) -> Result<Cow<'_, Self>> {
+        Ok(Cow::Borrowed(self))
+    }
+}
 
-        Ok(Some(if t_min >= T::zero() { t_min } else { t_max }))
+impl<T: Scalar> DistanceField<T> for Aabb<T> {
+    /// Distance to the nearest face, negative inside the `Aabb`.
+    fn signed_distance(&self, point: &Point3<T>) -> Result<T> {
+        let below = self.mins - point;
+        let above = point - self.maxs;
+        let offset = below.sup(&above);
+        let outside = offset.sup(&Vector3::zeros()).norm();
+        let inside = offset.max().min(T::zero());
+        Ok(outside + inside)
     }
 }
 
-impl<T: RealField + Copy> Bounded<T> for Aabb<T> {
-    fn aabb(&self) -> Result<Cow<Self>> {
-        Ok(Cow::Borrowed(self))
+impl<T: Scalar> Transformable<T> for Aabb<T> {
+    /// Compute the `Aabb` enclosing the transformed corners of this `Aabb`.
+    fn transform(&self, transform: &Matrix4<T>) -> Result<Self> {
+        Self::transform(self, transform)
     }
 }
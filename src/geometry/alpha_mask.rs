@@ -0,0 +1,44 @@
+//! Alpha mask structure.
+
+use crate::{error::Result, geometry::Heightmap, traits::Scalar};
+
+/// Opacity texture cutting holes out of a surface, such as the gaps between the leaves of a foliage card.
+///
+/// Opacities are sampled like the heights of a `Heightmap`, at the texture coordinates of each `Hit`.
+/// Where they fall below the `cutoff`, the surface is transparent and `Ray`s pass through it.
+#[derive(Debug, Clone)]
+pub struct AlphaMask<T: Scalar> {
+    /// Opacity of each texel, from zero (transparent) to one (opaque).
+    opacity: Heightmap<T>,
+    /// Opacity below which the surface is transparent.
+    cutoff: T,
+}
+
+impl<T: Scalar> AlphaMask<T> {
+    /// Construct a new `AlphaMask` instance, transparent wherever the opacity falls below the `cutoff`.
+    #[must_use]
+    pub const fn new(opacity: Heightmap<T>, cutoff: T) -> Self {
+        Self { opacity, cutoff }
+    }
+
+    /// Get the texel opacities.
+    #[must_use]
+    pub const fn opacity(&self) -> &Heightmap<T> {
+        &self.opacity
+    }
+
+    /// Get the opacity below which the surface is transparent.
+    #[must_use]
+    pub const fn cutoff(&self) -> T {
+        self.cutoff
+    }
+
+    /// Check if the surface is opaque at texture coordinates [u, v].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the opacity cannot be sampled.
+    pub fn is_opaque(&self, uv: [T; 2]) -> Result<bool> {
+        Ok(self.opacity.height(uv)? >= self.cutoff)
+    }
+}
@@ -0,0 +1,171 @@
+//! Capsule structure.
+
+use nalgebra::{Matrix4, Point3, Unit, Vector3};
+use std::borrow::Cow;
+
+use crate::{
+    error::{GeometryError, Result},
+    geometry::Aabb,
+    rt::{Hit, Ray},
+    traits::{Bounded, Bounds, DistanceField, Scalar, Traceable, Transformable, uniform_scale},
+};
+
+/// Capsule structure: every point within a radius of the line segment between two end points.
+#[derive(Debug, Clone)]
+pub struct Capsule<T: Scalar> {
+    /// Centre of the first hemispherical end.
+    pub start: Point3<T>,
+    /// Centre of the second hemispherical end.
+    pub end: Point3<T>,
+    /// Radius of the capsule.
+    pub radius: T,
+}
+
+impl<T: Scalar> Capsule<T> {
+    /// Construct a new `Capsule` instance.
+    /// Coincident end points give a sphere.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the radius is negative.
+    pub fn new(start: Point3<T>, end: Point3<T>, radius: T) -> Result<Self> {
+        if radius < T::zero() {
+            return Err(GeometryError::InvalidRadius {
+                radius: format!("{radius:?}"),
+            }
+            .into());
+        }
+        Ok(Self { start, end, radius })
+    }
+
+    /// Find the point of the axis segment nearest to a given point.
+    #[must_use]
+    pub fn closest_axis_point(&self, point: &Point3<T>) -> Point3<T> {
+        let axis = self.end - self.start;
+        let axis_squared = axis.norm_squared();
+        if axis_squared <= T::zero() {
+            return self.start;
+        }
+        let along = ((point - self.start).dot(&axis) / axis_squared).clamp(T::zero(), T::one());
+        self.start + axis * along
+    }
+
+    /// Distance along the `Ray` to the nearest intersection in front of its origin, if any.
+    /// Both crossings of the cylindrical side and of each hemispherical end are considered,
+    /// so a `Ray` starting inside the `Capsule` hits it on the way out.
+    fn nearest_root(&self, ray: &Ray<T>) -> Option<T> {
+        let epsilon = T::default_epsilon();
+        let mut nearest: Option<T> = None;
+        let mut consider = |t: T| {
+            if t > epsilon && nearest.is_none_or(|n| t < n) {
+                nearest = Some(t);
+            }
+        };
+
+        // Cylindrical side, as a quadratic in the distance travelled scaled by the squared axis length
+        let axis = self.end - self.start;
+        let to_origin = ray.origin - self.start;
+        let axis_squared = axis.norm_squared();
+        let axis_direction = axis.dot(&ray.direction);
+        let axis_origin = axis.dot(&to_origin);
+        let a = axis_squared - axis_direction * axis_direction;
+        let half_b = axis_squared * ray.direction.dot(&to_origin) - axis_origin * axis_direction;
+        let c = axis_squared * to_origin.norm_squared() - axis_origin * axis_origin - self.radius.powi(2) * axis_squared;
+        let discriminant = half_b.powi(2) - a * c;
+        if a > T::zero() && discriminant >= T::zero() {
+            let sqrt_discriminant = discriminant.sqrt();
+            for t in [(-half_b - sqrt_discriminant) / a, (-half_b + sqrt_discriminant) / a] {
+                let along = axis_origin + t * axis_direction;
+                if along >= T::zero() && along <= axis_squared {
+                    consider(t);
+                }
+            }
+        }
+
+        // Hemispherical ends, only beyond their end of the axis
+        for (centre, sign) in [(self.start, -T::one()), (self.end, T::one())] {
+            let oc = ray.origin - centre;
+            let half_b = oc.dot(&ray.direction);
+            let discriminant = half_b.powi(2) - (oc.norm_squared() - self.radius.powi(2));
+            if discriminant < T::zero() {
+                continue;
+            }
+            let sqrt_discriminant = discriminant.sqrt();
+            for t in [-half_b - sqrt_discriminant, -half_b + sqrt_discriminant] {
+                if (oc + ray.direction.scale(t)).dot(&axis) * sign >= T::zero() {
+                    consider(t);
+                }
+            }
+        }
+
+        nearest
+    }
+
+    /// Outward normal at a point on the surface of the `Capsule`.
+    fn normal_at(&self, point: &Point3<T>) -> Unit<Vector3<T>> {
+        Unit::new_normalize(point - self.closest_axis_point(point))
+    }
+}
+
+impl<T: Scalar> Bounded<T> for Capsule<T> {
+    fn aabb(&self) -> Result<Bounds<'_, T>> {
+        let r = Vector3::new(self.radius, self.radius, self.radius);
+        Ok(Bounds::Finite(Cow::Owned(Aabb::new(
+            self.start.inf(&self.end) - r,
+            self.start.sup(&self.end) + r,
+        )?)))
+    }
+
+    fn span(&self, direction: &Vector3<T>) -> Result<Option<[T; 2]>> {
+        let (start, end) = (self.start.coords.dot(direction), self.end.coords.dot(direction));
+        let reach = self.radius * direction.norm();
+        Ok(Some([start.min(end) - reach, start.max(end) + reach]))
+    }
+}
+
+impl<T: Scalar> DistanceField<T> for Capsule<T> {
+    fn signed_distance(&self, point: &Point3<T>) -> Result<T> {
+        Ok((point - self.closest_axis_point(point)).norm() - self.radius)
+    }
+}
+
+impl<T: Scalar> Traceable<T> for Capsule<T> {
+    fn intersect(&self, ray: &Ray<T>) -> Result<Option<Hit<T>>> {
+        let Some(t) = self.nearest_root(ray) else {
+            return Ok(None);
+        };
+        let normal = self.normal_at(&(ray.origin + ray.direction.scale(t)));
+        Ok(Some(Hit::new_unchecked(0, t, normal, normal)))
+    }
+
+    fn intersect_t(&self, ray: &Ray<T>) -> Result<Option<T>> {
+        Ok(self.nearest_root(ray))
+    }
+
+    fn sphere_cast(&self, ray: &Ray<T>, radius: T) -> Result<Option<Hit<T>>> {
+        // The swept sphere touches this capsule when its centre enters the capsule of the combined radius
+        let combined = Self {
+            start: self.start,
+            end: self.end,
+            radius: self.radius + radius,
+        };
+        if combined.signed_distance(&ray.origin)? <= T::zero() {
+            return Ok(None);
+        }
+        Ok(combined.nearest_root(ray).map(|t| {
+            let normal = self.normal_at(&(ray.origin + ray.direction.scale(t)));
+            Hit::new_unchecked(0, t, normal, normal)
+        }))
+    }
+}
+
+impl<T: Scalar> Transformable<T> for Capsule<T> {
+    /// Transform the `Capsule`, which must keep its shape (rotation, translation and uniform scale only).
+    fn transform(&self, transform: &Matrix4<T>) -> Result<Self> {
+        Self::new(
+            transform.transform_point(&self.start),
+            transform.transform_point(&self.end),
+            self.radius * uniform_scale(transform)?,
+        )
+    }
+}
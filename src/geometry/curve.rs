@@ -0,0 +1,139 @@
+//! Curve structure.
+
+use nalgebra::{Matrix4, Point3, Vector3};
+use std::sync::Arc;
+
+use crate::{
+    bvh::{Bvh, BvhConfig},
+    error::{GeometryError, Result},
+    geometry::{CurveSegment, CurveShape},
+    rt::{Hit, Ray},
+    traits::{Bounded, Bounds, FallibleNumeric, Scalar, Traceable, Transformable},
+};
+
+/// Curve structure: a collection of `CurveSegment`s with a `Bvh` over them, for fibres such as hair, grass and cables,
+/// which would take far too many `Triangle`s to mesh at render resolution.
+///
+/// Clones share their segments and `Bvh`, so many copies of the same `Curve` are cheap.
+/// `Hit`s are indexed by the segment which was hit.
+#[derive(Debug, Clone)]
+pub struct Curve<T: Scalar> {
+    /// Segments of the curve.
+    segments: Arc<[CurveSegment<T>]>,
+    /// Acceleration structure over the segments.
+    bvh: Arc<Bvh<T>>,
+}
+
+impl<T: Scalar> Curve<T> {
+    /// Construct a new `Curve` instance.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if BVH construction fails for the provided segments.
+    pub fn new(bvh_config: &BvhConfig<T>, segments: Vec<CurveSegment<T>>) -> Result<Self> {
+        let bvh = Bvh::new(bvh_config, &segments)?;
+        Ok(Self {
+            segments: segments.into(),
+            bvh: Arc::new(bvh),
+        })
+    }
+
+    /// Construct a `Curve` of joined segments from a strand of control points,
+    /// with each segment starting on the last point of the one before, as in a piecewise Bezier spline.
+    /// `n` segments take `3n + 1` points, and `n + 1` radii: one at each end of every segment.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The number of points is not `3n + 1` for at least one segment
+    /// - The number of radii is not one more than the number of segments
+    /// - Any radius is negative
+    /// - BVH construction fails
+    pub fn from_points(bvh_config: &BvhConfig<T>, points: &[Point3<T>], radii: &[T], shape: CurveShape) -> Result<Self> {
+        if points.len() < 4 || points.len() % 3 != 1 {
+            return Err(GeometryError::InvalidCurvePointCount { count: points.len() }.into());
+        }
+        let count = points.len() / 3;
+        if radii.len() != count + 1 {
+            return Err(GeometryError::MismatchedCurveRadiusCount {
+                segments: count,
+                radii: radii.len(),
+            }
+            .into());
+        }
+
+        let segments = (0..count)
+            .map(|index| {
+                let control_points = [0, 1, 2, 3].map(|offset| points[3 * index + offset]);
+                CurveSegment::new(control_points, [radii[index], radii[index + 1]], shape)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Self::new(bvh_config, segments)
+    }
+
+    /// Get a reference to the `CurveSegment`s in this `Curve`.
+    #[must_use]
+    pub fn segments(&self) -> &[CurveSegment<T>] {
+        &self.segments
+    }
+
+    /// Get a reference to the `Bvh` acceleration structure.
+    #[must_use]
+    pub fn bvh(&self) -> &Bvh<T> {
+        &self.bvh
+    }
+}
+
+impl<T: Scalar> Bounded<T> for Curve<T> {
+    /// Borrow the root bounds of the `Bvh`, which enclose every `CurveSegment`.
+    fn aabb(&self) -> Result<Bounds<'_, T>> {
+        self.bvh.aabb()
+    }
+
+    fn span(&self, direction: &Vector3<T>) -> Result<Option<[T; 2]>> {
+        let mut span: Option<[T; 2]> = None;
+        for segment in self.segments.iter() {
+            if let Some([min, max]) = segment.span(direction)? {
+                span = Some(span.map_or([min, max], |[lower, upper]| [lower.min(min), upper.max(max)]));
+            }
+        }
+        Ok(span)
+    }
+}
+
+impl<T: Scalar> Traceable<T> for Curve<T> {
+    fn intersect(&self, ray: &Ray<T>) -> Result<Option<Hit<T>>> {
+        self.intersect_within(ray, T::try_max_value()?)
+    }
+
+    fn intersect_within(&self, ray: &Ray<T>, max_distance: T) -> Result<Option<Hit<T>>> {
+        self.bvh.intersect_within(ray, &self.segments, max_distance).map(|opt| {
+            opt.map(|(segment_index, mut hit)| {
+                hit.index = segment_index;
+                hit
+            })
+        })
+    }
+
+    fn intersect_any(&self, ray: &Ray<T>, max_distance: T) -> Result<bool> {
+        self.bvh.intersect_any(ray, &self.segments, max_distance)
+    }
+}
+
+impl<T: Scalar> Transformable<T> for Curve<T> {
+    /// Transform every `CurveSegment` of the `Curve`, which must keep its shape (rotation, translation and uniform scale only).
+    /// The `Bvh` topology is kept and its bounds are refitted, rather than rebuilding it from scratch.
+    fn transform(&self, transform: &Matrix4<T>) -> Result<Self> {
+        let segments = self
+            .segments
+            .iter()
+            .map(|segment| segment.transform(transform))
+            .collect::<Result<Vec<_>>>()?;
+        let mut bvh = Bvh::clone(&self.bvh);
+        bvh.refit(&segments)?;
+        Ok(Self {
+            segments: segments.into(),
+            bvh: Arc::new(bvh),
+        })
+    }
+}
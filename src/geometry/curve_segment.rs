@@ -0,0 +1,235 @@
+//! Cubic Bezier curve segment structure.
+
+use nalgebra::{Matrix4, Point3, Vector3, center};
+use std::{borrow::Cow, cmp::Ordering, f64::consts::SQRT_2};
+
+use crate::{
+    config::MAX_CURVE_SUBDIVISIONS,
+    error::{GeometryError, Result},
+    geometry::{Aabb, Capsule, CurveShape},
+    rt::{Hit, Ray},
+    traits::{Bounded, Bounds, FallibleNumeric, Scalar, Traceable, Transformable, uniform_scale},
+};
+
+/// Cubic Bezier segment of a `Curve`, swept by a cross-section whose radius varies linearly along it.
+///
+/// `Ray`s are intersected by halving the segment until each piece is close to its chord,
+/// pruning pieces whose bounds the `Ray` misses, then testing the chords.
+/// `Hit`s carry texture coordinates [u, 0], where u runs from zero at the start of the segment to one at its end.
+#[derive(Debug, Clone)]
+pub struct CurveSegment<T: Scalar> {
+    /// Bezier control points. The segment passes through the first and last, and is pulled towards the others.
+    pub control_points: [Point3<T>; 4],
+    /// Radius at the start and end of the segment.
+    pub radii: [T; 2],
+    /// Cross-section swept along the segment.
+    pub shape: CurveShape,
+}
+
+impl<T: Scalar> CurveSegment<T> {
+    /// Construct a new `CurveSegment` instance.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either radius is negative.
+    pub fn new(control_points: [Point3<T>; 4], radii: [T; 2], shape: CurveShape) -> Result<Self> {
+        if let Some(radius) = radii.iter().find(|&&radius| radius < T::zero()) {
+            return Err(GeometryError::InvalidRadius {
+                radius: format!("{radius:?}"),
+            }
+            .into());
+        }
+        Ok(Self {
+            control_points,
+            radii,
+            shape,
+        })
+    }
+
+    /// Get the point on the axis of the segment at parameter `u`, from zero at the start to one at the end.
+    #[must_use]
+    pub fn point(&self, u: T) -> Point3<T> {
+        let lerp = |a: &Point3<T>, b: &Point3<T>| a + (b - a) * u;
+        let [p0, p1, p2, p3] = &self.control_points;
+        let (q0, q1, q2) = (lerp(p0, p1), lerp(p1, p2), lerp(p2, p3));
+        let (r0, r1) = (lerp(&q0, &q1), lerp(&q1, &q2));
+        lerp(&r0, &r1)
+    }
+
+    /// Get the radius of the segment at parameter `u`, from zero at the start to one at the end.
+    #[must_use]
+    pub fn radius(&self, u: T) -> T {
+        self.radii[0] + (self.radii[1] - self.radii[0]) * u
+    }
+
+    /// Number of times to halve the segment before testing chords, so each chord strays from the axis
+    /// by no more than a tenth of the radius. Straighter and thicker segments need fewer halvings.
+    fn subdivisions(&self) -> Result<usize> {
+        // The largest second difference of the control points bounds the curvature of the segment
+        let [p0, p1, p2, p3] = self.control_points;
+        let bend = ((p0 - p1) - (p1 - p2)).norm().max(((p1 - p2) - (p2 - p3)).norm());
+        let ratio = T::try_from_f64(7.5 * SQRT_2)? * bend / self.radii[0].max(self.radii[1]);
+        // A NaN ratio comes from a straight segment of zero radius, which needs no halving
+        if ratio.partial_cmp(&T::one()) != Some(Ordering::Greater) {
+            return Ok(0);
+        }
+
+        // Each halving quarters the distance between the pieces and their chords
+        let halvings = (ratio.log2() * T::try_from_f64(0.5)?).round();
+        Ok(halvings
+            .to_usize()
+            .map_or(MAX_CURVE_SUBDIVISIONS, |halvings| halvings.min(MAX_CURVE_SUBDIVISIONS)))
+    }
+
+    /// Nearest intersection in front of the `Ray` origin and nearer than `max_distance`, if any.
+    fn nearest(&self, ray: &Ray<T>, max_distance: T) -> Result<Option<Hit<T>>> {
+        let mut nearest = None;
+        self.nearest_recursive(
+            ray,
+            &self.control_points,
+            [T::zero(), T::one()],
+            self.subdivisions()?,
+            max_distance,
+            &mut nearest,
+        )?;
+        Ok(nearest)
+    }
+
+    /// Recursive helper for intersection, over the piece of the segment with the given control points
+    /// and parameter range, keeping the nearest intersection found so far.
+    fn nearest_recursive(
+        &self,
+        ray: &Ray<T>,
+        points: &[Point3<T>; 4],
+        range: [T; 2],
+        depth: usize,
+        max_distance: T,
+        nearest: &mut Option<Hit<T>>,
+    ) -> Result<()> {
+        let limit = nearest.as_ref().map_or(max_distance, |hit| hit.distance);
+        let [start_radius, end_radius] = range.map(|u| self.radius(u));
+
+        // Each piece lies within the convex hull of its control points, grown by its radius
+        let Some(hull) = Aabb::from_points(points.iter().copied()) else {
+            return Ok(());
+        };
+        if hull
+            .expand(start_radius.max(end_radius))?
+            .intersect_within(ray, limit)
+            .is_none()
+        {
+            return Ok(());
+        }
+
+        if depth > 0 {
+            let [p0, p1, p2, p3] = points;
+            let (q0, q1, q2) = (center(p0, p1), center(p1, p2), center(p2, p3));
+            let (r0, r1) = (center(&q0, &q1), center(&q1, &q2));
+            let middle_point = center(&r0, &r1);
+            let middle = (range[0] + range[1]) * T::try_from_f64(0.5)?;
+            self.nearest_recursive(
+                ray,
+                &[*p0, q0, r0, middle_point],
+                [range[0], middle],
+                depth - 1,
+                max_distance,
+                nearest,
+            )?;
+            return self.nearest_recursive(
+                ray,
+                &[middle_point, r1, q2, *p3],
+                [middle, range[1]],
+                depth - 1,
+                max_distance,
+                nearest,
+            );
+        }
+
+        // Test the chord of the piece, swept by the cross-section
+        let [start, _, _, end] = *points;
+        let axis = end - start;
+        let candidate = match self.shape {
+            CurveShape::Round => {
+                let capsule = Capsule {
+                    start,
+                    end,
+                    radius: (start_radius + end_radius) * T::try_from_f64(0.5)?,
+                };
+                capsule.intersect(ray)?.map(|hit| {
+                    let point = ray.origin + ray.direction.scale(hit.distance);
+                    let axis_squared = axis.norm_squared();
+                    let along = if axis_squared > T::zero() {
+                        ((point - start).dot(&axis) / axis_squared).clamp(T::zero(), T::one())
+                    } else {
+                        T::zero()
+                    };
+                    (hit.distance, along, hit.geometric_normal)
+                })
+            }
+            CurveShape::Flat => {
+                // Closest approach of the chord to the `Ray`, measured across the `Ray`
+                let across = |vector: Vector3<T>| vector - ray.direction.scale(vector.dot(&ray.direction));
+                let offset = across(start - ray.origin);
+                let chord = across(axis);
+                let chord_squared = chord.norm_squared();
+                let along = if chord_squared > T::zero() {
+                    (-offset.dot(&chord) / chord_squared).clamp(T::zero(), T::one())
+                } else {
+                    T::zero()
+                };
+                let radius = start_radius + (end_radius - start_radius) * along;
+                ((offset + chord * along).norm_squared() <= radius * radius)
+                    .then(|| ((start + axis * along - ray.origin).dot(&ray.direction), along, -ray.direction))
+            }
+        };
+
+        if let Some((distance, along, normal)) = candidate
+            && distance > T::default_epsilon()
+            && distance < limit
+        {
+            let mut hit = Hit::new_unchecked(0, distance, normal, normal);
+            hit.uv = Some([range[0] + (range[1] - range[0]) * along, T::zero()]);
+            *nearest = Some(hit);
+        }
+        Ok(())
+    }
+}
+
+impl<T: Scalar> Bounded<T> for CurveSegment<T> {
+    /// Bounds the control points rather than the segment itself, so may be slightly loose.
+    fn aabb(&self) -> Result<Bounds<'_, T>> {
+        let hull = Aabb::from_points(self.control_points).ok_or(GeometryError::UnboundedGeometry)?;
+        Ok(Bounds::Finite(Cow::Owned(hull.expand(self.radii[0].max(self.radii[1]))?)))
+    }
+
+    fn span(&self, direction: &Vector3<T>) -> Result<Option<[T; 2]>> {
+        let reach = self.radii[0].max(self.radii[1]) * direction.norm();
+        let [first, rest @ ..] = self.control_points.map(|point| point.coords.dot(direction));
+        let [min, max] = rest
+            .into_iter()
+            .fold([first; 2], |[min, max], distance| [min.min(distance), max.max(distance)]);
+        Ok(Some([min - reach, max + reach]))
+    }
+}
+
+impl<T: Scalar> Traceable<T> for CurveSegment<T> {
+    fn intersect(&self, ray: &Ray<T>) -> Result<Option<Hit<T>>> {
+        self.intersect_within(ray, T::try_max_value()?)
+    }
+
+    fn intersect_within(&self, ray: &Ray<T>, max_distance: T) -> Result<Option<Hit<T>>> {
+        self.nearest(ray, max_distance)
+    }
+}
+
+impl<T: Scalar> Transformable<T> for CurveSegment<T> {
+    /// Transform the `CurveSegment`, which must keep its shape (rotation, translation and uniform scale only).
+    fn transform(&self, transform: &Matrix4<T>) -> Result<Self> {
+        let scale = uniform_scale(transform)?;
+        Self::new(
+            self.control_points.map(|point| transform.transform_point(&point)),
+            self.radii.map(|radius| radius * scale),
+            self.shape,
+        )
+    }
+}
@@ -0,0 +1,14 @@
+//! Curve cross-section enumeration.
+
+use serde::{Deserialize, Serialize};
+
+/// Cross-section of a `Curve`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CurveShape {
+    /// Flat ribbon which always faces the incoming `Ray`, with a normal pointing back along it.
+    /// Cheapest to test, and indistinguishable from a tube for fibres spanning a few pixels, such as distant hair or grass.
+    Flat,
+    /// Tube with a round cross-section and outward normals, for curves seen up close, such as cables.
+    #[default]
+    Round,
+}
@@ -0,0 +1,36 @@
+//! Displacement mapping structure.
+
+use crate::{geometry::Heightmap, traits::Scalar};
+
+/// Heightmap displacement applied to the surface of a `Mesh` by `Mesh::displace`.
+///
+/// Each vertex is moved along its normal by the height found at its texture coordinates, times the `scale`.
+/// `Triangle`s may first be subdivided, so the surface can carry more detail than the source `Mesh`.
+#[derive(Debug, Clone)]
+pub struct Displacement<T: Scalar> {
+    /// Heights, looked up by the texture coordinates of each vertex.
+    pub heightmap: Heightmap<T>,
+    /// Distance moved along the vertex normal per unit of height. Negative scales push the surface inwards.
+    pub scale: T,
+    /// Number of times each `Triangle` is split into four before displacing.
+    pub subdivisions: usize,
+}
+
+impl<T: Scalar> Displacement<T> {
+    /// Construct a new `Displacement` instance, without subdivision.
+    #[must_use]
+    pub const fn new(heightmap: Heightmap<T>, scale: T) -> Self {
+        Self {
+            heightmap,
+            scale,
+            subdivisions: 0,
+        }
+    }
+
+    /// Set the number of times each `Triangle` is split into four before displacing.
+    #[must_use]
+    pub const fn with_subdivisions(mut self, subdivisions: usize) -> Self {
+        self.subdivisions = subdivisions;
+        self
+    }
+}
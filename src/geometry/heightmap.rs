@@ -0,0 +1,108 @@
+//! Heightmap structure.
+
+use std::any::type_name;
+
+use crate::{
+    error::{GeometryError, NumericError, Result},
+    traits::{FallibleNumeric, Scalar},
+};
+
+/// Regular grid of heights spanning texture coordinates [0, 1] in u and v,
+/// sampled with bilinear interpolation between texel centres and repeated beyond, so it tiles across a surface.
+#[derive(Debug, Clone)]
+pub struct Heightmap<T: Scalar> {
+    /// Number of texels along each axis [u, v].
+    resolution: [usize; 2],
+    /// Texel heights, with u varying fastest and v slowest.
+    values: Vec<T>,
+}
+
+impl<T: Scalar> Heightmap<T> {
+    /// Construct a new `Heightmap` from texel heights ordered with u varying fastest and v slowest.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - Either axis of the resolution is zero
+    /// - The number of values does not match the resolution
+    /// - Any height is not finite
+    pub fn new(resolution: [usize; 2], values: Vec<T>) -> Result<Self> {
+        let [width, height] = resolution;
+        if width == 0 || height == 0 {
+            return Err(GeometryError::InvalidHeightmapResolution { width, height }.into());
+        }
+        let expected = width * height;
+        if values.len() != expected {
+            return Err(GeometryError::MismatchedHeightmapValues {
+                values: values.len(),
+                expected,
+            }
+            .into());
+        }
+        if values.iter().any(|value| !value.is_finite()) {
+            return Err(GeometryError::InvalidHeightmapHeight.into());
+        }
+
+        Ok(Self { resolution, values })
+    }
+
+    /// Get the number of texels along each axis [u, v].
+    #[must_use]
+    pub const fn resolution(&self) -> [usize; 2] {
+        self.resolution
+    }
+
+    /// Get the texel heights, with u varying fastest and v slowest.
+    #[must_use]
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+
+    /// Height at texture coordinates [u, v], interpolated bilinearly between texel centres.
+    /// Coordinates outside [0, 1] wrap around, and interpolation across the edges blends with the opposite side.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the coordinates are not finite, or numeric conversion fails.
+    pub fn height(&self, uv: [T; 2]) -> Result<T> {
+        let half = T::try_from_f32(0.5)?;
+        let mut lower = [0; 2];
+        let mut upper = [0; 2];
+        let mut weights = [T::zero(); 2];
+        for axis in 0..2 {
+            let count = self.resolution[axis];
+            let wrapped = uv[axis] - uv[axis].floor();
+            let position = wrapped * T::try_from_usize(count)? - half;
+            let floor = position.floor();
+            // Positions before the first texel centre blend the last texel with the first
+            lower[axis] = if floor < T::zero() {
+                count - 1
+            } else {
+                to_usize(floor)?.min(count - 1)
+            };
+            upper[axis] = (lower[axis] + 1) % count;
+            weights[axis] = position - floor;
+        }
+
+        let [width, _] = self.resolution;
+        let value = |u: usize, v: usize| self.values[v * width + u];
+        let [wu, wv] = weights;
+        let bottom = value(lower[0], lower[1]) * (T::one() - wu) + value(upper[0], lower[1]) * wu;
+        let top = value(lower[0], upper[1]) * (T::one() - wu) + value(upper[0], upper[1]) * wu;
+        Ok(bottom * (T::one() - wv) + top * wv)
+    }
+}
+
+// == Utility functions ==
+
+/// Convert a non-negative whole value to `usize`.
+///
+/// # Errors
+///
+/// Returns an error if the value cannot be represented as a `usize`.
+fn to_usize<T: Scalar>(value: T) -> Result<usize> {
+    Ok(value.to_usize().ok_or_else(|| NumericError::TypeConversion {
+        from_type: type_name::<T>().to_string(),
+        to_type: "usize".to_string(),
+    })?)
+}
@@ -0,0 +1,222 @@
+//! Discrete oriented polytope structure.
+
+use nalgebra::{Point3, Vector3};
+use serde::{Deserialize, Serialize};
+use std::{array::from_fn, borrow::Cow};
+
+use crate::{
+    error::{GeodesicError, GeometryError, Result},
+    geometry::Aabb,
+    rt::Ray,
+    traits::{Bounded, BoundingVolume, Scalar},
+};
+
+/// Slab directions: the coordinate axes, then the six face diagonals, then the four corner diagonals.
+const DIRECTIONS: [[i8; 3]; 13] = [
+    [1, 0, 0],
+    [0, 1, 0],
+    [0, 0, 1],
+    [1, 1, 0],
+    [1, 0, 1],
+    [0, 1, 1],
+    [1, -1, 0],
+    [1, 0, -1],
+    [0, 1, -1],
+    [1, 1, 1],
+    [1, 1, -1],
+    [1, -1, 1],
+    [-1, 1, 1],
+];
+
+/// 14-DOP: slabs along the coordinate axes and the corner diagonals.
+pub type Dop14<T> = Kdop<T, 7>;
+/// 18-DOP: slabs along the coordinate axes and the face diagonals.
+pub type Dop18<T> = Kdop<T, 9>;
+/// 26-DOP: slabs along the coordinate axes, the face diagonals and the corner diagonals.
+pub type Dop26<T> = Kdop<T, 13>;
+
+/// Discrete oriented polytope (k-DOP): the intersection of `N` slabs along fixed directions, bounded by `2N` planes.
+///
+/// The first three slabs are always the coordinate axes, so a `Kdop` is never looser than the `Aabb` of its contents.
+/// The remaining slabs lie along diagonals, cutting the corners off the box around thin diagonal geometry.
+/// Only `N` of 7 (`Dop14`), 9 (`Dop18`) and 13 (`Dop26`) are supported; other values fail to compile.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(into = "SerializedKdop<T>", try_from = "SerializedKdop<T>")]
+pub struct Kdop<T: Scalar, const N: usize> {
+    /// Lower bound of each slab, as the dot product of its direction with the enclosed points.
+    mins: [T; N],
+    /// Upper bound of each slab, as the dot product of its direction with the enclosed points.
+    maxs: [T; N],
+}
+
+impl<T: Scalar, const N: usize> Kdop<T, N> {
+    /// Construct a new `Kdop` instance from the bounds of each slab, ordered as `directions`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any lower bound exceeds its corresponding upper bound.
+    pub fn new(mins: [T; N], maxs: [T; N]) -> Result<Self> {
+        if let Some(slab) = (0..N).find(|&slab| mins[slab] > maxs[slab]) {
+            return Err(GeometryError::InvalidKdopBounds {
+                slab,
+                min: format!("{:?}", mins[slab]),
+                max: format!("{:?}", maxs[slab]),
+            }
+            .into());
+        }
+        Ok(Self { mins, maxs })
+    }
+
+    /// Get the (unnormalised) direction of each slab.
+    #[must_use]
+    pub fn directions() -> [Vector3<T>; N] {
+        from_fn(|slab| {
+            let direction = Self::direction(slab);
+            Vector3::from_fn(|i, _| match direction[i] {
+                1 => T::one(),
+                -1 => -T::one(),
+                _ => T::zero(),
+            })
+        })
+    }
+
+    /// Get the lower bound of each slab.
+    #[must_use]
+    pub const fn mins(&self) -> &[T; N] {
+        &self.mins
+    }
+
+    /// Get the upper bound of each slab.
+    #[must_use]
+    pub const fn maxs(&self) -> &[T; N] {
+        &self.maxs
+    }
+
+    /// Get the direction of a slab, as signs of its components.
+    const fn direction(slab: usize) -> [i8; 3] {
+        const { assert!(N == 7 || N == 9 || N == 13, "a Kdop must have 7, 9 or 13 slabs") };
+        if N == 7 && slab >= 3 {
+            DIRECTIONS[slab + 6]
+        } else {
+            DIRECTIONS[slab]
+        }
+    }
+
+    /// Dot product of a vector with the direction of a slab.
+    fn project(vector: &Vector3<T>, slab: usize) -> T {
+        let direction = Self::direction(slab);
+        (0..3).fold(T::zero(), |sum, i| match direction[i] {
+            1 => sum + vector[i],
+            -1 => sum - vector[i],
+            _ => sum,
+        })
+    }
+}
+
+impl<T: Scalar, const N: usize> BoundingVolume<T> for Kdop<T, N> {
+    fn enclose<B: Bounded<T> + ?Sized>(shape: &B) -> Result<Option<Self>> {
+        let mut mins = [T::zero(); N];
+        let mut maxs = [T::zero(); N];
+        for (slab, direction) in Self::directions().iter().enumerate() {
+            let Some([min, max]) = shape.span(direction)? else {
+                return Ok(None);
+            };
+            mins[slab] = min;
+            maxs[slab] = max;
+        }
+        Self::new(mins, maxs).map(Some)
+    }
+
+    fn merge(&self, other: &Self) -> Result<Self> {
+        Self::new(
+            from_fn(|slab| self.mins[slab].min(other.mins[slab])),
+            from_fn(|slab| self.maxs[slab].max(other.maxs[slab])),
+        )
+    }
+
+    fn expand(&self, margin: T) -> Result<Self> {
+        let reach = Self::directions().map(|direction| margin * direction.norm());
+        Self::new(
+            from_fn(|slab| self.mins[slab] - reach[slab]),
+            from_fn(|slab| self.maxs[slab] + reach[slab]),
+        )
+    }
+
+    fn contains(&self, other: &Self) -> bool {
+        (0..N).all(|slab| self.mins[slab] <= other.mins[slab] && self.maxs[slab] >= other.maxs[slab])
+    }
+
+    /// Tests the slabs only, so two `Kdop`s may be reported to overlap when separated along some other direction.
+    fn overlaps(&self, other: &Self) -> bool {
+        (0..N).all(|slab| self.mins[slab] <= other.maxs[slab] && other.mins[slab] <= self.maxs[slab])
+    }
+
+    fn intersect_within(&self, ray: &Ray<T>, max_distance: T) -> Option<T> {
+        let mut t_min = T::zero();
+        let mut t_max = max_distance;
+        for slab in 0..N {
+            let origin = Self::project(&ray.origin.coords, slab);
+            let speed = Self::project(&ray.direction, slab);
+
+            // A `Ray` parallel to the slab must start between its planes (a NaN speed never does)
+            if speed == T::zero() {
+                if !(self.mins[slab]..=self.maxs[slab]).contains(&origin) {
+                    return None;
+                }
+                continue;
+            }
+
+            let t0 = (self.mins[slab] - origin) / speed;
+            let t1 = (self.maxs[slab] - origin) / speed;
+
+            // NaN origins or bounds give unordered distances, which never intersect
+            if t0.is_nan() || t1.is_nan() {
+                return None;
+            }
+
+            t_min = t_min.max(t0.min(t1));
+            t_max = t_max.min(t0.max(t1));
+            if t_min > t_max {
+                return None;
+            }
+        }
+        Some(t_min)
+    }
+
+    fn bounding_box(&self) -> Result<Cow<'_, Aabb<T>>> {
+        Ok(Cow::Owned(Aabb::new(
+            Point3::new(self.mins[0], self.mins[1], self.mins[2]),
+            Point3::new(self.maxs[0], self.maxs[1], self.maxs[2]),
+        )?))
+    }
+}
+
+/// Serialized representation of a `Kdop`, whose slab count is checked when read.
+#[derive(Serialize, Deserialize)]
+struct SerializedKdop<T> {
+    /// Lower bound of each slab.
+    mins: Vec<T>,
+    /// Upper bound of each slab.
+    maxs: Vec<T>,
+}
+
+impl<T: Scalar, const N: usize> From<Kdop<T, N>> for SerializedKdop<T> {
+    fn from(kdop: Kdop<T, N>) -> Self {
+        Self {
+            mins: kdop.mins.to_vec(),
+            maxs: kdop.maxs.to_vec(),
+        }
+    }
+}
+
+impl<T: Scalar, const N: usize> TryFrom<SerializedKdop<T>> for Kdop<T, N> {
+    type Error = GeodesicError;
+
+    fn try_from(serialized: SerializedKdop<T>) -> Result<Self> {
+        let count = serialized.mins.len().max(serialized.maxs.len());
+        let mismatch = || GeometryError::MismatchedKdopSlabs { expected: N, count };
+        let mins = serialized.mins.try_into().map_err(|_| mismatch())?;
+        let maxs = serialized.maxs.try_into().map_err(|_| mismatch())?;
+        Self::new(mins, maxs)
+    }
+}
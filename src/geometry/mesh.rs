@@ -1,43 +1,129 @@
 //! Triangle mesh structure.
 
-use nalgebra::{Point3, RealField, Unit, Vector3};
-use num_traits::ToPrimitive;
-use std::{borrow::Cow, fs::read_to_string, path::Path, str::FromStr};
+use nalgebra::{Matrix4, Point3, Unit, Vector3, center};
+use rand::Rng;
+use std::{
+    array::from_fn,
+    collections::{HashMap, HashSet},
+    str::FromStr,
+    sync::{Arc, OnceLock},
+};
+#[cfg(feature = "fs")]
+use std::{fs::read_to_string, path::Path};
 
+#[cfg(feature = "simd")]
+use crate::simd::SimdMesh;
 use crate::{
     bvh::{Bvh, BvhConfig},
-    error::{FileParsingError, Result},
-    geometry::{Aabb, Triangle},
+    config::{CONTAINMENT_DIRECTIONS, INTERIOR_SAMPLE_ATTEMPTS},
+    error::{BvhError, FileParsingError, GeometryError, Result, TransformationError},
+    geometry::{
+        AlphaMask, Displacement, MeshConfig, Triangle, TriangleIntersection, TriangleSoa, WavefrontMode, WavefrontWarning,
+    },
     rt::{Hit, Ray},
-    traits::{Bounded, Traceable},
+    scene::MemoryUsage,
+    traits::{
+        Bounded, Bounds, DistanceField, FallibleNumeric, Scalar, SurfaceSample, Traceable, Transformable, normal_matrix,
+        sample_unit,
+    },
 };
+#[cfg(feature = "simd")]
+use std::any::Any;
 
 /// Internal transient structure used to represent a `Triangle` in the `Mesh` using vertex and normal indices.
 struct Face {
     /// Indices of the vertex positions.
     vertex_indices: [usize; 3],
-    /// Indices of the vertex normals.
-    normal_indices: [usize; 3],
+    /// Indices of the vertex normals, if specified.
+    normal_indices: Option<[usize; 3]>,
+    /// Indices of the texture coordinates, if specified.
+    uv_indices: Option<[usize; 3]>,
+    /// Line of the source file on which the face is defined.
+    line: usize,
 }
 
 /// Surface composed of `Triangle`s.
-#[derive(Debug)]
-pub struct Mesh<T: RealField + Copy> {
+///
+/// The `Triangle`s, `Bvh` and lazily built traversal data are reference-counted,
+/// so cloning a `Mesh` to place it in several `Scene`s or threads does not copy them.
+#[derive(Debug, Clone)]
+pub struct Mesh<T: Scalar> {
     /// Component `Triangle` instances.
-    triangles: Vec<Triangle<T>>,
+    triangles: Arc<[Triangle<T>]>,
     /// `Bvh` acceleration structure.
-    bvh: Bvh<T>,
+    bvh: Arc<Bvh<T>>,
+    /// Number of degenerate `Triangle`s dropped during construction.
+    dropped_triangles: usize,
+    /// Configuration the `Triangle`s were prepared with, reused for `Mesh`es derived from this one.
+    config: MeshConfig,
+    /// Cumulative `Triangle` areas, computed on first use for surface sampling.
+    area_cdf: Arc<OnceLock<Vec<T>>>,
+    /// Structure-of-arrays copy of the `Triangle`s in `Bvh` leaf slot order, built on first use for scalar traversal.
+    /// Left empty when any `Triangle` requires watertight intersection or has an `AlphaMask`,
+    /// which are tested on the `Triangle`s directly.
+    soa: Arc<OnceLock<Option<Box<TriangleSoa<T>>>>>,
+    /// Packed copy of the `Bvh` and `Triangle`s for SIMD traversal, built on first use when `T` is `f32`.
+    /// Left empty when any `Triangle` requires watertight intersection or has an `AlphaMask`,
+    /// which the SIMD kernels do not implement.
+    #[cfg(feature = "simd")]
+    simd: Arc<OnceLock<Option<Box<SimdMesh>>>>,
 }
 
-impl<T: RealField + Copy + ToPrimitive> Mesh<T> {
-    /// Construct a new `Mesh` instance.
+impl<T: Scalar> Mesh<T> {
+    /// Construct a new `Mesh` instance, preparing the `Triangle`s with the default `MeshConfig`.
     ///
     /// # Errors
     ///
     /// Returns an error if BVH construction fails for the provided triangles.
     pub fn new(bvh_config: &BvhConfig<T>, triangles: Vec<Triangle<T>>) -> Result<Self> {
-        let bvh = Bvh::new(bvh_config, &triangles)?;
-        Ok(Self { triangles, bvh })
+        Self::new_with_config(bvh_config, MeshConfig::default(), triangles)
+    }
+
+    /// Construct a new `Mesh` instance.
+    /// Each `Triangle` is set to use the intersection algorithm selected by the `MeshConfig`,
+    /// and degenerate `Triangle`s are dropped if the `MeshConfig` requests it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if BVH construction fails for the provided triangles,
+    /// including when every `Triangle` is dropped as degenerate.
+    pub fn new_with_config(bvh_config: &BvhConfig<T>, mesh_config: MeshConfig, triangles: Vec<Triangle<T>>) -> Result<Self> {
+        Self::with_cached_bvh(bvh_config, mesh_config, triangles, None)
+    }
+
+    /// Construct a new `Mesh` instance as in `new_with_config`, reusing a previously built `Bvh` if it fits the prepared `Triangle`s.
+    /// Otherwise a new `Bvh` is built, so a stale or missing cache entry only costs the build it would have saved.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if BVH construction fails for the provided triangles,
+    /// including when every `Triangle` is dropped as degenerate.
+    pub fn with_cached_bvh(
+        bvh_config: &BvhConfig<T>,
+        mesh_config: MeshConfig,
+        triangles: Vec<Triangle<T>>,
+        bvh: Option<Bvh<T>>,
+    ) -> Result<Self> {
+        let (triangles, dropped_triangles) = prepare_triangles(mesh_config, triangles);
+        let bvh = match bvh {
+            Some(bvh) if bvh.fits(&triangles)? => bvh,
+            _ => Bvh::new(bvh_config, &triangles)?,
+        };
+        Ok(Self::from_parts(triangles, bvh, mesh_config, dropped_triangles))
+    }
+
+    /// Assemble a `Mesh` from prepared `Triangle`s and a `Bvh` which fits them, generating any missing vertex tangents.
+    fn from_parts(triangles: Vec<Triangle<T>>, bvh: Bvh<T>, config: MeshConfig, dropped_triangles: usize) -> Self {
+        Self {
+            triangles: with_generated_tangents(triangles).into(),
+            bvh: Arc::new(bvh),
+            dropped_triangles,
+            config,
+            area_cdf: Arc::default(),
+            soa: Arc::default(),
+            #[cfg(feature = "simd")]
+            simd: Arc::default(),
+        }
     }
 
     /// Get a reference to the `Triangle`s in this `Mesh`.
@@ -48,10 +134,195 @@ impl<T: RealField + Copy + ToPrimitive> Mesh<T> {
 
     /// Get a reference to the `Bvh` acceleration structure.
     #[must_use]
-    pub const fn bvh(&self) -> &Bvh<T> {
+    pub fn bvh(&self) -> &Bvh<T> {
         &self.bvh
     }
 
+    /// Check if this `Mesh` shares its `Triangle`s and `Bvh` with another, as clones of the same `Mesh` do.
+    #[must_use]
+    pub fn shares_data(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.triangles, &other.triangles) && Arc::ptr_eq(&self.bvh, &other.bvh)
+    }
+
+    /// Get the number of degenerate `Triangle`s dropped when the `Mesh` was constructed.
+    #[must_use]
+    pub const fn dropped_triangles(&self) -> usize {
+        self.dropped_triangles
+    }
+
+    /// Get the `MeshConfig` the `Triangle`s were prepared with.
+    #[must_use]
+    pub const fn config(&self) -> MeshConfig {
+        self.config
+    }
+
+    /// Get the number of bytes held by the `Mesh`: its `Triangle`s, its `Bvh`,
+    /// and whichever traversal and sampling caches have been built so far.
+    /// Clones share this data, so report the same usage.
+    #[must_use]
+    pub fn memory_usage(&self) -> MemoryUsage {
+        let mut other_bytes = self.area_cdf.get().map_or(0, |cdf| cdf.len() * size_of::<T>());
+        other_bytes += self.soa.get().and_then(Option::as_ref).map_or(0, |soa| soa.memory_bytes());
+        #[cfg(feature = "simd")]
+        {
+            other_bytes += self.simd.get().and_then(Option::as_ref).map_or(0, |simd| simd.memory_bytes());
+        }
+        self.bvh.memory_usage()
+            + MemoryUsage {
+                triangle_bytes: self.triangles.len() * size_of::<Triangle<T>>(),
+                other_bytes,
+                ..MemoryUsage::default()
+            }
+    }
+
+    /// Construct a simplified copy of the `Mesh` by vertex clustering.
+    /// Space is divided into cubic cells of the given size, and the vertices in each cell are merged at their mean position.
+    /// `Triangle`s left with fewer than three distinct cells, or spanning the same cells as another, are removed.
+    /// Vertex normals, texture coordinates, colours and `AlphaMask`s are kept from the original `Triangle`s,
+    /// and the new `Triangle`s are prepared with the `MeshConfig` of this `Mesh`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The cell size is not positive and finite
+    /// - Every `Triangle` is removed, or BVH construction otherwise fails
+    pub fn decimate(&self, bvh_config: &BvhConfig<T>, cell_size: T) -> Result<Self> {
+        if !(cell_size > T::zero() && cell_size.is_finite()) {
+            return Err(GeometryError::InvalidCellSize {
+                size: format!("{cell_size:?}"),
+            }
+            .into());
+        }
+
+        // Vertices too far out to be given a cell are left in place
+        let cell = |point: &Point3<T>| -> Option<[i64; 3]> {
+            let scaled = point.coords / cell_size;
+            Some([
+                scaled.x.floor().to_i64()?,
+                scaled.y.floor().to_i64()?,
+                scaled.z.floor().to_i64()?,
+            ])
+        };
+
+        let mut clusters: HashMap<[i64; 3], (Vector3<T>, T)> = HashMap::new();
+        for vertex in self.triangles.iter().flat_map(Triangle::vertices) {
+            if let Some(key) = cell(&vertex) {
+                let (sum, count) = clusters.entry(key).or_insert_with(|| (Vector3::zeros(), T::zero()));
+                *sum += vertex.coords;
+                *count += T::one();
+            }
+        }
+        let merge = |vertex: Point3<T>| {
+            cell(&vertex).map_or((None, vertex), |key| {
+                let (sum, count) = clusters[&key];
+                (Some(key), Point3::from(sum / count))
+            })
+        };
+
+        let mut spans = HashSet::new();
+        let triangles = self
+            .triangles
+            .iter()
+            .filter_map(|triangle| {
+                let [(k0, v0), (k1, v1), (k2, v2)] = triangle.vertices().map(merge);
+                if let (Some(k0), Some(k1), Some(k2)) = (k0, k1, k2) {
+                    let mut span = [k0, k1, k2];
+                    span.sort_unstable();
+                    if span[0] == span[1] || span[1] == span[2] || !spans.insert(span) {
+                        return None;
+                    }
+                }
+                let mut decimated = Triangle::new([v0, v1, v2], *triangle.normals());
+                if let Some(uvs) = triangle.uvs() {
+                    decimated = decimated.with_uvs(*uvs);
+                }
+                if let Some(colors) = triangle.colors() {
+                    decimated = decimated.with_colors(*colors);
+                }
+                if let Some(alpha_mask) = triangle.alpha_mask() {
+                    decimated = decimated.with_alpha_mask(Arc::clone(alpha_mask));
+                }
+                Some(decimated)
+            })
+            .collect();
+        Self::new_with_config(bvh_config, self.config, triangles)
+    }
+
+    /// Construct a copy of the `Mesh` with a heightmap `Displacement` applied to its surface.
+    /// `Triangle`s are first subdivided at the midpoints of their edges, interpolating every vertex attribute.
+    /// Each vertex is then moved along its normal by the height at its texture coordinates, and vertex normals
+    /// are rebuilt from the area-weighted geometric normals of the displaced `Triangle`s meeting at each vertex.
+    /// Vertices shared by neighbouring `Triangle`s stay joined only if they share normals and texture coordinates,
+    /// so hard edges and texture seams may open into cracks.
+    /// The new `Triangle`s are prepared with the `MeshConfig` of this `Mesh`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - Any `Triangle` has no texture coordinates
+    /// - A height cannot be sampled
+    /// - BVH construction fails
+    pub fn displace(&self, bvh_config: &BvhConfig<T>, displacement: &Displacement<T>) -> Result<Self> {
+        if let Some(index) = self.triangles.iter().position(|triangle| triangle.uvs().is_none()) {
+            return Err(GeometryError::MissingDisplacementUvs { index }.into());
+        }
+
+        let mut triangles = self.triangles.to_vec();
+        for _ in 0..displacement.subdivisions {
+            let mut subdivided = Vec::with_capacity(triangles.len() * 4);
+            for triangle in &triangles {
+                subdivided.extend(subdivide(triangle)?);
+            }
+            triangles = subdivided;
+        }
+
+        let mut displaced = Vec::with_capacity(triangles.len());
+        for triangle in &triangles {
+            let mut vertices = triangle.vertices();
+            if let Some(uvs) = triangle.uvs() {
+                for ((vertex, normal), uv) in vertices.iter_mut().zip(triangle.normals()).zip(uvs) {
+                    *vertex += normal.scale(displacement.heightmap.height(*uv)? * displacement.scale);
+                }
+            }
+            displaced.push(vertices);
+        }
+
+        // Vertices are matched by their exact displaced position, with negative zeros folded into positive ones
+        let key = |point: &Point3<T>| [0, 1, 2].map(|axis| point[axis].to_f64().map(|c| (c + 0.0).to_bits()));
+        let mut normal_sums: HashMap<_, Vector3<T>> = HashMap::new();
+        for [a, b, c] in &displaced {
+            let weighted = (b - a).cross(&(c - a));
+            for vertex in [a, b, c] {
+                *normal_sums.entry(key(vertex)).or_insert_with(Vector3::zeros) += weighted;
+            }
+        }
+
+        let triangles = triangles
+            .iter()
+            .zip(displaced)
+            .map(|(triangle, vertices)| {
+                let face = (vertices[1] - vertices[0]).cross(&(vertices[2] - vertices[0]));
+                let normals = [0, 1, 2].map(|corner| {
+                    Unit::try_new(normal_sums[&key(&vertices[corner])], T::zero())
+                        .or_else(|| Unit::try_new(face, T::zero()))
+                        .unwrap_or_else(|| triangle.normals()[corner])
+                });
+                let mut displaced = Triangle::new(vertices, normals);
+                if let Some(uvs) = triangle.uvs() {
+                    displaced = displaced.with_uvs(*uvs);
+                }
+                if let Some(colors) = triangle.colors() {
+                    displaced = displaced.with_colors(*colors);
+                }
+                if let Some(alpha_mask) = triangle.alpha_mask() {
+                    displaced = displaced.with_alpha_mask(Arc::clone(alpha_mask));
+                }
+                displaced
+            })
+            .collect();
+        Self::new_with_config(bvh_config, self.config, triangles)
+    }
+
     /// Load a `Mesh` from a wavefront (.obj) file.
     ///
     /// # Errors
@@ -61,7 +332,25 @@ impl<T: RealField + Copy + ToPrimitive> Mesh<T> {
     /// - The file contains invalid OBJ format data
     /// - Numeric parsing fails
     /// - BVH construction fails
+    #[cfg(feature = "fs")]
     pub fn load<P: AsRef<Path>>(bvh_config: &BvhConfig<T>, path: P) -> Result<Self>
+    where
+        T: FromStr,
+    {
+        Self::load_with_config(bvh_config, MeshConfig::default(), path)
+    }
+
+    /// Load a `Mesh` from a wavefront (.obj) file, preparing its `Triangle`s with the given `MeshConfig`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The file cannot be read
+    /// - The file contains invalid OBJ format data
+    /// - Numeric parsing fails
+    /// - BVH construction fails
+    #[cfg(feature = "fs")]
+    pub fn load_with_config<P: AsRef<Path>>(bvh_config: &BvhConfig<T>, mesh_config: MeshConfig, path: P) -> Result<Self>
     where
         T: FromStr,
     {
@@ -69,7 +358,7 @@ impl<T: RealField + Copy + ToPrimitive> Mesh<T> {
             path: path.as_ref().display().to_string(),
         })?;
 
-        Self::from_wavefront(bvh_config, &file_string)
+        Self::from_wavefront_with_config(bvh_config, mesh_config, &file_string)
     }
 
     /// Construct a `Mesh` from a wavefront (.obj) string.
@@ -86,116 +375,490 @@ impl<T: RealField + Copy + ToPrimitive> Mesh<T> {
     where
         T: FromStr,
     {
-        let mut vertices = Vec::new();
-        let mut normals = Vec::new();
-        let mut faces = Vec::new();
+        Self::from_wavefront_with_config(bvh_config, MeshConfig::default(), obj_string)
+    }
 
-        for (line_num, line) in obj_string.lines().enumerate() {
-            let tokens: Vec<&str> = line.split_whitespace().collect();
-            if tokens.is_empty() {
-                continue;
-            }
+    /// Construct a `Mesh` from a wavefront (.obj) string, preparing its `Triangle`s with the given `MeshConfig`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The OBJ string contains invalid format data
+    /// - Required vertex or normal data is missing
+    /// - Face indices are out of bounds
+    /// - Numeric parsing fails
+    /// - BVH construction fails
+    pub fn from_wavefront_with_config(bvh_config: &BvhConfig<T>, mesh_config: MeshConfig, obj_string: &str) -> Result<Self>
+    where
+        T: FromStr,
+    {
+        Self::new_with_config(bvh_config, mesh_config, parse_wavefront(obj_string)?)
+    }
 
-            match tokens.first() {
-                Some(&"v") => {
-                    if tokens.len() < 4 {
-                        return Err(FileParsingError::MissingVertexPosition { line: line_num + 1 }.into());
-                    }
-                    let vertex = parse_vertex_position(&tokens[1..], line_num + 1)?;
-                    vertices.push(vertex);
-                }
-                Some(&"vn") => {
-                    if tokens.len() < 4 {
-                        return Err(FileParsingError::MissingVertexNormal { line: line_num + 1 }.into());
-                    }
-                    let normal = parse_vertex_normal(&tokens[1..], line_num + 1)?;
-                    normals.push(normal);
-                }
-                Some(&"f") => {
-                    if tokens.len() < 4 {
-                        return Err(FileParsingError::InvalidFaceData {
-                            line: line_num + 1,
-                            message: "Face must have at least 3 vertices".to_string(),
-                        }
-                        .into());
-                    }
-                    let face = parse_face(&tokens[1..], line_num + 1)?;
-                    faces.push(face);
+    /// Construct a `Mesh` from a wavefront (.obj) string, also returning the non-fatal issues found while parsing.
+    /// In `WavefrontMode::Lenient` malformed faces are repaired or skipped, with a warning, rather than rejected.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The OBJ string contains invalid format data
+    /// - Required vertex or normal data is missing (strict mode only)
+    /// - Face indices are out of bounds (strict mode only)
+    /// - Numeric parsing fails
+    /// - BVH construction fails
+    pub fn from_wavefront_with_warnings(
+        bvh_config: &BvhConfig<T>,
+        obj_string: &str,
+        mode: WavefrontMode,
+    ) -> Result<(Self, Vec<WavefrontWarning>)>
+    where
+        T: FromStr,
+    {
+        let (triangles, warnings) = parse_wavefront_with_warnings(obj_string, mode)?;
+        Ok((Self::new(bvh_config, triangles)?, warnings))
+    }
+
+    /// Construct a `Mesh` from indexed vertex buffers, as generated procedurally or exported by other engines.
+    /// Normals and texture coordinates are indexed as `positions`; flat face normals are used if normals are omitted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - Normals or texture coordinates are given but do not match the number of positions
+    /// - A face references a non-existent vertex
+    /// - BVH construction fails
+    pub fn from_buffers(
+        bvh_config: &BvhConfig<T>,
+        positions: &[[T; 3]],
+        normals: Option<&[[T; 3]]>,
+        uvs: Option<&[[T; 2]]>,
+        indices: &[[u32; 3]],
+    ) -> Result<Self> {
+        let faces = indices.iter().map(|face| face.map(|index| index as usize));
+        Self::new(bvh_config, triangles_from_buffers(positions, normals, uvs, None, faces)?)
+    }
+
+    /// Construct a `Mesh` from `Triangle`s and a previously built `Bvh`, skipping `Bvh` construction.
+    /// The `Triangle`s are prepared as in `new`, with the default `MeshConfig`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the `Bvh` does not correctly bound the prepared `Triangle`s.
+    pub fn with_bvh(triangles: Vec<Triangle<T>>, bvh: Bvh<T>) -> Result<Self> {
+        let mesh_config = MeshConfig::default();
+        let (triangles, dropped_triangles) = prepare_triangles(mesh_config, triangles);
+        if !bvh.fits(&triangles)? {
+            return Err(BvhError::MismatchedGeometry.into());
+        }
+        Ok(Self::from_parts(triangles, bvh, mesh_config, dropped_triangles))
+    }
+
+    /// Construct a copy of the `Mesh` with an `AlphaMask` cutting holes out of every `Triangle`,
+    /// so foliage cards and fences let `Ray`s through their transparent texels. The `Bvh` is shared, not rebuilt.
+    /// The mask is looked up by texture coordinates, so has no effect on `Triangle`s without them.
+    #[must_use]
+    pub fn with_alpha_mask(&self, alpha_mask: &Arc<AlphaMask<T>>) -> Self {
+        let triangles: Vec<_> = self
+            .triangles
+            .iter()
+            .map(|triangle| triangle.clone().with_alpha_mask(Arc::clone(alpha_mask)))
+            .collect();
+        Self {
+            triangles: triangles.into(),
+            bvh: Arc::clone(&self.bvh),
+            dropped_triangles: self.dropped_triangles,
+            config: self.config,
+            area_cdf: Arc::clone(&self.area_cdf),
+            soa: Arc::default(),
+            #[cfg(feature = "simd")]
+            simd: Arc::default(),
+        }
+    }
+}
+
+impl<T: Scalar> Mesh<T> {
+    /// Find the nearest intersection of a `Ray` within `max_distance` which the `filter` accepts.
+    /// The `filter` is called on every `Hit` found during traversal, indexed by its `Triangle`,
+    /// and rejected `Hit`s are passed through as if the `Triangle` were not there, as for an `AlphaMask` on an `Instance`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an intersection test or the `filter` fails.
+    pub fn intersect_filtered<F>(&self, ray: &Ray<T>, max_distance: T, filter: F) -> Result<Option<Hit<T>>>
+    where
+        F: Fn(&Hit<T>) -> Result<bool>,
+    {
+        let hit_at = |slot: usize| -> Result<Option<Hit<T>>> {
+            let index = self.bvh.indices()[slot];
+            Ok(self.triangles[index].intersect_within(ray, max_distance)?.map(|mut hit| {
+                hit.index = index;
+                hit
+            }))
+        };
+        let Some((slot, _)) = self.bvh.intersect_slots_within(ray, max_distance, |slot| {
+            Ok(match hit_at(slot)? {
+                Some(hit) => filter(&hit)?.then_some(hit.distance),
+                None => None,
+            })
+        })?
+        else {
+            return Ok(None);
+        };
+        hit_at(slot)
+    }
+
+    /// Test if a `Ray` intersects the `Mesh` within `max_distance` anywhere the `filter` accepts,
+    /// calling it on each `Hit` found as in `intersect_filtered`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an intersection test or the `filter` fails.
+    pub fn intersect_any_filtered<F>(&self, ray: &Ray<T>, max_distance: T, filter: F) -> Result<bool>
+    where
+        F: Fn(&Hit<T>) -> Result<bool>,
+    {
+        self.bvh.intersect_any_slots(ray, max_distance, |slot| {
+            let index = self.bvh.indices()[slot];
+            self.triangles[index]
+                .intersect_within(ray, max_distance)?
+                .map_or(Ok(false), |mut hit| {
+                    hit.index = index;
+                    filter(&hit)
+                })
+        })
+    }
+
+    /// Check if any `Triangle` of this `Mesh` intersects any `Triangle` of another `Mesh`, for collision detection.
+    /// Each `Mesh` may be placed by an optional object-to-world transformation. Touching `Triangle`s intersect.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a transformation is not invertible, or a bounding box calculation fails.
+    pub fn intersects(
+        &self,
+        transform: Option<&Matrix4<T>>,
+        other: &Self,
+        other_transform: Option<&Matrix4<T>>,
+    ) -> Result<bool> {
+        Ok(!self
+            .overlapping_triangles(transform, other, other_transform, true)?
+            .is_empty())
+    }
+
+    /// Find every pair of intersecting `Triangle`s between this `Mesh` and another `Mesh`, as
+    /// (index in this `Mesh`, index in the other `Mesh`), in ascending order.
+    /// Each `Mesh` may be placed by an optional object-to-world transformation. Touching `Triangle`s intersect.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a transformation is not invertible, or a bounding box calculation fails.
+    pub fn intersecting_triangles(
+        &self,
+        transform: Option<&Matrix4<T>>,
+        other: &Self,
+        other_transform: Option<&Matrix4<T>>,
+    ) -> Result<Vec<(usize, usize)>> {
+        self.overlapping_triangles(transform, other, other_transform, false)
+    }
+
+    /// Find the point on the surface of the `Mesh` nearest to a given point,
+    /// along with the index of the `Triangle` on which it lies.
+    /// `Bvh` nodes further away than the nearest point found so far are skipped.
+    #[must_use]
+    pub fn closest_point(&self, point: &Point3<T>) -> (usize, Point3<T>) {
+        let mut closest = (0, self.triangles[0].closest_point(point));
+        let mut distance_squared = (closest.1 - point).norm_squared();
+        self.bvh.visit(|node, _| {
+            if node.volume.distance_squared(point) > distance_squared {
+                return false;
+            }
+            for &index in self.bvh.leaf_shapes(node) {
+                let candidate = self.triangles[index].closest_point(point);
+                let candidate_squared = (candidate - point).norm_squared();
+                if candidate_squared < distance_squared {
+                    closest = (index, candidate);
+                    distance_squared = candidate_squared;
                 }
-                _ => {}
             }
+            true
+        });
+        closest
+    }
+
+    /// Check if a point lies inside the `Mesh`, which must be closed (watertight) for the result to be meaningful.
+    /// Surface crossings are counted along three skewed `Ray`s and the majority verdict taken,
+    /// so a crossing miscounted at a shared edge or small crack does not flip the result.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bounding box or an intersection calculation fails.
+    pub fn contains_point(&self, point: &Point3<T>) -> Result<bool> {
+        let aabb = self.aabb()?.try_finite()?;
+        if !aabb.contains_point(point) {
+            return Ok(false);
         }
 
-        if vertices.is_empty() {
-            return Err(FileParsingError::InvalidObjFormat {
-                message: "No vertices found in OBJ file".to_string(),
+        // Step just past each crossing, far enough to clear rounding but not to skip a thin wall
+        let step = (aabb.maxs - aabb.mins).norm() * T::default_epsilon().sqrt();
+        let mut inside_votes = 0;
+        for direction in CONTAINMENT_DIRECTIONS {
+            let direction = Unit::new_normalize(Vector3::new(
+                T::try_from_f64(direction[0])?,
+                T::try_from_f64(direction[1])?,
+                T::try_from_f64(direction[2])?,
+            ));
+            let mut origin = *point;
+            let mut crossings = 0;
+            for _ in 0..=self.triangles.len() {
+                let Some(distance) = self.intersect_t(&Ray::new(origin, direction))? else {
+                    break;
+                };
+                crossings += 1;
+                origin += direction.scale(distance + step);
+            }
+            if crossings % 2 == 1 {
+                inside_votes += 1;
             }
-            .into());
         }
+        Ok(inside_votes * 2 > CONTAINMENT_DIRECTIONS.len())
+    }
 
-        if faces.is_empty() {
-            return Err(FileParsingError::InvalidObjFormat {
-                message: "No faces found in OBJ file".to_string(),
+    /// Sample `count` points uniformly inside the `Mesh`, which must be closed (watertight).
+    /// Points are drawn uniformly within the bounding box, and kept if `contains_point` accepts them.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - No point is accepted after `INTERIOR_SAMPLE_ATTEMPTS` consecutive attempts, as the `Mesh` encloses no volume
+    /// - The bounding box or an intersection calculation fails
+    pub fn sample_interior<R: Rng + ?Sized>(&self, rng: &mut R, count: usize) -> Result<Vec<Point3<T>>> {
+        let aabb = self.aabb()?.try_finite()?.into_owned();
+        let extents = aabb.extents();
+        let mut points = Vec::with_capacity(count);
+        let mut attempts = 0;
+        while points.len() < count {
+            if attempts == INTERIOR_SAMPLE_ATTEMPTS {
+                return Err(GeometryError::ZeroVolume.into());
+            }
+            attempts += 1;
+
+            let candidate = aabb.mins
+                + Vector3::new(
+                    extents.x * sample_unit::<T, R>(rng)?,
+                    extents.y * sample_unit::<T, R>(rng)?,
+                    extents.z * sample_unit::<T, R>(rng)?,
+                );
+            if self.contains_point(&candidate)? {
+                points.push(candidate);
+                attempts = 0;
             }
-            .into());
         }
+        Ok(points)
+    }
+}
 
-        let triangles = faces
-            .into_iter()
-            .map(|face| {
-                if face.vertex_indices.iter().any(|&i| i >= vertices.len()) {
-                    return Err(FileParsingError::InvalidFaceData {
-                        line: 0, // We've lost line info here, could be improved
-                        message: "Face references non-existent vertex".to_string(),
+impl<T: Scalar> Mesh<T> {
+    /// Descend both `Bvh`s together, in the space of this `Mesh`, collecting pairs of intersecting `Triangle`s.
+    /// Stops at the first pair if `first_only` is set.
+    fn overlapping_triangles(
+        &self,
+        transform: Option<&Matrix4<T>>,
+        other: &Self,
+        other_transform: Option<&Matrix4<T>>,
+        first_only: bool,
+    ) -> Result<Vec<(usize, usize)>> {
+        // Map the other `Mesh` into the space of this one
+        let other_to_self = if transform.is_none() && other_transform.is_none() {
+            None
+        } else {
+            let world_to_self = match transform {
+                Some(transform) => transform.try_inverse().ok_or(TransformationError::NonInvertibleMatrix)?,
+                None => Matrix4::identity(),
+            };
+            Some(world_to_self * other_transform.copied().unwrap_or_else(Matrix4::identity))
+        };
+        let other_aabbs = other
+            .bvh
+            .nodes()
+            .iter()
+            .map(|node| other_to_self.map_or_else(|| Ok(node.volume.clone()), |matrix| node.volume.transform(&matrix)))
+            .collect::<Result<Vec<_>>>()?;
+        let other_vertices = |index: usize| {
+            let vertices = other.triangles[index].vertices();
+            other_to_self.map_or(vertices, |matrix| vertices.map(|vertex| matrix.transform_point(&vertex)))
+        };
+
+        let (nodes, other_nodes) = (self.bvh.nodes(), other.bvh.nodes());
+        let mut pairs = Vec::new();
+        let mut stack = vec![(0, 0)];
+        while let Some((index, other_index)) = stack.pop() {
+            let (node, other_node) = (&nodes[index], &other_nodes[other_index]);
+            if node.volume.intersection(&other_aabbs[other_index]).is_none() {
+                continue;
+            }
+
+            match (node.count > 0, other_node.count > 0) {
+                (true, true) => {
+                    let slots = &self.bvh.indices()[node.left_child..node.left_child + node.count];
+                    let other_slots = &other.bvh.indices()[other_node.left_child..other_node.left_child + other_node.count];
+                    for &other_triangle in other_slots {
+                        let other_vertices = other_vertices(other_triangle);
+                        for &triangle in slots {
+                            if triangles_overlap(&self.triangles[triangle].vertices(), &other_vertices) {
+                                pairs.push((triangle, other_triangle));
+                                if first_only {
+                                    return Ok(pairs);
+                                }
+                            }
+                        }
                     }
-                    .into());
                 }
-
-                if face.normal_indices.iter().any(|&i| i >= normals.len()) {
-                    return Err(FileParsingError::InvalidFaceData {
-                        line: 0,
-                        message: "Face references non-existent normal".to_string(),
+                (false, true) => stack.extend([(node.left_child, other_index), (node.left_child + 1, other_index)]),
+                (true, false) => stack.extend([(index, other_node.left_child), (index, other_node.left_child + 1)]),
+                // Descend into the larger node, so the boxes being compared stay similar in size
+                (false, false) => {
+                    if node.volume.surface_area()? >= other_aabbs[other_index].surface_area()? {
+                        stack.extend([(node.left_child, other_index), (node.left_child + 1, other_index)]);
+                    } else {
+                        stack.extend([(index, other_node.left_child), (index, other_node.left_child + 1)]);
                     }
-                    .into());
                 }
+            }
+        }
 
-                Ok(Triangle::new(
-                    [
-                        vertices[face.vertex_indices[0]],
-                        vertices[face.vertex_indices[1]],
-                        vertices[face.vertex_indices[2]],
-                    ],
-                    [
-                        normals[face.normal_indices[0]],
-                        normals[face.normal_indices[1]],
-                        normals[face.normal_indices[2]],
-                    ],
-                ))
-            })
-            .collect::<Result<Vec<_>>>()?;
+        pairs.sort_unstable();
+        Ok(pairs)
+    }
+
+    /// Check if every `Triangle` uses the Moller-Trumbore intersection test without an `AlphaMask`,
+    /// so the packed traversal copies can be used.
+    fn is_packable(&self) -> bool {
+        self.triangles
+            .iter()
+            .all(|triangle| triangle.intersection() == TriangleIntersection::MollerTrumbore && triangle.alpha_mask().is_none())
+    }
 
-        let bvh = Bvh::new(bvh_config, &triangles)?;
-        Ok(Self { triangles, bvh })
+    /// Get the structure-of-arrays copy of the `Triangle`s, building it on first use.
+    /// Returns `None` if the `Mesh` must be traversed with watertight intersection tests instead.
+    fn soa(&self) -> Option<&TriangleSoa<T>> {
+        self.soa
+            .get_or_init(|| {
+                self.is_packable()
+                    .then(|| Box::new(TriangleSoa::new(&self.triangles, self.bvh.indices())))
+            })
+            .as_deref()
     }
 }
 
-impl<T: RealField + Copy> Bounded<T> for Mesh<T> {
-    fn aabb(&self) -> Result<Cow<Aabb<T>>> {
-        // Initialise with the first triangle's AABB
-        let mut aabb = self.triangles[0].aabb()?.into_owned();
+impl<T: Scalar> Bounded<T> for Mesh<T> {
+    /// Borrow the root bounds of the `Bvh`, which enclose every `Triangle`.
+    fn aabb(&self) -> Result<Bounds<'_, T>> {
+        self.bvh.aabb()
+    }
+
+    /// Projects every vertex, so takes time proportional to the number of `Triangle`s.
+    fn span(&self, direction: &Vector3<T>) -> Result<Option<[T; 2]>> {
+        Ok(self
+            .triangles
+            .iter()
+            .flat_map(Triangle::vertices)
+            .map(|vertex| vertex.coords.dot(direction))
+            .fold(None, |span, distance| {
+                Some(span.map_or([distance; 2], |[min, max]: [T; 2]| [min.min(distance), max.max(distance)]))
+            }))
+    }
+}
 
-        // Merge AABBs of all triangles
-        for triangle in &self.triangles[1..] {
-            aabb = aabb.merge(&*triangle.aabb()?)?;
-        }
+impl<T: Scalar> DistanceField<T> for Mesh<T> {
+    /// Approximate signed distance to a closed `Mesh`. The distance to the nearest `Triangle` is exact,
+    /// but the sign is taken from the side of its geometric normal on which the point lies,
+    /// so may be wrong near edges and vertices where the surface folds sharply.
+    fn signed_distance(&self, point: &Point3<T>) -> Result<T> {
+        let (index, closest) = self.closest_point(point);
+        let offset = point - closest;
+        let distance = offset.norm();
+        Ok(if offset.dot(self.triangles[index].geometric_normal()) < T::zero() {
+            -distance
+        } else {
+            distance
+        })
+    }
+}
 
-        Ok(Cow::Owned(aabb))
+#[cfg(feature = "simd")]
+impl Mesh<f32> {
+    /// Get the packed SIMD copy of the `Mesh`, building it on first use.
+    /// Returns `None` if the `Mesh` must be traversed with watertight intersection tests instead.
+    fn simd(&self) -> Option<&SimdMesh> {
+        self.simd
+            .get_or_init(|| self.is_packable().then(|| Box::new(SimdMesh::new(self))))
+            .as_deref()
     }
 }
 
-impl<T: RealField + Copy + ToPrimitive> Traceable<T> for Mesh<T> {
+/// Single-precision `Mesh`es are traversed with SIMD kernels when the `simd` feature is enabled.
+/// The kernels repeat the scalar slab and `Triangle` arithmetic operation for operation,
+/// so they find the same misses and the same closest distance as scalar traversal,
+/// though of `Triangle`s hit at exactly the same distance, as across a shared edge, either may be reported.
+/// Each SIMD hit is still confirmed by the scalar `Triangle` test, falling back to scalar traversal if they disagree.
+/// Otherwise the structure-of-arrays copy of the `Triangle`s is used, unless watertight intersection is required.
+impl<T: Scalar> Traceable<T> for Mesh<T> {
     fn intersect(&self, ray: &Ray<T>) -> Result<Option<Hit<T>>> {
-        self.bvh.intersect(ray, &self.triangles).map(|opt| {
+        self.intersect_within(ray, T::try_max_value()?)
+    }
+
+    fn intersect_t(&self, ray: &Ray<T>) -> Result<Option<T>> {
+        #[cfg(feature = "simd")]
+        if let Some((simd, simd_ray)) = as_simd(self, ray) {
+            let Some((index, _)) = simd.intersect(simd_ray, f32::INFINITY, false) else {
+                return Ok(None);
+            };
+            if let Some(distance) = self.triangles[index].intersect_t(ray)? {
+                return Ok(Some(distance));
+            }
+        }
+
+        if let Some(soa) = self.soa() {
+            return Ok(self
+                .bvh
+                .intersect_slots(ray, |slot| Ok(soa.intersect(slot, ray).map(|[t, _, _]| t)))?
+                .map(|(_, distance)| distance));
+        }
+
+        self.bvh.intersect_t(ray, &self.triangles)
+    }
+
+    fn intersect_within(&self, ray: &Ray<T>, max_distance: T) -> Result<Option<Hit<T>>> {
+        #[cfg(feature = "simd")]
+        if let Some((simd, simd_ray)) = as_simd(self, ray)
+            && let Some(limit) = max_distance.to_f32()
+        {
+            let Some((index, _)) = simd.intersect(simd_ray, limit, false) else {
+                return Ok(None);
+            };
+            // Build the full `Hit` from the scalar test, falling back to scalar traversal if rounding disagrees
+            if let Some(mut hit) = self.triangles[index].intersect_within(ray, max_distance)? {
+                hit.index = index;
+                return Ok(Some(hit));
+            }
+        }
+
+        if let Some(soa) = self.soa() {
+            let Some((slot, _)) = self
+                .bvh
+                .intersect_slots_within(ray, max_distance, |slot| Ok(soa.intersect(slot, ray).map(|[t, _, _]| t)))?
+            else {
+                return Ok(None);
+            };
+            // Build the full `Hit` from the `Triangle`, whose test uses identical arithmetic
+            let index = self.bvh.indices()[slot];
+            if let Some(mut hit) = self.triangles[index].intersect_within(ray, max_distance)? {
+                hit.index = index;
+                return Ok(Some(hit));
+            }
+        }
+
+        self.bvh.intersect_within(ray, &self.triangles, max_distance).map(|opt| {
             opt.map(|(triangle_index, mut hit)| {
                 hit.index = triangle_index;
                 hit
@@ -204,18 +867,494 @@ impl<T: RealField + Copy + ToPrimitive> Traceable<T> for Mesh<T> {
     }
 
     fn intersect_any(&self, ray: &Ray<T>, max_distance: T) -> Result<bool> {
+        #[cfg(feature = "simd")]
+        if let Some((simd, simd_ray)) = as_simd(self, ray)
+            && let Some(limit) = max_distance.to_f32()
+        {
+            let Some((index, _)) = simd.intersect(simd_ray, limit, true) else {
+                return Ok(false);
+            };
+            if self.triangles[index].intersect_any(ray, max_distance)? {
+                return Ok(true);
+            }
+        }
+
+        if let Some(soa) = self.soa() {
+            return self.bvh.intersect_any_slots(ray, max_distance, |slot| {
+                Ok(soa
+                    .intersect(slot, ray)
+                    .is_some_and(|[distance, _, _]| distance <= max_distance))
+            });
+        }
+
         self.bvh.intersect_any(ray, &self.triangles, max_distance)
     }
+
+    fn sphere_cast(&self, ray: &Ray<T>, radius: T) -> Result<Option<Hit<T>>> {
+        Ok(self
+            .bvh
+            .sphere_cast(ray, radius, &self.triangles)?
+            .map(|(triangle_index, mut hit)| {
+                hit.index = triangle_index;
+                hit
+            }))
+    }
+}
+
+impl<T: Scalar> Transformable<T> for Mesh<T> {
+    /// Transform every `Triangle` of the `Mesh`.
+    /// The `Bvh` topology is kept and its bounds are refitted, rather than rebuilding it from scratch.
+    fn transform(&self, transform: &Matrix4<T>) -> Result<Self> {
+        let normal_transform = normal_matrix(transform)?;
+        let triangles: Vec<_> = self
+            .triangles
+            .iter()
+            .map(|triangle| triangle.transform_with(transform, &normal_transform))
+            .collect();
+        let mut bvh = Bvh::clone(&self.bvh);
+        bvh.refit(&triangles)?;
+        Ok(Self {
+            triangles: triangles.into(),
+            bvh: Arc::new(bvh),
+            dropped_triangles: self.dropped_triangles,
+            config: self.config,
+            area_cdf: Arc::default(),
+            soa: Arc::default(),
+            #[cfg(feature = "simd")]
+            simd: Arc::default(),
+        })
+    }
+}
+
+impl<T: Scalar> Mesh<T> {
+    /// Get the cumulative `Triangle` areas, computing them on first use.
+    fn area_cdf(&self) -> Result<&[T]> {
+        if let Some(cdf) = self.area_cdf.get() {
+            return Ok(cdf);
+        }
+        let mut total = T::zero();
+        let cdf = self
+            .triangles
+            .iter()
+            .map(|triangle| {
+                total += triangle.area()?;
+                Ok(total)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(self.area_cdf.get_or_init(|| cdf))
+    }
+}
+
+impl<T: Scalar> SurfaceSample<T> for Mesh<T> {
+    fn area(&self) -> Result<T> {
+        Ok(self.area_cdf()?.last().copied().unwrap_or_else(T::zero))
+    }
+
+    /// Sample a point uniformly over the `Mesh` by choosing a `Triangle` in proportion to its area.
+    fn sample_point<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<(Point3<T>, Unit<Vector3<T>>, T)> {
+        let cdf = self.area_cdf()?;
+        let area = cdf.last().copied().unwrap_or_else(T::zero);
+        if area <= T::zero() {
+            return Err(GeometryError::ZeroArea.into());
+        }
+
+        let target = sample_unit::<T, R>(rng)? * area;
+        let index = cdf.partition_point(|&cumulative| cumulative <= target).min(cdf.len() - 1);
+        let (point, normal, _) = self.triangles[index].sample_point(rng)?;
+        Ok((point, normal, area.recip()))
+    }
 }
 
 // == Utility functions ==
 
-/// Parse a vertex position from an .obj file string.
-fn parse_vertex_position<T: RealField + Copy + FromStr>(coords: &[&str], line: usize) -> Result<Point3<T>> {
-    if coords.len() != 3 {
+/// Get the packed SIMD copy of a `Mesh` and reinterpret the `Ray` as single precision,
+/// if `T` is `f32` and the `Mesh` can be traversed with the SIMD kernels.
+#[cfg(feature = "simd")]
+fn as_simd<'a, T: Scalar>(mesh: &'a Mesh<T>, ray: &'a Ray<T>) -> Option<(&'a SimdMesh, &'a Ray<f32>)> {
+    let mesh: &dyn Any = mesh;
+    let ray: &dyn Any = ray;
+    Some((mesh.downcast_ref::<Mesh<f32>>()?.simd()?, ray.downcast_ref()?))
+}
+
+/// Prepare `Triangle`s for a `Mesh` as the `MeshConfig` requests, dropping degenerate `Triangle`s if asked
+/// and setting the intersection algorithm of the rest. Returns the kept `Triangle`s and the number dropped.
+fn prepare_triangles<T: Scalar>(mesh_config: MeshConfig, triangles: Vec<Triangle<T>>) -> (Vec<Triangle<T>>, usize) {
+    let count = triangles.len();
+    let triangles: Vec<_> = triangles
+        .into_iter()
+        .filter(|triangle| !(mesh_config.drop_degenerate_triangles && triangle.is_degenerate()))
+        .map(|triangle| triangle.with_intersection(mesh_config.triangle_intersection))
+        .collect();
+    let dropped = count - triangles.len();
+    (triangles, dropped)
+}
+
+/// Set the vertex tangents of every `Triangle` with texture coordinates but no tangents, as `MikkTSpace` generates them.
+/// Each corner contributes the direction of increasing u across its face, projected onto the plane of its normal and
+/// weighted by its angle, to every corner sharing its position, normal, texture coordinates and handedness.
+/// Corners left without a tangent, such as those of faces with degenerate texture coordinates, get an arbitrary one.
+fn with_generated_tangents<T: Scalar>(triangles: Vec<Triangle<T>>) -> Vec<Triangle<T>> {
+    let needs_tangents = |triangle: &Triangle<T>| triangle.uvs().is_some() && triangle.vertex_tangents().is_none();
+    if !triangles.iter().any(needs_tangents) {
+        return triangles;
+    }
+
+    // Handedness is negative where the texture coordinates are mirrored
+    let handedness = |triangle: &Triangle<T>| {
+        triangle.tangents().map_or_else(T::one, |[tangent, bitangent]| {
+            if triangle.geometric_normal().dot(&tangent.cross(&bitangent)) < T::zero() {
+                -T::one()
+            } else {
+                T::one()
+            }
+        })
+    };
+    let bits = |value: T| value.to_f64().map(|value| (value + 0.0).to_bits());
+    let key = |triangle: &Triangle<T>, corner: usize, handedness: T| {
+        let (vertex, normal) = (triangle.vertices()[corner], triangle.normals()[corner]);
+        let uv = triangle.uvs().map_or_else(|| [T::zero(); 2], |uvs| uvs[corner]);
+        (
+            [vertex.x, vertex.y, vertex.z, normal.x, normal.y, normal.z, uv[0], uv[1]].map(bits),
+            handedness < T::zero(),
+        )
+    };
+
+    let mut sums: HashMap<_, Vector3<T>> = HashMap::new();
+    for triangle in triangles.iter().filter(|triangle| needs_tangents(triangle)) {
+        let Some([tangent, _]) = triangle.tangents() else {
+            continue;
+        };
+        let handedness = handedness(triangle);
+        let vertices = triangle.vertices();
+        for corner in 0..3 {
+            let normal = triangle.normals()[corner];
+            let angle = (vertices[(corner + 1) % 3] - vertices[corner]).angle(&(vertices[(corner + 2) % 3] - vertices[corner]));
+            let Some(projected) = Unit::try_new(tangent - normal.scale(normal.dot(&tangent)), T::zero()) else {
+                continue;
+            };
+            *sums.entry(key(triangle, corner, handedness)).or_insert_with(Vector3::zeros) += projected.scale(angle);
+        }
+    }
+
+    triangles
+        .into_iter()
+        .map(|triangle| {
+            if !needs_tangents(&triangle) {
+                return triangle;
+            }
+            let handedness = handedness(&triangle);
+            let vertex_tangents = [0, 1, 2].map(|corner| {
+                let normal = triangle.normals()[corner];
+                let tangent = sums
+                    .get(&key(&triangle, corner, handedness))
+                    .and_then(|sum| Unit::try_new(sum - normal.scale(normal.dot(sum)), T::zero()))
+                    .unwrap_or_else(|| {
+                        let helper = if normal.x.abs() > normal.y.abs() {
+                            Vector3::y()
+                        } else {
+                            Vector3::x()
+                        };
+                        Unit::new_normalize(normal.cross(&helper))
+                    });
+                [tangent.x, tangent.y, tangent.z, handedness]
+            });
+            triangle.with_vertex_tangents(vertex_tangents)
+        })
+        .collect()
+}
+
+/// Split a `Triangle` into four at the midpoints of its edges, keeping its winding.
+/// Normals, texture coordinates and colours are interpolated to the midpoints, and the `AlphaMask` is kept.
+fn subdivide<T: Scalar>(triangle: &Triangle<T>) -> Result<[Triangle<T>; 4]> {
+    let half = T::try_from_f64(0.5)?;
+    let mean = |a: T, b: T| (a + b) * half;
+
+    // Corners, then the midpoints of the edges 0-1, 1-2 and 2-0
+    let edges = [[0, 1], [1, 2], [2, 0]];
+    let corners = triangle.vertices();
+    let vertices: Vec<_> = corners
+        .into_iter()
+        .chain(edges.map(|[a, b]| center(&corners[a], &corners[b])))
+        .collect();
+    let normals: Vec<_> = triangle
+        .normals()
+        .iter()
+        .copied()
+        .chain(edges.map(|[a, b]| Unit::new_normalize(triangle.normals()[a].into_inner() + triangle.normals()[b].into_inner())))
+        .collect();
+    let uvs: Option<Vec<_>> = triangle.uvs().map(|uvs| {
+        uvs.iter()
+            .copied()
+            .chain(edges.map(|[a, b]| [mean(uvs[a][0], uvs[b][0]), mean(uvs[a][1], uvs[b][1])]))
+            .collect()
+    });
+    let colors: Option<Vec<_>> = triangle.colors().map(|colors| {
+        colors
+            .iter()
+            .copied()
+            .chain(edges.map(|[a, b]| from_fn(|channel| mean(colors[a][channel], colors[b][channel]))))
+            .collect()
+    });
+
+    Ok([[0, 3, 5], [3, 1, 4], [5, 4, 2], [3, 4, 5]].map(|face| {
+        let mut part =
+            Triangle::new(face.map(|i| vertices[i]), face.map(|i| normals[i])).with_intersection(triangle.intersection());
+        if let Some(uvs) = &uvs {
+            part = part.with_uvs(face.map(|i| uvs[i]));
+        }
+        if let Some(colors) = &colors {
+            part = part.with_colors(face.map(|i| colors[i]));
+        }
+        if let Some(alpha_mask) = triangle.alpha_mask() {
+            part = part.with_alpha_mask(Arc::clone(alpha_mask));
+        }
+        part
+    }))
+}
+
+/// Check if two triangles intersect or touch, using the separating axis theorem.
+/// Candidate axes are both face normals, the cross products of each pair of edges, and the in-plane edge normals,
+/// which separate coplanar triangles. Degenerate axes project everything to a point, and never separate.
+fn triangles_overlap<T: Scalar>(a: &[Point3<T>; 3], b: &[Point3<T>; 3]) -> bool {
+    let edges = |vertices: &[Point3<T>; 3]| {
+        [
+            vertices[1] - vertices[0],
+            vertices[2] - vertices[1],
+            vertices[0] - vertices[2],
+        ]
+    };
+    let (edges_a, edges_b) = (edges(a), edges(b));
+    let (normal_a, normal_b) = (edges_a[0].cross(&edges_a[1]), edges_b[0].cross(&edges_b[1]));
+
+    // Project relative to a shared vertex to limit rounding far from the origin
+    let separates = |axis: Vector3<T>| {
+        let project = |vertices: &[Point3<T>; 3]| {
+            let distances = vertices.map(|vertex| (vertex - a[0]).dot(&axis));
+            (
+                distances[0].min(distances[1]).min(distances[2]),
+                distances[0].max(distances[1]).max(distances[2]),
+            )
+        };
+        let ((min_a, max_a), (min_b, max_b)) = (project(a), project(b));
+        max_a < min_b || max_b < min_a
+    };
+
+    let mut axes = [normal_a, normal_b]
+        .into_iter()
+        .chain(
+            edges_a
+                .iter()
+                .flat_map(|edge_a| edges_b.iter().map(move |edge_b| edge_a.cross(edge_b))),
+        )
+        .chain(edges_a.iter().map(|edge| normal_a.cross(edge)))
+        .chain(edges_b.iter().map(|edge| normal_b.cross(edge)));
+    !axes.any(separates)
+}
+
+/// Assemble `Triangle`s from indexed vertex buffers.
+/// Normals, texture coordinates and colours are indexed as `positions`; flat face normals are used if normals are omitted.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - Normals, texture coordinates or colours are given but do not match the number of positions
+/// - A face references a non-existent vertex
+pub fn triangles_from_buffers<T: Scalar>(
+    positions: &[[T; 3]],
+    normals: Option<&[[T; 3]]>,
+    uvs: Option<&[[T; 2]]>,
+    colors: Option<&[[T; 4]]>,
+    faces: impl IntoIterator<Item = [usize; 3]>,
+) -> Result<Vec<Triangle<T>>> {
+    if let Some(normals) = normals
+        && normals.len() != positions.len()
+    {
+        return Err(GeometryError::MismatchedNormalCount {
+            positions: positions.len(),
+            normals: normals.len(),
+        }
+        .into());
+    }
+    if let Some(uvs) = uvs
+        && uvs.len() != positions.len()
+    {
+        return Err(GeometryError::MismatchedUvCount {
+            positions: positions.len(),
+            uvs: uvs.len(),
+        }
+        .into());
+    }
+    if let Some(colors) = colors
+        && colors.len() != positions.len()
+    {
+        return Err(GeometryError::MismatchedColorCount {
+            positions: positions.len(),
+            colors: colors.len(),
+        }
+        .into());
+    }
+
+    faces
+        .into_iter()
+        .map(|face| {
+            if let Some(&index) = face.iter().find(|&&i| i >= positions.len()) {
+                return Err(GeometryError::VertexIndexOutOfBounds {
+                    index,
+                    count: positions.len(),
+                }
+                .into());
+            }
+
+            let vertices = face.map(|i| Point3::from(positions[i]));
+            let normals = normals.map_or_else(
+                || {
+                    let normal = Unit::new_normalize((vertices[1] - vertices[0]).cross(&(vertices[2] - vertices[0])));
+                    [normal; 3]
+                },
+                |normals| face.map(|i| Unit::new_normalize(Vector3::from(normals[i]))),
+            );
+
+            let mut triangle = Triangle::new(vertices, normals);
+            if let Some(uvs) = uvs {
+                triangle = triangle.with_uvs(face.map(|i| uvs[i]));
+            }
+            if let Some(colors) = colors {
+                triangle = triangle.with_colors(face.map(|i| colors[i]));
+            }
+            Ok(triangle)
+        })
+        .collect()
+}
+
+/// Parse the `Triangle`s of a wavefront (.obj) string.
+pub fn parse_wavefront<T: Scalar + FromStr>(obj_string: &str) -> Result<Vec<Triangle<T>>> {
+    Ok(parse_wavefront_with_warnings(obj_string, WavefrontMode::Strict)?.0)
+}
+
+/// Parse the `Triangle`s of a wavefront (.obj) string, along with any non-fatal issues found.
+/// Unsupported directives, object and group declarations are skipped.
+/// Degenerate faces are reported but kept, leaving the `MeshConfig` of the `Mesh` to decide whether they are dropped.
+/// In `WavefrontMode::Lenient` malformed faces are skipped, out-of-range indices are clamped,
+/// and faces without vertex normals use their geometric normal.
+/// Texture coordinates, and vertex colours given after the vertex position, are kept on the `Triangle`s.
+pub fn parse_wavefront_with_warnings<T: Scalar + FromStr>(
+    obj_string: &str,
+    mode: WavefrontMode,
+) -> Result<(Vec<Triangle<T>>, Vec<WavefrontWarning>)> {
+    let mut vertices = Vec::new();
+    let mut colors = Vec::new();
+    let mut normals = Vec::new();
+    let mut uvs = Vec::new();
+    let mut faces = Vec::new();
+    let mut warnings = Vec::new();
+
+    for (line_num, line) in obj_string.lines().enumerate() {
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        if tokens.is_empty() {
+            continue;
+        }
+
+        match tokens.first() {
+            Some(&"v") => {
+                if tokens.len() < 4 {
+                    return Err(FileParsingError::MissingVertexPosition { line: line_num + 1 }.into());
+                }
+                let (vertex, color) = parse_vertex_position(&tokens[1..], line_num + 1)?;
+                vertices.push(vertex);
+                colors.push(color);
+            }
+            Some(&"vn") => {
+                if tokens.len() < 4 {
+                    return Err(FileParsingError::MissingVertexNormal { line: line_num + 1 }.into());
+                }
+                let normal = parse_vertex_normal(&tokens[1..], line_num + 1)?;
+                normals.push(normal);
+            }
+            Some(&"vt") => uvs.push(parse_texture_coordinate(&tokens[1..], line_num + 1)?),
+            Some(&"f") => match parse_face(&tokens[1..], line_num + 1, mode, &mut warnings) {
+                Ok(face) => faces.push(face),
+                Err(FileParsingError::InvalidFaceData { line, message }) if mode == WavefrontMode::Lenient => {
+                    warnings.push(WavefrontWarning::MalformedFace { line, message });
+                }
+                Err(error) => return Err(error.into()),
+            },
+            Some(&("o" | "g")) => warnings.push(WavefrontWarning::IgnoredGroup {
+                line: line_num + 1,
+                name: tokens[1..].join(" "),
+            }),
+            Some(directive) if !directive.starts_with('#') => warnings.push(WavefrontWarning::IgnoredDirective {
+                line: line_num + 1,
+                directive: (*directive).to_string(),
+            }),
+            _ => {}
+        }
+    }
+
+    if vertices.is_empty() {
+        return Err(FileParsingError::InvalidObjFormat {
+            message: "No vertices found in OBJ file".to_string(),
+        }
+        .into());
+    }
+
+    if faces.is_empty() {
+        return Err(FileParsingError::InvalidObjFormat {
+            message: "No faces found in OBJ file".to_string(),
+        }
+        .into());
+    }
+
+    let mut triangles = Vec::with_capacity(faces.len());
+    for face in faces {
+        let message = "Face references non-existent vertex";
+        let Some(vertex_indices) = check_indices(face.vertex_indices, vertices.len(), face.line, mode, message, &mut warnings)?
+        else {
+            continue;
+        };
+        let positions = vertex_indices.map(|index| vertices[index]);
+
+        let message = "Face references non-existent normal";
+        let normal_indices = match face.normal_indices {
+            Some(indices) => check_indices(indices, normals.len(), face.line, mode, message, &mut warnings)?,
+            None => None,
+        };
+        let vertex_normals = normal_indices.map_or_else(
+            || {
+                warnings.push(WavefrontWarning::DefaultNormals { line: face.line });
+                [Unit::new_normalize((positions[1] - positions[0]).cross(&(positions[2] - positions[0]))); 3]
+            },
+            |indices| indices.map(|index| normals[index]),
+        );
+
+        let message = "Face references non-existent texture coordinate";
+        let uv_indices = match face.uv_indices {
+            Some(indices) => check_indices(indices, uvs.len(), face.line, mode, message, &mut warnings)?,
+            None => None,
+        };
+
+        let mut triangle = Triangle::new(positions, vertex_normals);
+        if let Some(indices) = uv_indices {
+            triangle = triangle.with_uvs(indices.map(|index| uvs[index]));
+        }
+        if let [Some(a), Some(b), Some(c)] = vertex_indices.map(|index| colors[index]) {
+            triangle = triangle.with_colors([a, b, c]);
+        }
+        if triangle.is_degenerate() {
+            warnings.push(WavefrontWarning::DegenerateFace { line: face.line });
+        }
+        triangles.push(triangle);
+    }
+
+    Ok((triangles, warnings))
+}
+
+/// Parse a vertex position from an .obj file string, along with its colour if given.
+/// Colours follow the position as red, green and blue components, as written by scanners, and are opaque.
+fn parse_vertex_position<T: Scalar + FromStr>(coords: &[&str], line: usize) -> Result<(Point3<T>, Option<[T; 4]>)> {
+    if coords.len() != 3 && coords.len() != 6 {
         return Err(FileParsingError::InvalidFaceData {
             line,
-            message: "Vertex position must have exactly 3 coordinates".to_string(),
+            message: "Vertex position must have exactly 3 coordinates, optionally followed by 3 colour components".to_string(),
         }
         .into());
     }
@@ -234,11 +1373,52 @@ fn parse_vertex_position<T: RealField + Copy + FromStr>(coords: &[&str], line: u
     let y = parse_coord(coords[1])?;
     let z = parse_coord(coords[2])?;
 
-    Ok(Point3::new(x, y, z))
+    let color = if coords.len() == 6 {
+        Some([
+            parse_coord(coords[3])?,
+            parse_coord(coords[4])?,
+            parse_coord(coords[5])?,
+            T::one(),
+        ])
+    } else {
+        None
+    };
+
+    Ok((Point3::new(x, y, z), color))
+}
+
+/// Parse a texture coordinate from an .obj file string.
+/// The v coordinate defaults to zero if omitted, and any w coordinate is ignored.
+fn parse_texture_coordinate<T: Scalar + FromStr>(coords: &[&str], line: usize) -> Result<[T; 2]> {
+    if coords.is_empty() {
+        return Err(FileParsingError::MissingTextureCoordinate { line }.into());
+    }
+    if coords.len() > 3 {
+        return Err(FileParsingError::InvalidFaceData {
+            line,
+            message: "Texture coordinate must have at most 3 components".to_string(),
+        }
+        .into());
+    }
+
+    let parse_coord = |coord: &str| -> Result<T> {
+        coord.parse::<T>().map_err(|_| {
+            FileParsingError::InvalidCoordinate {
+                value: coord.to_string(),
+                line,
+            }
+            .into()
+        })
+    };
+
+    let u = parse_coord(coords[0])?;
+    let v = coords.get(1).map_or_else(|| Ok(T::zero()), |coord| parse_coord(coord))?;
+
+    Ok([u, v])
 }
 
 /// Parse a vertex normal from an .obj file string.
-fn parse_vertex_normal<T: RealField + Copy + FromStr>(coords: &[&str], line: usize) -> Result<Unit<Vector3<T>>> {
+fn parse_vertex_normal<T: Scalar + FromStr>(coords: &[&str], line: usize) -> Result<Unit<Vector3<T>>> {
     if coords.len() != 3 {
         return Err(FileParsingError::InvalidFaceData {
             line,
@@ -265,17 +1445,33 @@ fn parse_vertex_normal<T: RealField + Copy + FromStr>(coords: &[&str], line: usi
 }
 
 /// Parse a face from an .obj file string.
-fn parse_face(tokens: &[&str], line: usize) -> Result<Face> {
+/// Zero indices are invalid in the 1-based OBJ format, and are treated as 1 with a warning.
+/// Missing normal indices are an error in strict mode, and are left unset in lenient mode.
+fn parse_face(
+    tokens: &[&str],
+    line: usize,
+    mode: WavefrontMode,
+    warnings: &mut Vec<WavefrontWarning>,
+) -> std::result::Result<Face, FileParsingError> {
+    if tokens.len() < 3 {
+        return Err(FileParsingError::InvalidFaceData {
+            line,
+            message: "Face must have at least 3 vertices".to_string(),
+        });
+    }
+
     if tokens.len() != 3 {
         return Err(FileParsingError::InvalidFaceData {
             line,
             message: "Face must have exactly 3 vertex indices (triangular faces only)".to_string(),
-        }
-        .into());
+        });
     }
 
     let mut vertex_indices = [0; 3];
     let mut normal_indices = [0; 3];
+    let mut uv_indices = [0; 3];
+    let mut has_normals = true;
+    let mut has_uvs = true;
 
     for (i, token) in tokens.iter().enumerate() {
         let parts: Vec<&str> = token.split('/').collect();
@@ -284,37 +1480,264 @@ fn parse_face(tokens: &[&str], line: usize) -> Result<Face> {
             return Err(FileParsingError::InvalidFaceData {
                 line,
                 message: "Face must specify vertex indices".to_string(),
-            }
-            .into());
+            });
         }
 
-        vertex_indices[i] = parts[0]
-            .parse::<usize>()
-            .map_err(|_| FileParsingError::InvalidFaceData {
-                line,
-                message: format!("Invalid vertex index: {}", parts[0]),
-            })?
-            .saturating_sub(1); // OBJ indices are 1-based
+        vertex_indices[i] = parts[0].parse::<usize>().map_err(|_| FileParsingError::InvalidFaceData {
+            line,
+            message: format!("Invalid vertex index: {}", parts[0]),
+        })?;
 
-        if parts.len() < 3 {
-            return Err(FileParsingError::InvalidFaceData {
-                line,
-                message: "Face must specify normal indices".to_string(),
+        match parts.get(1).filter(|part| !part.is_empty()) {
+            Some(uv_part) => {
+                uv_indices[i] = uv_part.parse::<usize>().map_err(|_| FileParsingError::InvalidFaceData {
+                    line,
+                    message: format!("Invalid texture coordinate index: {uv_part}"),
+                })?;
             }
-            .into());
+            None => has_uvs = false,
         }
 
-        normal_indices[i] = parts[2]
-            .parse::<usize>()
-            .map_err(|_| FileParsingError::InvalidFaceData {
-                line,
-                message: format!("Invalid normal index: {}", parts[2]),
-            })?
-            .saturating_sub(1); // OBJ indices are 1-based
+        let Some(normal_part) = parts.get(2).filter(|part| !part.is_empty()) else {
+            if mode == WavefrontMode::Strict {
+                return Err(FileParsingError::InvalidFaceData {
+                    line,
+                    message: "Face must specify normal indices".to_string(),
+                });
+            }
+            has_normals = false;
+            continue;
+        };
+
+        normal_indices[i] = normal_part.parse::<usize>().map_err(|_| FileParsingError::InvalidFaceData {
+            line,
+            message: format!("Invalid normal index: {normal_part}"),
+        })?;
+    }
+
+    if vertex_indices.contains(&0) || (has_normals && normal_indices.contains(&0)) || (has_uvs && uv_indices.contains(&0)) {
+        warnings.push(WavefrontWarning::ZeroIndex { line });
     }
 
+    // OBJ indices are 1-based
+    let vertex_indices = vertex_indices.map(|index| index.saturating_sub(1));
+    let normal_indices = normal_indices.map(|index| index.saturating_sub(1));
+    let uv_indices = uv_indices.map(|index| index.saturating_sub(1));
+
     Ok(Face {
         vertex_indices,
-        normal_indices,
+        normal_indices: has_normals.then_some(normal_indices),
+        uv_indices: has_uvs.then_some(uv_indices),
+        line,
     })
 }
+
+/// Check that the indices of a face lie within a list of `len` elements.
+/// Out-of-range indices are an error in strict mode, and are clamped to the last element in lenient mode.
+/// Returns `None` in lenient mode if the list is empty.
+fn check_indices(
+    indices: [usize; 3],
+    len: usize,
+    line: usize,
+    mode: WavefrontMode,
+    message: &str,
+    warnings: &mut Vec<WavefrontWarning>,
+) -> Result<Option<[usize; 3]>> {
+    if indices.iter().all(|&index| index < len) {
+        return Ok(Some(indices));
+    }
+
+    if mode == WavefrontMode::Strict {
+        return Err(FileParsingError::InvalidFaceData {
+            line,
+            message: message.to_string(),
+        }
+        .into());
+    }
+
+    if len == 0 {
+        return Ok(None);
+    }
+    warnings.push(WavefrontWarning::ClampedIndex { line });
+    Ok(Some(indices.map(|index| index.min(len - 1))))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        bvh::BvhConfig,
+        error::Result,
+        geometry::{Mesh, MeshConfig, WavefrontMode, WavefrontWarning},
+    };
+
+    /// Unit square, followed on line 8 by a zero-area face along its diagonal.
+    const SQUARE_WITH_SLIVER: &str =
+        "v 0 0 0\nv 1 0 0\nv 1 1 0\nv 0 1 0\nvn 0 0 1\nf 1//1 2//1 3//1\nf 1//1 3//1 4//1\nf 1//1 3//1 1//1\n";
+
+    #[test]
+    fn wavefront_parsing_reports_but_keeps_degenerate_faces() -> Result<()> {
+        for mode in [WavefrontMode::Strict, WavefrontMode::Lenient] {
+            let (mesh, warnings) = Mesh::<f64>::from_wavefront_with_warnings(&BvhConfig::default(), SQUARE_WITH_SLIVER, mode)?;
+            assert_eq!(mesh.triangles().len(), 3);
+            assert_eq!(mesh.dropped_triangles(), 0);
+            assert!(warnings.contains(&WavefrontWarning::DegenerateFace { line: 8 }));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn mesh_config_decides_whether_degenerate_wavefront_faces_are_dropped() -> Result<()> {
+        let bvh_config = BvhConfig::default();
+
+        let kept = Mesh::<f64>::from_wavefront_with_config(
+            &bvh_config,
+            MeshConfig::default().with_drop_degenerate_triangles(false),
+            SQUARE_WITH_SLIVER,
+        )?;
+        assert_eq!(kept.triangles().len(), 3);
+        assert_eq!(kept.dropped_triangles(), 0);
+
+        let dropped = Mesh::<f64>::from_wavefront_with_config(
+            &bvh_config,
+            MeshConfig::default().with_drop_degenerate_triangles(true),
+            SQUARE_WITH_SLIVER,
+        )?;
+        assert_eq!(dropped.triangles().len(), 2);
+        assert_eq!(dropped.dropped_triangles(), 1);
+        Ok(())
+    }
+
+    #[cfg(feature = "simd")]
+    mod simd {
+        use nalgebra::{Point3, Unit, Vector3};
+
+        use crate::{
+            bvh::BvhConfig,
+            error::Result,
+            geometry::{Aabb, Mesh, Triangle},
+            rt::Ray,
+            testing::SceneGenerator,
+            traits::Traceable,
+        };
+
+        /// Check that SIMD traversal of a `Mesh` agrees with scalar traversal of its `Bvh` for every query.
+        fn assert_simd_matches_scalar(mesh: &Mesh<f32>, rays: &[Ray<f32>]) -> Result<()> {
+            assert!(mesh.simd().is_some(), "mesh should be traversed with the SIMD kernels");
+            for ray in rays {
+                let scalar = mesh.bvh.intersect_t(ray, &mesh.triangles)?;
+                assert_eq!(mesh.intersect_t(ray)?, scalar, "intersect_t of {ray:?}");
+                assert_eq!(mesh.intersect(ray)?.map(|hit| hit.distance), scalar, "intersect of {ray:?}");
+                if let Some(hit) = mesh.intersect(ray)? {
+                    assert_eq!(mesh.triangles[hit.index].intersect_t(ray)?, scalar, "hit index of {ray:?}");
+                }
+                for max_distance in [0.1, 0.5, 2.0, f32::MAX] {
+                    assert_eq!(
+                        mesh.intersect_any(ray, max_distance)?,
+                        mesh.bvh.intersect_any(ray, &mesh.triangles, max_distance)?,
+                        "intersect_any of {ray:?} within {max_distance}"
+                    );
+                    assert_eq!(
+                        mesh.intersect_within(ray, max_distance)?.map(|hit| hit.distance),
+                        mesh.bvh
+                            .intersect_within(ray, &mesh.triangles, max_distance)?
+                            .map(|(_, hit)| hit.distance),
+                        "intersect_within of {ray:?} within {max_distance}"
+                    );
+                }
+            }
+            Ok(())
+        }
+
+        #[test]
+        fn simd_matches_scalar_for_random_meshes() -> Result<()> {
+            let bounds = Aabb::new(Point3::new(-1.0, -1.0, -1.0), Point3::new(1.0, 1.0, 1.0))?;
+            // Triangle and leaf sizes which are not multiples of the packet width leave lanes unoccupied
+            for (seed, count, max_shapes_per_node) in [(1, 1, 4), (2, 5, 4), (3, 13, 5), (4, 200, 11), (5, 1000, 16)] {
+                let bvh_config = BvhConfig {
+                    max_shapes_per_node,
+                    ..BvhConfig::default()
+                };
+                let generator = SceneGenerator::new(bounds.clone(), seed);
+                let mesh = generator.triangle_soup(&bvh_config, count, 0.4)?;
+                assert_simd_matches_scalar(&mesh, &generator.rays(500)?)?;
+            }
+            Ok(())
+        }
+
+        #[test]
+        fn simd_matches_scalar_for_edge_on_and_parallel_rays() -> Result<()> {
+            // Flat grid of unit squares in the z = 0 plane, whose `Bvh` nodes have zero thickness
+            let size: i16 = 5;
+            let normal = Vector3::z_axis();
+            let mut triangles = Vec::new();
+            for y in 0..size {
+                for x in 0..size {
+                    let [x, y] = [x, y].map(f32::from);
+                    let corners = [[x, y], [x + 1.0, y], [x + 1.0, y + 1.0], [x, y + 1.0]].map(|[x, y]| Point3::new(x, y, 0.0));
+                    triangles.push(Triangle::new([corners[0], corners[1], corners[2]], [normal; 3]));
+                    triangles.push(Triangle::new([corners[0], corners[2], corners[3]], [normal; 3]));
+                }
+            }
+            let bvh_config = BvhConfig {
+                max_shapes_per_node: 3,
+                ..BvhConfig::default()
+            };
+            let mesh = Mesh::new(&bvh_config, triangles)?;
+
+            let mut rays = Vec::new();
+            let extent = f32::from(size);
+            for step in 0..=(4 * size) {
+                let offset = f32::from(step) / 4.0;
+                // Through vertices and along shared edges, from either side of the plane
+                for direction in [-Vector3::<f32>::z_axis(), Vector3::z_axis()] {
+                    rays.push(Ray::new(Point3::new(offset, offset, -direction.z), direction));
+                    rays.push(Ray::new(Point3::new(offset, 0.5, -direction.z), direction));
+                    rays.push(Ray::new(Point3::new(offset, offset.min(1.0), -direction.z), direction));
+                }
+                // Lying within the plane, and grazing it
+                for z in [0.0, f32::EPSILON, -f32::EPSILON] {
+                    rays.push(Ray::new(Point3::new(-1.0, offset, z), Vector3::x_axis()));
+                    rays.push(Ray::new(Point3::new(offset, -1.0, z), Vector3::y_axis()));
+                    rays.push(Ray::new(
+                        Point3::new(-1.0, -1.0 + offset, z),
+                        Unit::new_normalize(Vector3::new(1.0, 1.0, 0.0)),
+                    ));
+                    rays.push(Ray::new(
+                        Point3::new(-1.0, offset, z),
+                        Unit::new_normalize(Vector3::new(extent, 1.0, 1.0e-6)),
+                    ));
+                }
+            }
+            assert_simd_matches_scalar(&mesh, &rays)?;
+
+            // Ridges of two `Triangle`s folded about a shared edge along the x axis, so both are hit at almost the same distance
+            let mut triangles = Vec::new();
+            for (ridge, slope) in [0.0_f32, 0.5, 1.0e-3, 1.0e-6, 3.0].into_iter().enumerate() {
+                let z = f32::from(u8::try_from(ridge).expect("few ridges")) * 2.0;
+                let ends = [Point3::new(-1.0, 0.0, z), Point3::new(1.0, 0.0, z)];
+                for side in [-1.0, 1.0] {
+                    let outer = Point3::new(0.0, side, z + slope);
+                    let normal = Unit::new_normalize((ends[1] - ends[0]).cross(&(outer - ends[0])));
+                    triangles.push(Triangle::new([ends[0], ends[1], outer], [normal; 3]));
+                }
+            }
+            let ridges = Mesh::new(&BvhConfig::default(), triangles)?;
+
+            // Aimed at points on each shared edge, from above and below, square on and at a slant
+            rays.clear();
+            for ridge in 0..5_u8 {
+                let z = f32::from(ridge) * 2.0;
+                for step in -8_i16..=8 {
+                    let target = Point3::new(f32::from(step) / 10.0, 0.0, z);
+                    for [x, y] in [[0.0, 0.0], [0.1, 0.0], [0.0, 0.3], [0.2, -0.7], [0.0, 1.0e-4]] {
+                        for direction in [Vector3::new(x, y, 1.0), Vector3::new(x, y, -1.0)] {
+                            let direction = Unit::new_normalize(direction);
+                            rays.push(Ray::new(target - direction.scale(1.5), direction));
+                        }
+                    }
+                }
+            }
+            assert_simd_matches_scalar(&ridges, &rays)
+        }
+    }
+}
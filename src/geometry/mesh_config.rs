@@ -0,0 +1,33 @@
+//! Mesh configuration structure.
+
+use serde::{Deserialize, Serialize};
+
+use crate::geometry::TriangleIntersection;
+
+/// Configuration of how the `Triangle`s of a `Mesh` are prepared as it is constructed.
+/// The `Bvh` built over them is configured separately, by a `BvhConfig`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct MeshConfig {
+    /// Algorithm used to intersect `Ray`s with the `Triangle`s.
+    #[serde(default)]
+    pub triangle_intersection: TriangleIntersection,
+    /// Drop zero-area and non-finite `Triangle`s, rather than adding them to the `Bvh`.
+    #[serde(default)]
+    pub drop_degenerate_triangles: bool,
+}
+
+impl MeshConfig {
+    /// Set the algorithm used to intersect `Ray`s with the `Triangle`s.
+    #[must_use]
+    pub const fn with_triangle_intersection(mut self, triangle_intersection: TriangleIntersection) -> Self {
+        self.triangle_intersection = triangle_intersection;
+        self
+    }
+
+    /// Set whether zero-area and non-finite `Triangle`s are dropped.
+    #[must_use]
+    pub const fn with_drop_degenerate_triangles(mut self, drop_degenerate_triangles: bool) -> Self {
+        self.drop_degenerate_triangles = drop_degenerate_triangles;
+        self
+    }
+}
@@ -1,11 +1,38 @@
 mod aabb;
+mod alpha_mask;
+mod capsule;
+mod curve;
+mod curve_segment;
+mod curve_shape;
+mod displacement;
+mod heightmap;
+mod kdop;
 mod mesh;
+mod mesh_config;
 mod plane;
 mod sphere;
 mod triangle;
+mod triangle_intersection;
+mod triangle_soa;
+mod wavefront_mode;
+mod wavefront_warning;
 
 pub use aabb::Aabb;
+pub use alpha_mask::AlphaMask;
+pub use capsule::Capsule;
+pub use curve::Curve;
+pub use curve_segment::CurveSegment;
+pub use curve_shape::CurveShape;
+pub use displacement::Displacement;
+pub use heightmap::Heightmap;
+pub use kdop::{Dop14, Dop18, Dop26, Kdop};
 pub use mesh::Mesh;
+pub use mesh::{parse_wavefront, triangles_from_buffers};
+pub use mesh_config::MeshConfig;
 pub use plane::Plane;
 pub use sphere::Sphere;
 pub use triangle::Triangle;
+pub use triangle_intersection::TriangleIntersection;
+pub use triangle_soa::TriangleSoa;
+pub use wavefront_mode::WavefrontMode;
+pub use wavefront_warning::WavefrontWarning;
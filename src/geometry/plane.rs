@@ -1,28 +1,47 @@
 //! Infinite plane structure.
 
-use nalgebra::{Point3, RealField, Unit, Vector3};
-use std::borrow::Cow;
+use nalgebra::{Matrix4, Point3, Unit, Vector3};
 
 use crate::{
     error::Result,
-    geometry::Aabb,
     rt::{Hit, Ray},
-    traits::{Bounded, FallibleNumeric, Traceable},
+    traits::{Bounded, Bounds, DistanceField, Scalar, Traceable, Transformable, normal_matrix},
 };
 
 /// Infinite plane defined by a surface location and the normal vector.
 #[derive(Debug, Clone)]
-pub struct Plane<T: RealField + Copy> {
+pub struct Plane<T: Scalar> {
     /// A point on the plane.
-    pub point: Point3<T>,
+    point: Point3<T>,
     /// Normal vector of the plane.
-    pub normal: Unit<Vector3<T>>,
+    normal: Unit<Vector3<T>>,
+    /// Signed distance of the plane from the origin along its normal (`normal` dot `point`).
+    distance: T,
 }
 
-impl<T: RealField + Copy> Plane<T> {
+impl<T: Scalar> Plane<T> {
     /// Construct a new `Plane` instance.
-    pub const fn new(point: Point3<T>, normal: Unit<Vector3<T>>) -> Self {
-        Self { point, normal }
+    pub fn new(point: Point3<T>, normal: Unit<Vector3<T>>) -> Self {
+        let distance = normal.dot(&point.coords);
+        Self { point, normal, distance }
+    }
+
+    /// Get the reference point on the `Plane`.
+    #[must_use]
+    pub const fn point(&self) -> &Point3<T> {
+        &self.point
+    }
+
+    /// Get the normal vector of the `Plane`.
+    #[must_use]
+    pub const fn normal(&self) -> &Unit<Vector3<T>> {
+        &self.normal
+    }
+
+    /// Get the signed distance of the `Plane` from the origin along its normal.
+    #[must_use]
+    pub const fn distance(&self) -> T {
+        self.distance
     }
 
     /// Create a `Plane` from three non-collinear points.
@@ -47,21 +66,9 @@ impl<T: RealField + Copy> Plane<T> {
     pub fn yz_plane(x: T) -> Self {
         Self::new(Point3::new(x, T::zero(), T::zero()), Unit::new_unchecked(Vector3::x()))
     }
-}
-
-impl<T: RealField + Copy> Bounded<T> for Plane<T> {
-    fn aabb(&self) -> Result<Cow<Aabb<T>>> {
-        // Infinite planes have infinite bounding boxes so we use very large values to approximate infinity
-        let large_value = T::try_from_f64(1e12)?;
-        Ok(Cow::Owned(Aabb::new(
-            Point3::new(-large_value, -large_value, -large_value),
-            Point3::new(large_value, large_value, large_value),
-        )?))
-    }
-}
 
-impl<T: RealField + Copy> Traceable<T> for Plane<T> {
-    fn intersect(&self, ray: &Ray<T>) -> Result<Option<Hit<T>>> {
+    /// Distance along the `Ray` to the `Plane`, and the cosine term between the `Ray` direction and the normal.
+    fn intersect_distance(&self, ray: &Ray<T>) -> Option<(T, T)> {
         let epsilon = T::default_epsilon();
 
         // Calculate the denominator of the ray-plane intersection formula
@@ -69,17 +76,36 @@ impl<T: RealField + Copy> Traceable<T> for Plane<T> {
 
         // Check if ray is parallel to the plane (denominator near zero)
         if denominator.abs() < epsilon {
-            return Ok(None);
+            return None;
         }
 
         // Calculate the distance along the ray to the intersection point
-        let to_point = self.point - ray.origin;
-        let t = to_point.dot(&self.normal) / denominator;
+        let t = (self.distance - ray.origin.coords.dot(&self.normal)) / denominator;
 
         // Check if intersection is behind the ray origin
-        if t < epsilon {
+        (t >= epsilon).then_some((t, denominator))
+    }
+}
+
+impl<T: Scalar> Bounded<T> for Plane<T> {
+    /// Infinite planes cannot be enclosed by any `Aabb`.
+    fn aabb(&self) -> Result<Bounds<'_, T>> {
+        Ok(Bounds::Unbounded)
+    }
+}
+
+impl<T: Scalar> DistanceField<T> for Plane<T> {
+    /// Distance along the normal, so negative behind the `Plane`.
+    fn signed_distance(&self, point: &Point3<T>) -> Result<T> {
+        Ok(point.coords.dot(&self.normal) - self.distance)
+    }
+}
+
+impl<T: Scalar> Traceable<T> for Plane<T> {
+    fn intersect(&self, ray: &Ray<T>) -> Result<Option<Hit<T>>> {
+        let Some((t, denominator)) = self.intersect_distance(ray) else {
             return Ok(None);
-        }
+        };
 
         // For planes, geometric normal and interpolated normal are the same
         let normal = if denominator < T::zero() {
@@ -90,6 +116,36 @@ impl<T: RealField + Copy> Traceable<T> for Plane<T> {
             Unit::new_unchecked(-self.normal.as_ref())
         };
 
-        Ok(Some(Hit::new(0, t, normal, normal)?))
+        Ok(Some(Hit::new_unchecked(0, t, normal, normal)))
+    }
+
+    fn intersect_t(&self, ray: &Ray<T>) -> Result<Option<T>> {
+        Ok(self.intersect_distance(ray).map(|(t, _)| t))
+    }
+
+    fn sphere_cast(&self, ray: &Ray<T>, radius: T) -> Result<Option<Hit<T>>> {
+        let offset = ray.origin.coords.dot(&self.normal) - self.distance;
+        let approach = ray.direction.dot(&self.normal);
+
+        // Already touching the plane, or not moving towards it
+        if offset.abs() <= radius || offset * approach >= T::zero() {
+            return Ok(None);
+        }
+
+        // Contact when the centre reaches `radius` from the plane, on the side it started
+        let (side, normal) = if offset > T::zero() {
+            (radius, self.normal)
+        } else {
+            (-radius, Unit::new_unchecked(-self.normal.as_ref()))
+        };
+        let t = (side - offset) / approach;
+        Ok(Some(Hit::new_unchecked(0, t, normal, normal)))
+    }
+}
+
+impl<T: Scalar> Transformable<T> for Plane<T> {
+    fn transform(&self, transform: &Matrix4<T>) -> Result<Self> {
+        let normal = Unit::new_normalize(normal_matrix(transform)? * self.normal.into_inner());
+        Ok(Self::new(transform.transform_point(&self.point), normal))
     }
 }
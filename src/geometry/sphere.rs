@@ -1,25 +1,29 @@
 //! Sphere structure.
 
-use nalgebra::{Point3, RealField, Unit, Vector3};
+use nalgebra::{Matrix4, Point3, Unit, Vector3};
+use rand::Rng;
 use std::borrow::Cow;
 
 use crate::{
     error::{GeometryError, Result},
     geometry::Aabb,
     rt::{Hit, Ray},
-    traits::{Bounded, FallibleNumeric, Traceable},
+    traits::{
+        Bounded, Bounds, DistanceField, FallibleNumeric, Scalar, SolidAngle, SurfaceSample, Traceable, Transformable,
+        sample_unit, uniform_scale,
+    },
 };
 
 /// Sphere structure defined by a center point and a radius.
 #[derive(Debug, Clone)]
-pub struct Sphere<T: RealField + Copy> {
+pub struct Sphere<T: Scalar> {
     /// Center of the sphere.
     pub center: Point3<T>,
     /// Radius of the sphere.
     pub radius: T,
 }
 
-impl<T: RealField + Copy> Sphere<T> {
+impl<T: Scalar> Sphere<T> {
     /// Construct a new `Sphere` instance.
     ///
     /// # Errors
@@ -34,49 +38,81 @@ impl<T: RealField + Copy> Sphere<T> {
         }
         Ok(Self { center, radius })
     }
-}
-
-impl<T: RealField + Copy> Bounded<T> for Sphere<T> {
-    fn aabb(&self) -> Result<Cow<Aabb<T>>> {
-        let r = Vector3::new(self.radius, self.radius, self.radius);
-        Ok(Cow::Owned(Aabb::new(self.center - r, self.center + r)?))
-    }
-}
 
-impl<T: RealField + Copy> Traceable<T> for Sphere<T> {
-    fn intersect(&self, ray: &Ray<T>) -> Result<Option<Hit<T>>> {
+    /// Distance along the `Ray` to the nearest intersection in front of its origin, if any.
+    fn nearest_root(&self, ray: &Ray<T>) -> Option<T> {
         let epsilon = T::default_epsilon();
 
         // Vector from ray origin to sphere center
         let oc = ray.origin - self.center;
 
-        // Quadratic equation coefficients: at^2 + bt + c = 0
+        // Quadratic equation coefficients, using half of the linear term: at^2 + 2bt + c = 0
         let a = ray.direction.dot(&ray.direction);
-        let b = T::try_from_u8(2)? * oc.dot(&ray.direction);
+        let half_b = oc.dot(&ray.direction);
         let c = oc.dot(&oc) - self.radius * self.radius;
 
-        // Discriminant
-        let discriminant = b * b - T::try_from_u8(4)? * a * c;
+        // Quarter discriminant
+        let discriminant = half_b.powi(2) - a * c;
 
         // No intersection if discriminant is negative
         if discriminant < T::zero() {
-            return Ok(None);
+            return None;
         }
 
         let sqrt_discriminant = discriminant.sqrt();
-        let two_a = T::try_from_u8(2)? * a;
 
         // Calculate both roots
-        let t1 = (-b - sqrt_discriminant) / two_a;
-        let t2 = (-b + sqrt_discriminant) / two_a;
+        let t1 = (-half_b - sqrt_discriminant) / a;
+        let t2 = (-half_b + sqrt_discriminant) / a;
 
         // Choose the closest positive intersection
-        let t = if t1 > epsilon {
-            t1
+        if t1 > epsilon {
+            Some(t1)
         } else if t2 > epsilon {
-            t2
+            Some(t2)
         } else {
-            return Ok(None); // No valid intersection
+            None
+        }
+    }
+
+    /// Distance along the `Ray` at which it enters the `Sphere`.
+    /// Returns `None` if the `Ray` misses, points away, or starts inside the `Sphere`.
+    pub fn entry_distance(&self, ray: &Ray<T>) -> Option<T> {
+        let oc = ray.origin - self.center;
+        let half_b = oc.dot(&ray.direction);
+        let c = oc.dot(&oc) - self.radius * self.radius;
+        if c < T::zero() || half_b >= T::zero() {
+            return None;
+        }
+
+        // The direction is a unit vector, so the quadratic coefficient is one
+        let discriminant = half_b.powi(2) - c;
+        (discriminant >= T::zero()).then(|| -half_b - discriminant.sqrt())
+    }
+}
+
+impl<T: Scalar> Bounded<T> for Sphere<T> {
+    fn aabb(&self) -> Result<Bounds<'_, T>> {
+        let r = Vector3::new(self.radius, self.radius, self.radius);
+        Ok(Bounds::Finite(Cow::Owned(Aabb::new(self.center - r, self.center + r)?)))
+    }
+
+    fn span(&self, direction: &Vector3<T>) -> Result<Option<[T; 2]>> {
+        let (centre, reach) = (self.center.coords.dot(direction), self.radius * direction.norm());
+        Ok(Some([centre - reach, centre + reach]))
+    }
+}
+
+impl<T: Scalar> DistanceField<T> for Sphere<T> {
+    fn signed_distance(&self, point: &Point3<T>) -> Result<T> {
+        Ok((point - self.center).norm() - self.radius)
+    }
+}
+
+impl<T: Scalar> Traceable<T> for Sphere<T> {
+    fn intersect(&self, ray: &Ray<T>) -> Result<Option<Hit<T>>> {
+        let Some(t) = self.nearest_root(ray) else {
+            return Ok(None);
         };
 
         // Calculate intersection point and normal
@@ -84,6 +120,65 @@ impl<T: RealField + Copy> Traceable<T> for Sphere<T> {
         let normal_vector = (intersection_point - self.center) / self.radius;
         let normal = Unit::new_normalize(normal_vector);
 
-        Ok(Some(Hit::new(0, t, normal, normal)?))
+        Ok(Some(Hit::new_unchecked(0, t, normal, normal)))
+    }
+
+    fn intersect_t(&self, ray: &Ray<T>) -> Result<Option<T>> {
+        Ok(self.nearest_root(ray))
+    }
+
+    fn sphere_cast(&self, ray: &Ray<T>, radius: T) -> Result<Option<Hit<T>>> {
+        // The swept sphere touches this one when its centre enters the sphere of the combined radius
+        let combined = Self {
+            center: self.center,
+            radius: self.radius + radius,
+        };
+        Ok(combined.entry_distance(ray).map(|t| {
+            let normal = Unit::new_normalize(ray.origin + ray.direction.scale(t) - self.center);
+            Hit::new_unchecked(0, t, normal, normal)
+        }))
+    }
+}
+
+impl<T: Scalar> Transformable<T> for Sphere<T> {
+    /// Transform the `Sphere`, which must remain a sphere (rotation, translation and uniform scale only).
+    fn transform(&self, transform: &Matrix4<T>) -> Result<Self> {
+        Self::new(
+            transform.transform_point(&self.center),
+            self.radius * uniform_scale(transform)?,
+        )
+    }
+}
+
+impl<T: Scalar> SolidAngle<T> for Sphere<T> {
+    /// Solid angle of the cone of directions which reach the `Sphere`, or the whole sphere of directions from inside it.
+    fn solid_angle(&self, point: &Point3<T>) -> Result<T> {
+        let distance_squared = (self.center - point).norm_squared();
+        let radius_squared = self.radius.powi(2);
+        if distance_squared <= radius_squared {
+            return Ok(T::try_from_u8(4)? * T::pi());
+        }
+        Ok(T::two_pi() * (T::one() - (T::one() - radius_squared / distance_squared).sqrt()))
+    }
+}
+
+impl<T: Scalar> SurfaceSample<T> for Sphere<T> {
+    fn area(&self) -> Result<T> {
+        Ok(T::try_from_u8(4)? * T::pi() * self.radius * self.radius)
+    }
+
+    fn sample_point<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<(Point3<T>, Unit<Vector3<T>>, T)> {
+        let area = self.area()?;
+        if area <= T::zero() {
+            return Err(GeometryError::ZeroArea.into());
+        }
+
+        // Uniform direction: uniform height and azimuth (Archimedes' hat-box theorem)
+        let z = T::one() - T::try_from_u8(2)? * sample_unit::<T, R>(rng)?;
+        let phi = T::two_pi() * sample_unit::<T, R>(rng)?;
+        let r = (T::one() - z * z).max(T::zero()).sqrt();
+        let normal = Unit::new_normalize(Vector3::new(r * phi.cos(), r * phi.sin(), z));
+
+        Ok((self.center + normal.into_inner() * self.radius, normal, area.recip()))
     }
 }
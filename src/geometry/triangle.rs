@@ -1,128 +1,533 @@
-use nalgebra::{Point3, RealField, Unit, Vector3};
-use std::borrow::Cow;
+use nalgebra::{Matrix3, Matrix4, Point3, Unit, Vector3};
+use rand::Rng;
+use std::{borrow::Cow, sync::Arc};
 
 use crate::{
-    error::Result,
-    geometry::Aabb,
-    rt::{Hit, Ray},
-    traits::{Bounded, Traceable},
+    error::{GeometryError, Result},
+    geometry::{Aabb, AlphaMask, Sphere, TriangleIntersection},
+    rt::{Hit, Ray, TangentFrame},
+    traits::{
+        Bounded, Bounds, FallibleNumeric, Scalar, SolidAngle, SurfaceSample, Traceable, Transformable, normal_matrix,
+        sample_unit,
+    },
 };
 
 /// `Triangle` geometry embedded in 3D space.
-#[derive(Debug)]
-pub struct Triangle<T: RealField + Copy> {
-    /// First vertex position (vertex 0).
-    vertex0: Point3<T>,
+#[derive(Debug, Clone)]
+pub struct Triangle<T: Scalar> {
+    /// Vertex positions.
+    vertices: [Point3<T>; 3],
     /// Vertex normals for interpolation.
     normals: [Unit<Vector3<T>>; 3],
+    /// Vertex texture coordinates [u, v] for interpolation, if any.
+    uvs: Option<[[T; 2]; 3]>,
+    /// Vertex colours [red, green, blue, alpha] for interpolation, if any.
+    colors: Option<[[T; 4]; 3]>,
+    /// Vertex tangents [x, y, z, handedness] for interpolation, if any.
+    vertex_tangents: Option<[[T; 4]; 3]>,
+    /// Opacity texture, looked up by the interpolated texture coordinates, through which `Ray`s pass where transparent.
+    alpha_mask: Option<Arc<AlphaMask<T>>>,
     /// Edge from vertex 0 to vertex 1.
     edge1: Vector3<T>,
     /// Edge from vertex 0 to vertex 2.
     edge2: Vector3<T>,
+    /// Unnormalised normal (`edge1` cross `edge2`), whose length is twice the area.
+    normal: Vector3<T>,
     /// Geometric normal.
     geometric_normal: Unit<Vector3<T>>,
+    /// Size-relative tolerance used by the intersection tests.
+    epsilon: T,
+    /// Algorithm used for `Ray` intersection tests.
+    intersection: TriangleIntersection,
 }
 
-impl<T: RealField + Copy> Triangle<T> {
+impl<T: Scalar> Triangle<T> {
     /// Construct a new `Triangle` instance.
     pub fn new(vertices: [Point3<T>; 3], normals: [Unit<Vector3<T>>; 3]) -> Self {
         let edge1 = vertices[1] - vertices[0];
         let edge2 = vertices[2] - vertices[0];
-        let geometric_normal = Unit::new_normalize(edge1.cross(&edge2));
+        let normal = edge1.cross(&edge2);
+        let geometric_normal = Unit::new_normalize(normal);
+        let epsilon = T::default_epsilon() * edge1.norm_squared().max(edge2.norm_squared()).sqrt();
 
         Self {
-            vertex0: vertices[0],
+            vertices,
             normals,
+            uvs: None,
+            colors: None,
+            vertex_tangents: None,
+            alpha_mask: None,
             edge1,
             edge2,
+            normal,
             geometric_normal,
+            epsilon,
+            intersection: TriangleIntersection::default(),
         }
     }
-}
 
-impl<T: RealField + Copy> Bounded<T> for Triangle<T> {
-    /// Compute the `Aabb` of the `Triangle`.
-    fn aabb(&self) -> Result<Cow<Aabb<T>>> {
-        let min_x = self
-            .vertex0
-            .x
-            .min(self.vertex0.x + self.edge1.x)
-            .min(self.vertex0.x + self.edge2.x);
-        let min_y = self
-            .vertex0
-            .y
-            .min(self.vertex0.y + self.edge1.y)
-            .min(self.vertex0.y + self.edge2.y);
-        let min_z = self
-            .vertex0
-            .z
-            .min(self.vertex0.z + self.edge1.z)
-            .min(self.vertex0.z + self.edge2.z);
-
-        let max_x = self
-            .vertex0
-            .x
-            .max(self.vertex0.x + self.edge1.x)
-            .max(self.vertex0.x + self.edge2.x);
-        let max_y = self
-            .vertex0
-            .y
-            .max(self.vertex0.y + self.edge1.y)
-            .max(self.vertex0.y + self.edge2.y);
-        let max_z = self
-            .vertex0
-            .z
-            .max(self.vertex0.z + self.edge1.z)
-            .max(self.vertex0.z + self.edge2.z);
-
-        Ok(Cow::Owned(Aabb::new(
-            Point3::new(min_x, min_y, min_z),
-            Point3::new(max_x, max_y, max_z),
-        )?))
+    /// Set the algorithm used for `Ray` intersection tests.
+    #[must_use]
+    pub const fn with_intersection(mut self, intersection: TriangleIntersection) -> Self {
+        self.intersection = intersection;
+        self
     }
-}
 
-impl<T: RealField + Copy> Traceable<T> for Triangle<T> {
-    fn intersect(&self, ray: &Ray<T>) -> Result<Option<Hit<T>>> {
-        // Use a relative epsilon based on the triangle's size
-        let edge_length_sq = self.edge1.norm_squared().max(self.edge2.norm_squared());
-        let epsilon = T::default_epsilon() * edge_length_sq.sqrt();
+    /// Get the algorithm used for `Ray` intersection tests.
+    #[must_use]
+    pub const fn intersection(&self) -> TriangleIntersection {
+        self.intersection
+    }
+
+    /// Set the texture coordinates [u, v] of each vertex, which are interpolated into every `Hit`.
+    #[must_use]
+    pub const fn with_uvs(mut self, uvs: [[T; 2]; 3]) -> Self {
+        self.uvs = Some(uvs);
+        self
+    }
+
+    /// Set the colour [red, green, blue, alpha] of each vertex, which is interpolated into every `Hit`.
+    #[must_use]
+    pub const fn with_colors(mut self, colors: [[T; 4]; 3]) -> Self {
+        self.colors = Some(colors);
+        self
+    }
+
+    /// Set the tangent [x, y, z, handedness] of each vertex, which is interpolated into the tangent frame of every `Hit`.
+    /// A negative handedness marks mirrored texture coordinates, as in `MikkTSpace`.
+    #[must_use]
+    pub const fn with_vertex_tangents(mut self, vertex_tangents: [[T; 4]; 3]) -> Self {
+        self.vertex_tangents = Some(vertex_tangents);
+        self
+    }
+
+    /// Set the `AlphaMask` cutting holes out of the `Triangle`, which `Ray`s pass through.
+    /// The mask is shared, so every `Triangle` of a foliage card or fence can reference the same texture.
+    /// It is looked up by the interpolated texture coordinates, so has no effect on a `Triangle` without them.
+    #[must_use]
+    pub fn with_alpha_mask(mut self, alpha_mask: Arc<AlphaMask<T>>) -> Self {
+        self.alpha_mask = Some(alpha_mask);
+        self
+    }
+
+    /// Get the `AlphaMask` of the `Triangle`, if any.
+    #[must_use]
+    pub const fn alpha_mask(&self) -> Option<&Arc<AlphaMask<T>>> {
+        self.alpha_mask.as_ref()
+    }
+
+    /// Get the vertex tangents [x, y, z, handedness] of the `Triangle`, if any.
+    #[must_use]
+    pub const fn vertex_tangents(&self) -> Option<&[[T; 4]; 3]> {
+        self.vertex_tangents.as_ref()
+    }
+
+    /// Get the vertex texture coordinates of the `Triangle`, if any.
+    #[must_use]
+    pub const fn uvs(&self) -> Option<&[[T; 2]; 3]> {
+        self.uvs.as_ref()
+    }
+
+    /// Get the rates of change of position with the texture coordinates [dP/du, dP/dv], which span the tangent space
+    /// of a normal map. Returns `None` if the `Triangle` has no texture coordinates, or they are degenerate.
+    #[must_use]
+    pub fn tangents(&self) -> Option<[Vector3<T>; 2]> {
+        let [uv0, uv1, uv2] = self.uvs?;
+        let (du1, dv1) = (uv1[0] - uv0[0], uv1[1] - uv0[1]);
+        let (du2, dv2) = (uv2[0] - uv0[0], uv2[1] - uv0[1]);
+        let determinant = du1 * dv2 - du2 * dv1;
+        if determinant == T::zero() {
+            return None;
+        }
+        Some([
+            (self.edge1 * dv2 - self.edge2 * dv1) / determinant,
+            (self.edge2 * du1 - self.edge1 * du2) / determinant,
+        ])
+    }
+
+    /// Get the vertex colours of the `Triangle`, if any.
+    #[must_use]
+    pub const fn colors(&self) -> Option<&[[T; 4]; 3]> {
+        self.colors.as_ref()
+    }
+
+    /// Transform the `Triangle` with a precomputed `normal_matrix` of the transform,
+    /// keeping its vertex attributes, `AlphaMask` and intersection algorithm.
+    /// Vertex tangents follow the surface, and change handedness if the transform mirrors it.
+    /// Saves inverting the transform for every `Triangle` of a `Mesh`.
+    #[must_use]
+    pub fn transform_with(&self, transform: &Matrix4<T>, normal_transform: &Matrix3<T>) -> Self {
+        let mirrored = normal_transform.determinant() < T::zero();
+        let vertex_tangents = self.vertex_tangents.map(|tangents| {
+            tangents.map(|[x, y, z, handedness]| {
+                let tangent = transform.transform_vector(&Vector3::new(x, y, z));
+                [
+                    tangent.x,
+                    tangent.y,
+                    tangent.z,
+                    if mirrored { -handedness } else { handedness },
+                ]
+            })
+        });
+        Self {
+            uvs: self.uvs,
+            colors: self.colors,
+            vertex_tangents,
+            alpha_mask: self.alpha_mask.clone(),
+            ..Self::new(
+                self.vertices.map(|v| transform.transform_point(&v)),
+                self.normals.map(|n| Unit::new_normalize(normal_transform * n.into_inner())),
+            )
+            .with_intersection(self.intersection)
+        }
+    }
+
+    /// Get the vertex positions of the `Triangle`.
+    #[must_use]
+    pub const fn vertices(&self) -> [Point3<T>; 3] {
+        self.vertices
+    }
+
+    /// Get the edge vectors from vertex 0 to vertices 1 and 2.
+    #[must_use]
+    pub const fn edges(&self) -> [Vector3<T>; 2] {
+        [self.edge1, self.edge2]
+    }
 
-        let h = ray.direction.cross(&self.edge2);
-        let a = self.edge1.dot(&h);
+    /// Get the vertex normals of the `Triangle`.
+    #[must_use]
+    pub const fn normals(&self) -> &[Unit<Vector3<T>>; 3] {
+        &self.normals
+    }
+
+    /// Get the geometric normal of the `Triangle`, facing the side from which its vertices wind anticlockwise.
+    #[must_use]
+    pub const fn geometric_normal(&self) -> &Unit<Vector3<T>> {
+        &self.geometric_normal
+    }
+
+    /// Check if the `Triangle` has zero area, or any non-finite vertex position or normal.
+    /// Such `Triangle`s produce invalid bounding boxes and NaN normals when intersected.
+    #[must_use]
+    pub fn is_degenerate(&self) -> bool {
+        let area_squared = self.normal.norm_squared();
+        !(area_squared > T::zero() && area_squared.is_finite())
+            || self.normals.iter().any(|normal| normal.iter().any(|c| !c.is_finite()))
+    }
+
+    /// Find the point of the `Triangle`, including its edges and vertices, nearest to a given point.
+    /// Uses the Voronoi region method of Ericson.
+    #[must_use]
+    pub fn closest_point(&self, point: &Point3<T>) -> Point3<T> {
+        let [a, b, c] = self.vertices;
+        let (ab, ac) = (self.edge1, self.edge2);
+
+        // Vertex region of `a`
+        let ap = point - a;
+        let (d1, d2) = (ab.dot(&ap), ac.dot(&ap));
+        if d1 <= T::zero() && d2 <= T::zero() {
+            return a;
+        }
+
+        // Vertex region of `b`
+        let bp = point - b;
+        let (d3, d4) = (ab.dot(&bp), ac.dot(&bp));
+        if d3 >= T::zero() && d4 <= d3 {
+            return b;
+        }
+
+        // Edge region of `ab`
+        let vc = d1 * d4 - d3 * d2;
+        if vc <= T::zero() && d1 >= T::zero() && d3 <= T::zero() {
+            return a + ab * (d1 / (d1 - d3));
+        }
+
+        // Vertex region of `c`
+        let cp = point - c;
+        let (d5, d6) = (ab.dot(&cp), ac.dot(&cp));
+        if d6 >= T::zero() && d5 <= d6 {
+            return c;
+        }
+
+        // Edge region of `ac`
+        let vb = d5 * d2 - d1 * d6;
+        if vb <= T::zero() && d2 >= T::zero() && d6 <= T::zero() {
+            return a + ac * (d2 / (d2 - d6));
+        }
+
+        // Edge region of `bc`
+        let va = d3 * d6 - d5 * d4;
+        if va <= T::zero() && d4 >= d3 && d5 >= d6 {
+            return b + (c - b) * ((d4 - d3) / ((d4 - d3) + (d5 - d6)));
+        }
+
+        // Face region
+        let denominator = (va + vb + vc).recip();
+        a + ab * (vb * denominator) + ac * (vc * denominator)
+    }
+
+    /// Moller-Trumbore intersection test.
+    /// Returns the distance along the `Ray` and the barycentric weights of vertices 1 and 2, if the `Ray` hits.
+    /// The stored normal saves a cross product per `Ray`, and lets hits behind the origin exit early.
+    fn moller_trumbore(&self, ray: &Ray<T>) -> Option<[T; 3]> {
+        let denominator = ray.direction.dot(&self.normal);
 
         // Early exit for parallel rays
-        if a.abs() < epsilon {
-            return Ok(None);
+        if denominator.abs() < self.epsilon {
+            return None;
+        }
+
+        let inv_denominator = denominator.recip();
+        let s = ray.origin - self.vertices[0];
+        let t = -s.dot(&self.normal) * inv_denominator;
+
+        // Early exit for intersections behind the ray origin
+        if t <= self.epsilon {
+            return None;
         }
 
-        let inv_a = T::one() / a;
-        let s = ray.origin - self.vertex0;
-        let u = inv_a * s.dot(&h);
+        let q = s.cross(&ray.direction);
+        let u = -self.edge2.dot(&q) * inv_denominator;
 
         // Early exits for barycentric coordinates
         if u < T::zero() || u > T::one() {
-            return Ok(None);
+            return None;
         }
 
-        let q = s.cross(&self.edge1);
-        let v = inv_a * ray.direction.dot(&q);
+        let v = self.edge1.dot(&q) * inv_denominator;
 
-        if v < T::zero() || u + v > T::one() {
-            return Ok(None);
+        (v >= T::zero() && u + v <= T::one()).then_some([t, u, v])
+    }
+
+    /// Watertight intersection test of Woop, Benthin and Wald (2013).
+    /// Returns the distance along the `Ray` and the barycentric weights of vertices 1 and 2, if the `Ray` hits.
+    fn watertight(&self, ray: &Ray<T>) -> Option<[T; 3]> {
+        // Permute axes so the ray travels mostly along z, keeping the winding
+        let kz = ray.direction.iamax();
+        let (mut kx, mut ky) = ((kz + 1) % 3, (kz + 2) % 3);
+        if ray.direction[kz] < T::zero() {
+            (kx, ky) = (ky, kx);
         }
 
-        let t = inv_a * self.edge2.dot(&q);
+        // Shear so the ray direction becomes the unit z axis
+        let sz = ray.direction[kz].recip();
+        let sx = ray.direction[kx] * sz;
+        let sy = ray.direction[ky] * sz;
 
-        if t <= epsilon {
-            return Ok(None);
+        // Project the vertices, relative to the ray origin, onto the sheared xy plane
+        let [a, b, c] = self.vertices.map(|vertex| vertex - ray.origin);
+        let (ax, ay) = (a[kx] - sx * a[kz], a[ky] - sy * a[kz]);
+        let (bx, by) = (b[kx] - sx * b[kz], b[ky] - sy * b[kz]);
+        let (cx, cy) = (c[kx] - sx * c[kz], c[ky] - sy * c[kz]);
+
+        // Scaled barycentric coordinates, which are computed identically for edges shared between triangles
+        let weight0 = cx * by - cy * bx;
+        let weight1 = ax * cy - ay * cx;
+        let weight2 = bx * ay - by * ax;
+        let zero = T::zero();
+        if (weight0 < zero || weight1 < zero || weight2 < zero) && (weight0 > zero || weight1 > zero || weight2 > zero) {
+            return None;
         }
 
+        // Degenerate or NaN vertices leave no well-defined hit
+        let determinant = weight0 + weight1 + weight2;
+        if determinant == zero || !determinant.is_finite() {
+            return None;
+        }
+
+        // Scaled hit distance, which must lie in front of the ray origin
+        let scaled_t = (weight0 * a[kz] + weight1 * b[kz] + weight2 * c[kz]) * sz;
+        let t = scaled_t / determinant;
+        if t <= self.epsilon {
+            return None;
+        }
+
+        Some([t, weight1 / determinant, weight2 / determinant])
+    }
+
+    /// Sweep a sphere of `radius` against the face of the `Triangle`, displaced towards the sweep origin.
+    /// Returns the distance travelled and the contact normal, if the sphere touches the face interior.
+    fn sweep_face(&self, ray: &Ray<T>, radius: T) -> Option<(T, Unit<Vector3<T>>)> {
+        let offset = (ray.origin - self.vertices[0]).dot(&self.geometric_normal);
+        let approach = ray.direction.dot(&self.geometric_normal);
+
+        // Already touching the plane of the face, or not moving towards it
+        if offset.abs() <= radius || offset * approach >= T::zero() {
+            return None;
+        }
+
+        let (side, normal) = if offset > T::zero() {
+            (radius, self.geometric_normal)
+        } else {
+            (-radius, Unit::new_unchecked(-self.geometric_normal.as_ref()))
+        };
+        let t = (side - offset) / approach;
+
+        // The contact point must lie within all three edges
+        let contact = ray.origin + ray.direction.scale(t) - self.geometric_normal.scale(side);
+        let inside = (0..3).all(|i| {
+            let (a, b) = (self.vertices[i], self.vertices[(i + 1) % 3]);
+            (b - a).cross(&(contact - a)).dot(&self.normal) >= T::zero()
+        });
+        inside.then_some((t, normal))
+    }
+
+    /// Sweep a sphere of `radius` against the edge from `a` to `b`, treated as a cylinder without end caps.
+    /// Returns the distance travelled and the contact normal, if the sphere touches the edge between its ends.
+    fn sweep_edge(ray: &Ray<T>, radius: T, a: Point3<T>, b: Point3<T>) -> Option<(T, Unit<Vector3<T>>)> {
+        let edge = b - a;
+        let to_origin = ray.origin - a;
+        let edge_squared = edge.dot(&edge);
+        let edge_direction = edge.dot(&ray.direction);
+        let edge_origin = edge.dot(&to_origin);
+
+        // Quadratic in the distance travelled, scaled by the squared edge length
+        let k2 = edge_squared - edge_direction * edge_direction;
+        let k1 = edge_squared * ray.direction.dot(&to_origin) - edge_origin * edge_direction;
+        let k0 = edge_squared * to_origin.dot(&to_origin) - edge_origin * edge_origin - radius * radius * edge_squared;
+
+        // Moving parallel to the edge, or already within the cylinder; the vertices handle both
+        if k2 <= T::zero() || k0 < T::zero() {
+            return None;
+        }
+
+        let discriminant = k1.powi(2) - k2 * k0;
+        if discriminant < T::zero() {
+            return None;
+        }
+
+        let t = (-k1 - discriminant.sqrt()) / k2;
+        let along = edge_origin + t * edge_direction;
+        if t <= T::zero() || along < T::zero() || along > edge_squared {
+            return None;
+        }
+
+        let centre = ray.origin + ray.direction.scale(t);
+        Some((t, Unit::new_normalize(centre - (a + edge * (along / edge_squared)))))
+    }
+
+    /// Tangent frame at a point with the given barycentric weights and shading normal, interpolating the vertex tangents
+    /// if set, or otherwise aligned with the texture coordinates of the face. Returns `None` without texture coordinates.
+    fn tangent_frame(&self, normal: Unit<Vector3<T>>, weights: [T; 3]) -> Option<TangentFrame<T>> {
+        if let Some(tangents) = &self.vertex_tangents {
+            let [x, y, z, handedness] = interpolate(tangents, weights);
+            return TangentFrame::new(normal, &Vector3::new(x, y, z), handedness);
+        }
+        let [tangent, bitangent] = self.tangents()?;
+        TangentFrame::new(normal, &tangent, normal.cross(&tangent).dot(&bitangent))
+    }
+
+    /// Check if the `AlphaMask`, if any, is opaque at the given barycentric weights.
+    /// A `Triangle` without texture coordinates is opaque everywhere.
+    fn is_opaque(&self, weights: [T; 3]) -> Result<bool> {
+        match (&self.alpha_mask, &self.uvs) {
+            (Some(alpha_mask), Some(uvs)) => alpha_mask.is_opaque(interpolate(uvs, weights)),
+            _ => Ok(true),
+        }
+    }
+
+    /// Test a `Ray` with the selected intersection algorithm, passing through transparent parts of the `AlphaMask`.
+    fn intersect_barycentric(&self, ray: &Ray<T>) -> Result<Option<[T; 3]>> {
+        let Some([t, u, v]) = (match self.intersection {
+            TriangleIntersection::MollerTrumbore => self.moller_trumbore(ray),
+            TriangleIntersection::Watertight => self.watertight(ray),
+        }) else {
+            return Ok(None);
+        };
+        Ok(self.is_opaque([T::one() - u - v, u, v])?.then_some([t, u, v]))
+    }
+}
+
+impl<T: Scalar> Bounded<T> for Triangle<T> {
+    /// Compute the `Aabb` of the `Triangle`.
+    fn aabb(&self) -> Result<Bounds<'_, T>> {
+        let [a, b, c] = &self.vertices;
+        Ok(Bounds::Finite(Cow::Owned(Aabb::new(a.inf(b).inf(c), a.sup(b).sup(c))?)))
+    }
+
+    fn span(&self, direction: &Vector3<T>) -> Result<Option<[T; 2]>> {
+        let [a, b, c] = self.vertices.map(|vertex| vertex.coords.dot(direction));
+        Ok(Some([a.min(b).min(c), a.max(b).max(c)]))
+    }
+}
+
+impl<T: Scalar> Traceable<T> for Triangle<T> {
+    fn intersect(&self, ray: &Ray<T>) -> Result<Option<Hit<T>>> {
+        let Some([t, u, v]) = self.intersect_barycentric(ray)? else {
+            return Ok(None);
+        };
+
         // Optimized normal interpolation
         let w = T::one() - u - v;
         let interpolated_normal =
             Unit::new_normalize(self.normals[0].scale(w) + self.normals[1].scale(u) + self.normals[2].scale(v));
 
-        Ok(Some(Hit::new(0, t, self.geometric_normal, interpolated_normal)?))
+        let mut hit = Hit::new_unchecked(0, t, self.geometric_normal, interpolated_normal).with_barycentric([w, u, v]);
+        hit.uv = self.uvs.map(|uvs| interpolate(&uvs, [w, u, v]));
+        hit.color = self.colors.map(|colors| interpolate(&colors, [w, u, v]));
+        hit.tangent_frame = self.tangent_frame(interpolated_normal, [w, u, v]);
+        Ok(Some(hit))
     }
+
+    fn intersect_t(&self, ray: &Ray<T>) -> Result<Option<T>> {
+        Ok(self.intersect_barycentric(ray)?.map(|[t, _, _]| t))
+    }
+
+    fn sphere_cast(&self, ray: &Ray<T>, radius: T) -> Result<Option<Hit<T>>> {
+        // The first contact is with the face, an edge or a vertex
+        let face = self.sweep_face(ray, radius);
+        let edges = (0..3).filter_map(|i| Self::sweep_edge(ray, radius, self.vertices[i], self.vertices[(i + 1) % 3]));
+        let vertices = self.vertices.iter().filter_map(|&vertex| {
+            let t = Sphere { center: vertex, radius }.entry_distance(ray)?;
+            Some((t, Unit::new_normalize(ray.origin + ray.direction.scale(t) - vertex)))
+        });
+
+        let mut closest: Option<(T, Unit<Vector3<T>>)> = None;
+        for (t, normal) in face.into_iter().chain(edges).chain(vertices) {
+            if closest.is_none_or(|(closest_t, _)| t < closest_t) {
+                closest = Some((t, normal));
+            }
+        }
+        Ok(closest.map(|(t, normal)| Hit::new_unchecked(0, t, normal, normal)))
+    }
+}
+
+impl<T: Scalar> Transformable<T> for Triangle<T> {
+    fn transform(&self, transform: &Matrix4<T>) -> Result<Self> {
+        Ok(self.transform_with(transform, &normal_matrix(transform)?))
+    }
+}
+
+impl<T: Scalar> SolidAngle<T> for Triangle<T> {
+    fn solid_angle(&self, point: &Point3<T>) -> Result<T> {
+        self.vertices.solid_angle(point)
+    }
+}
+
+impl<T: Scalar> SurfaceSample<T> for Triangle<T> {
+    fn area(&self) -> Result<T> {
+        Ok(self.normal.norm() / T::try_from_u8(2)?)
+    }
+
+    fn sample_point<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<(Point3<T>, Unit<Vector3<T>>, T)> {
+        let area = self.area()?;
+        if area <= T::zero() {
+            return Err(GeometryError::ZeroArea.into());
+        }
+
+        // Uniform barycentric coordinates via the square-root parameterisation
+        let root = sample_unit::<T, R>(rng)?.sqrt();
+        let u = T::one() - root;
+        let v = sample_unit::<T, R>(rng)? * root;
+        let point = self.vertices[0] + self.edge1 * u + self.edge2 * v;
+
+        Ok((point, self.geometric_normal, area.recip()))
+    }
+}
+
+// == Utility functions ==
+
+/// Interpolate per-vertex values with barycentric weights.
+fn interpolate<T: Scalar, const N: usize>(values: &[[T; N]; 3], [w, u, v]: [T; 3]) -> [T; N] {
+    std::array::from_fn(|i| values[0][i] * w + values[1][i] * u + values[2][i] * v)
 }
@@ -0,0 +1,14 @@
+//! Triangle intersection algorithm enumeration.
+
+use serde::{Deserialize, Serialize};
+
+/// Algorithm used to test `Ray`s against `Triangle`s.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TriangleIntersection {
+    /// Moller-Trumbore test with a size-relative epsilon. Fast, but `Ray`s can occasionally slip through shared edges.
+    #[default]
+    MollerTrumbore,
+    /// Watertight test of Woop, Benthin and Wald (2013).
+    /// Slightly slower, but never misses a `Ray` passing through an edge or vertex shared by adjacent `Triangle`s.
+    Watertight,
+}
@@ -0,0 +1,103 @@
+//! Structure-of-arrays triangle storage.
+
+use nalgebra::{Point3, Vector3};
+
+use crate::{geometry::Triangle, rt::Ray, traits::Scalar};
+
+/// `Triangle` intersection data stored in structure-of-arrays form, in `Bvh` leaf slot order.
+///
+/// The `Triangle`s of each `Bvh` leaf occupy a contiguous run of slots,
+/// so leaf test loops stream through densely packed arrays rather than striding over whole `Triangle`s.
+#[derive(Debug, Clone)]
+pub struct TriangleSoa<T: Scalar> {
+    /// First vertex positions.
+    vertex0: Vec<Point3<T>>,
+    /// Edges from vertex 0 to vertex 1.
+    edge1: Vec<Vector3<T>>,
+    /// Edges from vertex 0 to vertex 2.
+    edge2: Vec<Vector3<T>>,
+    /// Unnormalised normals.
+    normal: Vec<Vector3<T>>,
+    /// Size-relative intersection tolerances.
+    epsilon: Vec<T>,
+}
+
+impl<T: Scalar> TriangleSoa<T> {
+    /// Construct a new `TriangleSoa` instance, holding `triangles[order[slot]]` in each slot.
+    #[must_use]
+    pub fn new(triangles: &[Triangle<T>], order: &[usize]) -> Self {
+        let mut soa = Self {
+            vertex0: Vec::with_capacity(order.len()),
+            edge1: Vec::with_capacity(order.len()),
+            edge2: Vec::with_capacity(order.len()),
+            normal: Vec::with_capacity(order.len()),
+            epsilon: Vec::with_capacity(order.len()),
+        };
+        for &index in order {
+            let triangle = &triangles[index];
+            let [e1, e2] = triangle.edges();
+            soa.vertex0.push(triangle.vertices()[0]);
+            soa.edge1.push(e1);
+            soa.edge2.push(e2);
+            soa.normal.push(e1.cross(&e2));
+            // Matches the tolerance of the `Triangle` intersection test
+            soa.epsilon
+                .push(T::default_epsilon() * e1.norm_squared().max(e2.norm_squared()).sqrt());
+        }
+        soa
+    }
+
+    /// Get the number of slots.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.vertex0.len()
+    }
+
+    /// Check if there are no slots.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.vertex0.is_empty()
+    }
+
+    /// Get the number of bytes held by the slots.
+    #[must_use]
+    pub const fn memory_bytes(&self) -> usize {
+        self.len() * (size_of::<Point3<T>>() + 3 * size_of::<Vector3<T>>() + size_of::<T>())
+    }
+
+    /// Moller-Trumbore intersection test of a `Ray` against the `Triangle` in a slot,
+    /// with the same arithmetic as the `Triangle` test.
+    /// Returns the distance along the `Ray` and the barycentric weights of vertices 1 and 2, if the `Ray` hits.
+    #[must_use]
+    pub fn intersect(&self, slot: usize, ray: &Ray<T>) -> Option<[T; 3]> {
+        let normal = &self.normal[slot];
+        let epsilon = self.epsilon[slot];
+        let denominator = ray.direction.dot(normal);
+
+        // Early exit for parallel rays
+        if denominator.abs() < epsilon {
+            return None;
+        }
+
+        let inv_denominator = denominator.recip();
+        let s = ray.origin - self.vertex0[slot];
+        let t = -s.dot(normal) * inv_denominator;
+
+        // Early exit for intersections behind the ray origin
+        if t <= epsilon {
+            return None;
+        }
+
+        let q = s.cross(&ray.direction);
+        let u = -self.edge2[slot].dot(&q) * inv_denominator;
+
+        // Early exits for barycentric coordinates
+        if u < T::zero() || u > T::one() {
+            return None;
+        }
+
+        let v = self.edge1[slot].dot(&q) * inv_denominator;
+
+        (v >= T::zero() && u + v <= T::one()).then_some([t, u, v])
+    }
+}
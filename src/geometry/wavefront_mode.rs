@@ -0,0 +1,12 @@
+//! Wavefront (.obj) parsing mode enumeration.
+
+/// Strictness of wavefront (.obj) parsing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WavefrontMode {
+    /// Reject any malformed face, out-of-range index or missing normal with an error.
+    #[default]
+    Strict,
+    /// Skip malformed faces, clamp out-of-range indices and use geometric normals where vertex normals are missing,
+    /// reporting each repair as a `WavefrontWarning`. Suited to loading slightly broken files from large datasets.
+    Lenient,
+}
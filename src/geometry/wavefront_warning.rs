@@ -0,0 +1,80 @@
+//! Non-fatal wavefront (.obj) parsing warnings.
+
+use std::fmt::{self, Display, Formatter};
+
+/// Recoverable issue found while parsing a wavefront (.obj) string, reported with the line on which it occurred.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WavefrontWarning {
+    /// A directive which is not supported (such as texture coordinates or materials) was skipped.
+    IgnoredDirective {
+        /// Line of the directive.
+        line: usize,
+        /// Keyword of the directive.
+        directive: String,
+    },
+    /// An object or group declaration was skipped, so its faces were merged into the `Mesh`.
+    IgnoredGroup {
+        /// Line of the declaration.
+        line: usize,
+        /// Name of the object or group.
+        name: String,
+    },
+    /// A face used the invalid index zero, which was treated as referring to the first element.
+    ZeroIndex {
+        /// Line of the face.
+        line: usize,
+    },
+    /// A face with zero area, or a non-finite position or normal, was found.
+    /// It is kept unless the `MeshConfig` of the `Mesh` drops degenerate `Triangle`s.
+    DegenerateFace {
+        /// Line of the face.
+        line: usize,
+    },
+    /// A malformed face was skipped in lenient mode.
+    MalformedFace {
+        /// Line of the face.
+        line: usize,
+        /// Description of the problem.
+        message: String,
+    },
+    /// A face index beyond the end of the vertex or normal list was clamped to the last element in lenient mode.
+    ClampedIndex {
+        /// Line of the face.
+        line: usize,
+    },
+    /// A face without usable vertex normals was given its geometric normal in lenient mode.
+    DefaultNormals {
+        /// Line of the face.
+        line: usize,
+    },
+}
+
+impl WavefrontWarning {
+    /// Get the line of the source string on which the warning occurred.
+    #[must_use]
+    pub const fn line(&self) -> usize {
+        match self {
+            Self::IgnoredDirective { line, .. }
+            | Self::IgnoredGroup { line, .. }
+            | Self::ZeroIndex { line }
+            | Self::DegenerateFace { line }
+            | Self::MalformedFace { line, .. }
+            | Self::ClampedIndex { line }
+            | Self::DefaultNormals { line } => *line,
+        }
+    }
+}
+
+impl Display for WavefrontWarning {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::IgnoredDirective { line, directive } => write!(f, "Ignored `{directive}` directive at line {line}"),
+            Self::IgnoredGroup { line, name } => write!(f, "Ignored object or group `{name}` at line {line}"),
+            Self::ZeroIndex { line } => write!(f, "Replaced invalid face index 0 with 1 at line {line}"),
+            Self::DegenerateFace { line } => write!(f, "Found degenerate face at line {line}"),
+            Self::MalformedFace { line, message } => write!(f, "Skipped malformed face at line {line}: {message}"),
+            Self::ClampedIndex { line } => write!(f, "Clamped out-of-range face index at line {line}"),
+            Self::DefaultNormals { line } => write!(f, "Used the geometric normal for face at line {line}"),
+        }
+    }
+}
@@ -45,7 +45,7 @@
 #![deny(unsafe_op_in_unsafe_fn)]
 #![deny(unstable_features)]
 #![deny(unused_assignments)]
-#![deny(unused_crate_dependencies)]
+#![cfg_attr(not(test), deny(unused_crate_dependencies))]
 #![deny(unused_extern_crates)]
 #![deny(unused_import_braces)]
 #![deny(unused_imports)]
@@ -77,22 +77,65 @@
 
 mod bvh;
 mod config;
+mod debug;
 mod error;
 mod geometry;
+mod media;
+mod render;
 mod rt;
 mod scene;
+mod sensors;
 mod serialization;
+#[cfg(feature = "simd")]
+mod simd;
+mod source;
+mod testing;
 mod traits;
 
 pub mod prelude {
     //! Prelude of commonly used types and traits in the `geodesic` crate.
     pub use crate::{
-        bvh::{Bvh, BvhBuilder, BvhConfig, BvhNode},
+        bvh::{
+            Bvh, BvhArena, BvhBuilder, BvhConfig, BvhNode, ConfigSplit, MedianSplit, MiddleSplit, MixedBvh, SahSplit, Split,
+            SplitMethod,
+        },
+        debug::ObjRecorder,
         error::GeodesicError,
-        geometry::{Aabb, Mesh, Plane, Sphere, Triangle},
-        rt::{Hit, Ray},
-        scene::{Assets, Camera, Instance, Projection, Scene, SceneBuilder, SceneObject},
-        serialization::{SerializedAssets, SerializedCamera, SerializedProjection, SerializedScene, SerializedSceneObject},
-        traits::{Bounded, Persistable, Traceable},
+        geometry::{
+            Aabb, AlphaMask, Capsule, Curve, CurveSegment, CurveShape, Displacement, Dop14, Dop18, Dop26, Heightmap, Kdop,
+            Mesh, MeshConfig, Plane, Sphere, Triangle, TriangleIntersection, TriangleSoa, WavefrontMode, WavefrontWarning,
+        },
+        media::{Density, DensityGrid, Medium},
+        render::{
+            AdaptiveSampling, DepthEncoding, DepthMap, Film, FilmChannel, FilmSample, GBuffer, GBufferSample, Lightmap,
+            MediumMap, MediumSample, NormalMap, NormalTexel, Photon, PhotonInteraction, PhotonMap, PhotonTracing, Wireframe,
+            WireframePixel,
+        },
+        rt::{Hit, Pcg32, Ray, RayOrdering, TangentFrame, reflect, refract},
+        scene::{
+            Assets, BudgetPolicy, Camera, Instance, MemoryBudget, MemoryUsage, MeshCache, MortonOrder, Projection, Scene,
+            SceneBuilder, SceneObject, SurfacePoint,
+        },
+        sensors::{RayBundle, SensorScan},
+        serialization::{
+            BvhCache, ExportConfig, GltfImport, SceneExport, ScenePatch, SerializedAssets, SerializedCamera,
+            SerializedCameraMetadata, SerializedCameras, SerializedInstanceGrid, SerializedInstanceScatter, SerializedMesh,
+            SerializedProjection, SerializedRotation, SerializedScene, SerializedSceneObject, SerializedTransform,
+        },
+        source::MemorySource,
+        testing::SceneGenerator,
+        traits::{
+            AssetSource, Bounded, BoundingVolume, Bounds, DebugLogger, DistanceField, Geometry, Persistable, Scalar,
+            SolidAngle, SplitStrategy, SurfaceSample, Traceable, Transformable,
+        },
     };
+
+    #[cfg(feature = "fs")]
+    pub use crate::source::FileSystemSource;
+
+    #[cfg(feature = "rayon")]
+    pub use crate::render::{FilmTile, RenderSettings, RenderThreads, TileOrder};
+
+    #[cfg(feature = "watch")]
+    pub use crate::serialization::{SceneChange, SceneWatcher};
 }
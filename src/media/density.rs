@@ -0,0 +1,64 @@
+//! Density distribution of a participating medium.
+
+use nalgebra::Point3;
+
+use crate::{error::Result, media::DensityGrid, rt::Ray, traits::Scalar};
+
+/// Spatial distribution of a participating `Medium`, relative to its extinction coefficient.
+#[derive(Debug, Clone)]
+pub enum Density<T: Scalar> {
+    /// Constant density of one everywhere, as in homogeneous fog.
+    Uniform,
+    /// Density of one at `base_height`, decaying exponentially with height (along `z`) at the `falloff` rate,
+    /// as in ground fog or an atmosphere.
+    Exponential {
+        /// Height at which the density is one.
+        base_height: T,
+        /// Rate at which the density decays per unit of height.
+        falloff: T,
+    },
+    /// Density interpolated from a voxel grid, and zero outside it.
+    Grid(DensityGrid<T>),
+}
+
+impl<T: Scalar> Density<T> {
+    /// Density at a point.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if numeric conversion fails.
+    pub fn at(&self, point: &Point3<T>) -> Result<T> {
+        match self {
+            Self::Uniform => Ok(T::one()),
+            Self::Exponential { base_height, falloff } => Ok((-*falloff * (point.z - *base_height)).exp()),
+            Self::Grid(grid) => grid.density(point),
+        }
+    }
+
+    /// Integrate the density along a `Ray` between the `start` and `end` distances.
+    /// Uniform and exponential densities are integrated exactly, and grids by ray marching.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if numeric conversion fails.
+    pub fn integral(&self, ray: &Ray<T>, start: T, end: T) -> Result<T> {
+        if end <= start {
+            return Ok(T::zero());
+        }
+        match self {
+            Self::Uniform => Ok(end - start),
+            Self::Exponential { base_height, falloff } => {
+                // The density decays by exp(-x) over the segment, so the integral is its start density times
+                // the length, scaled by (1 - exp(-x)) / x, which tends to one as the segment levels out
+                let length = end - start;
+                let initial = (-*falloff * (ray.origin.z + ray.direction.z * start - *base_height)).exp();
+                let x = *falloff * ray.direction.z * length;
+                if x == T::zero() {
+                    return Ok(initial * length);
+                }
+                Ok(initial * length * -(-x).exp_m1() / x)
+            }
+            Self::Grid(grid) => grid.integral(ray, start, end),
+        }
+    }
+}
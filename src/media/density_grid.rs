@@ -0,0 +1,188 @@
+//! Voxel density grid structure.
+
+use nalgebra::{Point3, Unit};
+use std::any::type_name;
+
+use crate::{
+    error::{MediumError, NumericError, Result},
+    geometry::Aabb,
+    rt::Ray,
+    traits::{FallibleNumeric, Scalar},
+};
+
+/// Regular grid of density values spanning an `Aabb`, sampled with trilinear interpolation between voxel centres.
+/// The density is zero outside the bounds.
+#[derive(Debug, Clone)]
+pub struct DensityGrid<T: Scalar> {
+    /// Bounds spanned by the grid.
+    aabb: Aabb<T>,
+    /// Number of voxels along each axis [x, y, z].
+    resolution: [usize; 3],
+    /// Voxel densities, with x varying fastest and z slowest.
+    values: Vec<T>,
+}
+
+impl<T: Scalar> DensityGrid<T> {
+    /// Construct a new `DensityGrid` from voxel densities ordered with x varying fastest and z slowest.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - Any axis of the resolution is zero
+    /// - The number of values does not match the resolution
+    /// - Any density is negative or not finite
+    /// - The bounds have zero extent along any axis
+    pub fn new(aabb: Aabb<T>, resolution: [usize; 3], values: Vec<T>) -> Result<Self> {
+        let [nx, ny, nz] = resolution;
+        if nx == 0 || ny == 0 || nz == 0 {
+            return Err(MediumError::InvalidGridResolution { nx, ny, nz }.into());
+        }
+        let expected = nx * ny * nz;
+        if values.len() != expected {
+            return Err(MediumError::MismatchedGridValues {
+                values: values.len(),
+                expected,
+            }
+            .into());
+        }
+        if values.iter().any(|&value| value < T::zero() || !value.is_finite()) {
+            return Err(MediumError::InvalidGridDensity.into());
+        }
+        if aabb.extents().iter().any(|&extent| extent <= T::zero()) {
+            return Err(MediumError::FlatGridBounds.into());
+        }
+
+        Ok(Self {
+            aabb,
+            resolution,
+            values,
+        })
+    }
+
+    /// Get the bounds spanned by the `DensityGrid`.
+    #[must_use]
+    pub const fn aabb(&self) -> &Aabb<T> {
+        &self.aabb
+    }
+
+    /// Get the number of voxels along each axis [x, y, z].
+    #[must_use]
+    pub const fn resolution(&self) -> [usize; 3] {
+        self.resolution
+    }
+
+    /// Get the voxel densities, with x varying fastest and z slowest.
+    #[must_use]
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+
+    /// Density at a point, interpolated trilinearly between voxel centres and held constant beyond the outer centres.
+    /// Returns zero outside the bounds of the `DensityGrid`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if numeric conversion fails.
+    pub fn density(&self, point: &Point3<T>) -> Result<T> {
+        if !self.aabb.contains_point(point) {
+            return Ok(T::zero());
+        }
+
+        let half = T::try_from_f32(0.5)?;
+        let extents = self.aabb.extents();
+        let mut lower = [0; 3];
+        let mut upper = [0; 3];
+        let mut weights = [T::zero(); 3];
+        for axis in 0..3 {
+            let count = self.resolution[axis];
+            let last = T::try_from_usize(count - 1)?;
+            let position = ((point[axis] - self.aabb.mins[axis]) / extents[axis] * T::try_from_usize(count)? - half)
+                .clamp(T::zero(), last);
+            let floor = position.floor();
+            lower[axis] = to_usize(floor)?.min(count - 1);
+            upper[axis] = (lower[axis] + 1).min(count - 1);
+            weights[axis] = position - floor;
+        }
+
+        let mut density = T::zero();
+        for corner in 0..8 {
+            let mut weight = T::one();
+            let mut index = [0; 3];
+            for axis in 0..3 {
+                if corner & (1 << axis) == 0 {
+                    weight *= T::one() - weights[axis];
+                    index[axis] = lower[axis];
+                } else {
+                    weight *= weights[axis];
+                    index[axis] = upper[axis];
+                }
+            }
+            density += weight * self.value(index);
+        }
+        Ok(density)
+    }
+
+    /// Integrate the density along a `Ray` between the `start` and `end` distances.
+    /// The part of the segment within the bounds is marched with the midpoint rule, at half the smallest voxel size.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if numeric conversion fails.
+    pub fn integral(&self, ray: &Ray<T>, start: T, end: T) -> Result<T> {
+        if end <= start {
+            return Ok(T::zero());
+        }
+
+        // Clip the segment to the bounds, finding the exit by entering from the far end
+        let from = ray.origin + ray.direction.scale(start);
+        let to = ray.origin + ray.direction.scale(end);
+        let length = end - start;
+        let Some(entry) = self.aabb.intersect_within(&Ray::new(from, ray.direction), length) else {
+            return Ok(T::zero());
+        };
+        let Some(exit) = self
+            .aabb
+            .intersect_within(&Ray::new(to, Unit::new_unchecked(-ray.direction.into_inner())), length)
+        else {
+            return Ok(T::zero());
+        };
+        let (entry, exit) = (start + entry, end - exit);
+        if exit <= entry {
+            return Ok(T::zero());
+        }
+
+        let half = T::try_from_f32(0.5)?;
+        let extents = self.aabb.extents();
+        let voxel_size = |axis: usize| -> Result<T> { Ok(extents[axis] / T::try_from_usize(self.resolution[axis])?) };
+        let step_size = voxel_size(0)?.min(voxel_size(1)?).min(voxel_size(2)?) * half;
+        let steps = to_usize(((exit - entry) / step_size).ceil())?.max(1);
+        let step = (exit - entry) / T::try_from_usize(steps)?;
+
+        let mut total = T::zero();
+        for index in 0..steps {
+            let distance = entry + step * (T::try_from_usize(index)? + half);
+            total += self.density(&(ray.origin + ray.direction.scale(distance)))?;
+        }
+        Ok(total * step)
+    }
+
+    /// Get the density of a voxel.
+    fn value(&self, [x, y, z]: [usize; 3]) -> T {
+        let [nx, ny, _] = self.resolution;
+        self.values[(z * ny + y) * nx + x]
+    }
+}
+
+// == Utility functions ==
+
+/// Convert a non-negative whole value to `usize`.
+///
+/// # Errors
+///
+/// Returns an error if the value cannot be represented as a `usize`.
+fn to_usize<T: Scalar>(value: T) -> Result<usize> {
+    Ok(value.to_usize().ok_or_else(|| NumericError::TypeConversion {
+        from_type: type_name::<T>().to_string(),
+        to_type: "usize".to_string(),
+    })?)
+}
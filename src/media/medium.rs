@@ -0,0 +1,152 @@
+//! Participating medium structure.
+
+use nalgebra::Point3;
+
+use crate::{
+    error::{MediumError, Result},
+    media::{Density, DensityGrid},
+    rt::Ray,
+    traits::Scalar,
+};
+
+/// Participating medium, such as fog or smoke, which attenuates and scatters light travelling through it.
+///
+/// The extinction coefficient at a point is the `Density` there times the `extinction` of the `Medium`,
+/// of which the `albedo` fraction is scattered rather than absorbed.
+#[derive(Debug, Clone)]
+pub struct Medium<T: Scalar> {
+    /// Spatial distribution of the `Medium`.
+    density: Density<T>,
+    /// Extinction coefficient (per unit length) where the density is one.
+    extinction: T,
+    /// Single scattering albedo, the fraction of extinction due to scattering.
+    albedo: T,
+}
+
+impl<T: Scalar> Medium<T> {
+    /// Construct a new `Medium` with the given `Density` and extinction coefficient, which scatters all the light
+    /// it extinguishes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the extinction coefficient is negative or not finite.
+    pub fn new(density: Density<T>, extinction: T) -> Result<Self> {
+        if extinction < T::zero() || !extinction.is_finite() {
+            return Err(MediumError::InvalidExtinction {
+                extinction: format!("{extinction:?}"),
+            }
+            .into());
+        }
+        Ok(Self {
+            density,
+            extinction,
+            albedo: T::one(),
+        })
+    }
+
+    /// Construct a homogeneous `Medium`, with the same extinction coefficient everywhere.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the extinction coefficient is negative or not finite.
+    pub fn homogeneous(extinction: T) -> Result<Self> {
+        Self::new(Density::Uniform, extinction)
+    }
+
+    /// Construct an exponential height fog, whose extinction coefficient is `extinction` at `base_height`
+    /// and decays exponentially with height (along `z`) at the `falloff` rate.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The extinction coefficient is negative or not finite
+    /// - The falloff is negative or not finite
+    pub fn height_fog(extinction: T, base_height: T, falloff: T) -> Result<Self> {
+        if falloff < T::zero() || !falloff.is_finite() {
+            return Err(MediumError::InvalidFalloff {
+                falloff: format!("{falloff:?}"),
+            }
+            .into());
+        }
+        Self::new(Density::Exponential { base_height, falloff }, extinction)
+    }
+
+    /// Construct a `Medium` whose extinction coefficient is the density of a `DensityGrid` times `extinction`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the extinction coefficient is negative or not finite.
+    pub fn grid(grid: DensityGrid<T>, extinction: T) -> Result<Self> {
+        Self::new(Density::Grid(grid), extinction)
+    }
+
+    /// Set the single scattering albedo, the fraction of extinguished light which is scattered rather than absorbed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the albedo lies outside [0, 1].
+    pub fn with_albedo(mut self, albedo: T) -> Result<Self> {
+        if !(T::zero()..=T::one()).contains(&albedo) {
+            return Err(MediumError::InvalidAlbedo {
+                albedo: format!("{albedo:?}"),
+            }
+            .into());
+        }
+        self.albedo = albedo;
+        Ok(self)
+    }
+
+    /// Get the spatial distribution of the `Medium`.
+    #[must_use]
+    pub const fn density(&self) -> &Density<T> {
+        &self.density
+    }
+
+    /// Get the extinction coefficient where the density is one.
+    #[must_use]
+    pub const fn extinction(&self) -> T {
+        self.extinction
+    }
+
+    /// Get the single scattering albedo.
+    #[must_use]
+    pub const fn albedo(&self) -> T {
+        self.albedo
+    }
+
+    /// Extinction coefficient at a point.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if numeric conversion fails.
+    pub fn extinction_at(&self, point: &Point3<T>) -> Result<T> {
+        Ok(self.density.at(point)? * self.extinction)
+    }
+
+    /// Scattering coefficient at a point.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if numeric conversion fails.
+    pub fn scattering_at(&self, point: &Point3<T>) -> Result<T> {
+        Ok(self.extinction_at(point)? * self.albedo)
+    }
+
+    /// Optical depth along a `Ray` between the `start` and `end` distances: the integral of the extinction coefficient.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if numeric conversion fails.
+    pub fn optical_depth(&self, ray: &Ray<T>, start: T, end: T) -> Result<T> {
+        Ok(self.density.integral(ray, start, end)? * self.extinction)
+    }
+
+    /// Fraction of light transmitted along a `Ray` between the `start` and `end` distances.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if numeric conversion fails.
+    pub fn transmittance(&self, ray: &Ray<T>, start: T, end: T) -> Result<T> {
+        Ok((-self.optical_depth(ray, start, end)?).exp())
+    }
+}
@@ -0,0 +1,7 @@
+mod density;
+mod density_grid;
+mod medium;
+
+pub use density::Density;
+pub use density_grid::DensityGrid;
+pub use medium::Medium;
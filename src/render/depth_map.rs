@@ -0,0 +1,99 @@
+//! Depth map render pass.
+
+use crate::{
+    error::{GeometryError, RenderError, Result},
+    scene::{Camera, Scene},
+    traits::{Scalar, Traceable},
+};
+
+/// Encoding used to map camera-space depth into the unit range of a `DepthMap`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DepthEncoding {
+    /// Linear depth: `0` at the near plane, `1` at the far plane (Kinect-style).
+    Linear,
+    /// Inverse depth (disparity): `1` at the near plane, `0` at the far plane (MiDaS-style).
+    Inverse,
+}
+
+/// Normalised depth image of a `Scene` as seen by a `Camera`.
+#[derive(Debug, Clone)]
+pub struct DepthMap<T: Scalar> {
+    /// Resolution of the image in pixels [height, width].
+    resolution: [usize; 2],
+    /// Row-major encoded depth values in the range [0, 1].
+    values: Vec<T>,
+}
+
+impl<T: Scalar> DepthMap<T> {
+    /// Render a `DepthMap` by tracing a single primary `Ray` through every pixel of the `Camera`.
+    /// Depth is measured along the `Camera`'s forward axis and clamped to the [`near`, `far`] range
+    /// before encoding. Pixels whose `Ray` escapes the `Scene` are encoded as lying on the far plane.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - `near` is not positive or is not less than `far`
+    /// - `Ray` generation or `Scene` intersection fails
+    pub fn render(scene: &Scene<'_, T>, camera: &Camera<T>, near: T, far: T, encoding: DepthEncoding) -> Result<Self> {
+        if near <= T::zero() || near >= far {
+            return Err(RenderError::InvalidDepthRange {
+                near: format!("{near:?}"),
+                far: format!("{far:?}"),
+            }
+            .into());
+        }
+
+        let resolution = *camera.resolution();
+        let forward = camera.forward();
+
+        // Trace in Morton order for coherent `Bvh` traversal, storing the results in row-major order
+        let mut values = vec![T::zero(); resolution[0] * resolution[1]];
+        for [row, col] in camera.morton_pixels() {
+            let ray = camera.generate_ray([row, col])?;
+            let depth = scene
+                .intersect_t(&ray)?
+                .map_or(far, |distance| distance * ray.direction.dot(&forward))
+                .clamp(near, far);
+
+            values[row * resolution[1] + col] = match encoding {
+                DepthEncoding::Linear => (depth - near) / (far - near),
+                DepthEncoding::Inverse => (depth.recip() - far.recip()) / (near.recip() - far.recip()),
+            };
+        }
+
+        Ok(Self { resolution, values })
+    }
+}
+
+impl<T: Scalar> DepthMap<T> {
+    /// Returns the resolution of the `DepthMap` [height, width].
+    #[must_use]
+    pub const fn resolution(&self) -> &[usize; 2] {
+        &self.resolution
+    }
+
+    /// Returns all encoded depth values in row-major order.
+    #[must_use]
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+
+    /// Get the encoded depth at the given pixel index.
+    /// A position of [0, 0] corresponds to the top-left pixel of the image.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the pixel index is out of bounds for the image resolution.
+    pub fn value(&self, pixel_index: [usize; 2]) -> Result<T> {
+        if pixel_index[0] >= self.resolution[0] || pixel_index[1] >= self.resolution[1] {
+            return Err(GeometryError::PixelOutOfBounds {
+                row: pixel_index[0],
+                col: pixel_index[1],
+                res_height: self.resolution[0],
+                res_width: self.resolution[1],
+            }
+            .into());
+        }
+        Ok(self.values[pixel_index[0] * self.resolution[1] + pixel_index[1]])
+    }
+}
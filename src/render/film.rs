@@ -0,0 +1,552 @@
+//! Sample accumulating film.
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+#[cfg(feature = "rayon")]
+use std::{
+    ops::Range,
+    sync::{Mutex, PoisonError},
+};
+
+use crate::{
+    error::{GeometryError, RenderError, Result},
+    rt::{Pcg32, Ray},
+    scene::Camera,
+    traits::{FallibleNumeric, Scalar},
+};
+#[cfg(feature = "rayon")]
+use crate::{render::RenderSettings, scene::MortonOrder};
+
+/// Settings of an adaptive `Film` pass, which keeps sampling each pixel until its estimate converges.
+#[derive(Debug, Clone, Copy)]
+pub struct AdaptiveSampling<T: Scalar> {
+    /// Samples taken in every pixel before its variance is trusted. At least two.
+    pub min_samples: usize,
+    /// Samples added to each unconverged pixel per pass.
+    pub batch_samples: usize,
+    /// Cap on the samples taken in any pixel.
+    pub max_samples: usize,
+    /// Standard error of the pixel mean at or below which a pixel has converged.
+    pub threshold: T,
+}
+
+/// Result of shading one `Film` sample: the estimate, and the number of `Ray`s traced to make it.
+/// Shading closures may return a bare estimate instead, which counts only the camera `Ray`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FilmSample<T: Scalar> {
+    /// Estimate of the sample.
+    pub value: T,
+    /// Number of `Ray`s traced, including the camera `Ray`.
+    pub rays: usize,
+}
+
+/// Per-pixel channel of a `Film`, such as an arbitrary output variable for diagnosing convergence or training denoisers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilmChannel {
+    /// Mean of the samples.
+    Mean,
+    /// Unbiased sample variance of the samples.
+    Variance,
+    /// Standard error of the mean.
+    StandardError,
+    /// Number of samples taken.
+    SampleCount,
+    /// Number of `Ray`s traced by all samples.
+    RayCount,
+}
+
+/// Snapshot of a rectangular tile of a `Film`, passed to preview callbacks while a render proceeds.
+#[cfg(feature = "rayon")]
+#[derive(Debug, Clone)]
+pub struct FilmTile<T: Scalar> {
+    /// Rows of the image covered by the tile.
+    pub rows: Range<usize>,
+    /// Columns of the image covered by the tile.
+    pub cols: Range<usize>,
+    /// Row-major mean of the samples in each pixel of the tile.
+    pub means: Vec<T>,
+    /// Row-major number of samples taken in each pixel of the tile.
+    pub sample_counts: Vec<usize>,
+}
+
+/// Running statistics of the samples taken in one pixel, updated by Welford's algorithm.
+#[derive(Debug, Clone, Copy)]
+struct PixelStatistics<T: Scalar> {
+    /// Number of samples taken.
+    count: usize,
+    /// Mean of the samples.
+    mean: T,
+    /// Sum of squared differences of the samples from their mean.
+    squared_deviation: T,
+    /// Number of `Ray`s traced by the samples.
+    rays: usize,
+}
+
+/// Image accumulating samples of a scalar estimate, such as radiance, in each pixel of a `Camera`.
+///
+/// Tracks the variance of every pixel, so further samples can be concentrated where the estimate is still noisy.
+#[derive(Debug, Clone)]
+pub struct Film<T: Scalar> {
+    /// Resolution of the image in pixels [height, width].
+    resolution: [usize; 2],
+    /// Row-major pixel statistics.
+    pixels: Vec<PixelStatistics<T>>,
+}
+
+impl<T: Scalar> Film<T> {
+    /// Construct a new `Film` instance with no samples.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either width or height in the resolution is zero.
+    pub fn new(resolution: [usize; 2]) -> Result<Self> {
+        if resolution[0] == 0 || resolution[1] == 0 {
+            return Err(GeometryError::InvalidResolution {
+                width: resolution[1],
+                height: resolution[0],
+            }
+            .into());
+        }
+        Ok(Self {
+            resolution,
+            pixels: vec![PixelStatistics::empty(); resolution[0] * resolution[1]],
+        })
+    }
+
+    /// Add a sample to the given pixel.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The pixel index is out of bounds for the image resolution
+    /// - Numeric conversion fails
+    pub fn add_sample<S: Into<FilmSample<T>>>(&mut self, pixel_index: [usize; 2], sample: S) -> Result<()> {
+        let index = self.index(pixel_index)?;
+        self.pixels[index].add(sample.into())
+    }
+
+    /// Take `samples` more samples in every pixel, each estimated by `shade` from a jittered `Camera` `Ray`.
+    /// Every sample draws from its own `Pcg32::for_sample` stream, passed to `shade`,
+    /// so identical seeds reproduce identical images.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The `Camera` resolution differs from that of the `Film`
+    /// - `Ray` generation or `shade` fails
+    pub fn render<F, S>(&mut self, camera: &Camera<T>, samples: usize, seed: u64, mut shade: F) -> Result<()>
+    where
+        F: FnMut(&Ray<T>, &mut Pcg32) -> Result<S>,
+        S: Into<FilmSample<T>>,
+    {
+        self.check_camera(camera)?;
+        for pixel_index in camera.morton_pixels() {
+            self.sample_pixel(camera, pixel_index, samples, seed, &mut shade)?;
+        }
+        Ok(())
+    }
+
+    /// Sample every pixel `min_samples` times, then repeatedly add `batch_samples` to each pixel whose
+    /// standard error still exceeds the threshold, until every pixel has converged or reached `max_samples`.
+    /// Samples are drawn as in `render`, so the result only depends on the seed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - `min_samples` is less than two, `batch_samples` is zero, or `max_samples` is less than `min_samples`
+    /// - The threshold is negative
+    /// - The `Camera` resolution differs from that of the `Film`
+    /// - `Ray` generation or `shade` fails
+    pub fn render_adaptive<F, S>(
+        &mut self,
+        camera: &Camera<T>,
+        sampling: &AdaptiveSampling<T>,
+        seed: u64,
+        mut shade: F,
+    ) -> Result<()>
+    where
+        F: FnMut(&Ray<T>, &mut Pcg32) -> Result<S>,
+        S: Into<FilmSample<T>>,
+    {
+        if sampling.min_samples < 2
+            || sampling.batch_samples == 0
+            || sampling.max_samples < sampling.min_samples
+            || sampling.threshold < T::zero()
+        {
+            return Err(RenderError::InvalidAdaptiveSampling {
+                min: sampling.min_samples,
+                batch: sampling.batch_samples,
+                max: sampling.max_samples,
+                threshold: format!("{:?}", sampling.threshold),
+            }
+            .into());
+        }
+        self.check_camera(camera)?;
+
+        let mut refining = true;
+        while refining {
+            refining = false;
+            for pixel_index in camera.morton_pixels() {
+                let pixel = self.pixels[pixel_index[0] * self.resolution[1] + pixel_index[1]];
+                let samples = if pixel.count < sampling.min_samples {
+                    sampling.min_samples - pixel.count
+                } else if pixel.count < sampling.max_samples && standard_error(&pixel)? > sampling.threshold {
+                    sampling.batch_samples.min(sampling.max_samples - pixel.count)
+                } else {
+                    continue;
+                };
+                self.sample_pixel(camera, pixel_index, samples, seed, &mut shade)?;
+                refining = true;
+            }
+        }
+        Ok(())
+    }
+
+    /// Take `samples` more samples in a pixel, numbering them on from those already taken.
+    fn sample_pixel<F, S>(
+        &mut self,
+        camera: &Camera<T>,
+        pixel_index: [usize; 2],
+        samples: usize,
+        seed: u64,
+        shade: &mut F,
+    ) -> Result<()>
+    where
+        F: FnMut(&Ray<T>, &mut Pcg32) -> Result<S>,
+        S: Into<FilmSample<T>>,
+    {
+        let taken = self.pixels[pixel_index[0] * self.resolution[1] + pixel_index[1]].count;
+        for sample in taken..taken + samples {
+            let mut rng = Pcg32::for_sample(seed, pixel_index, sample);
+            let ray = camera.generate_jittered_ray(pixel_index, &mut rng)?;
+            let sample = shade(&ray, &mut rng)?;
+            self.add_sample(pixel_index, sample)?;
+        }
+        Ok(())
+    }
+
+    /// Check that a `Camera` renders images of the resolution of the `Film`.
+    fn check_camera(&self, camera: &Camera<T>) -> Result<()> {
+        let resolution = camera.resolution();
+        if *resolution != self.resolution {
+            return Err(RenderError::MismatchedFilm {
+                rows: self.resolution[0],
+                cols: self.resolution[1],
+                camera_rows: resolution[0],
+                camera_cols: resolution[1],
+            }
+            .into());
+        }
+        Ok(())
+    }
+
+    /// Get the row-major index of a pixel.
+    fn index(&self, pixel_index: [usize; 2]) -> Result<usize> {
+        if pixel_index[0] >= self.resolution[0] || pixel_index[1] >= self.resolution[1] {
+            return Err(GeometryError::PixelOutOfBounds {
+                row: pixel_index[0],
+                col: pixel_index[1],
+                res_height: self.resolution[0],
+                res_width: self.resolution[1],
+            }
+            .into());
+        }
+        Ok(pixel_index[0] * self.resolution[1] + pixel_index[1])
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<T: Scalar> Film<T> {
+    /// Take `samples` more samples in every pixel as in `render`, in parallel tasks scheduled by the `RenderSettings`.
+    /// Samples draw from the same `Pcg32::for_sample` streams as `render`, so the estimates agree up to rounding.
+    /// The samples of a pixel taken by different tasks are combined by Chan's parallel update,
+    /// in batch order when the settings are deterministic, or as tasks complete otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The tile size or sample batch of the settings is zero
+    /// - The settings request a thread pool which cannot be built
+    /// - The `Camera` resolution differs from that of the `Film`
+    /// - `Ray` generation or `shade` fails
+    pub fn render_parallel<F, S>(
+        &mut self,
+        camera: &Camera<T>,
+        samples: usize,
+        seed: u64,
+        settings: &RenderSettings,
+        shade: F,
+    ) -> Result<()>
+    where
+        F: Fn(&Ray<T>, &mut Pcg32) -> Result<S> + Sync,
+        S: Into<FilmSample<T>>,
+    {
+        self.render_progressive(camera, samples, seed, settings, shade, |_| {})
+    }
+
+    /// Render as in `render_parallel`, calling `on_tile` with a `FilmTile` snapshot each time a batch of samples
+    /// of a tile has been merged into the `Film`, so a preview can be updated while the render proceeds.
+    /// When `settings.deterministic` is set, every tile receives its first batch before any tile receives its second,
+    /// so the whole image sharpens together. Otherwise batches are taken in any order as threads become free.
+    /// `on_tile` is called from the render threads, without holding any lock on the `Film`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The tile size or sample batch of the settings is zero
+    /// - The settings request a thread pool which cannot be built
+    /// - The `Camera` resolution differs from that of the `Film`
+    /// - `Ray` generation or `shade` fails
+    pub fn render_progressive<F, S, P>(
+        &mut self,
+        camera: &Camera<T>,
+        samples: usize,
+        seed: u64,
+        settings: &RenderSettings,
+        shade: F,
+        on_tile: P,
+    ) -> Result<()>
+    where
+        F: Fn(&Ray<T>, &mut Pcg32) -> Result<S> + Sync,
+        S: Into<FilmSample<T>>,
+        P: Fn(FilmTile<T>) + Sync,
+    {
+        settings.validate()?;
+        self.check_camera(camera)?;
+
+        let tiles = settings.tile_order.tiles(self.resolution, settings.tile_size);
+        let batches: Vec<Range<usize>> = (0..samples)
+            .step_by(settings.sample_batch)
+            .map(|start| start..(start + settings.sample_batch).min(samples))
+            .collect();
+        let tasks = |batch: &Range<usize>| tiles.iter().map(|tile| (tile.clone(), batch.clone())).collect::<Vec<_>>();
+
+        // Deterministic renders take the batches in rounds, so every pixel merges its batches in order.
+        // Within a round each pixel belongs to a single task, so tasks can still merge as they complete.
+        let rounds: Vec<Vec<_>> = if settings.deterministic {
+            batches.iter().map(tasks).collect()
+        } else {
+            vec![batches.iter().flat_map(tasks).collect()]
+        };
+
+        // Sample numbers continue on from those already taken, as in `render`
+        let taken: Vec<usize> = self.pixels.iter().map(|pixel| pixel.count).collect();
+        let width = self.resolution[1];
+        let sample_tile = |[rows, cols]: &[Range<usize>; 2], batch: Range<usize>| {
+            MortonOrder::new(rows.clone(), cols.clone())
+                .map(|pixel_index| {
+                    let index = pixel_index[0] * width + pixel_index[1];
+                    let mut statistics = PixelStatistics::empty();
+                    for sample in batch.clone() {
+                        let mut rng = Pcg32::for_sample(seed, pixel_index, taken[index] + sample);
+                        let ray = camera.generate_jittered_ray(pixel_index, &mut rng)?;
+                        statistics.add(shade(&ray, &mut rng)?.into())?;
+                    }
+                    Ok((index, statistics))
+                })
+                .collect::<Result<Vec<_>>>()
+        };
+
+        let pixels = Mutex::new(&mut self.pixels);
+        settings.threads.install(|| {
+            rounds.into_iter().try_for_each(|round| {
+                round.into_par_iter().try_for_each(|(tile, batch)| {
+                    let results = sample_tile(&tile, batch)?;
+                    let mut pixels = pixels.lock().unwrap_or_else(PoisonError::into_inner);
+                    let merged = results
+                        .iter()
+                        .try_for_each(|(index, statistics)| pixels[*index].merge(statistics));
+                    let snapshot = FilmTile::new(tile, &pixels, width);
+                    drop(pixels);
+                    merged?;
+                    on_tile(snapshot);
+                    Ok(())
+                })
+            })
+        })?
+    }
+}
+
+impl<T: Scalar> Film<T> {
+    /// Returns the resolution of the `Film` [height, width].
+    #[must_use]
+    pub const fn resolution(&self) -> &[usize; 2] {
+        &self.resolution
+    }
+
+    /// Returns the mean of the samples of every pixel in row-major order, zero where none have been taken.
+    #[must_use]
+    pub fn means(&self) -> Vec<T> {
+        self.pixels.iter().map(|pixel| pixel.mean).collect()
+    }
+
+    /// Returns the number of samples taken in every pixel in row-major order.
+    #[must_use]
+    pub fn sample_counts(&self) -> Vec<usize> {
+        self.pixels.iter().map(|pixel| pixel.count).collect()
+    }
+
+    /// Returns the unbiased sample variance of every pixel in row-major order, zero where fewer than two samples
+    /// have been taken.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if numeric conversion fails.
+    pub fn variances(&self) -> Result<Vec<T>> {
+        self.pixels.iter().map(variance).collect()
+    }
+
+    /// Returns the standard error of the mean of every pixel in row-major order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if numeric conversion fails.
+    pub fn standard_errors(&self) -> Result<Vec<T>> {
+        self.pixels.iter().map(standard_error).collect()
+    }
+
+    /// Returns the number of `Ray`s traced by the samples of every pixel in row-major order.
+    #[must_use]
+    pub fn ray_counts(&self) -> Vec<usize> {
+        self.pixels.iter().map(|pixel| pixel.rays).collect()
+    }
+
+    /// Returns a channel of every pixel in row-major order, with counts converted to the precision of the `Film`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if numeric conversion fails.
+    pub fn channel(&self, channel: FilmChannel) -> Result<Vec<T>> {
+        match channel {
+            FilmChannel::Mean => Ok(self.means()),
+            FilmChannel::Variance => self.variances(),
+            FilmChannel::StandardError => self.standard_errors(),
+            FilmChannel::SampleCount => self.pixels.iter().map(|pixel| Ok(T::try_from_usize(pixel.count)?)).collect(),
+            FilmChannel::RayCount => self.pixels.iter().map(|pixel| Ok(T::try_from_usize(pixel.rays)?)).collect(),
+        }
+    }
+
+    /// Get the mean of the samples of the given pixel, or zero if none have been taken.
+    /// A position of [0, 0] corresponds to the top-left pixel of the image.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the pixel index is out of bounds for the image resolution.
+    pub fn mean(&self, pixel_index: [usize; 2]) -> Result<T> {
+        Ok(self.pixels[self.index(pixel_index)?].mean)
+    }
+
+    /// Get the unbiased sample variance of the given pixel, or zero if fewer than two samples have been taken.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The pixel index is out of bounds for the image resolution
+    /// - Numeric conversion fails
+    pub fn variance(&self, pixel_index: [usize; 2]) -> Result<T> {
+        variance(&self.pixels[self.index(pixel_index)?])
+    }
+
+    /// Get the number of samples taken in the given pixel.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the pixel index is out of bounds for the image resolution.
+    pub fn sample_count(&self, pixel_index: [usize; 2]) -> Result<usize> {
+        Ok(self.pixels[self.index(pixel_index)?].count)
+    }
+
+    /// Get the number of `Ray`s traced by the samples of the given pixel.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the pixel index is out of bounds for the image resolution.
+    pub fn ray_count(&self, pixel_index: [usize; 2]) -> Result<usize> {
+        Ok(self.pixels[self.index(pixel_index)?].rays)
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<T: Scalar> FilmTile<T> {
+    /// Copy a tile [rows, cols] out of the row-major pixels of a `Film` of the given width.
+    fn new([rows, cols]: [Range<usize>; 2], pixels: &[PixelStatistics<T>], width: usize) -> Self {
+        let tile_pixels: Vec<_> = rows
+            .clone()
+            .flat_map(|row| &pixels[row * width + cols.start..row * width + cols.end])
+            .collect();
+        Self {
+            means: tile_pixels.iter().map(|pixel| pixel.mean).collect(),
+            sample_counts: tile_pixels.iter().map(|pixel| pixel.count).collect(),
+            rows,
+            cols,
+        }
+    }
+}
+
+impl<T: Scalar> From<T> for FilmSample<T> {
+    /// A bare estimate, made by tracing only the camera `Ray`.
+    fn from(value: T) -> Self {
+        Self { value, rays: 1 }
+    }
+}
+
+impl<T: Scalar> PixelStatistics<T> {
+    /// Construct the statistics of a pixel with no samples.
+    fn empty() -> Self {
+        Self {
+            count: 0,
+            mean: T::zero(),
+            squared_deviation: T::zero(),
+            rays: 0,
+        }
+    }
+
+    /// Add a sample.
+    fn add(&mut self, FilmSample { value, rays }: FilmSample<T>) -> Result<()> {
+        self.count += 1;
+        self.rays += rays;
+        let delta = value - self.mean;
+        self.mean += delta / T::try_from_usize(self.count)?;
+        self.squared_deviation += delta * (value - self.mean);
+        Ok(())
+    }
+
+    /// Combine the statistics of a separate set of samples of the same pixel.
+    #[cfg(feature = "rayon")]
+    fn merge(&mut self, other: &Self) -> Result<()> {
+        if self.count == 0 {
+            *self = *other;
+            return Ok(());
+        }
+        if other.count == 0 {
+            return Ok(());
+        }
+        let count = self.count + other.count;
+        let (n_a, n_b, n) = (
+            T::try_from_usize(self.count)?,
+            T::try_from_usize(other.count)?,
+            T::try_from_usize(count)?,
+        );
+        let delta = other.mean - self.mean;
+        self.mean += delta * n_b / n;
+        self.squared_deviation += other.squared_deviation + delta * delta * n_a * n_b / n;
+        self.count = count;
+        self.rays += other.rays;
+        Ok(())
+    }
+}
+
+// == Utility functions ==
+
+/// Unbiased sample variance of a pixel, or zero if fewer than two samples have been taken.
+fn variance<T: Scalar>(pixel: &PixelStatistics<T>) -> Result<T> {
+    if pixel.count < 2 {
+        return Ok(T::zero());
+    }
+    Ok(pixel.squared_deviation / T::try_from_usize(pixel.count - 1)?)
+}
+
+/// Standard error of the mean of a pixel.
+fn standard_error<T: Scalar>(pixel: &PixelStatistics<T>) -> Result<T> {
+    Ok((variance(pixel)? / T::try_from_usize(pixel.count.max(1))?).sqrt())
+}
@@ -0,0 +1,105 @@
+//! Geometry buffer render pass.
+
+use nalgebra::{Point3, Unit, Vector3};
+
+use crate::{
+    error::{GeometryError, Result},
+    scene::{Camera, Scene},
+    traits::Scalar,
+};
+
+/// Per-pixel geometric data recorded by a `GBuffer` pass.
+#[derive(Debug, Clone)]
+pub struct GBufferSample<T: Scalar> {
+    /// World-space position of the closest intersection.
+    pub position: Point3<T>,
+    /// Geometric normal at the intersection point.
+    pub geometric_normal: Unit<Vector3<T>>,
+    /// Phong shading normal at the intersection point.
+    pub interpolated_normal: Unit<Vector3<T>>,
+    /// Distance from the `Camera` along the sampling `Ray`.
+    pub distance: T,
+    /// Depth of the intersection along the `Camera`'s forward axis.
+    pub depth: T,
+    /// Index of the `SceneObject` which was hit.
+    pub object_id: usize,
+    /// Index of the primitive within the `SceneObject` which was hit (e.g. the `Triangle` of a `Mesh`).
+    pub primitive_id: usize,
+    /// Interpolated texture coordinates [u, v], if the surface has them.
+    pub uv: Option<[T; 2]>,
+    /// Interpolated colour [red, green, blue, alpha], if the surface has vertex colours.
+    pub color: Option<[T; 4]>,
+}
+
+/// Geometry buffer containing the primary-ray intersection data of every pixel of a `Camera`.
+#[derive(Debug, Clone)]
+pub struct GBuffer<T: Scalar> {
+    /// Resolution of the buffer in pixels [height, width].
+    resolution: [usize; 2],
+    /// Row-major samples, `None` where the primary `Ray` escaped the `Scene`.
+    samples: Vec<Option<GBufferSample<T>>>,
+}
+
+impl<T: Scalar> GBuffer<T> {
+    /// Render a `GBuffer` by tracing a single primary `Ray` through every pixel of the `Camera`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `Ray` generation or `Scene` intersection fails.
+    pub fn render(scene: &Scene<'_, T>, camera: &Camera<T>) -> Result<Self> {
+        let resolution = *camera.resolution();
+        let forward = camera.forward();
+
+        // Trace in Morton order for coherent `Bvh` traversal, storing the results in row-major order
+        let mut samples = vec![None; resolution[0] * resolution[1]];
+        for [row, col] in camera.morton_pixels() {
+            let ray = camera.generate_ray([row, col])?;
+            samples[row * resolution[1] + col] = scene.intersect_object(&ray)?.map(|(object_id, hit)| GBufferSample {
+                position: ray.origin + ray.direction.scale(hit.distance),
+                geometric_normal: hit.geometric_normal,
+                interpolated_normal: hit.interpolated_normal,
+                distance: hit.distance,
+                depth: hit.distance * ray.direction.dot(&forward),
+                object_id,
+                primitive_id: hit.index,
+                uv: hit.uv,
+                color: hit.color,
+            });
+        }
+
+        Ok(Self { resolution, samples })
+    }
+}
+
+impl<T: Scalar> GBuffer<T> {
+    /// Returns the resolution of the `GBuffer` [height, width].
+    #[must_use]
+    pub const fn resolution(&self) -> &[usize; 2] {
+        &self.resolution
+    }
+
+    /// Returns all samples in row-major order.
+    #[must_use]
+    pub fn samples(&self) -> &[Option<GBufferSample<T>>] {
+        &self.samples
+    }
+
+    /// Get the sample at the given pixel index.
+    /// A position of [0, 0] corresponds to the top-left pixel of the image.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the pixel index is out of bounds for the buffer resolution.
+    pub fn sample(&self, pixel_index: [usize; 2]) -> Result<Option<&GBufferSample<T>>> {
+        if pixel_index[0] >= self.resolution[0] || pixel_index[1] >= self.resolution[1] {
+            return Err(GeometryError::PixelOutOfBounds {
+                row: pixel_index[0],
+                col: pixel_index[1],
+                res_height: self.resolution[0],
+                res_width: self.resolution[1],
+            }
+            .into());
+        }
+        Ok(self.samples[pixel_index[0] * self.resolution[1] + pixel_index[1]].as_ref())
+    }
+}
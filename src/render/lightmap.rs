@@ -0,0 +1,196 @@
+//! Lightmap baking pass.
+
+use nalgebra::{Point3, Unit, Vector2, Vector3};
+
+use crate::{
+    error::{GeometryError, RenderError, Result},
+    geometry::{Mesh, Triangle},
+    rt::{Pcg32, Ray},
+    scene::Scene,
+    traits::{FallibleNumeric, Scalar, Traceable, sample_cosine_hemisphere},
+};
+
+/// Texture atlas of lighting baked over the surface of a `Mesh`, laid out by its texture coordinates.
+///
+/// Texel [0, 0] is the top-left of the atlas, where u is zero and v is one.
+#[derive(Debug, Clone)]
+pub struct Lightmap<T: Scalar> {
+    /// Resolution of the atlas in texels [height, width].
+    resolution: [usize; 2],
+    /// Row-major baked values, `None` where no `Triangle` covers the texel centre.
+    values: Vec<Option<T>>,
+}
+
+impl<T: Scalar> Lightmap<T> {
+    /// Bake the ambient occlusion of a `Mesh` placed in a `Scene`: the cosine-weighted fraction of directions
+    /// from the centre of each texel which escape within `max_distance`, estimated from `samples` `Ray`s.
+    /// Every sample draws from its own `Pcg32::for_sample` stream, so identical seeds bake identical atlases.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The resolution is zero, or `samples` is zero
+    /// - No `Triangle` of the `Mesh` has texture coordinates
+    /// - An occlusion query fails
+    pub fn bake_occlusion(
+        scene: &Scene<'_, T>,
+        mesh: &Mesh<T>,
+        resolution: [usize; 2],
+        samples: usize,
+        max_distance: T,
+        seed: u64,
+    ) -> Result<Self> {
+        if samples == 0 {
+            return Err(RenderError::NoOcclusionSamples.into());
+        }
+        let count = T::try_from_usize(samples)?;
+        let values = rasterise(mesh, resolution, |texel, _, position, normal| {
+            let mut open = 0;
+            for sample in 0..samples {
+                let mut rng = Pcg32::for_sample(seed, texel, sample);
+                let direction = sample_cosine_hemisphere(normal, &mut rng)?;
+                if !scene.intersect_any(&Ray::new(*position, direction), max_distance)? {
+                    open += 1;
+                }
+            }
+            Ok(Some(T::try_from_usize(open)? / count))
+        })?;
+        Ok(Self { resolution, values })
+    }
+
+    /// Bake the direct irradiance of a `Mesh` placed in a `Scene`, from a point light of unit intensity at `light`,
+    /// at the centre of each texel. Scale the result by the intensity of the light.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The resolution is zero
+    /// - No `Triangle` of the `Mesh` has texture coordinates
+    /// - A visibility query fails
+    pub fn bake_irradiance(scene: &Scene<'_, T>, mesh: &Mesh<T>, resolution: [usize; 2], light: &Point3<T>) -> Result<Self> {
+        let values = rasterise(mesh, resolution, |_, _, position, normal| {
+            let offset = light - position;
+            let distance = offset.norm();
+            let direction = Unit::new_normalize(offset);
+            let cosine = normal.dot(&direction);
+            if distance <= T::zero() || cosine <= T::zero() || scene.intersect_any(&Ray::new(*position, direction), distance)? {
+                return Ok(Some(T::zero()));
+            }
+            Ok(Some(cosine / (distance * distance)))
+        })?;
+        Ok(Self { resolution, values })
+    }
+}
+
+impl<T: Scalar> Lightmap<T> {
+    /// Returns the resolution of the `Lightmap` [height, width].
+    #[must_use]
+    pub const fn resolution(&self) -> &[usize; 2] {
+        &self.resolution
+    }
+
+    /// Returns all baked values in row-major order, `None` where no `Triangle` covers the texel.
+    #[must_use]
+    pub fn values(&self) -> &[Option<T>] {
+        &self.values
+    }
+
+    /// Get the baked value at the given texel index, or `None` if no `Triangle` covers it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the texel index is out of bounds for the atlas resolution.
+    pub fn value(&self, texel_index: [usize; 2]) -> Result<Option<T>> {
+        if texel_index[0] >= self.resolution[0] || texel_index[1] >= self.resolution[1] {
+            return Err(GeometryError::PixelOutOfBounds {
+                row: texel_index[0],
+                col: texel_index[1],
+                res_height: self.resolution[0],
+                res_width: self.resolution[1],
+            }
+            .into());
+        }
+        Ok(self.values[texel_index[0] * self.resolution[1] + texel_index[1]])
+    }
+}
+
+// == Utility functions ==
+
+/// Rasterise the `Triangle`s of a `Mesh` into a texture atlas of the given resolution, laid out by their texture
+/// coordinates. `texel` is evaluated with the texel index, the `Triangle`, and the surface point and interpolated
+/// normal under the centre of each covered texel. Returns the row-major results, `None` for uncovered texels.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The resolution is zero
+/// - No `Triangle` of the `Mesh` has texture coordinates
+/// - `texel` fails
+pub fn rasterise<T, V, F>(mesh: &Mesh<T>, resolution: [usize; 2], mut texel: F) -> Result<Vec<Option<V>>>
+where
+    T: Scalar,
+    V: Clone,
+    F: FnMut([usize; 2], &Triangle<T>, &Point3<T>, &Unit<Vector3<T>>) -> Result<Option<V>>,
+{
+    if resolution[0] == 0 || resolution[1] == 0 {
+        return Err(GeometryError::InvalidResolution {
+            width: resolution[1],
+            height: resolution[0],
+        }
+        .into());
+    }
+    if mesh.triangles().iter().all(|triangle| triangle.uvs().is_none()) {
+        return Err(RenderError::MissingTextureCoordinates.into());
+    }
+
+    let [height, width] = resolution;
+    let (rows, cols) = (T::try_from_usize(height)?, T::try_from_usize(width)?);
+    let half = T::try_from_f32(0.5)?;
+    let mut values = vec![None; height * width];
+    for triangle in mesh.triangles() {
+        let Some(uvs) = triangle.uvs() else {
+            continue;
+        };
+
+        // Texel space, with rows increasing downwards from v equal to one
+        let corners = uvs.map(|[u, v]| Vector2::new(u * cols - half, (T::one() - v) * rows - half));
+        let (mins, maxs) = corners.iter().fold((corners[0], corners[0]), |(mins, maxs), corner| {
+            (mins.inf(corner), maxs.sup(corner))
+        });
+        let rows_covered = texel_index(mins.y.ceil(), height)..=texel_index(maxs.y.floor(), height);
+        let cols_covered = texel_index(mins.x.ceil(), width)..=texel_index(maxs.x.floor(), width);
+
+        let vertices = triangle.vertices();
+        let normals = triangle.normals();
+        for row in rows_covered {
+            for col in cols_covered.clone() {
+                let centre = Vector2::new(T::try_from_usize(col)?, T::try_from_usize(row)?);
+                let Some([w, u, v]) = barycentric(&corners, &centre) else {
+                    continue;
+                };
+                let position = Point3::from(vertices[0].coords * w + vertices[1].coords * u + vertices[2].coords * v);
+                let normal = Unit::new_normalize(normals[0].scale(w) + normals[1].scale(u) + normals[2].scale(v));
+                values[row * width + col] = texel([row, col], triangle, &position, &normal)?;
+            }
+        }
+    }
+    Ok(values)
+}
+
+/// Convert a whole texel coordinate to an index, clamped to the `count` texels along its axis.
+fn texel_index<T: Scalar>(value: T, count: usize) -> usize {
+    value.max(T::zero()).to_usize().unwrap_or(count).min(count - 1)
+}
+
+/// Barycentric weights of a point within a texture space triangle, or `None` if it lies outside or is degenerate.
+fn barycentric<T: Scalar>(corners: &[Vector2<T>; 3], point: &Vector2<T>) -> Option<[T; 3]> {
+    let (edge1, edge2, offset) = (corners[1] - corners[0], corners[2] - corners[0], point - corners[0]);
+    let denominator = edge1.perp(&edge2);
+    if denominator == T::zero() {
+        return None;
+    }
+    let u = offset.perp(&edge2) / denominator;
+    let v = edge1.perp(&offset) / denominator;
+    let w = T::one() - u - v;
+    (u >= T::zero() && v >= T::zero() && w >= T::zero()).then_some([w, u, v])
+}
@@ -0,0 +1,132 @@
+//! Participating medium render pass.
+
+use nalgebra::Point3;
+
+use crate::{
+    error::{GeometryError, RenderError, Result},
+    scene::{Camera, Scene},
+    traits::Scalar,
+};
+
+/// Per-pixel participating medium data recorded by a `MediumMap` pass.
+///
+/// A pixel of surface radiance `L` is seen through the `Medium`s as `L * transmittance + I * in_scattering`,
+/// for a light of intensity `I`.
+#[derive(Debug, Clone, Copy)]
+pub struct MediumSample<T: Scalar> {
+    /// Distance marched along the primary `Ray`, to the first surface or to the far distance if it escaped.
+    pub distance: T,
+    /// Fraction of the light from the end of the march which reaches the `Camera`.
+    pub transmittance: T,
+    /// Radiance scattered once towards the `Camera` from a light of unit intensity, or zero if unlit.
+    pub in_scattering: T,
+}
+
+/// Participating medium image of a `Scene` as seen by a `Camera`, for fog and light shaft effects.
+#[derive(Debug, Clone)]
+pub struct MediumMap<T: Scalar> {
+    /// Resolution of the image in pixels [height, width].
+    resolution: [usize; 2],
+    /// Row-major samples.
+    samples: Vec<MediumSample<T>>,
+}
+
+impl<T: Scalar> MediumMap<T> {
+    /// Render the transmittance of a `MediumMap` by marching a single primary `Ray` through every pixel of the `Camera`
+    /// to the first surface, passing through the boundaries of `SceneObject`s filled with a `Medium`.
+    /// `Ray`s which escape the `Scene` are marched to the `far` distance.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - `far` is not positive and finite
+    /// - `Ray` generation, `Scene` intersection or `Medium` integration fails
+    pub fn render(scene: &Scene<'_, T>, camera: &Camera<T>, far: T) -> Result<Self> {
+        Self::render_with(scene, camera, far, None)
+    }
+
+    /// Render a `MediumMap` as in `render`, additionally estimating the light scattered once towards the `Camera`
+    /// from a point light of unit intensity at `light`, marching each `Ray` in `steps` equal steps.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - `far` is not positive and finite
+    /// - `steps` is zero
+    /// - `Ray` generation, `Scene` intersection or `Medium` integration fails
+    pub fn render_scattering(
+        scene: &Scene<'_, T>,
+        camera: &Camera<T>,
+        far: T,
+        light: &Point3<T>,
+        steps: usize,
+    ) -> Result<Self> {
+        Self::render_with(scene, camera, far, Some((light, steps)))
+    }
+
+    /// Render a `MediumMap`, with single scattering from a light and number of steps if given.
+    fn render_with(scene: &Scene<'_, T>, camera: &Camera<T>, far: T, scattering: Option<(&Point3<T>, usize)>) -> Result<Self> {
+        if far <= T::zero() || !far.is_finite() {
+            return Err(RenderError::InvalidMarchDistance { far: format!("{far:?}") }.into());
+        }
+
+        let resolution = *camera.resolution();
+
+        // Trace in Morton order for coherent `Bvh` traversal, storing the results in row-major order
+        let mut samples = vec![
+            MediumSample {
+                distance: far,
+                transmittance: T::one(),
+                in_scattering: T::zero(),
+            };
+            resolution[0] * resolution[1]
+        ];
+        for [row, col] in camera.morton_pixels() {
+            let ray = camera.generate_ray([row, col])?;
+            let distance = scene.surface_distance(&ray)?.map_or(far, |distance| distance.min(far));
+            samples[row * resolution[1] + col] = MediumSample {
+                distance,
+                transmittance: scene.transmittance(&ray, distance)?,
+                in_scattering: match scattering {
+                    Some((light, steps)) => scene.in_scattering(&ray, distance, light, steps)?,
+                    None => T::zero(),
+                },
+            };
+        }
+
+        Ok(Self { resolution, samples })
+    }
+}
+
+impl<T: Scalar> MediumMap<T> {
+    /// Returns the resolution of the `MediumMap` [height, width].
+    #[must_use]
+    pub const fn resolution(&self) -> &[usize; 2] {
+        &self.resolution
+    }
+
+    /// Returns all samples in row-major order.
+    #[must_use]
+    pub fn samples(&self) -> &[MediumSample<T>] {
+        &self.samples
+    }
+
+    /// Get the sample at the given pixel index.
+    /// A position of [0, 0] corresponds to the top-left pixel of the image.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the pixel index is out of bounds for the image resolution.
+    pub fn sample(&self, pixel_index: [usize; 2]) -> Result<&MediumSample<T>> {
+        if pixel_index[0] >= self.resolution[0] || pixel_index[1] >= self.resolution[1] {
+            return Err(GeometryError::PixelOutOfBounds {
+                row: pixel_index[0],
+                col: pixel_index[1],
+                res_height: self.resolution[0],
+                res_width: self.resolution[1],
+            }
+            .into());
+        }
+        Ok(&self.samples[pixel_index[0] * self.resolution[1] + pixel_index[1]])
+    }
+}
@@ -0,0 +1,23 @@
+mod depth_map;
+mod film;
+mod g_buffer;
+mod lightmap;
+mod medium_map;
+mod normal_map;
+mod photon_map;
+#[cfg(feature = "rayon")]
+mod render_settings;
+mod wireframe;
+
+pub use depth_map::{DepthEncoding, DepthMap};
+#[cfg(feature = "rayon")]
+pub use film::FilmTile;
+pub use film::{AdaptiveSampling, Film, FilmChannel, FilmSample};
+pub use g_buffer::{GBuffer, GBufferSample};
+pub use lightmap::Lightmap;
+pub use medium_map::{MediumMap, MediumSample};
+pub use normal_map::{NormalMap, NormalTexel};
+pub use photon_map::{Photon, PhotonInteraction, PhotonMap, PhotonTracing};
+#[cfg(feature = "rayon")]
+pub use render_settings::{RenderSettings, RenderThreads, TileOrder};
+pub use wireframe::{Wireframe, WireframePixel};
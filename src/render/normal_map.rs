@@ -0,0 +1,113 @@
+//! Normal map baking pass.
+
+use nalgebra::{Unit, Vector3};
+
+use crate::{
+    error::{GeometryError, RenderError, Result},
+    geometry::Mesh,
+    render::lightmap::rasterise,
+    rt::Ray,
+    traits::{Scalar, Traceable},
+};
+
+/// Detail of a high-poly surface recorded in one texel of a `NormalMap`.
+#[derive(Debug, Clone, Copy)]
+pub struct NormalTexel<T: Scalar> {
+    /// Normal of the high-poly surface in the tangent space of the low-poly surface, as [tangent, bitangent, normal]
+    /// components. Unperturbed surfaces record [0, 0, 1].
+    pub normal: Unit<Vector3<T>>,
+    /// Displacement of the high-poly surface from the low-poly surface along its normal.
+    pub height: T,
+}
+
+/// Texture atlas of the detail of a high-poly `Mesh`, baked over the texture coordinates of a low-poly `Mesh`.
+///
+/// Texel [0, 0] is the top-left of the atlas, where u is zero and v is one.
+#[derive(Debug, Clone)]
+pub struct NormalMap<T: Scalar> {
+    /// Resolution of the atlas in texels [height, width].
+    resolution: [usize; 2],
+    /// Row-major baked texels, `None` where no low-poly `Triangle` covers the texel centre, or no high-poly surface
+    /// lies within the search distance.
+    texels: Vec<Option<NormalTexel<T>>>,
+}
+
+impl<T: Scalar> NormalMap<T> {
+    /// Bake a `NormalMap` by casting a `Ray` from each texel of the low-poly `Mesh`, inwards along its normal from
+    /// `max_distance` outside the surface, and recording the first high-poly surface hit within `max_distance` inside.
+    /// Tangent space follows the texture coordinates of each low-poly `Triangle`, orthogonalised against its
+    /// interpolated normal.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The resolution is zero
+    /// - `max_distance` is not positive and finite
+    /// - No `Triangle` of the low-poly `Mesh` has texture coordinates
+    /// - An intersection calculation fails
+    pub fn bake(low: &Mesh<T>, high: &Mesh<T>, resolution: [usize; 2], max_distance: T) -> Result<Self> {
+        if max_distance <= T::zero() || !max_distance.is_finite() {
+            return Err(RenderError::InvalidBakeDistance {
+                distance: format!("{max_distance:?}"),
+            }
+            .into());
+        }
+
+        let texels = rasterise(low, resolution, |_, triangle, position, normal| {
+            let Some([tangent, bitangent]) = triangle.tangents() else {
+                return Ok(None);
+            };
+            let Some(hit) = high
+                .intersect(&Ray::new(position + normal.scale(max_distance), -*normal))?
+                .filter(|hit| hit.distance <= max_distance + max_distance)
+            else {
+                return Ok(None);
+            };
+
+            // Gram-Schmidt the tangent against the normal, keeping the handedness of the texture coordinates
+            let tangent = Unit::new_normalize(tangent - normal.scale(normal.dot(&tangent)));
+            let mut cross = normal.cross(&tangent);
+            if cross.dot(&bitangent) < T::zero() {
+                cross = -cross;
+            }
+            let detail = hit.interpolated_normal;
+            Ok(Some(NormalTexel {
+                normal: Unit::new_normalize(Vector3::new(detail.dot(&tangent), detail.dot(&cross), detail.dot(normal))),
+                height: max_distance - hit.distance,
+            }))
+        })?;
+        Ok(Self { resolution, texels })
+    }
+}
+
+impl<T: Scalar> NormalMap<T> {
+    /// Returns the resolution of the `NormalMap` [height, width].
+    #[must_use]
+    pub const fn resolution(&self) -> &[usize; 2] {
+        &self.resolution
+    }
+
+    /// Returns all baked texels in row-major order.
+    #[must_use]
+    pub fn texels(&self) -> &[Option<NormalTexel<T>>] {
+        &self.texels
+    }
+
+    /// Get the baked texel at the given texel index, or `None` if nothing was baked into it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the texel index is out of bounds for the atlas resolution.
+    pub fn texel(&self, texel_index: [usize; 2]) -> Result<Option<&NormalTexel<T>>> {
+        if texel_index[0] >= self.resolution[0] || texel_index[1] >= self.resolution[1] {
+            return Err(GeometryError::PixelOutOfBounds {
+                row: texel_index[0],
+                col: texel_index[1],
+                res_height: self.resolution[0],
+                res_width: self.resolution[1],
+            }
+            .into());
+        }
+        Ok(self.texels[texel_index[0] * self.resolution[1] + texel_index[1]].as_ref())
+    }
+}
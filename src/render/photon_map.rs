@@ -0,0 +1,259 @@
+//! Photon map structure.
+
+use nalgebra::{Point3, Unit, Vector3};
+use std::cmp::Ordering;
+
+use crate::{
+    error::{RenderError, Result},
+    rt::{Hit, Pcg32, Ray, reflect, refract},
+    scene::Scene,
+    traits::{FallibleNumeric, Scalar, sample_cosine_hemisphere, sample_sphere, sample_unit},
+};
+
+/// Packet of light power stored where it arrived at a diffuse surface.
+#[derive(Debug, Clone)]
+pub struct Photon<T: Scalar> {
+    /// Point at which the `Photon` landed.
+    pub position: Point3<T>,
+    /// Direction in which the `Photon` was travelling when it landed.
+    pub direction: Unit<Vector3<T>>,
+    /// Power carried by the `Photon`.
+    pub power: T,
+}
+
+/// How a `SceneObject` scatters the `Photon`s which land on it.
+#[derive(Debug, Clone, Copy)]
+pub enum PhotonInteraction<T: Scalar> {
+    /// Lambertian surface, where `Photon`s are stored, then reflected with probability `albedo`.
+    Diffuse {
+        /// Fraction of the arriving power reflected.
+        albedo: T,
+    },
+    /// Perfect mirror.
+    Mirror,
+    /// Smooth boundary of a transparent object, which reflects or refracts by the Fresnel equations.
+    /// The geometric normal of each `Hit` is taken to face out of the object.
+    Dielectric {
+        /// Refractive index of the object, relative to its surroundings.
+        refractive_index: T,
+    },
+    /// Black surface, where `Photon`s are lost.
+    Absorb,
+}
+
+/// Settings of a `PhotonMap` trace pass.
+#[derive(Debug, Clone, Copy)]
+pub struct PhotonTracing {
+    /// Number of `Photon`s emitted by the light.
+    pub photons: usize,
+    /// Maximum number of surfaces each `Photon` bounces from before it is discarded.
+    pub max_bounces: usize,
+    /// Store only caustic `Photon`s: those landing on a diffuse surface directly after a mirror or dielectric,
+    /// which a unidirectional path tracer resolves poorly.
+    pub caustics_only: bool,
+}
+
+/// `Photon`s stored in a balanced kd-tree, for density estimation of the light arriving at surfaces.
+#[derive(Debug, Clone)]
+pub struct PhotonMap<T: Scalar> {
+    /// `Photon`s in implicit kd-tree order: each range is split at its middle `Photon`.
+    photons: Vec<Photon<T>>,
+    /// Axis along which each `Photon` splits its range.
+    axes: Vec<usize>,
+}
+
+impl<T: Scalar> PhotonMap<T> {
+    /// Construct a new `PhotonMap` instance, building a kd-tree over the `Photon`s.
+    #[must_use]
+    pub fn new(mut photons: Vec<Photon<T>>) -> Self {
+        let mut axes = vec![0; photons.len()];
+        build(&mut photons, &mut axes);
+        Self { photons, axes }
+    }
+
+    /// Trace `Photon`s from a point light of total `power` at `light` through a `Scene`, storing them where they land
+    /// on diffuse surfaces. `interaction` gives the behaviour of the `SceneObject` hit, by its index.
+    /// Each `Photon` draws from its own `Pcg32` stream, so identical seeds build identical maps.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The number of `Photon`s is zero
+    /// - `Scene` intersection fails
+    /// - Numeric conversion fails
+    pub fn trace<F>(
+        scene: &Scene<'_, T>,
+        light: &Point3<T>,
+        power: T,
+        tracing: &PhotonTracing,
+        seed: u64,
+        interaction: F,
+    ) -> Result<Self>
+    where
+        F: Fn(usize, &Hit<T>) -> PhotonInteraction<T>,
+    {
+        if tracing.photons == 0 {
+            return Err(RenderError::NoPhotons.into());
+        }
+
+        let photon_power = power / T::try_from_usize(tracing.photons)?;
+        let mut photons = Vec::new();
+        for index in 0..tracing.photons {
+            let mut rng = Pcg32::new(seed, index as u64);
+            let mut ray = Ray::new(*light, sample_sphere(&mut rng)?);
+            let mut specular = false;
+            for _ in 0..=tracing.max_bounces {
+                let Some((object_id, hit)) = scene.intersect_object(&ray)? else {
+                    break;
+                };
+                let position = ray.at(hit.distance);
+                let facing = if hit.geometric_normal.dot(&ray.direction) > T::zero() {
+                    -hit.geometric_normal
+                } else {
+                    hit.geometric_normal
+                };
+
+                let direction = match interaction(object_id, &hit) {
+                    PhotonInteraction::Diffuse { albedo } => {
+                        if specular || !tracing.caustics_only {
+                            photons.push(Photon {
+                                position,
+                                direction: ray.direction,
+                                power: photon_power,
+                            });
+                        }
+                        if tracing.caustics_only || sample_unit::<T, _>(&mut rng)? >= albedo {
+                            break;
+                        }
+                        specular = false;
+                        sample_cosine_hemisphere(&facing, &mut rng)?
+                    }
+                    PhotonInteraction::Mirror => {
+                        specular = true;
+                        reflect(&ray.direction, &facing)
+                    }
+                    PhotonInteraction::Dielectric { refractive_index } => {
+                        specular = true;
+                        let entering = hit.geometric_normal.dot(&ray.direction) < T::zero();
+                        let eta = if entering {
+                            refractive_index.recip()
+                        } else {
+                            refractive_index
+                        };
+                        match refract(&ray.direction, &facing, eta) {
+                            Some(refracted)
+                                if sample_unit::<T, _>(&mut rng)? >= reflectance(-ray.direction.dot(&facing), eta) =>
+                            {
+                                refracted
+                            }
+                            _ => reflect(&ray.direction, &facing),
+                        }
+                    }
+                    PhotonInteraction::Absorb => break,
+                };
+                ray = scene.spawn_ray(position, direction)?;
+            }
+        }
+        Ok(Self::new(photons))
+    }
+
+    /// Get the stored `Photon`s, in kd-tree order.
+    #[must_use]
+    pub fn photons(&self) -> &[Photon<T>] {
+        &self.photons
+    }
+
+    /// Find every `Photon` within `radius` of a point.
+    #[must_use]
+    pub fn gather(&self, position: &Point3<T>, radius: T) -> Vec<&Photon<T>> {
+        let mut found = Vec::new();
+        self.gather_range(position, radius * radius, 0, self.photons.len(), &mut found);
+        found
+    }
+
+    /// Estimate the irradiance arriving at a surface point from the density of the `Photon`s within `radius`.
+    /// Only `Photon`s arriving on the side of the surface facing along `normal` are counted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `radius` is not positive and finite.
+    pub fn irradiance(&self, position: &Point3<T>, normal: &Unit<Vector3<T>>, radius: T) -> Result<T> {
+        if radius <= T::zero() || !radius.is_finite() {
+            return Err(RenderError::InvalidGatherRadius {
+                radius: format!("{radius:?}"),
+            }
+            .into());
+        }
+        let power = self
+            .gather(position, radius)
+            .into_iter()
+            .filter(|photon| photon.direction.dot(normal) < T::zero())
+            .fold(T::zero(), |total, photon| total + photon.power);
+        Ok(power / (T::pi() * radius * radius))
+    }
+
+    /// Collect the `Photon`s within the squared radius of a point from the kd-tree range [`start`, `end`).
+    fn gather_range<'a>(
+        &'a self,
+        position: &Point3<T>,
+        radius_squared: T,
+        start: usize,
+        end: usize,
+        found: &mut Vec<&'a Photon<T>>,
+    ) {
+        if start >= end {
+            return;
+        }
+        let middle = start + (end - start) / 2;
+        let photon = &self.photons[middle];
+        if (photon.position - position).norm_squared() <= radius_squared {
+            found.push(photon);
+        }
+
+        // Search the near side first, and the far side only if the sphere crosses the splitting plane
+        let axis = self.axes[middle];
+        let offset = position[axis] - photon.position[axis];
+        let (near, far) = if offset < T::zero() {
+            ((start, middle), (middle + 1, end))
+        } else {
+            ((middle + 1, end), (start, middle))
+        };
+        self.gather_range(position, radius_squared, near.0, near.1, found);
+        if offset * offset <= radius_squared {
+            self.gather_range(position, radius_squared, far.0, far.1, found);
+        }
+    }
+}
+
+// == Utility functions ==
+
+/// Arrange `Photon`s into an implicit kd-tree, splitting each range at its middle along its longest axis.
+fn build<T: Scalar>(photons: &mut [Photon<T>], split_axes: &mut [usize]) {
+    if photons.is_empty() {
+        return;
+    }
+    let (mut mins, mut maxs) = (photons[0].position, photons[0].position);
+    for photon in photons.iter() {
+        mins = mins.inf(&photon.position);
+        maxs = maxs.sup(&photon.position);
+    }
+    let axis = (maxs - mins).imax();
+
+    let middle = photons.len() / 2;
+    let _ = photons.select_nth_unstable_by(middle, |a, b| {
+        a.position[axis].partial_cmp(&b.position[axis]).unwrap_or(Ordering::Equal)
+    });
+    split_axes[middle] = axis;
+
+    let (left, right) = photons.split_at_mut(middle);
+    let (left_axes, right_axes) = split_axes.split_at_mut(middle);
+    build(left, left_axes);
+    build(&mut right[1..], &mut right_axes[1..]);
+}
+
+/// Fraction of light reflected by a dielectric boundary, by Schlick's approximation of the Fresnel equations.
+/// `cos_incident` is the cosine of the angle of incidence, and `eta` the ratio of the refractive indices.
+fn reflectance<T: Scalar>(cos_incident: T, eta: T) -> T {
+    let normal_reflectance = ((T::one() - eta) / (T::one() + eta)).powi(2);
+    normal_reflectance + (T::one() - normal_reflectance) * (T::one() - cos_incident).powi(5)
+}
@@ -0,0 +1,140 @@
+//! Parallel render scheduling settings.
+
+use rayon::{ThreadPool, ThreadPoolBuilder};
+use std::{ops::Range, sync::Arc};
+
+use crate::{
+    error::{RenderError, Result},
+    scene::MortonOrder,
+};
+
+/// Order in which the tiles of an image are handed to render threads.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TileOrder {
+    /// Tiles row by row, from the top-left of the image.
+    RowMajor,
+    /// Tiles along a Morton curve, so tiles rendered at the same time are neighbours sharing cached scene data.
+    #[default]
+    Morton,
+    /// Tiles in order of distance from the centre of the image, so the subject of a preview appears first.
+    CentreOut,
+}
+
+/// Threads on which a parallel render runs.
+#[derive(Debug, Clone, Default)]
+pub enum RenderThreads {
+    /// The rayon thread pool the render is called from, which is the global pool outside of any other.
+    #[default]
+    Current,
+    /// A dedicated pool of the given number of threads, built for the render.
+    Count(usize),
+    /// An existing thread pool, shared with other work.
+    Pool(Arc<ThreadPool>),
+}
+
+/// Settings of a parallel render, which splits the image into square tiles and the samples of each tile into batches.
+/// Each (tile, batch) pair is one task for the thread pool.
+#[derive(Debug, Clone)]
+pub struct RenderSettings {
+    /// Edge length of the square tiles of pixels, in pixels.
+    pub tile_size: usize,
+    /// Order in which tiles are scheduled.
+    pub tile_order: TileOrder,
+    /// Number of samples of each pixel in a tile taken by one task.
+    /// Smaller batches spread few tiles with many samples over more threads.
+    pub sample_batch: usize,
+    /// Threads on which to render.
+    pub threads: RenderThreads,
+    /// Accumulate the batches of each pixel in a fixed order, rather than as their tasks complete.
+    /// Deterministic renders are bitwise reproducible for a given seed and settings, whatever the thread count,
+    /// at the cost of waiting for every tile to finish each batch before any starts the next.
+    pub deterministic: bool,
+}
+
+impl Default for RenderSettings {
+    fn default() -> Self {
+        Self {
+            tile_size: 16,
+            tile_order: TileOrder::default(),
+            sample_batch: 64,
+            threads: RenderThreads::default(),
+            deterministic: true,
+        }
+    }
+}
+
+impl RenderSettings {
+    /// Check that the tile size and sample batch are positive.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either the tile size or sample batch is zero.
+    pub fn validate(&self) -> Result<()> {
+        if self.tile_size == 0 || self.sample_batch == 0 {
+            return Err(RenderError::InvalidRenderSettings {
+                tile_size: self.tile_size,
+                sample_batch: self.sample_batch,
+            }
+            .into());
+        }
+        Ok(())
+    }
+}
+
+impl TileOrder {
+    /// Split an image of the given resolution [height, width] into square tiles of `tile_size` pixels,
+    /// as [rows, cols] ranges in this order. Tiles at the bottom and right edges are cropped to the image.
+    #[must_use]
+    pub fn tiles(self, resolution: [usize; 2], tile_size: usize) -> Vec<[Range<usize>; 2]> {
+        let tile_size = tile_size.max(1);
+        let [tile_rows, tile_cols] = resolution.map(|pixels| pixels.div_ceil(tile_size));
+        let mut grid: Vec<[usize; 2]> = match self {
+            Self::Morton => MortonOrder::new(0..tile_rows, 0..tile_cols).collect(),
+            Self::RowMajor | Self::CentreOut => (0..tile_rows)
+                .flat_map(|row| (0..tile_cols).map(move |col| [row, col]))
+                .collect(),
+        };
+        if self == Self::CentreOut {
+            // Twice the offset of each tile centre from the image centre, in tiles, to stay in integers
+            grid.sort_by_key(|&[row, col]| {
+                let dr = (2 * row + 1).abs_diff(tile_rows);
+                let dc = (2 * col + 1).abs_diff(tile_cols);
+                dr * dr + dc * dc
+            });
+        }
+        grid.into_iter()
+            .map(|[row, col]| {
+                let (row, col) = (row * tile_size, col * tile_size);
+                [
+                    row..(row + tile_size).min(resolution[0]),
+                    col..(col + tile_size).min(resolution[1]),
+                ]
+            })
+            .collect()
+    }
+}
+
+impl RenderThreads {
+    /// Run an operation on these threads, returning its result.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a dedicated thread pool of zero threads is requested, or cannot be built.
+    pub fn install<R: Send, F: FnOnce() -> R + Send>(&self, operation: F) -> Result<R> {
+        match self {
+            Self::Current => Ok(operation()),
+            Self::Count(0) => Err(RenderError::ThreadPool {
+                message: "a thread pool needs at least one thread".to_string(),
+            }
+            .into()),
+            Self::Count(threads) => Ok(ThreadPoolBuilder::new()
+                .num_threads(*threads)
+                .build()
+                .map_err(|error| RenderError::ThreadPool {
+                    message: error.to_string(),
+                })?
+                .install(operation)),
+            Self::Pool(pool) => Ok(pool.install(operation)),
+        }
+    }
+}
@@ -0,0 +1,86 @@
+//! Wireframe render pass.
+
+use crate::{
+    error::{GeometryError, Result},
+    scene::{Camera, Scene},
+    traits::Scalar,
+};
+
+/// Classification of a pixel in a `Wireframe` render.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WireframePixel {
+    /// The primary `Ray` escaped the `Scene`.
+    Background,
+    /// The primary `Ray` hit the interior of a surface.
+    Surface,
+    /// The primary `Ray` hit a surface within the edge width of a `Triangle` edge.
+    Edge,
+}
+
+/// Wireframe overlay classifying each pixel of a `Camera` by its proximity to `Triangle` edges.
+#[derive(Debug, Clone)]
+pub struct Wireframe {
+    /// Resolution of the image in pixels [height, width].
+    resolution: [usize; 2],
+    /// Row-major pixel classifications.
+    pixels: Vec<WireframePixel>,
+}
+
+impl Wireframe {
+    /// Render a `Wireframe` by tracing a single primary `Ray` through every pixel of the `Camera`.
+    /// A hit is classified as an `Edge` when its smallest barycentric coordinate is below `edge_width`.
+    /// Non-triangular surfaces (spheres, planes) have no edges and are always classified as `Surface`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `Ray` generation or `Scene` intersection fails.
+    pub fn render<T: Scalar>(scene: &Scene<'_, T>, camera: &Camera<T>, edge_width: T) -> Result<Self> {
+        let resolution = *camera.resolution();
+
+        // Trace in Morton order for coherent `Bvh` traversal, storing the results in row-major order
+        let mut pixels = vec![WireframePixel::Background; resolution[0] * resolution[1]];
+        for [row, col] in camera.morton_pixels() {
+            let ray = camera.generate_ray([row, col])?;
+            pixels[row * resolution[1] + col] = match scene.intersect_object(&ray)? {
+                None => WireframePixel::Background,
+                Some((_, hit)) => match hit.barycentric {
+                    Some([w, u, v]) if w.min(u).min(v) < edge_width => WireframePixel::Edge,
+                    _ => WireframePixel::Surface,
+                },
+            };
+        }
+
+        Ok(Self { resolution, pixels })
+    }
+
+    /// Returns the resolution of the `Wireframe` [height, width].
+    #[must_use]
+    pub const fn resolution(&self) -> &[usize; 2] {
+        &self.resolution
+    }
+
+    /// Returns all pixels in row-major order.
+    #[must_use]
+    pub fn pixels(&self) -> &[WireframePixel] {
+        &self.pixels
+    }
+
+    /// Get the classification of the given pixel index.
+    /// A position of [0, 0] corresponds to the top-left pixel of the image.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the pixel index is out of bounds for the image resolution.
+    pub fn pixel(&self, pixel_index: [usize; 2]) -> Result<WireframePixel> {
+        if pixel_index[0] >= self.resolution[0] || pixel_index[1] >= self.resolution[1] {
+            return Err(GeometryError::PixelOutOfBounds {
+                row: pixel_index[0],
+                col: pixel_index[1],
+                res_height: self.resolution[0],
+                res_width: self.resolution[1],
+            }
+            .into());
+        }
+        Ok(self.pixels[pixel_index[0] * self.resolution[1] + pixel_index[1]])
+    }
+}
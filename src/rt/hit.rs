@@ -1,10 +1,14 @@
-use nalgebra::{RealField, Unit, Vector3};
+use nalgebra::{Unit, Vector3};
 
-use crate::error::{GeometryError, Result};
+use crate::{
+    error::{GeometryError, Result},
+    rt::TangentFrame,
+    traits::Scalar,
+};
 
 /// Records details of a ray intersection with a geometric surface.
 #[derive(Debug, Clone)]
-pub struct Hit<T: RealField + Copy> {
+pub struct Hit<T: Scalar> {
     /// Index of the internal geometry which was hit.
     pub index: usize,
     /// The distance to intersection.
@@ -13,9 +17,18 @@ pub struct Hit<T: RealField + Copy> {
     pub geometric_normal: Unit<Vector3<T>>,
     /// The Phong shading normal at the intersection point.
     pub interpolated_normal: Unit<Vector3<T>>,
+    /// Barycentric coordinates of the intersection point, weighting vertices [0, 1, 2], if the surface is a `Triangle`.
+    pub barycentric: Option<[T; 3]>,
+    /// Interpolated texture coordinates [u, v] at the intersection point, if the surface has them.
+    pub uv: Option<[T; 2]>,
+    /// Interpolated colour [red, green, blue, alpha] at the intersection point, if the surface has vertex colours.
+    pub color: Option<[T; 4]>,
+    /// Frame aligned with the texture coordinates at the intersection point, for tangent-space normal mapping,
+    /// if the surface has texture coordinates.
+    pub tangent_frame: Option<TangentFrame<T>>,
 }
 
-impl<T: RealField + Copy> Hit<T> {
+impl<T: Scalar> Hit<T> {
     /// Construct a new `Hit` instance.
     ///
     /// # Errors
@@ -39,6 +52,38 @@ impl<T: RealField + Copy> Hit<T> {
             distance,
             geometric_normal,
             interpolated_normal,
+            barycentric: None,
+            uv: None,
+            color: None,
+            tangent_frame: None,
         })
     }
+
+    /// Construct a new `Hit` instance without validating the distance.
+    /// Used on per-ray paths by primitives whose intersection tests only accept positive distances.
+    #[must_use]
+    pub const fn new_unchecked(
+        index: usize,
+        distance: T,
+        geometric_normal: Unit<Vector3<T>>,
+        interpolated_normal: Unit<Vector3<T>>,
+    ) -> Self {
+        Self {
+            index,
+            distance,
+            geometric_normal,
+            interpolated_normal,
+            barycentric: None,
+            uv: None,
+            color: None,
+            tangent_frame: None,
+        }
+    }
+
+    /// Set the barycentric coordinates of the intersection point.
+    #[must_use]
+    pub const fn with_barycentric(mut self, barycentric: [T; 3]) -> Self {
+        self.barycentric = Some(barycentric);
+        self
+    }
 }
@@ -1,5 +1,11 @@
 mod hit;
+mod pcg32;
 mod ray;
+mod ray_ordering;
+mod tangent_frame;
 
 pub use hit::Hit;
-pub use ray::Ray;
+pub use pcg32::Pcg32;
+pub use ray::{Ray, reflect, refract};
+pub use ray_ordering::RayOrdering;
+pub use tangent_frame::TangentFrame;
@@ -0,0 +1,111 @@
+//! Seedable random number generator structure.
+
+use rand::{Error, RngCore, SeedableRng};
+
+/// Multiplier of the underlying linear congruential generator.
+const MULTIPLIER: u64 = 6_364_136_223_846_793_005;
+
+/// Permuted congruential generator (PCG-XSH-RR), with 64 bits of state and 32 bits of output.
+///
+/// Each of its 2^63 streams is an independent sequence for the same seed, so stochastic work split across threads
+/// stays reproducible when every pixel and sample draws from a stream derived from its own index, with `for_sample`,
+/// rather than from a generator shared in whatever order the threads happen to run.
+#[expect(
+    missing_copy_implementations,
+    reason = "Implicit copies would silently repeat the sequence."
+)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pcg32 {
+    /// Current state.
+    state: u64,
+    /// Odd increment selecting the stream.
+    increment: u64,
+}
+
+impl Pcg32 {
+    /// Construct a new `Pcg32` instance from a seed and a stream index.
+    #[must_use]
+    pub const fn new(seed: u64, stream: u64) -> Self {
+        let mut rng = Self {
+            state: 0,
+            increment: (stream << 1) | 1,
+        };
+        rng.step();
+        rng.state = rng.state.wrapping_add(seed);
+        rng.step();
+        rng
+    }
+
+    /// Construct the `Pcg32` for one sample of one pixel of an image rendered with a given seed.
+    /// The stream is derived from the pixel index [row, column] and the sample number alone,
+    /// so the numbers drawn do not depend on the order in which, or the thread on which, samples are taken.
+    #[must_use]
+    pub const fn for_sample(seed: u64, pixel_index: [usize; 2], sample: usize) -> Self {
+        let stream = mix(mix(mix(pixel_index[0] as u64) ^ pixel_index[1] as u64) ^ sample as u64);
+        Self::new(seed, stream)
+    }
+
+    /// Advance the state by one step.
+    const fn step(&mut self) {
+        self.state = self.state.wrapping_mul(MULTIPLIER).wrapping_add(self.increment);
+    }
+}
+
+impl RngCore for Pcg32 {
+    fn next_u32(&mut self) -> u32 {
+        let state = self.state;
+        self.step();
+
+        // Xorshift the high bits down, then rotate by the top five bits
+        #[expect(
+            clippy::cast_possible_truncation,
+            reason = "Output is the low 32 bits of the xorshifted state."
+        )]
+        let xorshifted = (((state >> 18) ^ state) >> 27) as u32;
+        let rotation = (state >> 59) as u32;
+        xorshifted.rotate_right(rotation)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        (u64::from(self.next_u32()) << 32) | u64::from(self.next_u32())
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(4) {
+            let bytes = self.next_u32().to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl SeedableRng for Pcg32 {
+    /// Seed followed by the stream index, each as little-endian bytes.
+    type Seed = [u8; 16];
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        let (state, stream) = seed.split_at(8);
+        Self::new(
+            u64::from_le_bytes(state.try_into().unwrap_or_default()),
+            u64::from_le_bytes(stream.try_into().unwrap_or_default()),
+        )
+    }
+
+    fn seed_from_u64(seed: u64) -> Self {
+        Self::new(seed, 0)
+    }
+}
+
+// == Utility functions ==
+
+/// Scramble the bits of a value (the `SplitMix64` finaliser), so neighbouring indices select unrelated streams.
+const fn mix(value: u64) -> u64 {
+    let mut value = value.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    value = (value ^ (value >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    value = (value ^ (value >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    value ^ (value >> 31)
+}
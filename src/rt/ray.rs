@@ -1,10 +1,15 @@
 //! Ray structure.
 
-use nalgebra::{Point3, RealField, Unit, Vector3};
+use nalgebra::{ComplexField, Matrix4, Point3, Unit, Vector3};
+
+use crate::{
+    error::{GeometryError, Result},
+    traits::Scalar,
+};
 
 /// Geometric ray structure defined by an origin point and a direction vector.
 #[derive(Debug, Clone)]
-pub struct Ray<T: RealField + Copy> {
+pub struct Ray<T: Scalar> {
     /// Starting location.
     pub origin: Point3<T>,
     /// Direction.
@@ -15,7 +20,7 @@ pub struct Ray<T: RealField + Copy> {
     pub sign: [usize; 3],
 }
 
-impl<T: RealField + Copy> Ray<T> {
+impl<T: Scalar> Ray<T> {
     /// Construct a new `Ray` instance.
     pub fn new(origin: Point3<T>, direction: Unit<Vector3<T>>) -> Self {
         let inv_direction = Vector3::new(T::one() / direction.x, T::one() / direction.y, T::one() / direction.z);
@@ -32,4 +37,77 @@ impl<T: RealField + Copy> Ray<T> {
             sign,
         }
     }
+
+    /// Construct a new `Ray` instance, as in `new`, rejecting NaN and infinite components.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the origin or direction is not finite.
+    pub fn try_new(origin: Point3<T>, direction: Unit<Vector3<T>>) -> Result<Self> {
+        let ray = Self::new(origin, direction);
+        if !ray.is_finite() {
+            return Err(GeometryError::NonFiniteRay {
+                origin: format!("{:?}", ray.origin),
+                direction: format!("{:?}", ray.direction),
+            }
+            .into());
+        }
+        Ok(ray)
+    }
+
+    /// Check if the origin and direction of the `Ray` are free of NaN and infinite components.
+    /// Traversal treats any other `Ray` as missing everything, rather than propagating NaN distances.
+    #[must_use]
+    pub fn is_finite(&self) -> bool {
+        self.origin.iter().chain(self.direction.iter()).all(ComplexField::is_finite)
+    }
+
+    /// Get the point at distance `t` along the `Ray`.
+    #[must_use]
+    pub fn at(&self, t: T) -> Point3<T> {
+        self.origin + self.direction.scale(t)
+    }
+
+    /// Return a copy of the `Ray` mapped through an affine transformation, with its direction renormalised.
+    /// Distances along the transformed `Ray` differ from those along the original by the scale of the transformation
+    /// in the `Ray` direction.
+    #[must_use]
+    pub fn transformed(&self, transform: &Matrix4<T>) -> Self {
+        Self::new(
+            transform.transform_point(&self.origin),
+            Unit::new_normalize(transform.transform_vector(&self.direction)),
+        )
+    }
+}
+
+/// Reflect a direction about a surface normal, which may face either side of the surface.
+#[must_use]
+pub fn reflect<T: Scalar>(direction: &Unit<Vector3<T>>, normal: &Unit<Vector3<T>>) -> Unit<Vector3<T>> {
+    let two = T::one() + T::one();
+    Unit::new_normalize(direction.as_ref() - normal.scale(two * direction.dot(normal)))
+}
+
+/// Refract a direction through a surface by Snell's law, or return `None` on total internal reflection.
+///
+/// `eta` is the refractive index on the incident side divided by that on the transmitted side.
+/// The normal may face either side of the surface.
+#[must_use]
+pub fn refract<T: Scalar>(direction: &Unit<Vector3<T>>, normal: &Unit<Vector3<T>>, eta: T) -> Option<Unit<Vector3<T>>> {
+    // Face the normal against the incoming direction
+    let mut cos_incident = -direction.dot(normal);
+    let normal = if cos_incident < T::zero() {
+        cos_incident = -cos_incident;
+        -normal.into_inner()
+    } else {
+        normal.into_inner()
+    };
+
+    let sin_squared_transmitted = eta * eta * (T::one() - cos_incident * cos_incident).max(T::zero());
+    if sin_squared_transmitted > T::one() {
+        return None;
+    }
+    let cos_transmitted = (T::one() - sin_squared_transmitted).sqrt();
+    Some(Unit::new_normalize(
+        direction.scale(eta) + normal * (eta * cos_incident - cos_transmitted),
+    ))
 }
@@ -0,0 +1,50 @@
+//! Tangent frame structure.
+
+use nalgebra::{Unit, Vector3};
+
+use crate::traits::Scalar;
+
+/// Orthonormal frame at a surface point, aligned with its texture coordinates,
+/// in which tangent-space normal maps are defined.
+///
+/// Follows the `MikkTSpace` convention: the bitangent is the cross product of the normal and tangent,
+/// negated where the texture coordinates are mirrored.
+#[derive(Debug, Clone, Copy)]
+pub struct TangentFrame<T: Scalar> {
+    /// Direction of increasing u, perpendicular to the normal.
+    pub tangent: Unit<Vector3<T>>,
+    /// Direction of increasing v, perpendicular to the normal and tangent.
+    pub bitangent: Unit<Vector3<T>>,
+    /// Shading normal.
+    pub normal: Unit<Vector3<T>>,
+}
+
+impl<T: Scalar> TangentFrame<T> {
+    /// Construct a new `TangentFrame` from a normal and an approximate tangent, which is made perpendicular to the normal.
+    /// A negative `handedness` marks mirrored texture coordinates, flipping the bitangent.
+    /// Returns `None` if the tangent is parallel to the normal.
+    #[must_use]
+    pub fn new(normal: Unit<Vector3<T>>, tangent: &Vector3<T>, handedness: T) -> Option<Self> {
+        let tangent = Unit::try_new(tangent - normal.scale(normal.dot(tangent)), T::zero())?;
+        let bitangent = normal.cross(&tangent);
+        let bitangent = Unit::new_normalize(if handedness < T::zero() { -bitangent } else { bitangent });
+        Some(Self {
+            tangent,
+            bitangent,
+            normal,
+        })
+    }
+
+    /// Convert a vector from tangent space [tangent, bitangent, normal] to world space,
+    /// such as a normal read from a tangent-space normal map.
+    #[must_use]
+    pub fn to_world(&self, local: &Vector3<T>) -> Vector3<T> {
+        self.tangent.scale(local.x) + self.bitangent.scale(local.y) + self.normal.scale(local.z)
+    }
+
+    /// Convert a world space vector to tangent space [tangent, bitangent, normal].
+    #[must_use]
+    pub fn to_local(&self, world: &Vector3<T>) -> Vector3<T> {
+        Vector3::new(self.tangent.dot(world), self.bitangent.dot(world), self.normal.dot(world))
+    }
+}
@@ -1,43 +1,296 @@
 //! Scene structure for `Ray` tracing.
 
-use nalgebra::RealField;
-use num_traits::ToPrimitive;
-use std::collections::HashMap;
+use std::{collections::HashMap, str::FromStr};
+#[cfg(feature = "fs")]
+use std::{
+    fs::read_dir,
+    path::{Path, PathBuf},
+};
 
+#[cfg(feature = "fs")]
+use crate::error::{FileParsingError, GeodesicError};
 use crate::{
     bvh::BvhConfig,
     error::{Result, SceneError},
-    geometry::Mesh,
+    geometry::{Mesh, MeshConfig},
+    scene::{BudgetPolicy, MemoryBudget, MemoryUsage},
+    traits::{Bounded, FallibleNumeric, Scalar},
 };
 
 /// Builder for constructing `Scene` instances.
 #[derive(Debug)]
-pub struct Assets<T: RealField + Copy + ToPrimitive> {
+pub struct Assets<T: Scalar> {
     /// Bounding Volume Hierarchy configuration for applicable `Assets` constructed `Scene`s.
     pub bvh_config: BvhConfig<T>,
+    /// Configuration of how the `Triangle`s of `Mesh`es loaded or decimated by the `Assets` are prepared.
+    pub mesh_config: MeshConfig,
     /// Collection of `Mesh` instances available in `Scene`s.
     pub meshes: HashMap<String, Mesh<T>>,
+    /// Soft limit on the memory held by the `Mesh`es, checked as each is added or replaced.
+    pub budget: Option<MemoryBudget>,
 }
 
-impl<T: RealField + Copy + ToPrimitive> Assets<T> {
+impl<T: Scalar> Assets<T> {
     /// Construct a new empty `Assets` instance.
     pub fn empty(bvh_config: BvhConfig<T>) -> Self {
         Self {
             bvh_config,
+            mesh_config: MeshConfig::default(),
             meshes: HashMap::new(),
+            budget: None,
         }
     }
 
+    /// Set the `MeshConfig` used for `Mesh`es loaded or decimated by the `Assets` from now on.
+    #[must_use]
+    pub const fn with_mesh_config(mut self, mesh_config: MeshConfig) -> Self {
+        self.mesh_config = mesh_config;
+        self
+    }
+
+    /// Set the `MemoryBudget` respected by `Mesh`es added from now on.
+    #[must_use]
+    pub const fn with_budget(mut self, budget: MemoryBudget) -> Self {
+        self.budget = Some(budget);
+        self
+    }
+
     /// Add a `Mesh` to the `Assets`.
+    /// If the `Assets` have a `MemoryBudget` which the `Mesh` would exceed, it is rejected or decimated to fit.
     ///
     /// # Errors
     ///
-    /// Returns an error if an asset with the same ID already exists.
+    /// Returns an error if:
+    /// - An asset with the same ID already exists
+    /// - The `Mesh` does not fit in the remaining `MemoryBudget`, even if decimated
     pub fn add_mesh(mut self, id: &str, mesh: Mesh<T>) -> Result<Self> {
+        self.insert_mesh(id, mesh)?;
+        Ok(self)
+    }
+
+    /// Get the number of bytes held by the `Mesh`es. `Mesh`es registered under several IDs are counted once.
+    #[must_use]
+    pub fn memory_usage(&self) -> MemoryUsage {
+        let mut counted: Vec<&Mesh<T>> = Vec::with_capacity(self.meshes.len());
+        for mesh in self.meshes.values() {
+            if !counted.iter().any(|other| other.shares_data(mesh)) {
+                counted.push(mesh);
+            }
+        }
+        counted.into_iter().map(Mesh::memory_usage).sum()
+    }
+
+    /// Check if a `Mesh` with the given ID exists.
+    #[must_use]
+    pub fn contains(&self, id: &str) -> bool {
+        self.meshes.contains_key(id)
+    }
+
+    /// Get a reference to the `Mesh` with the given ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no asset with the given ID exists.
+    pub fn mesh(&self, id: &str) -> Result<&Mesh<T>> {
+        self.meshes
+            .get(id)
+            .ok_or_else(|| SceneError::AssetNotFound { id: id.to_string() }.into())
+    }
+
+    /// Iterate over all (ID, `Mesh`) pairs in the `Assets`, in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Mesh<T>)> {
+        self.meshes.iter().map(|(id, mesh)| (id.as_str(), mesh))
+    }
+
+    /// Remove the `Mesh` with the given ID, returning it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no asset with the given ID exists.
+    pub fn remove_mesh(&mut self, id: &str) -> Result<Mesh<T>> {
+        self.meshes
+            .remove(id)
+            .ok_or_else(|| SceneError::AssetNotFound { id: id.to_string() }.into())
+    }
+
+    /// Replace the `Mesh` with the given ID, returning the previous `Mesh`.
+    /// If the `Assets` have a `MemoryBudget`, the bytes freed by the previous `Mesh` count towards the space available,
+    /// and the `Mesh` is rejected or decimated to fit as in `add_mesh`. A rejected `Mesh` leaves the previous one in place.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - No asset with the given ID exists
+    /// - The `Mesh` does not fit in the remaining `MemoryBudget`, even if decimated
+    pub fn replace_mesh(&mut self, id: &str, mesh: Mesh<T>) -> Result<Mesh<T>> {
+        let previous = self.remove_mesh(id)?;
+        match self.fit_budget(id, mesh) {
+            Ok(mesh) => {
+                let _unused = self.meshes.insert(id.into(), mesh);
+                Ok(previous)
+            }
+            Err(err) => {
+                let _unused = self.meshes.insert(id.into(), previous);
+                Err(err)
+            }
+        }
+    }
+
+    /// Register a `Mesh` under an unused ID, enforcing the `MemoryBudget`.
+    fn insert_mesh(&mut self, id: &str, mesh: Mesh<T>) -> Result<()> {
         if self.meshes.contains_key(id) {
             return Err(SceneError::DuplicateAssetId { id: id.to_string() }.into());
         }
+        let mesh = self.fit_budget(id, mesh)?;
         let _unused = self.meshes.insert(id.into(), mesh);
-        Ok(self)
+        Ok(())
+    }
+
+    /// Fit a `Mesh` into the space left by the `Mesh`es already registered, if the `Assets` have a `MemoryBudget`.
+    /// A `Mesh` sharing its data with a registered one takes no further space.
+    fn fit_budget(&self, id: &str, mesh: Mesh<T>) -> Result<Mesh<T>> {
+        match self.budget {
+            Some(budget) if !self.meshes.values().any(|other| other.shares_data(&mesh)) => {
+                let available = budget.max_bytes.saturating_sub(self.memory_usage().total());
+                self.fit_mesh(id, mesh, available, budget.policy)
+            }
+            _ => Ok(mesh),
+        }
+    }
+
+    /// Fit a `Mesh` into the available number of bytes, according to the `BudgetPolicy`.
+    /// Decimation starts with cells of 1/1024 of the longest extent of the `Mesh`, doubling their size until it fits.
+    fn fit_mesh(&self, id: &str, mesh: Mesh<T>, available: usize, policy: BudgetPolicy) -> Result<Mesh<T>> {
+        let required = mesh.memory_usage().total();
+        if required <= available {
+            return Ok(mesh);
+        }
+        if policy == BudgetPolicy::Decimate {
+            let longest = mesh.aabb()?.finite().map_or_else(T::zero, |aabb| aabb.extents().max());
+            let mut cell_size = longest / T::try_from_usize(1024)?;
+            while cell_size > T::zero() && cell_size <= longest {
+                // Every `Triangle` collapsing is one more way of not fitting
+                if let Ok(decimated) = mesh.decimate(&self.bvh_config, cell_size)
+                    && decimated.memory_usage().total() <= available
+                {
+                    return Ok(decimated);
+                }
+                cell_size += cell_size;
+            }
+        }
+        Err(SceneError::MemoryBudgetExceeded {
+            id: id.to_string(),
+            required,
+            available,
+        }
+        .into())
+    }
+}
+
+impl<T: Scalar + FromStr> Assets<T> {
+    /// Load every mesh file in a directory whose extension is one of `extensions` (case-insensitive, e.g. `["obj"]`),
+    /// registering each under its file stem. Subdirectories are not searched.
+    ///
+    /// Files are loaded in path order. A file that fails to load, or whose stem is already registered,
+    /// is skipped and reported in the returned list of failures rather than aborting the whole load.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory cannot be read.
+    #[cfg(feature = "fs")]
+    pub fn load_dir<P: AsRef<Path>>(&mut self, path: P, extensions: &[&str]) -> Result<Vec<(PathBuf, GeodesicError)>> {
+        let mut paths = read_dir(path)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        paths.retain(|path| {
+            path.is_file()
+                && path
+                    .extension()
+                    .and_then(|ext| ext.to_str())
+                    .is_some_and(|ext| extensions.iter().any(|allowed| allowed.eq_ignore_ascii_case(ext)))
+        });
+        paths.sort();
+
+        let mut failures = Vec::new();
+        for path in paths {
+            if let Err(err) = self.load_file(&path) {
+                failures.push((path, err));
+            }
+        }
+        Ok(failures)
+    }
+
+    /// Load a single mesh file and register it under its file stem.
+    #[cfg(feature = "fs")]
+    fn load_file(&mut self, path: &Path) -> Result<()> {
+        let id = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .ok_or_else(|| FileParsingError::UnsupportedFormat {
+                path: path.display().to_string(),
+            })?;
+        if self.contains(id) {
+            return Err(SceneError::DuplicateAssetId { id: id.to_string() }.into());
+        }
+
+        let is_obj = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("obj"));
+        if !is_obj {
+            return Err(FileParsingError::UnsupportedFormat {
+                path: path.display().to_string(),
+            }
+            .into());
+        }
+
+        let mesh = Mesh::load_with_config(&self.bvh_config, self.mesh_config, path)?;
+        self.insert_mesh(id, mesh)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nalgebra::Point3;
+
+    use crate::{
+        bvh::BvhConfig,
+        error::Result,
+        geometry::Aabb,
+        scene::{Assets, BudgetPolicy, MemoryBudget},
+        testing::SceneGenerator,
+    };
+
+    #[test]
+    fn replace_mesh_respects_the_memory_budget() -> Result<()> {
+        let bvh_config = BvhConfig::default();
+        let generator = SceneGenerator::new(Aabb::new(Point3::new(-1.0, -1.0, -1.0), Point3::new(1.0, 1.0, 1.0))?, 7);
+        let small = generator.triangle_soup(&bvh_config, 100, 0.1)?;
+        let large = generator.triangle_soup(&bvh_config, 400, 0.1)?;
+        let [small_bytes, large_bytes] = [&small, &large].map(|mesh| mesh.memory_usage().total());
+        let assets = |max_bytes, policy| {
+            Assets::empty(bvh_config.clone())
+                .add_mesh("swapped", small.clone())
+                .map(|assets| assets.with_budget(MemoryBudget::new(max_bytes).with_policy(policy)))
+        };
+
+        // Fits only once the bytes of the replaced `Mesh` are freed
+        let mut fitting = assets(large_bytes + small_bytes / 2, BudgetPolicy::Reject)?;
+        let previous = fitting.replace_mesh("swapped", large.clone())?;
+        assert_eq!(previous.triangles().len(), 100);
+        assert_eq!(fitting.mesh("swapped")?.triangles().len(), 400);
+
+        // Rejected, leaving the replaced `Mesh` in place
+        let mut rejecting = assets(small_bytes + small_bytes / 2, BudgetPolicy::Reject)?;
+        assert!(rejecting.replace_mesh("swapped", large.clone()).is_err());
+        assert_eq!(rejecting.mesh("swapped")?.triangles().len(), 100);
+
+        // Decimated to fit
+        let max_bytes = large_bytes / 2;
+        let mut decimating = assets(max_bytes, BudgetPolicy::Decimate)?;
+        let _previous = decimating.replace_mesh("swapped", large)?;
+        assert!(decimating.mesh("swapped")?.triangles().len() < 400);
+        assert!(decimating.memory_usage().total() <= max_bytes);
+        Ok(())
     }
 }
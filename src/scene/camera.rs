@@ -1,32 +1,58 @@
-use nalgebra::{Point3, RealField, Rotation3, Unit, Vector3};
+use nalgebra::{Isometry3, Point3, Rotation3, Translation3, Unit, UnitQuaternion, Vector3};
+use rand::Rng;
 
 use crate::{
     error::{GeometryError, Result},
     rt::Ray,
-    scene::Projection,
-    traits::FallibleNumeric,
+    scene::{MortonOrder, Projection},
+    serialization::{SerializedCamera, SerializedProjection},
+    traits::{FallibleNumeric, Scalar, sample_unit},
 };
 
+const RADIANS_TO_DEGREES: f64 = 180.0 / std::f64::consts::PI;
+
 /// Generates sampling `Ray`.
+///
+/// In the camera frame the x-axis is forward, the y-axis left and the z-axis up, as in a `RayBundle` sensor frame.
 #[derive(Debug, Clone)]
-pub struct Camera<T: RealField + Copy> {
+pub struct Camera<T: Scalar> {
     /// Observation position.
     position: Point3<T>,
-    /// View target.
-    look_at: Point3<T>,
+    /// Rotation of the camera frame into the world.
+    orientation: UnitQuaternion<T>,
     /// Camera projection mode
     projection: Projection<T>,
     /// Resolution of the image in pixels.
     resolution: [usize; 2],
+    /// Offset of the image [vertical, horizontal], as fractions of the view height and width.
+    lens_shift: [T; 2],
+    /// Upward pitch of a perspective view (radians), applied without rotating the camera frame.
+    tilt: T,
 }
 
-impl<T: RealField + Copy> Camera<T> {
-    /// Constructs a new `Camera`.
+impl<T: Scalar> Camera<T> {
+    /// Constructs a new `Camera` facing a target, with the world z-axis upwards in the image.
+    /// A `Camera` facing straight up or down has the world y-axis upwards in the image instead.
     ///
     /// # Errors
     ///
     /// Returns an error if either width or height in the resolution is zero.
     pub fn new(position: Point3<T>, look_at: Point3<T>, projection: Projection<T>, resolution: [usize; 2]) -> Result<Self> {
+        Self::from_pose(position, look_at_orientation(&(look_at - position)), projection, resolution)
+    }
+
+    /// Constructs a new `Camera` from a position and the rotation of the camera frame into the world,
+    /// which unlike a view target also fixes the roll of the `Camera` about its forward axis.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either width or height in the resolution is zero.
+    pub fn from_pose(
+        position: Point3<T>,
+        orientation: UnitQuaternion<T>,
+        projection: Projection<T>,
+        resolution: [usize; 2],
+    ) -> Result<Self> {
         if resolution[0] == 0 || resolution[1] == 0 {
             return Err(GeometryError::InvalidResolution {
                 width: resolution[1],
@@ -37,17 +63,114 @@ impl<T: RealField + Copy> Camera<T> {
 
         Ok(Self {
             position,
-            look_at,
+            orientation,
             projection,
             resolution,
+            lens_shift: [T::zero(); 2],
+            tilt: T::zero(),
         })
     }
 
+    /// Constructs a new `Camera` from an isometry taking the camera frame into the world.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either width or height in the resolution is zero.
+    pub fn from_isometry(pose: &Isometry3<T>, projection: Projection<T>, resolution: [usize; 2]) -> Result<Self> {
+        Self::from_pose(pose.translation.vector.into(), pose.rotation, projection, resolution)
+    }
+
+    /// Shift the image [vertical, horizontal] by fractions of the view height and width, as with a shift lens.
+    /// Positive shifts move the view up and to the right without rotating the camera,
+    /// so a level camera can frame a tall building while its verticals stay parallel.
+    #[must_use]
+    pub const fn with_lens_shift(mut self, lens_shift: [T; 2]) -> Self {
+        self.lens_shift = lens_shift;
+        self
+    }
+
+    /// Pitch a perspective view upwards by `tilt` radians, as a vertical lens shift given by angle.
+    /// Image columns are swept about the vertical axis of the untilted camera,
+    /// so the verticals of a level camera stay parallel. Orthographic views are unaffected.
+    #[must_use]
+    pub const fn with_tilt(mut self, tilt: T) -> Self {
+        self.tilt = tilt;
+        self
+    }
+
+    /// Returns the lens shift [vertical, horizontal] of the `Camera`, as fractions of the view height and width.
+    pub const fn lens_shift(&self) -> [T; 2] {
+        self.lens_shift
+    }
+
+    /// Returns the upward pitch (radians) of the perspective view of the `Camera`.
+    pub const fn tilt(&self) -> T {
+        self.tilt
+    }
+
+    /// Returns the observation position of the `Camera`.
+    pub const fn position(&self) -> &Point3<T> {
+        &self.position
+    }
+
+    /// Returns the rotation of the camera frame into the world.
+    pub const fn orientation(&self) -> &UnitQuaternion<T> {
+        &self.orientation
+    }
+
+    /// Returns the isometry taking the camera frame into the world.
+    pub fn pose(&self) -> Isometry3<T> {
+        Isometry3::from_parts(Translation3::from(self.position.coords), self.orientation)
+    }
+
+    /// Returns the projection mode of the `Camera`.
+    pub const fn projection(&self) -> &Projection<T> {
+        &self.projection
+    }
+
     /// Returns the resolution of the `Camera`.
     pub const fn resolution(&self) -> &[usize; 2] {
         &self.resolution
     }
 
+    /// Iterate over every pixel index of the `Camera` in Morton (Z-order), for spatially coherent primary `Ray`s.
+    #[must_use]
+    pub fn morton_pixels(&self) -> MortonOrder {
+        MortonOrder::new(0..self.resolution[0], 0..self.resolution[1])
+    }
+
+    /// Convert the `Camera` back into its serialized representation.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if numeric type conversion of the projection fails.
+    pub fn to_serialized(&self) -> Result<SerializedCamera<T>> {
+        // Cameras without roll are stored by their view target, which is easier to read and edit
+        let forward = self.forward();
+        let rolled = look_at_orientation(&forward).angle_to(&self.orientation) > T::default_epsilon().sqrt();
+        let coords = self.orientation.coords;
+        Ok(SerializedCamera {
+            projection: SerializedProjection::from_projection(&self.projection)?,
+            position: self.position.into(),
+            look_at: (!rolled).then(|| (self.position + forward.into_inner()).into()),
+            orientation: rolled.then(|| [coords[0], coords[1], coords[2], coords[3]]),
+            resolution: self.resolution,
+            lens_shift: (self.lens_shift != [T::zero(); 2]).then_some(self.lens_shift),
+            tilt: (self.tilt != T::zero()).then_some(self.tilt * T::try_from_f64(RADIANS_TO_DEGREES)?),
+            metadata: None,
+        })
+    }
+
+    /// Returns the unit direction the `Camera` is facing.
+    pub fn forward(&self) -> Unit<Vector3<T>> {
+        self.orientation * Vector3::x_axis()
+    }
+
+    /// Returns the unit directions [right, up] of the image plane of the `Camera`.
+    fn image_axes(&self) -> [Unit<Vector3<T>>; 2] {
+        [self.orientation * -Vector3::y_axis(), self.orientation * Vector3::z_axis()]
+    }
+
     /// Generate a `Ray` for the given pixel index.
     /// A position of [0, 0] corresponds to the top-left pixel of the image.
     ///
@@ -56,7 +179,27 @@ impl<T: RealField + Copy> Camera<T> {
     /// Returns an error if:
     /// - The pixel index is out of bounds for the camera resolution
     /// - Numeric type conversions fail during ray generation
+    /// - The camera pose or projection gives a `Ray` which is not finite
     pub fn generate_ray(&self, pixel_index: [usize; 2]) -> Result<Ray<T>> {
+        self.generate_ray_at(pixel_index, [T::zero(); 2])
+    }
+
+    /// Generate a `Ray` through a uniformly random point within the given pixel, for anti-aliasing.
+    /// Draw from a `Pcg32::for_sample` stream to render identical images from identical seeds on any number of threads.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The pixel index is out of bounds for the camera resolution
+    /// - Numeric type conversions fail during ray generation
+    /// - The camera pose or projection gives a `Ray` which is not finite
+    pub fn generate_jittered_ray<R: Rng + ?Sized>(&self, pixel_index: [usize; 2], rng: &mut R) -> Result<Ray<T>> {
+        let offset = [sample_unit::<T, R>(rng)?, sample_unit::<T, R>(rng)?];
+        self.generate_ray_at(pixel_index, offset)
+    }
+
+    /// Generate a `Ray` through the point of a pixel at an offset [row, column] from its corner, as fractions of a pixel.
+    fn generate_ray_at(&self, pixel_index: [usize; 2], offset: [T; 2]) -> Result<Ray<T>> {
         if pixel_index[0] >= self.resolution[0] || pixel_index[1] >= self.resolution[1] {
             return Err(GeometryError::PixelOutOfBounds {
                 row: pixel_index[0],
@@ -68,57 +211,54 @@ impl<T: RealField + Copy> Camera<T> {
         }
 
         match self.projection {
-            Projection::Perspective(fov) => self.generate_perspective_ray(pixel_index, fov),
-            Projection::Orthographic(width) => self.generate_ortho_ray(pixel_index, width),
+            Projection::Perspective(fov) => self.generate_perspective_ray(pixel_index, offset, fov),
+            Projection::Orthographic(width) => {
+                let aspect_ratio = T::try_from_usize(self.resolution[1])? / T::try_from_usize(self.resolution[0])?;
+                self.generate_ortho_ray(pixel_index, offset, [width / aspect_ratio, width])
+            }
+            Projection::OrthographicView(view) => self.generate_ortho_ray(pixel_index, offset, view),
         }
     }
 
     /// Generate a `Ray` using a perspective projection.
-    fn generate_perspective_ray(&self, pixel_index: [usize; 2], fov: T) -> Result<Ray<T>> {
+    fn generate_perspective_ray(&self, pixel_index: [usize; 2], offset: [T; 2], fov: T) -> Result<Ray<T>> {
         let height = T::try_from_usize(self.resolution[0])?;
         let width = T::try_from_usize(self.resolution[1])?;
 
         // Normalize to [-0.5, 0.5] range
         let half = T::try_from_f32(0.5)?;
-        let d_row = (T::try_from_usize(pixel_index[0])? / height) - half;
-        let d_col = (T::try_from_usize(pixel_index[1])? / width) - half;
+        let d_row = ((T::try_from_usize(pixel_index[0])? + offset[0]) / height) - half - self.lens_shift[0];
+        let d_col = ((T::try_from_usize(pixel_index[1])? + offset[1]) / width) - half + self.lens_shift[1];
 
         let aspect_ratio = width / height;
         let half_fov = fov * half;
 
         let d_theta = -d_col * half_fov;
-        let d_phi = -d_row * (half_fov / aspect_ratio);
+        let d_phi = -d_row * (half_fov / aspect_ratio) + self.tilt;
 
-        let forward = Unit::new_normalize(self.look_at - self.position);
-        let right = Unit::new_normalize(forward.cross(&Vector3::z()));
-        let up = Unit::new_normalize(right.cross(&forward));
+        let forward = self.forward();
+        let [right, up] = self.image_axes();
 
         let vertical_rotation = Rotation3::from_axis_angle(&right, d_phi);
         let lateral_rotation = Rotation3::from_axis_angle(&up, d_theta);
 
         let direction = lateral_rotation * vertical_rotation * forward;
-        Ok(Ray::new(self.position, direction))
+        Ray::try_new(self.position, direction)
     }
 
-    /// Generate a `Ray` using an orthographic projection.
-    fn generate_ortho_ray(&self, pixel_index: [usize; 2], width: T) -> Result<Ray<T>> {
+    /// Generate a `Ray` using an orthographic projection with the given view [height, width].
+    fn generate_ortho_ray(&self, pixel_index: [usize; 2], offset: [T; 2], [view_height, view_width]: [T; 2]) -> Result<Ray<T>> {
         let height_px = T::try_from_usize(self.resolution[0])?;
         let width_px = T::try_from_usize(self.resolution[1])?;
 
-        // Normalize to [-0.5, 0.5] range
+        // Normalize to [-0.5, 0.5] range, with u increasing to the right and v increasing upwards from the bottom row
         let half = T::try_from_f32(0.5)?;
-        let u = (T::try_from_usize(pixel_index[1])? / width_px) - half;
-        let v = (T::try_from_usize(pixel_index[0])? / height_px) - half;
-
-        // Calculate aspect ratio and viewing dimensions
-        let aspect_ratio = width_px / height_px;
-        let view_width = width;
-        let view_height = -view_width / aspect_ratio;
+        let u = ((T::try_from_usize(pixel_index[1])? + offset[1]) / width_px) - half + self.lens_shift[1];
+        let v = half - ((T::try_from_usize(pixel_index[0])? + offset[0]) / height_px) + self.lens_shift[0];
 
         // Set up coordinate system
-        let forward = Unit::new_normalize(self.look_at - self.position);
-        let right = Unit::new_normalize(forward.cross(&Vector3::z()));
-        let up = Unit::new_normalize(right.cross(&forward));
+        let forward = self.forward();
+        let [right, up] = self.image_axes();
 
         // Calculate the ray origin on the viewing plane
         let horizontal_offset = right.as_ref() * (u * view_width);
@@ -127,6 +267,76 @@ impl<T: RealField + Copy> Camera<T> {
         let ray_origin = self.position + horizontal_offset + vertical_offset;
 
         // All rays have the same direction in orthographic projection
-        Ok(Ray::new(ray_origin, forward))
+        Ray::try_new(ray_origin, forward)
+    }
+}
+
+impl<T: Scalar> Camera<T> {
+    /// Move the `Camera` to a new position, keeping its orientation.
+    pub const fn set_position(&mut self, position: Point3<T>) {
+        self.position = position;
     }
+
+    /// Rotate the `Camera` to a new orientation, keeping its position.
+    pub const fn set_orientation(&mut self, orientation: UnitQuaternion<T>) {
+        self.orientation = orientation;
+    }
+
+    /// Turn the `Camera` to face a target, with the world z-axis upwards in the image, as in `Camera::new`.
+    pub fn set_look_at(&mut self, look_at: &Point3<T>) {
+        self.orientation = look_at_orientation(&(look_at - self.position));
+    }
+
+    /// Replace the projection mode of the `Camera`.
+    pub const fn set_projection(&mut self, projection: Projection<T>) {
+        self.projection = projection;
+    }
+
+    /// Switch the `Camera` to a perspective projection with the given field of view (radians).
+    pub const fn set_fov(&mut self, fov: T) {
+        self.projection = Projection::Perspective(fov);
+    }
+
+    /// Orbit the `Camera` about a target, turning it with its position so the target stays at the same point of the image.
+    /// `yaw` (radians) turns anticlockwise about the world z-axis seen from above,
+    /// and `pitch` (radians) raises the `Camera` about its right axis.
+    pub fn orbit_around(&mut self, target: &Point3<T>, yaw: T, pitch: T) {
+        let [right, _] = self.image_axes();
+        let rotation =
+            UnitQuaternion::from_axis_angle(&Vector3::z_axis(), yaw) * UnitQuaternion::from_axis_angle(&right, -pitch);
+        self.position = target + rotation * (self.position - target);
+        self.orientation = rotation * self.orientation;
+    }
+
+    /// Move the `Camera` within its image plane, by distances to the right and upwards.
+    pub fn pan(&mut self, right: T, up: T) {
+        let [right_axis, up_axis] = self.image_axes();
+        self.position += right_axis.scale(right) + up_axis.scale(up);
+    }
+
+    /// Move the `Camera` forwards by a distance, or backwards if it is negative.
+    pub fn dolly(&mut self, distance: T) {
+        self.position += self.forward().scale(distance);
+    }
+
+    /// Narrow the field of view or orthographic view by a factor, so factors greater than one zoom in.
+    pub fn zoom(&mut self, factor: T) {
+        self.projection = match self.projection {
+            Projection::Perspective(fov) => Projection::Perspective(fov / factor),
+            Projection::Orthographic(width) => Projection::Orthographic(width / factor),
+            Projection::OrthographicView(view) => Projection::OrthographicView(view.map(|extent| extent / factor)),
+        };
+    }
+}
+
+// == Utility functions ==
+
+/// Rotation of a camera frame facing along `forward` with the world z-axis upwards in the image,
+/// or the world y-axis when facing straight up or down.
+fn look_at_orientation<T: Scalar>(forward: &Vector3<T>) -> UnitQuaternion<T> {
+    let forward = Unit::new_normalize(*forward);
+    let right = Unit::try_new(forward.cross(&Vector3::z()), T::default_epsilon())
+        .unwrap_or_else(|| Unit::new_normalize(forward.cross(&Vector3::y())));
+    let up = right.cross(&forward);
+    UnitQuaternion::from_rotation_matrix(&Rotation3::from_basis_unchecked(&[*forward, -*right, up]))
 }
@@ -1,18 +1,19 @@
 //! Mesh instance structure.
 
-use nalgebra::{Matrix3, Matrix4, RealField, Unit};
-use num_traits::ToPrimitive;
+use nalgebra::{Matrix3, Matrix4, Point3, Unit, Vector3};
+use rand::Rng;
+use std::sync::{Arc, OnceLock};
 
 use crate::{
-    error::{Result, TransformationError},
-    geometry::{Aabb, Mesh},
+    error::{GeometryError, Result, TransformationError},
+    geometry::{Aabb, AlphaMask, Mesh},
     rt::{Hit, Ray},
-    traits::{Bounded, Traceable},
+    traits::{Bounded, FallibleNumeric, Scalar, SurfaceSample, Traceable, sample_unit, uniform_scale},
 };
 
 /// `Mesh` instance allowing for transformations without copying the original data.
 #[derive(Debug)]
-pub struct Instance<'a, T: RealField + Copy> {
+pub struct Instance<'a, T: Scalar> {
     /// Reference to `Mesh` data.
     mesh: &'a Mesh<T>,
     /// World-to-object transformation matrix.
@@ -23,9 +24,13 @@ pub struct Instance<'a, T: RealField + Copy> {
     world_aabb: Aabb<T>,
     /// Pre-computed normal transformation matrix (inverse transpose of upper 3x3)
     normal_transform: Matrix3<T>,
+    /// Cumulative world-space `Triangle` areas, for area-weighted sampling. Computed on first use.
+    area_cdf: OnceLock<Vec<T>>,
+    /// Opacity texture applied over the `Mesh`, through which `Ray`s pass where transparent.
+    alpha_mask: Option<Arc<AlphaMask<T>>>,
 }
 
-impl<'a, T: RealField + Copy + ToPrimitive> Instance<'a, T> {
+impl<'a, T: Scalar> Instance<'a, T> {
     /// Construct a new `Mesh` instance.
     ///
     /// # Errors
@@ -37,7 +42,7 @@ impl<'a, T: RealField + Copy + ToPrimitive> Instance<'a, T> {
         let world_to_object = transform.try_inverse().ok_or(TransformationError::NonInvertibleMatrix)?;
 
         let object_to_world = transform;
-        let world_aabb = mesh.aabb()?.transform(&object_to_world)?;
+        let world_aabb = mesh.aabb()?.try_finite()?.transform(&object_to_world)?;
 
         let upper_3x3 = world_to_object.fixed_view::<3, 3>(0, 0);
         let normal_transform = upper_3x3.transpose();
@@ -48,31 +53,61 @@ impl<'a, T: RealField + Copy + ToPrimitive> Instance<'a, T> {
             object_to_world,
             world_aabb,
             normal_transform,
+            area_cdf: OnceLock::new(),
+            alpha_mask: None,
         })
     }
 
+    /// Set an `AlphaMask` cutting holes out of this instance of the `Mesh`, looked up by the texture coordinates of each `Hit`,
+    /// so instances of one card can carry different cutouts. `Triangle`s without texture coordinates stay opaque.
+    /// The mask is tested during traversal, so `Ray`s pass through transparent texels to the surfaces behind.
+    #[must_use]
+    pub fn with_alpha_mask(mut self, alpha_mask: Arc<AlphaMask<T>>) -> Self {
+        self.alpha_mask = Some(alpha_mask);
+        self
+    }
+
+    /// Get the `AlphaMask` of this instance, if any.
+    pub const fn alpha_mask(&self) -> Option<&Arc<AlphaMask<T>>> {
+        self.alpha_mask.as_ref()
+    }
+
     /// Get a reference to the underlying `Mesh`.
     pub const fn mesh(&self) -> &Mesh<T> {
         self.mesh
     }
 
+    /// Get the object-to-world transformation matrix.
+    pub const fn transform(&self) -> &Matrix4<T> {
+        &self.object_to_world
+    }
+
     /// Get the world-space `Aabb`.
     pub const fn world_aabb(&self) -> &Aabb<T> {
         &self.world_aabb
     }
 
-    /// Transform a `Ray` from world space to object space.
-    fn transform_ray_to_object_space(&self, ray: &Ray<T>) -> Ray<T> {
-        // Transform origin using the built-in transform_point method
-        let object_origin = self.world_to_object.transform_point(&ray.origin);
-
-        // Transform direction as a vector (homogeneous coordinate w=0)
-        let object_direction_vector = self.world_to_object.transform_vector(&ray.direction);
+    /// Check if the `AlphaMask`, if any, is opaque at an object-space `Hit`.
+    fn is_opaque(&self, hit: &Hit<T>) -> Result<bool> {
+        match (&self.alpha_mask, hit.uv) {
+            (Some(alpha_mask), Some(uv)) => alpha_mask.is_opaque(uv),
+            _ => Ok(true),
+        }
+    }
 
-        // Normalize the direction
-        let object_direction = Unit::new_normalize(object_direction_vector);
+    /// Find the nearest object-space `Hit` of an object-space `Ray`, passing through transparent parts of the `AlphaMask`.
+    fn intersect_object(&self, object_ray: &Ray<T>, object_max_distance: T) -> Result<Option<Hit<T>>> {
+        if self.alpha_mask.is_some() {
+            return self
+                .mesh
+                .intersect_filtered(object_ray, object_max_distance, |hit| self.is_opaque(hit));
+        }
+        self.mesh.intersect_within(object_ray, object_max_distance)
+    }
 
-        Ray::new(object_origin, object_direction)
+    /// Transform a `Ray` from world space to object space.
+    fn transform_ray_to_object_space(&self, ray: &Ray<T>) -> Ray<T> {
+        ray.transformed(&self.world_to_object)
     }
 
     /// Transform a `Hit` from object space to world space.
@@ -85,24 +120,49 @@ impl<'a, T: RealField + Copy + ToPrimitive> Instance<'a, T> {
         let world_interpolated_normal_vector = self.normal_transform * hit.interpolated_normal.as_ref();
         hit.interpolated_normal = Unit::new_normalize(world_interpolated_normal_vector);
 
-        // Transform the distance from object space to world space. The hit distance is along the object-space ray, but we need it along the world-space ray
+        hit.distance = self.transform_distance_to_world_space(hit.distance, world_ray, object_ray);
+    }
+
+    /// Transform a distance along the object-space `Ray` to a distance along the world-space `Ray`.
+    fn transform_distance_to_world_space(&self, distance: T, world_ray: &Ray<T>, object_ray: &Ray<T>) -> T {
         // Calculate the actual world-space intersection point
-        let object_hit_point = object_ray.origin + object_ray.direction.scale(hit.distance);
+        let object_hit_point = object_ray.origin + object_ray.direction.scale(distance);
         let world_hit_point = self.object_to_world.transform_point(&object_hit_point);
 
         // Calculate the distance along the world ray to reach this point
         let to_hit = world_hit_point - world_ray.origin;
-        hit.distance = to_hit.dot(&world_ray.direction);
+        to_hit.dot(&world_ray.direction)
+    }
+
+    /// Get the cumulative world-space `Triangle` areas, computing them on first use.
+    fn area_cdf(&self) -> Result<&[T]> {
+        if let Some(cdf) = self.area_cdf.get() {
+            return Ok(cdf);
+        }
+        let linear = self.object_to_world.fixed_view::<3, 3>(0, 0);
+        let two = T::try_from_u8(2)?;
+        let mut total = T::zero();
+        let cdf = self
+            .mesh
+            .triangles()
+            .iter()
+            .map(|triangle| {
+                let [edge1, edge2] = triangle.edges();
+                total += (linear * edge1).cross(&(linear * edge2)).norm() / two;
+                total
+            })
+            .collect();
+        Ok(self.area_cdf.get_or_init(|| cdf))
     }
 }
 
-impl<T: RealField + Copy + ToPrimitive> Traceable<T> for Instance<'_, T> {
+impl<T: Scalar> Traceable<T> for Instance<'_, T> {
     fn intersect(&self, ray: &Ray<T>) -> Result<Option<Hit<T>>> {
         // Transform ray to object space
         let object_ray = self.transform_ray_to_object_space(ray);
 
         // Intersect with the mesh in object space
-        (self.mesh.intersect(&object_ray)?).map_or(Ok(None), |mut hit| {
+        (self.intersect_object(&object_ray, T::try_max_value()?)?).map_or(Ok(None), |mut hit| {
             // Transform hit back to world space
             self.transform_hit_to_world_space(&mut hit, ray, &object_ray);
             // The object_index from the mesh is the triangle index within the mesh
@@ -111,6 +171,31 @@ impl<T: RealField + Copy + ToPrimitive> Traceable<T> for Instance<'_, T> {
         })
     }
 
+    fn intersect_t(&self, ray: &Ray<T>) -> Result<Option<T>> {
+        let object_ray = self.transform_ray_to_object_space(ray);
+        let distance = if self.alpha_mask.is_some() {
+            self.intersect_object(&object_ray, T::try_max_value()?)?
+                .map(|hit| hit.distance)
+        } else {
+            self.mesh.intersect_t(&object_ray)?
+        };
+        Ok(distance.map(|distance| self.transform_distance_to_world_space(distance, ray, &object_ray)))
+    }
+
+    fn intersect_within(&self, ray: &Ray<T>, max_distance: T) -> Result<Option<Hit<T>>> {
+        let object_ray = self.transform_ray_to_object_space(ray);
+
+        // Distances along the `Ray` scale uniformly under the affine transform
+        let object_max_distance = max_distance * self.world_to_object.transform_vector(&ray.direction).norm();
+        Ok(self
+            .intersect_object(&object_ray, object_max_distance)?
+            .map(|mut hit| {
+                self.transform_hit_to_world_space(&mut hit, ray, &object_ray);
+                hit
+            })
+            .filter(|hit| hit.distance < max_distance))
+    }
+
     fn intersect_any(&self, ray: &Ray<T>, max_distance: T) -> Result<bool> {
         // Transform ray to object space
         let object_ray = self.transform_ray_to_object_space(ray);
@@ -120,6 +205,48 @@ impl<T: RealField + Copy + ToPrimitive> Traceable<T> for Instance<'_, T> {
         let world_endpoint = ray.origin + ray.direction.scale(max_distance);
         let object_endpoint = self.world_to_object.transform_point(&world_endpoint);
         let object_max_distance = (object_endpoint - object_ray.origin).norm();
+        if self.alpha_mask.is_some() {
+            return self
+                .mesh
+                .intersect_any_filtered(&object_ray, object_max_distance, |hit| self.is_opaque(hit));
+        }
         self.mesh.intersect_any(&object_ray, object_max_distance)
     }
+
+    /// Sweep a sphere against the instanced `Mesh`, which must not be scaled non-uniformly or sheared,
+    /// as the sphere would no longer be a sphere in object space. Any `AlphaMask` is ignored, so the sphere collides with the whole surface.
+    fn sphere_cast(&self, ray: &Ray<T>, radius: T) -> Result<Option<Hit<T>>> {
+        let object_ray = self.transform_ray_to_object_space(ray);
+        let object_radius = radius * uniform_scale(&self.world_to_object)?;
+
+        (self.mesh.sphere_cast(&object_ray, object_radius)?).map_or(Ok(None), |mut hit| {
+            self.transform_hit_to_world_space(&mut hit, ray, &object_ray);
+            Ok(Some(hit))
+        })
+    }
+}
+
+impl<T: Scalar> SurfaceSample<T> for Instance<'_, T> {
+    fn area(&self) -> Result<T> {
+        Ok(self.area_cdf()?.last().copied().unwrap_or_else(T::zero))
+    }
+
+    /// Sample a point uniformly over the world-space surface by choosing a `Triangle` in proportion to its
+    /// transformed area. Uniform points on a `Triangle` remain uniform under an affine transformation.
+    fn sample_point<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<(Point3<T>, Unit<Vector3<T>>, T)> {
+        let cdf = self.area_cdf()?;
+        let area = cdf.last().copied().unwrap_or_else(T::zero);
+        if area <= T::zero() {
+            return Err(GeometryError::ZeroArea.into());
+        }
+
+        let target = sample_unit::<T, R>(rng)? * area;
+        let index = cdf.partition_point(|&cumulative| cumulative <= target).min(cdf.len() - 1);
+        let (point, normal, _) = self.mesh.triangles()[index].sample_point(rng)?;
+        Ok((
+            self.object_to_world.transform_point(&point),
+            Unit::new_normalize(self.normal_transform * normal.into_inner()),
+            area.recip(),
+        ))
+    }
 }
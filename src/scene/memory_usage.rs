@@ -0,0 +1,96 @@
+//! Memory usage reporting and budgets.
+
+use serde::{Deserialize, Serialize};
+use std::{
+    iter::Sum,
+    ops::{Add, AddAssign},
+};
+
+/// Approximate number of bytes held by a structure, broken down by the kind of data.
+///
+/// Only the inline size of each element is counted; spare `Vec` capacity and allocator overhead are not.
+/// Data shared between clones, such as the `Triangle`s of a `Mesh`, is counted by every clone that reports it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemoryUsage {
+    /// Bytes of `Triangle` and other primitive geometry.
+    pub triangle_bytes: usize,
+    /// Bytes of `Bvh` nodes.
+    pub node_bytes: usize,
+    /// Bytes of `Bvh` shape indices.
+    pub index_bytes: usize,
+    /// Bytes of lazily built traversal and sampling caches, and other bookkeeping.
+    pub other_bytes: usize,
+}
+
+impl MemoryUsage {
+    /// Get the total number of bytes.
+    #[must_use]
+    pub const fn total(&self) -> usize {
+        self.triangle_bytes + self.node_bytes + self.index_bytes + self.other_bytes
+    }
+}
+
+impl Add for MemoryUsage {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self {
+            triangle_bytes: self.triangle_bytes + rhs.triangle_bytes,
+            node_bytes: self.node_bytes + rhs.node_bytes,
+            index_bytes: self.index_bytes + rhs.index_bytes,
+            other_bytes: self.other_bytes + rhs.other_bytes,
+        }
+    }
+}
+
+impl AddAssign for MemoryUsage {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sum for MemoryUsage {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), Add::add)
+    }
+}
+
+/// Action taken when a `Mesh` added to, or replaced in, `Assets` would exceed the `MemoryBudget`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BudgetPolicy {
+    /// Refuse the `Mesh` with an error.
+    #[default]
+    Reject,
+    /// Simplify the `Mesh` by vertex clustering until it fits, refusing it if no simplification does.
+    Decimate,
+}
+
+/// Soft limit on the total `MemoryUsage` of `Assets`, checked as each `Mesh` is added or replaced.
+///
+/// The budget is soft: caches built lazily after loading, such as the traversal copies of a `Mesh`, may exceed it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemoryBudget {
+    /// Maximum total number of bytes.
+    pub max_bytes: usize,
+    /// Action taken when a `Mesh` does not fit.
+    #[serde(default)]
+    pub policy: BudgetPolicy,
+}
+
+impl MemoryBudget {
+    /// Construct a new `MemoryBudget` which rejects `Mesh`es that do not fit.
+    #[must_use]
+    pub const fn new(max_bytes: usize) -> Self {
+        Self {
+            max_bytes,
+            policy: BudgetPolicy::Reject,
+        }
+    }
+
+    /// Set the action taken when a `Mesh` does not fit.
+    #[must_use]
+    pub const fn with_policy(mut self, policy: BudgetPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+}
@@ -0,0 +1,150 @@
+//! Content-addressed cache of loaded `Mesh`es.
+
+use std::{
+    collections::{HashMap, hash_map::DefaultHasher},
+    hash::{Hash, Hasher},
+    path::Path,
+    str::FromStr,
+};
+
+use crate::{
+    bvh::BvhConfig,
+    error::Result,
+    geometry::{Mesh, MeshConfig},
+    traits::{AssetSource, Scalar},
+};
+
+/// Cache of parsed `Mesh`es and their built `Bvh`s, keyed by a hash of the file contents and configuration.
+///
+/// The contents and configuration are compared in full on a hit, so colliding hashes never share a `Mesh`.
+/// Sharing one cache across several `Assets` loads avoids re-parsing and rebuilding identical mesh files.
+#[derive(Debug, Clone)]
+pub struct MeshCache<T: Scalar> {
+    /// Cached `Mesh`es, bucketed by the hash of their contents and configuration.
+    meshes: HashMap<u64, Vec<CachedMesh<T>>>,
+}
+
+/// `Mesh` held by a `MeshCache`, with the file contents and configuration it was built from.
+#[derive(Debug, Clone)]
+struct CachedMesh<T: Scalar> {
+    /// Wavefront (.obj) file contents.
+    contents: String,
+    /// Configuration of the `Bvh`.
+    bvh_config: BvhConfig<T>,
+    /// Configuration of the `Triangle`s.
+    mesh_config: MeshConfig,
+    /// Parsed `Mesh`.
+    mesh: Mesh<T>,
+}
+
+impl<T: Scalar> CachedMesh<T> {
+    /// Check if this entry was built from the given contents and configuration.
+    fn matches(&self, contents: &str, bvh_config: &BvhConfig<T>, mesh_config: MeshConfig) -> bool {
+        self.contents == contents
+            && self.mesh_config == mesh_config
+            && self.bvh_config.traverse_cost == bvh_config.traverse_cost
+            && self.bvh_config.intersect_cost == bvh_config.intersect_cost
+            && self.bvh_config.sah_buckets == bvh_config.sah_buckets
+            && self.bvh_config.max_shapes_per_node == bvh_config.max_shapes_per_node
+            && self.bvh_config.max_depth == bvh_config.max_depth
+            && self.bvh_config.split_method == bvh_config.split_method
+            && self.bvh_config.collapse_leaves == bvh_config.collapse_leaves
+    }
+}
+
+impl<T: Scalar> Default for MeshCache<T> {
+    fn default() -> Self {
+        Self { meshes: HashMap::new() }
+    }
+}
+
+impl<T: Scalar + FromStr> MeshCache<T> {
+    /// Construct a new empty `MeshCache`.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the number of cached `Mesh`es.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.meshes.values().map(Vec::len).sum()
+    }
+
+    /// Check if the cache is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.meshes.is_empty()
+    }
+
+    /// Remove all cached `Mesh`es.
+    pub fn clear(&mut self) {
+        self.meshes.clear();
+    }
+
+    /// Get the `Mesh` for a wavefront (.obj) file read from the given `AssetSource`, prepared with the default `MeshConfig`.
+    /// The file is only parsed, and its `Bvh` built, if identical contents have not been loaded with the same configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The file cannot be read from the source
+    /// - The file contents are not a valid wavefront (.obj) mesh
+    /// - BVH construction fails
+    pub fn load<S: AssetSource + ?Sized>(&mut self, bvh_config: &BvhConfig<T>, source: &S, path: &Path) -> Result<&Mesh<T>> {
+        self.load_with_config(bvh_config, MeshConfig::default(), source, path)
+    }
+
+    /// Get the `Mesh` for a wavefront (.obj) file read from the given `AssetSource`, prepared with the given `MeshConfig`.
+    /// The file is only parsed, and its `Bvh` built, if identical contents have not been loaded with the same configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The file cannot be read from the source
+    /// - The file contents are not a valid wavefront (.obj) mesh
+    /// - BVH construction fails
+    pub fn load_with_config<S: AssetSource + ?Sized>(
+        &mut self,
+        bvh_config: &BvhConfig<T>,
+        mesh_config: MeshConfig,
+        source: &S,
+        path: &Path,
+    ) -> Result<&Mesh<T>> {
+        let contents = source.read_to_string(path)?;
+
+        let mut hasher = DefaultHasher::new();
+        contents.hash(&mut hasher);
+        hash_bvh_config(bvh_config, &mut hasher);
+        mesh_config.hash(&mut hasher);
+        let bucket = self.meshes.entry(hasher.finish()).or_default();
+
+        let index = if let Some(index) = bucket
+            .iter()
+            .position(|cached| cached.matches(&contents, bvh_config, mesh_config))
+        {
+            index
+        } else {
+            let mesh = Mesh::from_wavefront_with_config(bvh_config, mesh_config, &contents)?;
+            bucket.push(CachedMesh {
+                contents,
+                bvh_config: bvh_config.clone(),
+                mesh_config,
+                mesh,
+            });
+            bucket.len() - 1
+        };
+        Ok(&bucket[index].mesh)
+    }
+}
+
+/// Feed the fields of a `BvhConfig` to a `Hasher`, hashing the costs by their bit patterns.
+fn hash_bvh_config<T: Scalar, H: Hasher>(bvh_config: &BvhConfig<T>, state: &mut H) {
+    bvh_config.traverse_cost.to_f64().map(f64::to_bits).hash(state);
+    bvh_config.intersect_cost.to_f64().map(f64::to_bits).hash(state);
+    bvh_config.sah_buckets.hash(state);
+    bvh_config.max_shapes_per_node.hash(state);
+    bvh_config.max_depth.hash(state);
+    bvh_config.split_method.hash(state);
+    bvh_config.collapse_leaves.hash(state);
+}
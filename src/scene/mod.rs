@@ -1,15 +1,23 @@
 mod assets;
 mod camera;
 mod instance;
+mod memory_usage;
+mod mesh_cache;
+mod morton_order;
 mod projection;
 mod scene;
 mod scene_builder;
 mod scene_object;
+mod surface_point;
 
 pub use assets::Assets;
 pub use camera::Camera;
 pub use instance::Instance;
+pub use memory_usage::{BudgetPolicy, MemoryBudget, MemoryUsage};
+pub use mesh_cache::MeshCache;
+pub use morton_order::MortonOrder;
 pub use projection::Projection;
 pub use scene::Scene;
 pub use scene_builder::SceneBuilder;
 pub use scene_object::SceneObject;
+pub use surface_point::SurfacePoint;
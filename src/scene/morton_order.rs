@@ -0,0 +1,67 @@
+//! Morton-ordered pixel traversal.
+
+use std::ops::Range;
+
+/// Iterator over the pixels [row, col] of a rectangular region in Morton (Z-order).
+///
+/// Successive pixels stay close together on screen, so their primary `Ray`s visit the same `Bvh` nodes
+/// and `Triangle`s while they are still in cache.
+/// The region need not be square, or a power of two in size, so it can be used to order the pixels of a single tile.
+#[derive(Debug, Clone)]
+pub struct MortonOrder {
+    /// Exclusive end row of the region.
+    row_end: usize,
+    /// Exclusive end column of the region.
+    col_end: usize,
+    /// Square blocks [row, col, size] still to be visited, with the next block last.
+    blocks: Vec<[usize; 3]>,
+    /// Number of pixels still to be yielded.
+    remaining: usize,
+}
+
+impl MortonOrder {
+    /// Construct a new `MortonOrder` over the given ranges of rows and columns.
+    #[must_use]
+    pub fn new(rows: Range<usize>, cols: Range<usize>) -> Self {
+        let remaining = rows.len() * cols.len();
+        let blocks = if remaining == 0 {
+            Vec::new()
+        } else {
+            vec![[rows.start, cols.start, rows.len().max(cols.len()).next_power_of_two()]]
+        };
+        Self {
+            row_end: rows.end,
+            col_end: cols.end,
+            blocks,
+            remaining,
+        }
+    }
+}
+
+impl Iterator for MortonOrder {
+    type Item = [usize; 2];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some([row, col, size]) = self.blocks.pop() {
+            if size == 1 {
+                self.remaining -= 1;
+                return Some([row, col]);
+            }
+
+            // Push the quadrants in reverse Z-order, skipping any which lie wholly outside the region
+            let half = size / 2;
+            for [quadrant_row, quadrant_col] in [[row + half, col + half], [row + half, col], [row, col + half], [row, col]] {
+                if quadrant_row < self.row_end && quadrant_col < self.col_end {
+                    self.blocks.push([quadrant_row, quadrant_col, half]);
+                }
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl ExactSizeIterator for MortonOrder {}
@@ -1,10 +1,12 @@
-use nalgebra::RealField;
+use crate::traits::Scalar;
 
 /// Camera type enumeration for different projection types.
 #[derive(Debug, Clone)]
-pub enum Projection<T: RealField + Copy> {
+pub enum Projection<T: Scalar> {
     /// Perspective projection with a field of view.
     Perspective(T),
-    /// Orthographic projection with a specified width.
+    /// Orthographic projection with a specified width. The height follows from the aspect ratio of the resolution.
     Orthographic(T),
+    /// Orthographic projection with a specified view [height, width], independent of the resolution.
+    OrthographicView([T; 2]),
 }
@@ -1,29 +1,52 @@
 //! Scene structure for `Ray` tracing.
 
-use nalgebra::RealField;
-use num_traits::ToPrimitive;
-use std::borrow::Cow;
+use nalgebra::{Point3, Unit, Vector3};
+use rand::Rng;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+use std::{
+    any::Any,
+    sync::{Arc, OnceLock},
+};
 
+#[cfg(feature = "rayon")]
+use crate::rt::RayOrdering;
 use crate::{
     bvh::{Bvh, BvhConfig},
-    error::{Result, SceneError},
-    geometry::Aabb,
+    config::{DEFAULT_RAY_BIAS_SCALE, MAX_MEDIUM_CROSSINGS},
+    error::{BvhError, GeometryError, Result, SceneError},
+    media::Medium,
     rt::{Hit, Ray},
-    scene::{SceneBuilder, SceneObject},
-    traits::{Bounded, Traceable},
+    scene::{Assets, MemoryUsage, SceneBuilder, SceneObject, SurfacePoint},
+    serialization::{SerializedScene, SerializedSceneObject},
+    traits::{Bounded, Bounds, FallibleNumeric, Scalar, SurfaceSample, Traceable, sample_unit},
 };
 
 /// Scene containing multiple `Traceable` objects.
 #[derive(Debug)]
-pub struct Scene<'a, T: RealField + Copy + ToPrimitive> {
+pub struct Scene<'a, T: Scalar> {
     /// Collection of `Traceable` objects in the scene.
     objects: Vec<SceneObject<'a, T>>,
-    /// `Bvh` acceleration structure for the scene.
-    bvh: Bvh<T>,
+    /// `Bvh` acceleration structure over the bounded `SceneObject`s, if there are any.
+    /// Shared, so later `Scene`s over the same objects can reuse it without a copy.
+    bvh: Option<Arc<Bvh<T>>>,
+    /// Indices of the unbounded `SceneObject`s, such as `Plane`s, which are tested outside the `Bvh`.
+    unbounded: Vec<usize>,
+    /// Distance skipped at each end of shadow and secondary `Ray`s, to avoid self-intersection.
+    ray_bias: T,
+    /// Cumulative surface areas of the `SceneObject`s, for area-weighted sampling. Computed on first use.
+    area_cdf: OnceLock<Vec<T>>,
+    /// Participating `Medium` filling the space of the `Scene`, if any.
+    medium: Option<Medium<T>>,
+    /// Participating `Medium`s filling the interiors of closed `SceneObject`s, by object index.
+    object_media: Vec<(usize, Medium<T>)>,
+    /// User data attached to the `SceneObject`s, indexed as `objects`. Only as long as the last object with a payload.
+    payloads: Vec<Option<Box<dyn Any + Send + Sync>>>,
 }
 
-impl<'a, T: RealField + Copy + ToPrimitive> Scene<'a, T> {
+impl<'a, T: Scalar> Scene<'a, T> {
     /// Construct a new `Scene` instance.
+    /// Unbounded objects, such as `Plane`s, are kept out of the `Bvh` and tested against every `Ray` directly.
     ///
     /// # Errors
     ///
@@ -35,8 +58,185 @@ impl<'a, T: RealField + Copy + ToPrimitive> Scene<'a, T> {
             return Err(SceneError::EmptyScene.into());
         }
 
-        let bvh = Bvh::new(config, &objects)?;
-        Ok(Self { objects, bvh })
+        let unbounded = unbounded_indices(&objects)?;
+        let bvh = if unbounded.len() < objects.len() {
+            Some(Bvh::new(config, &objects)?)
+        } else {
+            None
+        };
+        let ray_bias = default_ray_bias(bvh.as_ref())?;
+        Ok(Self {
+            objects,
+            bvh: bvh.map(Arc::new),
+            unbounded,
+            ray_bias,
+            area_cdf: OnceLock::new(),
+            medium: None,
+            object_media: Vec::new(),
+            payloads: Vec::new(),
+        })
+    }
+
+    /// Construct a `Scene` from objects and a previously built `Bvh`, skipping `Bvh` construction.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The objects vector is empty
+    /// - The `Bvh` does not correctly bound the given objects
+    pub fn with_bvh(objects: Vec<SceneObject<'a, T>>, bvh: Bvh<T>) -> Result<Self> {
+        if objects.is_empty() {
+            return Err(SceneError::EmptyScene.into());
+        }
+        if !bvh.fits(&objects)? {
+            return Err(BvhError::MismatchedGeometry.into());
+        }
+        Self::from_parts(objects, Arc::new(bvh))
+    }
+
+    /// Construct a new `Scene` instance as in `new`, reusing a previously built `Bvh` if it fits the objects.
+    /// Otherwise a new `Bvh` is built, so a stale or missing cache entry only costs the build it would have saved.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The objects vector is empty
+    /// - BVH construction fails for the scene objects
+    pub fn with_cached_bvh(config: &BvhConfig<T>, objects: Vec<SceneObject<'a, T>>, bvh: Option<Bvh<T>>) -> Result<Self> {
+        Self::with_shared_bvh(config, objects, bvh.map(Arc::new))
+    }
+
+    /// Construct a new `Scene` instance as in `with_cached_bvh`, sharing the `Bvh` rather than taking it,
+    /// so that it can be offered again to later `Scene`s without a copy. See `shared_bvh`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The objects vector is empty
+    /// - BVH construction fails for the scene objects
+    pub fn with_shared_bvh(config: &BvhConfig<T>, objects: Vec<SceneObject<'a, T>>, bvh: Option<Arc<Bvh<T>>>) -> Result<Self> {
+        match bvh {
+            Some(bvh) if !objects.is_empty() && bvh.fits(&objects)? => Self::from_parts(objects, bvh),
+            _ => Self::new(config, objects),
+        }
+    }
+
+    /// Assemble a `Scene` from non-empty objects and a `Bvh` which fits them.
+    fn from_parts(objects: Vec<SceneObject<'a, T>>, bvh: Arc<Bvh<T>>) -> Result<Self> {
+        let unbounded = unbounded_indices(&objects)?;
+        let ray_bias = default_ray_bias(Some(&bvh))?;
+        Ok(Self {
+            objects,
+            bvh: Some(bvh),
+            unbounded,
+            ray_bias,
+            area_cdf: OnceLock::new(),
+            medium: None,
+            object_media: Vec::new(),
+            payloads: Vec::new(),
+        })
+    }
+
+    /// Set the distance skipped at each end of shadow and secondary `Ray`s.
+    /// Defaults to a small fraction of the `Scene` size; raise it for low precision or large, distant geometry.
+    #[must_use]
+    pub const fn with_ray_bias(mut self, ray_bias: T) -> Self {
+        self.ray_bias = ray_bias;
+        self
+    }
+
+    /// Get the distance skipped at each end of shadow and secondary `Ray`s.
+    #[must_use]
+    pub const fn ray_bias(&self) -> T {
+        self.ray_bias
+    }
+
+    /// Fill the space of the `Scene` with a participating `Medium`, replacing any set before.
+    #[must_use]
+    pub fn with_medium(mut self, medium: Medium<T>) -> Self {
+        self.medium = Some(medium);
+        self
+    }
+
+    /// Fill the interior of a closed `SceneObject` with a participating `Medium`, replacing any attached to it before.
+    /// Its surface then only bounds the `Medium`, and is passed through by `surface_distance` and the `Medium` queries.
+    /// Inside is told apart from outside by the direction in which a `Ray` crosses the surface,
+    /// so the object must enclose a volume with outward facing normals. A `Plane` encloses the half-space behind it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The object index is out of bounds
+    /// - The object is a `Triangle`, which encloses no volume
+    pub fn with_object_medium(mut self, object_id: usize, medium: Medium<T>) -> Result<Self> {
+        match self.objects.get(object_id) {
+            None => {
+                return Err(SceneError::MediumIndexOutOfBounds {
+                    index: object_id,
+                    count: self.objects.len(),
+                }
+                .into());
+            }
+            Some(SceneObject::Triangle(_)) => return Err(SceneError::OpenMediumBoundary { index: object_id }.into()),
+            Some(_) => {}
+        }
+        self.object_media.retain(|(id, _)| *id != object_id);
+        self.object_media.push((object_id, medium));
+        Ok(self)
+    }
+
+    /// Get the participating `Medium` filling the space of the `Scene`, if any.
+    #[must_use]
+    pub const fn medium(&self) -> Option<&Medium<T>> {
+        self.medium.as_ref()
+    }
+
+    /// Get the participating `Medium` filling the interior of a `SceneObject`, if any.
+    #[must_use]
+    pub fn object_medium(&self, object_id: usize) -> Option<&Medium<T>> {
+        self.object_media
+            .iter()
+            .find(|(id, _)| *id == object_id)
+            .map(|(_, medium)| medium)
+    }
+
+    /// Attach user data, such as a game entity or simulation cell handle, to a `SceneObject`,
+    /// replacing any attached before. Retrieve it with `payload` from an object index reported by `intersect_object`,
+    /// or alongside the closest `Hit` with `intersect_payload`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the object index is out of bounds.
+    pub fn with_payload<P: Any + Send + Sync>(self, object_id: usize, payload: P) -> Result<Self> {
+        self.with_boxed_payload(object_id, Box::new(payload))
+    }
+
+    /// Attach already boxed user data to a `SceneObject`, as with `with_payload`.
+    /// The payload is retrieved by the type it held before boxing.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the object index is out of bounds.
+    pub fn with_boxed_payload(mut self, object_id: usize, payload: Box<dyn Any + Send + Sync>) -> Result<Self> {
+        if object_id >= self.objects.len() {
+            return Err(SceneError::PayloadIndexOutOfBounds {
+                index: object_id,
+                count: self.objects.len(),
+            }
+            .into());
+        }
+        if self.payloads.len() <= object_id {
+            self.payloads.resize_with(object_id + 1, || None);
+        }
+        self.payloads[object_id] = Some(payload);
+        Ok(self)
+    }
+
+    /// Get the user data attached to a `SceneObject`, if any is attached and it is of type `P`.
+    /// Object indices are reported by `intersect_object`, `sample_surface` and the render passes.
+    #[must_use]
+    pub fn payload<P: Any>(&self, object_id: usize) -> Option<&P> {
+        self.payloads.get(object_id)?.as_deref()?.downcast_ref()
     }
 
     /// Return a builder for constructing a `Scene`.
@@ -44,22 +244,646 @@ impl<'a, T: RealField + Copy + ToPrimitive> Scene<'a, T> {
     pub fn builder() -> SceneBuilder<'a, T> {
         SceneBuilder::default()
     }
+
+    /// Get a reference to the `SceneObject`s in this `Scene`.
+    #[must_use]
+    pub fn objects(&self) -> &[SceneObject<'a, T>] {
+        &self.objects
+    }
+
+    /// Get a reference to the `Bvh` acceleration structure, or `None` if every `SceneObject` is unbounded.
+    /// Unbounded `SceneObject`s are not referenced by the `Bvh`.
+    #[must_use]
+    pub fn bvh(&self) -> Option<&Bvh<T>> {
+        self.bvh.as_deref()
+    }
+
+    /// Get the shared `Bvh` acceleration structure, to offer to `with_shared_bvh` when the `Scene` is rebuilt.
+    #[must_use]
+    pub const fn shared_bvh(&self) -> Option<&Arc<Bvh<T>>> {
+        self.bvh.as_ref()
+    }
+
+    /// Get the indices of the unbounded `SceneObject`s, such as `Plane`s, which are tested outside the `Bvh`.
+    #[must_use]
+    pub fn unbounded(&self) -> &[usize] {
+        &self.unbounded
+    }
+
+    /// Get the number of bytes held by the `Scene`: its objects, its `Bvh`, and the `Mesh`es it owns.
+    /// The `Mesh`es referenced by `Instance`s belong to the `Assets`, so are not counted,
+    /// nor is any heap data behind custom `Geometry`, `Medium`s or payloads.
+    #[must_use]
+    pub fn memory_usage(&self) -> MemoryUsage {
+        let meshes: MemoryUsage = self
+            .objects
+            .iter()
+            .filter_map(|object| match object {
+                SceneObject::Mesh(mesh) => Some(mesh.memory_usage()),
+                _ => None,
+            })
+            .sum();
+        let own = MemoryUsage {
+            triangle_bytes: self.objects.len() * size_of::<SceneObject<'a, T>>(),
+            node_bytes: 0,
+            index_bytes: self.unbounded.len() * size_of::<usize>(),
+            other_bytes: self.area_cdf.get().map_or(0, |cdf| cdf.len() * size_of::<T>())
+                + self.object_media.len() * size_of::<(usize, Medium<T>)>()
+                + self.payloads.len() * size_of::<Option<Box<dyn Any + Send + Sync>>>(),
+        };
+        own + meshes + self.bvh.as_deref().map(Bvh::memory_usage).unwrap_or_default()
+    }
+
+    /// Find every pair of `SceneObject`s whose bounding boxes intersect or touch, using the `Scene` `Bvh`.
+    /// Unbounded `SceneObject`s are paired with every other object.
+    /// Useful as a physics broadphase, or to catch objects placed on top of one another.
+    /// Returns pairs of object indices, each with the lower index first, in ascending order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if bounding box calculations fail.
+    pub fn overlapping_pairs(&self) -> Result<Vec<(usize, usize)>> {
+        let mut pairs = match &self.bvh {
+            Some(bvh) => bvh.overlapping_pairs(&self.objects)?,
+            None => Vec::new(),
+        };
+        for &index in &self.unbounded {
+            pairs.extend(
+                (0..self.objects.len())
+                    .filter(|&other_index| other_index != index)
+                    .map(|other_index| (index.min(other_index), index.max(other_index))),
+            );
+        }
+        pairs.sort_unstable();
+        pairs.dedup();
+        Ok(pairs)
+    }
+
+    /// Convert the `Scene` back into its serialized representation.
+    /// `Instance`s are serialized by the identifier under which their `Mesh` is registered in the `Assets`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any `SceneObject` cannot be serialized.
+    pub fn to_serialized(&self, assets: &Assets<T>) -> Result<SerializedScene<T>> {
+        let objects = self
+            .objects
+            .iter()
+            .map(|object| SerializedSceneObject::from_scene_object(object, assets))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(SerializedScene { objects })
+    }
+
+    /// Construct a secondary `Ray` leaving a surface point, with its origin moved along `direction` by the ray bias,
+    /// so that it does not immediately hit the surface it leaves.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the origin or direction is not finite.
+    pub fn spawn_ray(&self, origin: Point3<T>, direction: Unit<Vector3<T>>) -> Result<Ray<T>> {
+        Ray::try_new(origin + direction.scale(self.ray_bias), direction)
+    }
+
+    /// Test for an intersection between a `Ray` and the `Scene`.
+    /// Returns the closest intersection if any, along with the index of the `SceneObject` which was hit.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the intersection calculation fails for any of the traversed objects.
+    pub fn intersect_object(&self, ray: &Ray<T>) -> Result<Option<(usize, Hit<T>)>> {
+        let closest = match &self.bvh {
+            Some(bvh) => bvh.intersect(ray, &self.objects)?,
+            None => None,
+        };
+        self.closest_unbounded(closest, |object| object.intersect(ray))
+    }
+
+    /// Test for an intersection between a `Ray` and the `Scene`, as in `intersect_object`.
+    /// Returns the closest intersection if any, along with the user data of type `P` attached to the object hit, if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the intersection calculation fails for any of the traversed objects.
+    pub fn intersect_payload<P: Any>(&self, ray: &Ray<T>) -> Result<Option<(Hit<T>, Option<&P>)>> {
+        Ok(self
+            .intersect_object(ray)?
+            .map(|(object_id, hit)| (hit, self.payload(object_id))))
+    }
+
+    /// Sweep a sphere of `radius` from `origin` along `direction`, as in a character controller move.
+    /// Returns the first contact within `max_distance` if any, along with the index of the `SceneObject` touched.
+    /// The `Hit` distance is how far the sphere centre travels before touching,
+    /// and its normals point from the contact point towards the sphere centre.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - `radius` is negative
+    /// - Any traversed `SceneObject` does not support sphere casts, or its calculation fails
+    pub fn sphere_cast(
+        &self,
+        origin: Point3<T>,
+        direction: Unit<Vector3<T>>,
+        radius: T,
+        max_distance: T,
+    ) -> Result<Option<(usize, Hit<T>)>> {
+        if radius < T::zero() {
+            return Err(GeometryError::InvalidRadius {
+                radius: format!("{radius:?}"),
+            }
+            .into());
+        }
+        let ray = Ray::new(origin, direction);
+        Ok(self
+            .sphere_cast_object(&ray, radius)?
+            .filter(|(_, hit)| hit.distance <= max_distance))
+    }
+
+    /// Sweep a sphere along a `Ray`, returning the first contact along with the index of the `SceneObject` touched.
+    fn sphere_cast_object(&self, ray: &Ray<T>, radius: T) -> Result<Option<(usize, Hit<T>)>> {
+        let closest = match &self.bvh {
+            Some(bvh) => bvh.sphere_cast(ray, radius, &self.objects)?,
+            None => None,
+        };
+        self.closest_unbounded(closest, |object| object.sphere_cast(ray, radius))
+    }
+
+    /// Test the unbounded `SceneObject`s, returning whichever is closer of the given hit and their closest hit.
+    fn closest_unbounded<F>(&self, mut closest: Option<(usize, Hit<T>)>, test: F) -> Result<Option<(usize, Hit<T>)>>
+    where
+        F: Fn(&SceneObject<'a, T>) -> Result<Option<Hit<T>>>,
+    {
+        for &index in &self.unbounded {
+            if let Some(hit) = test(&self.objects[index])?
+                && closest.as_ref().is_none_or(|(_, c)| hit.distance < c.distance)
+            {
+                closest = Some((index, hit));
+            }
+        }
+        Ok(closest)
+    }
+
+    /// Sample a point uniformly by area over the surfaces of the `Scene`,
+    /// choosing a `SceneObject` in proportion to its surface area and then a point uniformly over it.
+    /// `Plane`s, whose area is unbounded, and custom geometry are never sampled.
+    /// Returns the point along with the `SceneObject` it lies on, the outward geometric normal at the point,
+    /// and the probability density with respect to area.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The sampled surfaces have zero total area
+    /// - Numeric conversion fails
+    pub fn sample_surface<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<SurfacePoint<T>> {
+        let cdf = self.area_cdf()?;
+        let area = cdf.last().copied().unwrap_or_else(T::zero);
+        if area <= T::zero() {
+            return Err(GeometryError::ZeroArea.into());
+        }
+
+        let target = sample_unit::<T, R>(rng)? * area;
+        let index = cdf.partition_point(|&cumulative| cumulative <= target).min(cdf.len() - 1);
+        let (position, normal, _) = match &self.objects[index] {
+            SceneObject::Sphere(sphere) => sphere.sample_point(rng)?,
+            SceneObject::Triangle(triangle) => triangle.sample_point(rng)?,
+            SceneObject::Mesh(mesh) => mesh.sample_point(rng)?,
+            SceneObject::Instance(instance) => instance.sample_point(rng)?,
+            SceneObject::Plane(_) | SceneObject::Custom(_) => return Err(GeometryError::ZeroArea.into()),
+        };
+        Ok(SurfacePoint {
+            object_id: index,
+            position,
+            normal,
+            pdf: area.recip(),
+        })
+    }
+
+    /// Get the cumulative surface areas of the `SceneObject`s, computing them on first use.
+    fn area_cdf(&self) -> Result<&[T]> {
+        if let Some(cdf) = self.area_cdf.get() {
+            return Ok(cdf);
+        }
+        let mut total = T::zero();
+        let cdf = self
+            .objects
+            .iter()
+            .map(|object| {
+                total += match object {
+                    SceneObject::Sphere(sphere) => sphere.area()?,
+                    SceneObject::Triangle(triangle) => triangle.area()?,
+                    SceneObject::Mesh(mesh) => mesh.area()?,
+                    SceneObject::Instance(instance) => instance.area()?,
+                    SceneObject::Plane(_) | SceneObject::Custom(_) => T::zero(),
+                };
+                Ok(total)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(self.area_cdf.get_or_init(|| cdf))
+    }
+
+    /// Estimate the form factor from one surface to another by Monte Carlo integration over `samples` point pairs:
+    /// the fraction of the energy diffusely emitted by `from` which arrives directly at `to`.
+    /// Light only leaves and arrives on the side of each surface facing along its outward normal,
+    /// and pairs of points which cannot see one another through the `Scene` contribute nothing.
+    /// The surfaces may themselves be part of the `Scene`, as the ray bias is skipped at each end of every test.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - `samples` is zero
+    /// - Either surface has zero area
+    /// - A visibility test fails
+    pub fn form_factor<A, B, R>(&self, from: &A, to: &B, samples: usize, rng: &mut R) -> Result<T>
+    where
+        A: SurfaceSample<T>,
+        B: SurfaceSample<T>,
+        R: Rng + ?Sized,
+    {
+        if samples == 0 {
+            return Err(SceneError::NoFormFactorSamples.into());
+        }
+
+        let from_area = from.area()?;
+        let mut total = T::zero();
+        for _ in 0..samples {
+            let (from_point, from_normal, from_pdf) = from.sample_point(rng)?;
+            let (to_point, to_normal, to_pdf) = to.sample_point(rng)?;
+            let offset = to_point - from_point;
+            let distance_squared = offset.norm_squared();
+            if distance_squared <= T::zero() {
+                continue;
+            }
+
+            let distance = distance_squared.sqrt();
+            let direction = Unit::new_unchecked(offset / distance);
+            let cos_from = from_normal.dot(&direction);
+            let cos_to = -to_normal.dot(&direction);
+            if cos_from <= T::zero() || cos_to <= T::zero() || self.intersect_any(&Ray::new(from_point, direction), distance)? {
+                continue;
+            }
+            total += cos_from * cos_to / (T::pi() * distance_squared * from_pdf * to_pdf);
+        }
+
+        Ok(total / (from_area * T::try_from_usize(samples)?))
+    }
+
+    /// Distance to the closest surface hit by a `Ray`, passing through the boundaries of `SceneObject`s
+    /// filled with a `Medium`. Returns `None` if the `Ray` escapes the `Scene`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the intersection calculation fails for any of the traversed objects.
+    pub fn surface_distance(&self, ray: &Ray<T>) -> Result<Option<T>> {
+        let mut travelled = T::zero();
+        for _ in 0..MAX_MEDIUM_CROSSINGS {
+            let current = Ray::new(ray.origin + ray.direction.scale(travelled), ray.direction);
+            let Some((object_id, hit)) = self.intersect_object(&current)? else {
+                return Ok(None);
+            };
+            travelled += hit.distance;
+            if self.object_medium(object_id).is_none() {
+                return Ok(Some(travelled));
+            }
+            travelled += self.ray_bias;
+        }
+        Ok(None)
+    }
+
+    /// Fraction of light transmitted along a `Ray` over `distance` through the `Medium`s of the `Scene`.
+    /// Surfaces are not considered, so this is the attenuation of light already known to reach `distance`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - Locating the `Medium` boundaries along the `Ray` fails
+    /// - Numeric conversion fails
+    pub fn transmittance(&self, ray: &Ray<T>, distance: T) -> Result<T> {
+        let mut depth = T::zero();
+        for ([start, end], medium) in self.medium_spans(ray, distance)? {
+            depth += medium.optical_depth(ray, start, end)?;
+        }
+        Ok((-depth).exp())
+    }
+
+    /// Estimate the radiance scattered back along a `Ray`, from within `distance`, by the `Medium`s of the `Scene`,
+    /// which are lit by a point light of unit intensity at `light` and scatter isotropically.
+    /// Only light scattered once is counted. The `Ray` is marched in `steps` equal steps, from the middle of each
+    /// of which the light is tested for visibility past the surfaces, and attenuated by the `Medium`s in between.
+    /// Scale the result by the intensity of the light.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - `steps` is zero
+    /// - Visibility tests, or locating the `Medium` boundaries, fail
+    /// - Numeric conversion fails
+    pub fn in_scattering(&self, ray: &Ray<T>, distance: T, light: &Point3<T>, steps: usize) -> Result<T> {
+        if steps == 0 {
+            return Err(SceneError::NoScatteringSteps.into());
+        }
+        let spans = self.medium_spans(ray, distance)?;
+        if spans.is_empty() {
+            return Ok(T::zero());
+        }
+
+        let half = T::try_from_f32(0.5)?;
+        let phase = (T::try_from_u8(4)? * T::pi()).recip();
+        let step = distance / T::try_from_usize(steps)?;
+        let mut depth = T::zero();
+        let mut radiance = T::zero();
+        for index in 0..steps {
+            let start = step * T::try_from_usize(index)?;
+            let (middle, end) = (start + step * half, start + step);
+            let point = ray.origin + ray.direction.scale(middle);
+
+            let mut depth_to_middle = depth;
+            let mut scattering = T::zero();
+            for ([span_start, span_end], medium) in &spans {
+                depth_to_middle += medium.optical_depth(ray, start.max(*span_start), middle.min(*span_end))?;
+                depth += medium.optical_depth(ray, start.max(*span_start), end.min(*span_end))?;
+                if (*span_start..*span_end).contains(&middle) {
+                    scattering += medium.scattering_at(&point)?;
+                }
+            }
+
+            let offset = light - point;
+            let light_distance = offset.norm();
+            if scattering <= T::zero() || light_distance <= T::zero() {
+                continue;
+            }
+            let light_ray = Ray::new(point, Unit::new_unchecked(offset / light_distance));
+            if self
+                .surface_distance(&light_ray)?
+                .is_some_and(|surface| surface < light_distance)
+            {
+                continue;
+            }
+            radiance += (-depth_to_middle).exp() * scattering * phase * self.transmittance(&light_ray, light_distance)?
+                / light_distance.powi(2)
+                * step;
+        }
+        Ok(radiance)
+    }
+
+    /// Spans of a `Ray`, up to `distance`, lying within each `Medium` of the `Scene`.
+    fn medium_spans(&self, ray: &Ray<T>, distance: T) -> Result<Vec<([T; 2], &Medium<T>)>> {
+        let mut spans: Vec<_> = self.medium.iter().map(|medium| ([T::zero(), distance], medium)).collect();
+        for (object_id, medium) in &self.object_media {
+            spans.extend(
+                self.interior_spans(&self.objects[*object_id], ray, distance)?
+                    .into_iter()
+                    .map(|span| (span, medium)),
+            );
+        }
+        Ok(spans)
+    }
+
+    /// Spans of a `Ray`, up to `distance`, lying within a closed `SceneObject`.
+    /// Each crossing of its surface leaves the object if the `Ray` travels along the outward normal, and enters it otherwise.
+    fn interior_spans(&self, object: &SceneObject<'_, T>, ray: &Ray<T>, distance: T) -> Result<Vec<[T; 2]>> {
+        let mut spans = Vec::new();
+        let mut entered: Option<T> = None;
+        let mut travelled = T::zero();
+        for _ in 0..MAX_MEDIUM_CROSSINGS {
+            let current = Ray::new(ray.origin + ray.direction.scale(travelled), ray.direction);
+            let Some(hit) = object.intersect(&current)? else {
+                break;
+            };
+            let crossing = travelled + hit.distance;
+            if hit.geometric_normal.dot(&ray.direction) > T::zero() {
+                // Leaving, so the `Ray` was inside since it last entered, or since its origin
+                let start = entered.take().unwrap_or_else(T::zero);
+                spans.push([start, crossing.min(distance)]);
+            } else if crossing < distance {
+                entered = Some(crossing);
+            }
+            if crossing >= distance {
+                break;
+            }
+            travelled = crossing + self.ray_bias;
+        }
+
+        // A `Ray` still inside when it escapes has left through a gap in the surface, or past the last crossing tested
+        if let Some(start) = entered {
+            spans.push([start, distance]);
+        }
+        Ok(spans)
+    }
+
+    /// Test a batch of `Ray`s against the `Scene` in parallel, tracing them in the given `RayOrdering`.
+    /// Returns the closest intersection of each `Ray`, in the same order as the `Ray`s.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the intersection calculation fails for any of the `Ray`s.
+    #[cfg(feature = "rayon")]
+    pub fn intersect_batch(&self, rays: &[Ray<T>], ordering: RayOrdering) -> Result<Vec<Option<Hit<T>>>> {
+        trace_batch(rays, ordering, |ray| self.intersect(ray))
+    }
+
+    /// Test whether each of a batch of `Ray`s hits the `Scene` within `max_distance`, in parallel,
+    /// tracing them in the given `RayOrdering`.
+    /// The ray bias is skipped at each end of every `Ray`, as in `intersect_any`.
+    /// Returns one flag per `Ray`, in the same order as the `Ray`s.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the intersection test fails for any of the `Ray`s.
+    #[cfg(feature = "rayon")]
+    pub fn intersect_any_batch(&self, rays: &[Ray<T>], max_distance: T, ordering: RayOrdering) -> Result<Vec<bool>> {
+        trace_batch(rays, ordering, |ray| self.intersect_any(ray, max_distance))
+    }
+}
+
+impl<T: Scalar> Bounded<T> for Scene<'_, T> {
+    /// The bounds of the `Bvh`, or `Unbounded` if the `Scene` contains any unbounded `SceneObject`s.
+    fn aabb(&self) -> Result<Bounds<'_, T>> {
+        match &self.bvh {
+            Some(bvh) if self.unbounded.is_empty() => bvh.aabb(),
+            _ => Ok(Bounds::Unbounded),
+        }
+    }
 }
 
-impl<T: RealField + Copy + ToPrimitive> Bounded<T> for Scene<'_, T> {
-    fn aabb(&self) -> Result<Cow<Aabb<T>>> {
-        self.bvh.aabb()
+impl<T: Scalar> SurfaceSample<T> for Scene<'_, T> {
+    /// Total surface area of the `SceneObject`s, excluding `Plane`s and custom geometry.
+    fn area(&self) -> Result<T> {
+        Ok(self.area_cdf()?.last().copied().unwrap_or_else(T::zero))
+    }
+
+    /// Sample a point uniformly by area over the surfaces of the `Scene`, as in `sample_surface`.
+    fn sample_point<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<(Point3<T>, Unit<Vector3<T>>, T)> {
+        let sample = self.sample_surface(rng)?;
+        Ok((sample.position, sample.normal, sample.pdf))
     }
 }
 
-impl<T: RealField + Copy + ToPrimitive> Traceable<T> for Scene<'_, T> {
+impl<T: Scalar> Traceable<T> for Scene<'_, T> {
     fn intersect(&self, ray: &Ray<T>) -> Result<Option<Hit<T>>> {
         // Use the BVH to find the closest intersection
         // The BVH returns the object index within the scene
-        self.bvh.intersect(ray, &self.objects).map(|opt| opt.map(|(_, hit)| hit))
+        self.intersect_object(ray).map(|opt| opt.map(|(_, hit)| hit))
+    }
+
+    fn intersect_t(&self, ray: &Ray<T>) -> Result<Option<T>> {
+        let mut closest = match &self.bvh {
+            Some(bvh) => bvh.intersect_t(ray, &self.objects)?,
+            None => None,
+        };
+        for &index in &self.unbounded {
+            if let Some(distance) = self.objects[index].intersect_t(ray)?
+                && closest.is_none_or(|c| distance < c)
+            {
+                closest = Some(distance);
+            }
+        }
+        Ok(closest)
+    }
+
+    /// Test for the closest intersection between a `Ray` and the `Scene` nearer than `max_distance`.
+    /// Objects, and the nodes of `Mesh` hierarchies, lying beyond the bound are never traversed.
+    fn intersect_within(&self, ray: &Ray<T>, max_distance: T) -> Result<Option<Hit<T>>> {
+        let closest = match &self.bvh {
+            Some(bvh) => bvh.intersect_within(ray, &self.objects, max_distance)?,
+            None => None,
+        };
+        Ok(self
+            .closest_unbounded(closest, |object| object.intersect_within(ray, max_distance))?
+            .map(|(_, hit)| hit))
     }
 
+    /// Test if a `Ray` hits the `Scene` within `max_distance`, ignoring hits within the ray bias of either end.
+    /// A shadow `Ray` can therefore start on a surface and end on a light without hitting either.
     fn intersect_any(&self, ray: &Ray<T>, max_distance: T) -> Result<bool> {
-        self.bvh.intersect_any(ray, &self.objects, max_distance)
+        let max_distance = max_distance - self.ray_bias - self.ray_bias;
+        if max_distance <= T::zero() {
+            return Ok(false);
+        }
+        let ray = Ray::new(ray.origin + ray.direction.scale(self.ray_bias), ray.direction);
+        if let Some(bvh) = &self.bvh
+            && bvh.intersect_any(&ray, &self.objects, max_distance)?
+        {
+            return Ok(true);
+        }
+        for &index in &self.unbounded {
+            if self.objects[index].intersect_any(&ray, max_distance)? {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    fn sphere_cast(&self, ray: &Ray<T>, radius: T) -> Result<Option<Hit<T>>> {
+        Ok(self.sphere_cast_object(ray, radius)?.map(|(_, hit)| hit))
+    }
+}
+
+// == Utility functions ==
+/// Trace a batch of `Ray`s in parallel in the given `RayOrdering`, returning the results in the order of the `Ray`s.
+#[cfg(feature = "rayon")]
+fn trace_batch<T, R, F>(rays: &[Ray<T>], ordering: RayOrdering, trace: F) -> Result<Vec<R>>
+where
+    T: Scalar,
+    R: Send,
+    F: Fn(&Ray<T>) -> Result<R> + Sync,
+{
+    if ordering == RayOrdering::Input {
+        return rays.par_iter().map(&trace).collect();
+    }
+
+    let mut traced = ordering
+        .trace_order(rays)
+        .into_par_iter()
+        .map(|index| Ok((index, trace(&rays[index])?)))
+        .collect::<Result<Vec<_>>>()?;
+    traced.par_sort_unstable_by_key(|&(index, _)| index);
+    Ok(traced.into_iter().map(|(_, result)| result).collect())
+}
+
+/// Compute the default ray bias of a `Scene` from the size of its `Bvh`.
+/// A `Scene` of only unbounded objects has no size, so the scale is used as the bias directly.
+fn default_ray_bias<T: Scalar>(bvh: Option<&Bvh<T>>) -> Result<T> {
+    let scale = T::try_from_f64(DEFAULT_RAY_BIAS_SCALE)?;
+    Ok(match bvh {
+        Some(bvh) => bvh.aabb()?.try_finite()?.extents().norm() * scale,
+        None => scale,
+    })
+}
+
+/// Collect the indices of the unbounded objects.
+fn unbounded_indices<T: Scalar>(objects: &[SceneObject<'_, T>]) -> Result<Vec<usize>> {
+    let mut unbounded = Vec::new();
+    for (index, object) in objects.iter().enumerate() {
+        if !object.aabb()?.is_finite() {
+            unbounded.push(index);
+        }
+    }
+    Ok(unbounded)
+}
+
+#[cfg(test)]
+mod tests {
+    use nalgebra::{Point3, Unit, Vector3};
+
+    use crate::{
+        bvh::BvhConfig,
+        error::Result,
+        geometry::Sphere,
+        rt::Ray,
+        scene::{Scene, SceneObject},
+        traits::Traceable,
+    };
+
+    #[test]
+    fn payload_is_retrieved_for_the_object_hit() -> Result<()> {
+        let objects = vec![
+            SceneObject::Sphere(Sphere::new(Point3::new(0.0, 0.0, 5.0), 1.0)?),
+            SceneObject::Sphere(Sphere::new(Point3::new(0.0, 0.0, 10.0), 1.0)?),
+            SceneObject::Sphere(Sphere::new(Point3::new(5.0, 0.0, 5.0), 1.0)?),
+        ];
+        let scene = Scene::<f64>::new(&BvhConfig::default(), objects)?
+            .with_payload(0, "near")?
+            .with_payload(2, "aside")?;
+        let forward = Ray::new(Point3::origin(), Unit::new_normalize(Vector3::z()));
+
+        let (object_id, hit) = scene.intersect_object(&forward)?.expect("ray should hit the nearer sphere");
+        assert_eq!(object_id, 0);
+        assert_eq!(scene.payload::<&str>(object_id), Some(&"near"));
+
+        let (payload_hit, payload) = scene
+            .intersect_payload::<&str>(&forward)?
+            .expect("ray should hit the nearer sphere");
+        assert!((payload_hit.distance - hit.distance).abs() < f64::EPSILON);
+        assert_eq!(payload, Some(&"near"));
+        assert!(
+            scene
+                .intersect_payload::<u32>(&forward)?
+                .is_some_and(|(_, payload)| payload.is_none())
+        );
+
+        let behind = Ray::new(Point3::new(0.0, 0.0, 7.5), Unit::new_normalize(Vector3::z()));
+        assert!(
+            scene
+                .intersect_payload::<&str>(&behind)?
+                .is_some_and(|(_, payload)| payload.is_none())
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn non_finite_rays_miss_the_scene_and_are_refused_by_spawn_ray() -> Result<()> {
+        let objects = vec![SceneObject::Sphere(Sphere::new(Point3::new(0.0, 0.0, 5.0), 1.0)?)];
+        let scene = Scene::<f64>::new(&BvhConfig::default(), objects)?;
+        let direction = Unit::new_normalize(Vector3::z());
+
+        let ray = Ray::new(Point3::new(f64::NAN, 0.0, 0.0), direction);
+        assert!(scene.intersect(&ray)?.is_none());
+        assert!(scene.intersect_object(&ray)?.is_none());
+        assert!(!scene.intersect_any(&ray, f64::MAX)?);
+
+        assert!(Ray::try_new(Point3::new(f64::NAN, 0.0, 0.0), direction).is_err());
+        assert!(scene.spawn_ray(Point3::new(0.0, f64::INFINITY, 0.0), direction).is_err());
+        assert!(scene.spawn_ray(Point3::origin(), direction).is_ok());
+        Ok(())
     }
 }
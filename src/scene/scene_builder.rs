@@ -1,25 +1,30 @@
 //! Scene structure for `Ray` tracing.
 
-use nalgebra::{Matrix4, Point3, RealField, Unit, Vector3};
-use num_traits::ToPrimitive;
+use nalgebra::{Matrix4, Point3, Unit, Vector3};
+use std::any::Any;
 
 use crate::{
     bvh::BvhConfig,
     error::{Result, SceneError},
     geometry::{Mesh, Sphere, Triangle},
     scene::{Instance, Scene, SceneObject},
+    traits::{Geometry, Scalar},
 };
 
 /// Builder for constructing `Scene` instances.
 #[derive(Debug)]
-pub struct SceneBuilder<'a, T: RealField + Copy + ToPrimitive> {
+pub struct SceneBuilder<'a, T: Scalar> {
     /// List of objects in the scene.
     objects: Vec<SceneObject<'a, T>>,
     /// Configuration for the `Bvh` acceleration structure.
     bvh_config: BvhConfig<T>,
+    /// Ray bias overriding the `Scene` default, if set.
+    ray_bias: Option<T>,
+    /// User data attached to the objects, by object index.
+    payloads: Vec<(usize, Box<dyn Any + Send + Sync>)>,
 }
 
-impl<'a, T: RealField + Copy + ToPrimitive> SceneBuilder<'a, T> {
+impl<'a, T: Scalar> SceneBuilder<'a, T> {
     /// Set the `Bvh` configuration for the scene
     #[must_use]
     pub const fn with_bvh_config(mut self, config: BvhConfig<T>) -> Self {
@@ -27,6 +32,13 @@ impl<'a, T: RealField + Copy + ToPrimitive> SceneBuilder<'a, T> {
         self
     }
 
+    /// Set the distance skipped at each end of shadow and secondary `Ray`s, overriding the scale-relative default.
+    #[must_use]
+    pub const fn with_ray_bias(mut self, ray_bias: T) -> Self {
+        self.ray_bias = Some(ray_bias);
+        self
+    }
+
     /// Add a `Sphere` object to the scene.
     ///
     /// # Errors
@@ -57,6 +69,27 @@ impl<'a, T: RealField + Copy + ToPrimitive> SceneBuilder<'a, T> {
         Ok(self)
     }
 
+    /// Add a user-defined `Geometry` object to the scene.
+    /// The object is placed in the scene `Bvh` alongside the built-in primitives.
+    #[must_use]
+    pub fn add_custom<G: Geometry<T> + Send + Sync + 'a>(mut self, geometry: G) -> Self {
+        self.objects.push(SceneObject::Custom(Box::new(geometry)));
+        self
+    }
+
+    /// Attach user data to the most recently added object, as with `Scene::with_payload`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no objects have been added yet.
+    pub fn with_payload<P: Any + Send + Sync>(mut self, payload: P) -> Result<Self> {
+        let Some(object_id) = self.objects.len().checked_sub(1) else {
+            return Err(SceneError::PayloadIndexOutOfBounds { index: 0, count: 0 }.into());
+        };
+        self.payloads.push((object_id, Box::new(payload)));
+        Ok(self)
+    }
+
     /// Build the `Scene` with the current configuration and `SceneObjects`.
     ///
     /// # Errors
@@ -69,15 +102,24 @@ impl<'a, T: RealField + Copy + ToPrimitive> SceneBuilder<'a, T> {
             return Err(SceneError::EmptyScene.into());
         }
 
-        Scene::new(&self.bvh_config, self.objects)
+        let mut scene = Scene::new(&self.bvh_config, self.objects)?;
+        for (object_id, payload) in self.payloads {
+            scene = scene.with_boxed_payload(object_id, payload)?;
+        }
+        Ok(match self.ray_bias {
+            Some(ray_bias) => scene.with_ray_bias(ray_bias),
+            None => scene,
+        })
     }
 }
 
-impl<T: RealField + Copy + ToPrimitive> Default for SceneBuilder<'_, T> {
+impl<T: Scalar> Default for SceneBuilder<'_, T> {
     fn default() -> Self {
         Self {
             objects: Vec::new(),
             bvh_config: BvhConfig::default(),
+            ray_bias: None,
+            payloads: Vec::new(),
         }
     }
 }
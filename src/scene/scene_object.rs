@@ -1,20 +1,21 @@
 //! Scene object structure.
 
-use nalgebra::RealField;
-use num_traits::ToPrimitive;
-use std::borrow::Cow;
+use nalgebra::Vector3;
+use std::{
+    borrow::Cow,
+    fmt::{self, Debug, Formatter},
+};
 
 use crate::{
     error::Result,
-    geometry::{Aabb, Mesh, Plane, Sphere, Triangle},
+    geometry::{Mesh, Plane, Sphere, Triangle},
     rt::{Hit, Ray},
     scene::Instance,
-    traits::{Bounded, Traceable},
+    traits::{Bounded, Bounds, Geometry, Scalar, Traceable},
 };
 
 /// Enumeration of all `Traceable` objects that can be added to a `Scene`.
-#[derive(Debug)]
-pub enum SceneObject<'a, T: RealField + Copy> {
+pub enum SceneObject<'a, T: Scalar> {
     /// A sphere primitive.
     Sphere(Sphere<T>),
     /// An infinite plane primitive.
@@ -25,21 +26,49 @@ pub enum SceneObject<'a, T: RealField + Copy> {
     Mesh(Mesh<T>),
     /// A mesh instance with transformation.
     Instance(Instance<'a, T>),
+    /// User-defined geometry.
+    Custom(Box<dyn Geometry<T> + Send + Sync + 'a>),
+}
+
+impl<T: Scalar> Debug for SceneObject<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            SceneObject::Sphere(sphere) => f.debug_tuple("Sphere").field(sphere).finish(),
+            SceneObject::Plane(plane) => f.debug_tuple("Plane").field(plane).finish(),
+            SceneObject::Triangle(triangle) => f.debug_tuple("Triangle").field(triangle).finish(),
+            SceneObject::Mesh(mesh) => f.debug_tuple("Mesh").field(mesh).finish(),
+            SceneObject::Instance(instance) => f.debug_tuple("Instance").field(instance).finish(),
+            SceneObject::Custom(_) => f.debug_tuple("Custom").finish_non_exhaustive(),
+        }
+    }
 }
 
-impl<T: RealField + Copy + ToPrimitive> Bounded<T> for SceneObject<'_, T> {
-    fn aabb(&self) -> Result<Cow<Aabb<T>>> {
+impl<T: Scalar> Bounded<T> for SceneObject<'_, T> {
+    fn aabb(&self) -> Result<Bounds<'_, T>> {
         match self {
             SceneObject::Sphere(sphere) => sphere.aabb(),
             SceneObject::Plane(plane) => plane.aabb(),
             SceneObject::Triangle(triangle) => triangle.aabb(),
             SceneObject::Mesh(mesh) => mesh.aabb(),
-            SceneObject::Instance(instance) => Ok(Cow::Borrowed(instance.world_aabb())),
+            SceneObject::Instance(instance) => Ok(Bounds::Finite(Cow::Borrowed(instance.world_aabb()))),
+            SceneObject::Custom(geometry) => geometry.aabb(),
+        }
+    }
+
+    /// The span of an `Instance` is that of its world-space `Aabb`, rather than of every transformed `Triangle`.
+    fn span(&self, direction: &Vector3<T>) -> Result<Option<[T; 2]>> {
+        match self {
+            SceneObject::Sphere(sphere) => sphere.span(direction),
+            SceneObject::Plane(plane) => plane.span(direction),
+            SceneObject::Triangle(triangle) => triangle.span(direction),
+            SceneObject::Mesh(mesh) => mesh.span(direction),
+            SceneObject::Instance(instance) => Ok(Some(instance.world_aabb().span(direction))),
+            SceneObject::Custom(geometry) => geometry.span(direction),
         }
     }
 }
 
-impl<T: RealField + Copy + ToPrimitive> Traceable<T> for SceneObject<'_, T> {
+impl<T: Scalar> Traceable<T> for SceneObject<'_, T> {
     fn intersect(&self, ray: &Ray<T>) -> Result<Option<Hit<T>>> {
         match self {
             SceneObject::Sphere(sphere) => sphere.intersect(ray),
@@ -47,6 +76,29 @@ impl<T: RealField + Copy + ToPrimitive> Traceable<T> for SceneObject<'_, T> {
             SceneObject::Triangle(triangle) => triangle.intersect(ray),
             SceneObject::Mesh(mesh) => mesh.intersect(ray),
             SceneObject::Instance(instance) => instance.intersect(ray),
+            SceneObject::Custom(geometry) => geometry.intersect(ray),
+        }
+    }
+
+    fn intersect_t(&self, ray: &Ray<T>) -> Result<Option<T>> {
+        match self {
+            SceneObject::Sphere(sphere) => sphere.intersect_t(ray),
+            SceneObject::Plane(plane) => plane.intersect_t(ray),
+            SceneObject::Triangle(triangle) => triangle.intersect_t(ray),
+            SceneObject::Mesh(mesh) => mesh.intersect_t(ray),
+            SceneObject::Instance(instance) => instance.intersect_t(ray),
+            SceneObject::Custom(geometry) => geometry.intersect_t(ray),
+        }
+    }
+
+    fn intersect_within(&self, ray: &Ray<T>, max_distance: T) -> Result<Option<Hit<T>>> {
+        match self {
+            SceneObject::Sphere(sphere) => sphere.intersect_within(ray, max_distance),
+            SceneObject::Plane(plane) => plane.intersect_within(ray, max_distance),
+            SceneObject::Triangle(triangle) => triangle.intersect_within(ray, max_distance),
+            SceneObject::Mesh(mesh) => mesh.intersect_within(ray, max_distance),
+            SceneObject::Instance(instance) => instance.intersect_within(ray, max_distance),
+            SceneObject::Custom(geometry) => geometry.intersect_within(ray, max_distance),
         }
     }
 
@@ -57,6 +109,18 @@ impl<T: RealField + Copy + ToPrimitive> Traceable<T> for SceneObject<'_, T> {
             SceneObject::Triangle(triangle) => triangle.intersect_any(ray, max_distance),
             SceneObject::Mesh(mesh) => mesh.intersect_any(ray, max_distance),
             SceneObject::Instance(instance) => instance.intersect_any(ray, max_distance),
+            SceneObject::Custom(geometry) => geometry.intersect_any(ray, max_distance),
+        }
+    }
+
+    fn sphere_cast(&self, ray: &Ray<T>, radius: T) -> Result<Option<Hit<T>>> {
+        match self {
+            SceneObject::Sphere(sphere) => sphere.sphere_cast(ray, radius),
+            SceneObject::Plane(plane) => plane.sphere_cast(ray, radius),
+            SceneObject::Triangle(triangle) => triangle.sphere_cast(ray, radius),
+            SceneObject::Mesh(mesh) => mesh.sphere_cast(ray, radius),
+            SceneObject::Instance(instance) => instance.sphere_cast(ray, radius),
+            SceneObject::Custom(geometry) => geometry.sphere_cast(ray, radius),
         }
     }
 }
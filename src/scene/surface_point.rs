@@ -0,0 +1,18 @@
+//! Scene surface sample structure.
+
+use nalgebra::{Point3, Unit, Vector3};
+
+use crate::traits::Scalar;
+
+/// Point sampled on the surface of a `Scene`.
+#[derive(Debug, Clone)]
+pub struct SurfacePoint<T: Scalar> {
+    /// Index of the `SceneObject` on which the point lies.
+    pub object_id: usize,
+    /// World-space position of the point.
+    pub position: Point3<T>,
+    /// Outward geometric normal at the point.
+    pub normal: Unit<Vector3<T>>,
+    /// Probability density with which the point was sampled, with respect to area.
+    pub pdf: T,
+}
@@ -0,0 +1,5 @@
+mod ray_bundle;
+mod sensor_scan;
+
+pub use ray_bundle::RayBundle;
+pub use sensor_scan::SensorScan;
@@ -0,0 +1,110 @@
+//! Structured bundle of sensor `Ray`s.
+
+use nalgebra::{Point3, Rotation3, Unit, Vector3};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use crate::{
+    error::{RenderError, Result},
+    rt::Ray,
+    scene::{Camera, Scene},
+    sensors::SensorScan,
+    traits::Scalar,
+};
+
+/// Grid of `Ray`s cast together by a sensor, such as the beams of a lidar scan or the pixels of a depth camera.
+#[derive(Debug, Clone)]
+pub struct RayBundle<T: Scalar> {
+    /// Shape of the grid [rows, columns].
+    shape: [usize; 2],
+    /// Row-major `Ray`s.
+    rays: Vec<Ray<T>>,
+}
+
+impl<T: Scalar> RayBundle<T> {
+    /// Construct a new `RayBundle` from row-major `Ray`s.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the number of `Ray`s does not match the shape.
+    pub fn new(shape: [usize; 2], rays: Vec<Ray<T>>) -> Result<Self> {
+        if rays.len() != shape[0] * shape[1] {
+            return Err(RenderError::MismatchedRayBundle {
+                rays: rays.len(),
+                rows: shape[0],
+                cols: shape[1],
+            }
+            .into());
+        }
+        Ok(Self { shape, rays })
+    }
+
+    /// Construct a spherical scan pattern, as swept by a rotating multi-beam lidar.
+    /// Each row is one beam elevation and each column one azimuth, both in radians.
+    /// In the sensor frame the x-axis is forward (zero azimuth and elevation), the y-axis left and the z-axis up;
+    /// `orientation` rotates the sensor frame into the world.
+    pub fn spherical(origin: Point3<T>, orientation: &Rotation3<T>, elevations: &[T], azimuths: &[T]) -> Self {
+        let rays = elevations
+            .iter()
+            .flat_map(|&elevation| {
+                azimuths.iter().map(move |&azimuth| {
+                    let (sin_elevation, cos_elevation) = elevation.sin_cos();
+                    let (sin_azimuth, cos_azimuth) = azimuth.sin_cos();
+                    let local = Vector3::new(cos_elevation * cos_azimuth, cos_elevation * sin_azimuth, sin_elevation);
+                    Ray::new(origin, Unit::new_normalize(orientation * local))
+                })
+            })
+            .collect();
+
+        Self {
+            shape: [elevations.len(), azimuths.len()],
+            rays,
+        }
+    }
+
+    /// Construct the grid of primary `Ray`s of a `Camera`, as seen by a depth camera.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `Ray` generation fails.
+    pub fn from_camera(camera: &Camera<T>) -> Result<Self> {
+        let [height, width] = *camera.resolution();
+        let rays = (0..height)
+            .flat_map(|row| (0..width).map(move |col| [row, col]))
+            .map(|pixel_index| camera.generate_ray(pixel_index))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self {
+            shape: [height, width],
+            rays,
+        })
+    }
+
+    /// Returns the shape of the grid [rows, columns].
+    #[must_use]
+    pub const fn shape(&self) -> &[usize; 2] {
+        &self.shape
+    }
+
+    /// Returns all `Ray`s in row-major order.
+    #[must_use]
+    pub fn rays(&self) -> &[Ray<T>] {
+        &self.rays
+    }
+
+    /// Cast every `Ray` against a `Scene`, in parallel when the `rayon` feature is enabled.
+    /// Hits further than `max_range` are reported as misses.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the intersection calculation fails for any of the `Ray`s.
+    pub fn cast(&self, scene: &Scene<'_, T>, max_range: T) -> Result<SensorScan<T>> {
+        let cast_ray = |ray: &Ray<T>| Ok(scene.intersect_object(ray)?.filter(|(_, hit)| hit.distance <= max_range));
+
+        #[cfg(feature = "rayon")]
+        let hits = self.rays.par_iter().map(cast_ray).collect::<Result<Vec<_>>>()?;
+        #[cfg(not(feature = "rayon"))]
+        let hits = self.rays.iter().map(cast_ray).collect::<Result<Vec<_>>>()?;
+
+        Ok(SensorScan::new(self.shape, &hits))
+    }
+}
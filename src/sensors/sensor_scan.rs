@@ -0,0 +1,80 @@
+//! Result buffers of a sensor `RayBundle` cast.
+
+use nalgebra::{Unit, Vector3};
+
+use crate::{
+    error::{GeometryError, Result},
+    rt::Hit,
+    traits::Scalar,
+};
+
+/// Range, normal and object buffers recorded by casting a `RayBundle`, in the same row-major order as its `Ray`s.
+/// Each entry is `None` where the `Ray` found nothing within range.
+#[derive(Debug, Clone)]
+pub struct SensorScan<T: Scalar> {
+    /// Shape of the buffers [rows, columns].
+    shape: [usize; 2],
+    /// Distance along each `Ray` to the closest hit.
+    ranges: Vec<Option<T>>,
+    /// Geometric normal at each hit.
+    normals: Vec<Option<Unit<Vector3<T>>>>,
+    /// Index of the `SceneObject` hit by each `Ray`.
+    object_ids: Vec<Option<usize>>,
+}
+
+impl<T: Scalar> SensorScan<T> {
+    /// Construct a new `SensorScan` from the row-major (`SceneObject` index, `Hit`) of each `Ray`.
+    pub fn new(shape: [usize; 2], hits: &[Option<(usize, Hit<T>)>]) -> Self {
+        Self {
+            shape,
+            ranges: hits.iter().map(|hit| hit.as_ref().map(|(_, hit)| hit.distance)).collect(),
+            normals: hits
+                .iter()
+                .map(|hit| hit.as_ref().map(|(_, hit)| hit.geometric_normal))
+                .collect(),
+            object_ids: hits.iter().map(|hit| hit.as_ref().map(|&(object_id, _)| object_id)).collect(),
+        }
+    }
+
+    /// Returns the shape of the buffers [rows, columns].
+    #[must_use]
+    pub const fn shape(&self) -> &[usize; 2] {
+        &self.shape
+    }
+
+    /// Returns the range buffer in row-major order.
+    #[must_use]
+    pub fn ranges(&self) -> &[Option<T>] {
+        &self.ranges
+    }
+
+    /// Returns the normal buffer in row-major order.
+    #[must_use]
+    pub fn normals(&self) -> &[Option<Unit<Vector3<T>>>] {
+        &self.normals
+    }
+
+    /// Returns the object index buffer in row-major order.
+    #[must_use]
+    pub fn object_ids(&self) -> &[Option<usize>] {
+        &self.object_ids
+    }
+
+    /// Get the range recorded at the given [row, column] index.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the index is out of bounds for the buffer shape.
+    pub fn range(&self, index: [usize; 2]) -> Result<Option<T>> {
+        if index[0] >= self.shape[0] || index[1] >= self.shape[1] {
+            return Err(GeometryError::PixelOutOfBounds {
+                row: index[0],
+                col: index[1],
+                res_height: self.shape[0],
+                res_width: self.shape[1],
+            }
+            .into());
+        }
+        Ok(self.ranges[index[0] * self.shape[1] + index[1]])
+    }
+}
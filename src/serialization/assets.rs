@@ -1,32 +1,169 @@
-use nalgebra::RealField;
-use num_traits::ToPrimitive;
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "fs")]
+use std::path::Path;
 use std::{path::PathBuf, str::FromStr};
 
-use crate::{bvh::BvhConfig, error::Result, geometry::Mesh, scene::Assets};
+use crate::{
+    bvh::BvhConfig,
+    error::Result,
+    geometry::{Mesh, MeshConfig},
+    scene::{Assets, MemoryBudget, MeshCache},
+    serialization::{BvhCache, SerializedMesh},
+    traits::{AssetSource, Scalar},
+};
+
+#[cfg(feature = "fs")]
+use crate::{source::FileSystemSource, traits::Persistable};
 
 /// Serialized representation of `Assets` used by `Scene`s.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct SerializedAssets<T: RealField + Copy + ToPrimitive> {
+pub struct SerializedAssets<T: Scalar> {
     /// Bounding Volume Hierarchy configuration for applicable `Assets` and `Scene`s.
     pub bvh_config: Option<BvhConfig<T>>,
-    /// List of `Mesh` files to be loaded.
-    pub meshes: Vec<(String, PathBuf)>, // (identifier, file path)
+    /// Configuration of how the `Triangle`s of the `Mesh`es are prepared.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mesh_config: Option<MeshConfig>,
+    /// List of `Mesh`es to be loaded, either from file or from inline data.
+    pub meshes: Vec<(String, SerializedMesh<T>)>, // (identifier, mesh source)
+    /// Additional directories searched for relative mesh file paths.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub search_paths: Vec<PathBuf>,
+    /// Soft limit on the memory held by the built `Mesh`es, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory_budget: Option<MemoryBudget>,
 }
 
-impl<T: RealField + Copy + ToPrimitive + FromStr> SerializedAssets<T> {
+impl<T: Scalar + FromStr> SerializedAssets<T> {
     /// Construct an `Assets` instance.
+    /// Relative mesh file paths are resolved against the `search_paths`, then the current working directory.
     ///
     /// # Errors
     ///
-    /// Returns an error if any of the `Mesh` files cannot be loaded.
+    /// Returns an error if any of the `Mesh`es cannot be built.
+    #[cfg(feature = "fs")]
     pub fn build(self) -> Result<Assets<T>> {
+        self.build_relative_to("")
+    }
+
+    /// Construct an `Assets` instance, resolving relative mesh file paths against `base_dir`.
+    ///
+    /// Relative paths are tried against `base_dir` first, then each of the `search_paths`
+    /// (themselves relative to `base_dir` if not absolute), and finally the current working directory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of the `Mesh`es cannot be built.
+    #[cfg(feature = "fs")]
+    pub fn build_relative_to<P: AsRef<Path>>(self, base_dir: P) -> Result<Assets<T>> {
+        let source = self.source_relative_to(base_dir.as_ref());
+        self.build_from(&source)
+    }
+
+    /// Construct the `FileSystemSource` resolving relative mesh file paths against `base_dir`, then the `search_paths`.
+    #[cfg(feature = "fs")]
+    fn source_relative_to(&self, base_dir: &Path) -> FileSystemSource {
+        self.search_paths
+            .iter()
+            .fold(FileSystemSource::new().with_root(base_dir), |source, root| {
+                source.with_root(base_dir.join(root))
+            })
+    }
+
+    /// Construct an `Assets` instance, reading mesh files from the given `AssetSource`.
+    /// The `search_paths` are not applied; path resolution is left to the source.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of the `Mesh`es cannot be read from the source or built.
+    pub fn build_from<S: AssetSource + ?Sized>(self, source: &S) -> Result<Assets<T>> {
+        let bvh_config = self.bvh_config.unwrap_or_default();
+        let mesh_config = self.mesh_config.unwrap_or_default();
+        let mut assets = Assets::empty(bvh_config.clone()).with_mesh_config(mesh_config);
+        assets.budget = self.memory_budget;
+        for (name, mesh) in self.meshes {
+            assets = assets.add_mesh(&name, mesh.build_from_with_config(&bvh_config, mesh_config, source)?)?;
+        }
+        Ok(assets)
+    }
+
+    /// Construct an `Assets` instance, reading mesh files from the given `AssetSource`
+    /// and reusing matching `Mesh` `Bvh`s from the `BvhCache` instead of rebuilding them.
+    /// `Bvh`s which are missing from the cache, or no longer fit their `Mesh`, are rebuilt.
+    /// The cache is consumed, so its `Bvh`s are moved into the `Mesh`es rather than copied.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of the `Mesh`es cannot be read from the source or built.
+    pub fn build_with_bvhs<S: AssetSource + ?Sized>(self, source: &S, mut bvhs: BvhCache<T>) -> Result<Assets<T>> {
+        let bvh_config = self.bvh_config.unwrap_or_default();
+        let mesh_config = self.mesh_config.unwrap_or_default();
+        let mut assets = Assets::empty(bvh_config.clone()).with_mesh_config(mesh_config);
+        assets.budget = self.memory_budget;
+        for (name, mesh) in self.meshes {
+            let bvh = bvhs.meshes.remove(&name);
+            let mesh = Mesh::with_cached_bvh(&bvh_config, mesh_config, mesh.triangles_from(source)?, bvh)?;
+            assets = assets.add_mesh(&name, mesh)?;
+        }
+        Ok(assets)
+    }
+
+    /// Construct an `Assets` instance, reading mesh files from the given `AssetSource`
+    /// and reusing any `Mesh`es already present in the `MeshCache`.
+    /// Newly loaded mesh files are added to the cache. Inline meshes are not cached.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of the `Mesh`es cannot be read from the source or built.
+    pub fn build_cached<S: AssetSource + ?Sized>(self, source: &S, cache: &mut MeshCache<T>) -> Result<Assets<T>> {
         let bvh_config = self.bvh_config.unwrap_or_default();
-        let mut assets = Assets::empty(bvh_config.clone());
-        for (name, path) in self.meshes {
-            let mesh = Mesh::load(&bvh_config, path);
-            assets = assets.add_mesh(&name, mesh?)?;
+        let mesh_config = self.mesh_config.unwrap_or_default();
+        let mut assets = Assets::empty(bvh_config.clone()).with_mesh_config(mesh_config);
+        assets.budget = self.memory_budget;
+        for (name, mesh) in self.meshes {
+            let mesh = match mesh {
+                SerializedMesh::File(path) => cache.load_with_config(&bvh_config, mesh_config, source, &path)?.clone(),
+                inline @ SerializedMesh::Inline { .. } => inline.build_from_with_config(&bvh_config, mesh_config, source)?,
+            };
+            assets = assets.add_mesh(&name, mesh)?;
         }
         Ok(assets)
     }
+
+    /// Load a `SerializedAssets` file and construct an `Assets` instance,
+    /// resolving relative mesh file paths against the directory containing the file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The file cannot be read or is not valid JSON
+    /// - Any of the `Mesh`es cannot be built
+    #[cfg(feature = "fs")]
+    pub fn load_and_build<P: AsRef<Path>>(path: P) -> Result<Assets<T>>
+    where
+        Self: Persistable,
+    {
+        let path = path.as_ref();
+        let base_dir = path.parent().unwrap_or_else(|| Path::new(""));
+        Self::load(path)?.build_relative_to(base_dir)
+    }
+
+    /// Load a `SerializedAssets` file and construct an `Assets` instance as in `load_and_build`,
+    /// reusing any `Mesh`es already present in the `MeshCache`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The file cannot be read or is not valid JSON
+    /// - Any of the `Mesh`es cannot be built
+    #[cfg(feature = "fs")]
+    pub fn load_and_build_cached<P: AsRef<Path>>(path: P, cache: &mut MeshCache<T>) -> Result<Assets<T>>
+    where
+        Self: Persistable,
+    {
+        let path = path.as_ref();
+        let base_dir = path.parent().unwrap_or_else(|| Path::new(""));
+        let assets = Self::load(path)?;
+        let source = assets.source_relative_to(base_dir);
+        assets.build_cached(&source, cache)
+    }
 }
@@ -0,0 +1,98 @@
+//! Standard (RFC 4648) base64 encoding, used for buffers embedded in glTF data URIs.
+
+use crate::error::{FileParsingError, Result};
+
+/// Characters of the standard base64 alphabet.
+const BASE64_ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Encode bytes as standard padded base64.
+pub fn encode_base64(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let group = chunk
+            .iter()
+            .enumerate()
+            .fold(0_u32, |group, (i, &byte)| group | (u32::from(byte) << (16 - 8 * i)));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                let index = (group >> (18 - 6 * i)) & 0x3F;
+                encoded.push(char::from(BASE64_ALPHABET[index as usize]));
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+/// Decode standard base64 data, ignoring padding.
+///
+/// # Errors
+///
+/// Returns an error if the data contains a character outside the base64 alphabet.
+pub fn decode_base64(encoded: &str) -> Result<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(encoded.len() * 3 / 4);
+    let mut accumulator = 0_u32;
+    let mut bits = 0;
+    for c in encoded.bytes().filter(|&c| c != b'=') {
+        let value = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            _ => {
+                return Err(FileParsingError::InvalidBase64 {
+                    character: char::from(c),
+                }
+                .into());
+            }
+        };
+        accumulator = (accumulator << 6) | u32::from(value);
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((accumulator >> bits).to_le_bytes()[0]);
+        }
+    }
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_base64, encode_base64};
+    use crate::error::Result;
+
+    #[test]
+    fn encodes_rfc_4648_test_vectors() -> Result<()> {
+        for (decoded, encoded) in [
+            ("", ""),
+            ("f", "Zg=="),
+            ("fo", "Zm8="),
+            ("foo", "Zm9v"),
+            ("foob", "Zm9vYg=="),
+            ("fooba", "Zm9vYmE="),
+            ("foobar", "Zm9vYmFy"),
+        ] {
+            assert_eq!(encode_base64(decoded.as_bytes()), encoded);
+            assert_eq!(decode_base64(encoded)?, decoded.as_bytes());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn round_trips_every_byte_value() -> Result<()> {
+        let bytes: Vec<u8> = (0..=u8::MAX).chain((0..=u8::MAX).rev()).collect();
+        for length in 0..bytes.len() {
+            assert_eq!(decode_base64(&encode_base64(&bytes[..length]))?, &bytes[..length]);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_characters_outside_the_alphabet() {
+        for encoded in ["Zm9v YmFy", "Zm9v-YmFy", "Zm9v_YmFy", "Zm9vYmFy\n", "Zé"] {
+            assert!(decode_base64(encoded).is_err(), "{encoded:?} should not decode");
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::{
+    bvh::Bvh,
+    scene::{Assets, Scene},
+    traits::Scalar,
+};
+
+/// Previously built `Bvh`s of a `Scene` and its `Assets`, saved to skip `Bvh` construction on load.
+///
+/// Cached `Bvh`s are validated against the geometry they are applied to,
+/// and are rebuilt if they no longer match.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BvhCache<T: Scalar> {
+    /// Scene-level `Bvh`.
+    pub scene: Option<Bvh<T>>,
+    /// Per-mesh `Bvh`s, keyed by asset identifier.
+    pub meshes: BTreeMap<String, Bvh<T>>,
+}
+
+impl<T: Scalar> Default for BvhCache<T> {
+    fn default() -> Self {
+        Self {
+            scene: None,
+            meshes: BTreeMap::new(),
+        }
+    }
+}
+
+impl<T: Scalar> BvhCache<T> {
+    /// Collect the `Bvh`s of every `Mesh` in the `Assets`.
+    #[must_use]
+    pub fn from_assets(assets: &Assets<T>) -> Self {
+        Self {
+            scene: None,
+            meshes: assets.iter().map(|(id, mesh)| (id.to_string(), mesh.bvh().clone())).collect(),
+        }
+    }
+
+    /// Collect the `Bvh`s of a `Scene` and every `Mesh` in its `Assets`.
+    #[must_use]
+    pub fn from_scene(scene: &Scene<'_, T>, assets: &Assets<T>) -> Self {
+        Self {
+            scene: scene.bvh().cloned(),
+            ..Self::from_assets(assets)
+        }
+    }
+}
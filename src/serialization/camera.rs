@@ -1,34 +1,146 @@
-use nalgebra::{Point3, RealField};
-use num_traits::ToPrimitive;
+use nalgebra::{Point3, Quaternion, UnitQuaternion};
 use serde::{Deserialize, Serialize};
 
-use crate::{error::Result, scene::Camera, serialization::SerializedProjection};
+use crate::{
+    error::{RenderError, Result, TransformationError},
+    scene::Camera,
+    serialization::SerializedProjection,
+    traits::{FallibleNumeric, Scalar},
+};
+
+const DEGREES_TO_RADIANS: f64 = std::f64::consts::PI / 180.0;
+
+/// Optional descriptive metadata attached to a `SerializedCamera`.
+/// These values are not used by `Camera` itself, but are carried through for downstream renderers and dataset tools.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SerializedCameraMetadata<T: Scalar> {
+    /// Shutter open duration (seconds).
+    pub shutter: Option<T>,
+    /// Exposure hint, in ISO-style sensitivity units.
+    pub exposure: Option<T>,
+    /// Near clipping distance.
+    pub near: Option<T>,
+    /// Far clipping distance.
+    pub far: Option<T>,
+}
 
 /// Serialized representation of a `Camera`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct SerializedCamera<T: RealField + Copy> {
+pub struct SerializedCamera<T: Scalar> {
     /// Camera projection mode
     pub projection: SerializedProjection<T>,
     /// View point.
     pub position: [T; 3],
-    /// Target position.
-    pub look_at: [T; 3],
+    /// Target position, if the orientation is not given.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub look_at: Option<[T; 3]>,
+    /// Rotation of the camera frame into the world as quaternion components [x, y, z, w], if the target is not given.
+    /// In the camera frame the x-axis is forward, the y-axis left and the z-axis up. Normalised on construction.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub orientation: Option<[T; 4]>,
     /// Resolution of the camera in pixels (width, height).
     pub resolution: [usize; 2],
+    /// Optional lens shift [vertical, horizontal], as fractions of the view height and width.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lens_shift: Option<[T; 2]>,
+    /// Optional upward pitch of a perspective view (degrees), applied without rotating the camera.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tilt: Option<T>,
+    /// Optional camera metadata.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<SerializedCameraMetadata<T>>,
+}
+
+/// Serialized collection of named `Camera`s, such as the viewpoints of an animation or multi-view dataset.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SerializedCameras<T: Scalar> {
+    /// List of `Camera`s.
+    pub cameras: Vec<(String, SerializedCamera<T>)>, // (identifier, camera)
 }
 
-impl<T: RealField + Copy + ToPrimitive> SerializedCamera<T> {
+impl<T: Scalar> SerializedCamera<T> {
     /// Construct a `Camera` instance.
     ///
     /// # Errors
     ///
     /// Returns an error if:
+    /// - Not exactly one of `look_at` and `orientation` is given, or the orientation quaternion has zero length
     /// - Projection building fails
     /// - Camera construction fails due to invalid parameters
+    /// - Metadata clipping distances are given but `near` is not positive or is not less than `far`
     pub fn build(self) -> Result<Camera<T>> {
+        if let Some(SerializedCameraMetadata {
+            near: Some(near),
+            far: Some(far),
+            ..
+        }) = self.metadata
+            && (near <= T::zero() || near >= far)
+        {
+            return Err(RenderError::InvalidClipRange {
+                near: format!("{near:?}"),
+                far: format!("{far:?}"),
+            }
+            .into());
+        }
+
         let position = Point3::new(self.position[0], self.position[1], self.position[2]);
-        let look_at = Point3::new(self.look_at[0], self.look_at[1], self.look_at[2]);
         let projection = self.projection.build()?;
-        Camera::new(position, look_at, projection, self.resolution)
+        let mut camera = match (self.look_at, self.orientation) {
+            (Some(look_at), None) => Camera::new(position, look_at.into(), projection, self.resolution)?,
+            (None, Some([x, y, z, w])) => {
+                let orientation = UnitQuaternion::try_new(Quaternion::new(w, x, y, z), T::zero())
+                    .ok_or(TransformationError::DegenerateRotation)?;
+                Camera::from_pose(position, orientation, projection, self.resolution)?
+            }
+            _ => return Err(RenderError::AmbiguousCameraOrientation.into()),
+        };
+        if let Some(lens_shift) = self.lens_shift {
+            camera = camera.with_lens_shift(lens_shift);
+        }
+        if let Some(tilt) = self.tilt {
+            camera = camera.with_tilt(tilt * T::try_from_f64(DEGREES_TO_RADIANS)?);
+        }
+        Ok(camera)
+    }
+}
+
+impl<T: Scalar> SerializedCameras<T> {
+    /// Get the `SerializedCamera` with the given identifier.
+    #[must_use]
+    pub fn get(&self, id: &str) -> Option<&SerializedCamera<T>> {
+        self.cameras.iter().find_map(|(name, camera)| (name == id).then_some(camera))
+    }
+
+    /// Construct the `Camera` with the given identifier.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - No camera with the given identifier exists
+    /// - Camera construction fails
+    pub fn build_camera(&self, id: &str) -> Result<Camera<T>> {
+        self.get(id)
+            .ok_or_else(|| RenderError::CameraNotFound { id: id.to_string() })?
+            .clone()
+            .build()
+    }
+
+    /// Construct all `Camera`s, in file order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - Two cameras share the same identifier
+    /// - Any camera fails to build
+    pub fn build(self) -> Result<Vec<(String, Camera<T>)>> {
+        let mut cameras: Vec<(String, Camera<T>)> = Vec::with_capacity(self.cameras.len());
+        for (id, camera) in self.cameras {
+            if cameras.iter().any(|(existing, _)| *existing == id) {
+                return Err(RenderError::DuplicateCameraId { id }.into());
+            }
+            let camera = camera.build()?;
+            cameras.push((id, camera));
+        }
+        Ok(cameras)
     }
 }
@@ -0,0 +1,304 @@
+//! Export of a built `Scene` to external mesh formats.
+
+use nalgebra::{Matrix3, Point3, Unit, Vector3};
+use serde_json::json;
+use std::any::type_name;
+#[cfg(feature = "fs")]
+use std::{fs::write, path::Path};
+
+use crate::{
+    error::{NumericError, RenderError, Result, TransformationError},
+    geometry::{Plane, Sphere, Triangle},
+    scene::{Scene, SceneObject},
+    serialization::{WavefrontWriter, base64::encode_base64},
+    traits::{FallibleNumeric, Scalar},
+};
+
+/// glTF component type identifier for 32-bit floats.
+const GLTF_FLOAT: u32 = 5126;
+/// glTF buffer view target for vertex attributes.
+const GLTF_ARRAY_BUFFER: u32 = 34962;
+
+/// Configuration for tessellating analytic primitives when exporting a `Scene`.
+#[derive(Debug, Clone)]
+pub struct ExportConfig<T: Scalar> {
+    /// Number of longitudinal segments used for `Sphere`s. Half as many latitudinal bands are used.
+    pub sphere_segments: usize,
+    /// Half-width of the square used to represent each infinite `Plane`.
+    pub plane_extent: T,
+}
+
+/// World-space triangle soup of a `Scene`, grouped by `SceneObject`, ready to be written to an external format.
+#[derive(Debug, Clone)]
+pub struct SceneExport<T: Scalar> {
+    /// Named groups of world-space `Triangle`s, one per `SceneObject`.
+    groups: Vec<(String, Vec<Triangle<T>>)>,
+}
+
+impl<T: Scalar> Default for ExportConfig<T> {
+    fn default() -> Self {
+        Self {
+            sphere_segments: 32,
+            plane_extent: nalgebra::convert(100.0),
+        }
+    }
+}
+
+impl<T: Scalar> SceneExport<T> {
+    /// Flatten a `Scene` into world-space `Triangle`s.
+    /// `Instance` transforms are applied, `Sphere`s are tessellated and `Plane`s are represented by a finite square.
+    /// Custom geometry has no triangle representation and is skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - `sphere_segments` is less than 4
+    /// - `plane_extent` is not positive
+    /// - An `Instance` transform cannot be inverted
+    /// - Numeric conversion fails
+    pub fn new(scene: &Scene<'_, T>, config: &ExportConfig<T>) -> Result<Self> {
+        if config.sphere_segments < 4 || config.plane_extent <= T::zero() {
+            return Err(RenderError::InvalidTessellation {
+                segments: config.sphere_segments,
+                extent: format!("{:?}", config.plane_extent),
+            }
+            .into());
+        }
+
+        let groups = scene
+            .objects()
+            .iter()
+            .enumerate()
+            .map(|(index, object)| {
+                Ok(Some(match object {
+                    SceneObject::Sphere(sphere) => {
+                        (format!("sphere_{index}"), tessellate_sphere(sphere, config.sphere_segments)?)
+                    }
+                    SceneObject::Plane(plane) => (format!("plane_{index}"), tessellate_plane(plane, config.plane_extent)),
+                    SceneObject::Triangle(triangle) => (format!("triangle_{index}"), vec![triangle.clone()]),
+                    SceneObject::Mesh(mesh) => (format!("mesh_{index}"), mesh.triangles().to_vec()),
+                    SceneObject::Instance(instance) => {
+                        let transform = instance.transform();
+                        let inverse = transform.try_inverse().ok_or(TransformationError::NonInvertibleMatrix)?;
+                        let normal_transform: Matrix3<T> = inverse.fixed_view::<3, 3>(0, 0).transpose();
+                        let triangles = instance
+                            .mesh()
+                            .triangles()
+                            .iter()
+                            .map(|triangle| triangle.transform_with(transform, &normal_transform))
+                            .collect();
+                        (format!("instance_{index}"), triangles)
+                    }
+                    SceneObject::Custom(_) => return Ok(None),
+                }))
+            })
+            .filter_map(Result::transpose)
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self { groups })
+    }
+
+    /// Get the named groups of world-space `Triangle`s.
+    #[must_use]
+    pub fn groups(&self) -> &[(String, Vec<Triangle<T>>)] {
+        &self.groups
+    }
+
+    /// Write the export as a wavefront (.obj) string, with one object per `SceneObject`.
+    /// Texture coordinates are written where `Triangle`s have them, and vertex colours follow the vertex positions
+    /// without their alpha.
+    #[must_use]
+    pub fn to_wavefront(&self) -> String {
+        let mut obj = WavefrontWriter::new();
+        for (name, triangles) in &self.groups {
+            obj.object(name);
+            for triangle in triangles {
+                let vertices = [0, 1, 2].map(|i| {
+                    let color = triangle.colors().map(|colors| {
+                        let [r, g, b, _] = colors[i];
+                        [r, g, b]
+                    });
+                    obj.vertex(&triangle.vertices()[i], color)
+                });
+                let uvs = triangle.uvs().map(|uvs| uvs.map(|uv| obj.uv(uv)));
+                let normals = triangle.normals().map(|normal| obj.normal(&normal));
+                obj.face(vertices, uvs, Some(normals));
+            }
+        }
+        obj.into_string()
+    }
+
+    /// Save the export as a wavefront (.obj) file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    #[cfg(feature = "fs")]
+    pub fn save_wavefront<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        Ok(write(path, self.to_wavefront())?)
+    }
+
+    /// Write the export as a self-contained glTF 2.0 (.gltf) JSON string, with one node per `SceneObject`.
+    /// Vertex data is stored as single-precision floats in an embedded base64 buffer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - A coordinate cannot be converted to `f32`
+    /// - The document cannot be serialized
+    pub fn to_gltf(&self) -> Result<String> {
+        let mut buffer = Vec::new();
+        let mut meshes = Vec::with_capacity(self.groups.len());
+        let mut accessors = Vec::with_capacity(self.groups.len() * 2);
+        let mut buffer_views = Vec::with_capacity(self.groups.len() * 2);
+
+        for (name, triangles) in self.groups.iter().filter(|(_, triangles)| !triangles.is_empty()) {
+            let positions = triangles
+                .iter()
+                .flat_map(|t| t.vertices().map(|v| v.coords))
+                .collect::<Vec<_>>();
+            let normals = triangles
+                .iter()
+                .flat_map(|t| t.normals().map(Unit::into_inner))
+                .collect::<Vec<_>>();
+
+            let mut mins = [f32::MAX; 3];
+            let mut maxs = [f32::MIN; 3];
+            for (index, vectors) in [&positions, &normals].into_iter().enumerate() {
+                let byte_offset = buffer.len();
+                for vector in vectors {
+                    for (axis, &value) in vector.iter().enumerate() {
+                        let value = value.to_f32().ok_or_else(|| NumericError::TypeConversion {
+                            from_type: type_name::<T>().to_string(),
+                            to_type: "f32".to_string(),
+                        })?;
+                        if index == 0 {
+                            mins[axis] = mins[axis].min(value);
+                            maxs[axis] = maxs[axis].max(value);
+                        }
+                        buffer.extend_from_slice(&value.to_le_bytes());
+                    }
+                }
+                buffer_views.push(json!({
+                    "buffer": 0,
+                    "byteOffset": byte_offset,
+                    "byteLength": buffer.len() - byte_offset,
+                    "target": GLTF_ARRAY_BUFFER,
+                }));
+                let mut accessor = json!({
+                    "bufferView": buffer_views.len() - 1,
+                    "componentType": GLTF_FLOAT,
+                    "count": vectors.len(),
+                    "type": "VEC3",
+                });
+                if index == 0 {
+                    accessor["min"] = json!(mins);
+                    accessor["max"] = json!(maxs);
+                }
+                accessors.push(accessor);
+            }
+
+            meshes.push(json!({
+                "name": name,
+                "primitives": [{
+                    "attributes": { "POSITION": accessors.len() - 2, "NORMAL": accessors.len() - 1 },
+                }],
+            }));
+        }
+
+        let nodes = meshes
+            .iter()
+            .enumerate()
+            .map(|(index, mesh)| json!({ "name": mesh["name"], "mesh": index }))
+            .collect::<Vec<_>>();
+
+        let document = json!({
+            "asset": { "version": "2.0", "generator": "geodesic" },
+            "scene": 0,
+            "scenes": [{ "nodes": (0..nodes.len()).collect::<Vec<_>>() }],
+            "nodes": nodes,
+            "meshes": meshes,
+            "accessors": accessors,
+            "bufferViews": buffer_views,
+            "buffers": [{
+                "byteLength": buffer.len(),
+                "uri": format!("data:application/octet-stream;base64,{}", encode_base64(&buffer)),
+            }],
+        });
+
+        Ok(serde_json::to_string_pretty(&document)?)
+    }
+
+    /// Save the export as a self-contained glTF 2.0 (.gltf) file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - A coordinate cannot be converted to `f32`
+    /// - The file cannot be written
+    #[cfg(feature = "fs")]
+    pub fn save_gltf<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        Ok(write(path, self.to_gltf()?)?)
+    }
+}
+
+// == Utility functions ==
+
+/// Tessellate a `Sphere` into a latitude-longitude grid of `Triangle`s with smooth normals.
+fn tessellate_sphere<T: Scalar>(sphere: &Sphere<T>, segments: usize) -> Result<Vec<Triangle<T>>> {
+    let bands = segments / 2;
+    let segments_t = T::try_from_usize(segments)?;
+    let bands_t = T::try_from_usize(bands)?;
+
+    let direction = |band: usize, segment: usize| -> Result<Vector3<T>> {
+        let theta = T::pi() * T::try_from_usize(band)? / bands_t;
+        let phi = T::two_pi() * T::try_from_usize(segment)? / segments_t;
+        Ok(Vector3::new(theta.sin() * phi.cos(), theta.sin() * phi.sin(), theta.cos()))
+    };
+    let triangle = |directions: [Vector3<T>; 3]| {
+        Triangle::new(
+            directions.map(|d| sphere.center + d * sphere.radius),
+            directions.map(Unit::new_normalize),
+        )
+    };
+
+    let mut triangles = Vec::with_capacity(2 * segments * (bands - 1));
+    for band in 0..bands {
+        for segment in 0..segments {
+            let a = direction(band, segment)?;
+            let b = direction(band + 1, segment)?;
+            let c = direction(band + 1, segment + 1)?;
+            let d = direction(band, segment + 1)?;
+            // Pole bands collapse to a single triangle per segment
+            if band != 0 {
+                triangles.push(triangle([a, c, d]));
+            }
+            if band + 1 != bands {
+                triangles.push(triangle([a, b, c]));
+            }
+        }
+    }
+    Ok(triangles)
+}
+
+/// Represent an infinite `Plane` by a square of two `Triangle`s centred on its reference point.
+fn tessellate_plane<T: Scalar>(plane: &Plane<T>, extent: T) -> Vec<Triangle<T>> {
+    let normal = *plane.normal();
+    let helper = if normal.x.abs() < normal.z.abs() {
+        Vector3::x()
+    } else {
+        Vector3::z()
+    };
+    let u = normal.cross(&helper).normalize() * extent;
+    let v = normal.cross(&u);
+    let corners: [Point3<T>; 4] = [
+        plane.point() - u - v,
+        plane.point() + u - v,
+        plane.point() + u + v,
+        plane.point() - u + v,
+    ];
+    vec![
+        Triangle::new([corners[0], corners[1], corners[2]], [normal; 3]),
+        Triangle::new([corners[0], corners[2], corners[3]], [normal; 3]),
+    ]
+}
@@ -0,0 +1,877 @@
+//! glTF 2.0 scene import.
+
+use nalgebra::{Matrix3, Matrix4, Point3, Quaternion, Rotation3, Translation3, Unit, UnitQuaternion, Vector3};
+use num_traits::ToPrimitive;
+use serde::Deserialize;
+use std::{ops::Range, path::Path};
+
+use crate::{
+    bvh::BvhConfig,
+    error::{FileParsingError, GeodesicError, Result},
+    geometry::{Mesh, MeshConfig, Triangle},
+    scene::{Assets, Camera, Projection, Scene},
+    serialization::base64::decode_base64,
+    traits::{AssetSource, FallibleNumeric, Scalar},
+};
+
+#[cfg(feature = "fs")]
+use crate::source::FileSystemSource;
+
+/// Magic bytes at the start of a binary glTF (.glb) file.
+const GLB_MAGIC: &[u8; 4] = b"glTF";
+/// Chunk type of the JSON chunk in a binary glTF file.
+const GLB_JSON_CHUNK: u32 = 0x4E4F_534A;
+/// Chunk type of the binary buffer chunk in a binary glTF file.
+const GLB_BIN_CHUNK: u32 = 0x004E_4942;
+/// Primitive mode of triangle lists, the default when unspecified.
+const TRIANGLES_MODE: u32 = 4;
+
+/// Top level of a glTF JSON document (only the parts used for import).
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Document {
+    scene: Option<usize>,
+    #[serde(default)]
+    scenes: Vec<DocumentScene>,
+    #[serde(default)]
+    nodes: Vec<Node>,
+    #[serde(default)]
+    meshes: Vec<DocumentMesh>,
+    #[serde(default)]
+    cameras: Vec<DocumentCamera>,
+    #[serde(default)]
+    accessors: Vec<Accessor>,
+    #[serde(default)]
+    buffer_views: Vec<BufferView>,
+    #[serde(default)]
+    buffers: Vec<Buffer>,
+}
+
+/// Set of root nodes.
+#[derive(Deserialize)]
+struct DocumentScene {
+    #[serde(default)]
+    nodes: Vec<usize>,
+}
+
+/// Node of the scene hierarchy.
+#[derive(Deserialize)]
+struct Node {
+    #[serde(default)]
+    children: Vec<usize>,
+    mesh: Option<usize>,
+    camera: Option<usize>,
+    matrix: Option<[f64; 16]>,
+    translation: Option<[f64; 3]>,
+    rotation: Option<[f64; 4]>,
+    scale: Option<[f64; 3]>,
+}
+
+/// Mesh composed of one or more primitives.
+#[derive(Deserialize)]
+struct DocumentMesh {
+    name: Option<String>,
+    primitives: Vec<Primitive>,
+}
+
+/// Geometry to be rendered.
+#[derive(Deserialize)]
+struct Primitive {
+    attributes: Attributes,
+    indices: Option<usize>,
+    mode: Option<u32>,
+}
+
+/// Vertex attribute accessors of a primitive.
+#[derive(Deserialize)]
+#[expect(non_snake_case, reason = "Field names match the glTF attribute semantics.")]
+struct Attributes {
+    POSITION: usize,
+    NORMAL: Option<usize>,
+}
+
+/// Camera projection.
+#[derive(Deserialize)]
+struct DocumentCamera {
+    perspective: Option<Perspective>,
+    orthographic: Option<Orthographic>,
+}
+
+/// Perspective camera parameters.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Perspective {
+    yfov: f64,
+    aspect_ratio: Option<f64>,
+}
+
+/// Orthographic camera parameters.
+#[derive(Deserialize)]
+struct Orthographic {
+    xmag: f64,
+    ymag: f64,
+}
+
+/// Typed view into a buffer view.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Accessor {
+    buffer_view: Option<usize>,
+    #[serde(default)]
+    byte_offset: usize,
+    component_type: u32,
+    count: usize,
+    #[serde(rename = "type")]
+    kind: String,
+}
+
+/// Contiguous slice of a buffer.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BufferView {
+    buffer: usize,
+    #[serde(default)]
+    byte_offset: usize,
+    byte_length: usize,
+    byte_stride: Option<usize>,
+}
+
+/// Binary data source.
+#[derive(Deserialize)]
+struct Buffer {
+    uri: Option<String>,
+}
+
+/// Camera placement extracted from the scene hierarchy.
+#[derive(Debug, Clone)]
+struct GltfCamera<T: Scalar> {
+    /// World-space camera position.
+    position: Point3<T>,
+    /// Rotation of the camera frame into the world.
+    orientation: UnitQuaternion<T>,
+    /// Vertical field of view (radians) and optional aspect ratio, or orthographic width.
+    projection: GltfProjection<T>,
+}
+
+/// Projection parameters as specified by a glTF camera.
+#[derive(Debug, Clone, Copy)]
+enum GltfProjection<T: Scalar> {
+    /// Vertical field of view (radians) and optional fixed aspect ratio.
+    Perspective(T, Option<T>),
+    /// View [height, width].
+    Orthographic([T; 2]),
+}
+
+/// Contents of a glTF 2.0 (.gltf or .glb) file: mesh `Assets`, their placements, and cameras.
+#[derive(Debug)]
+pub struct GltfImport<T: Scalar> {
+    /// Meshes of the file, registered by name (or `mesh_<index>` when unnamed or not unique).
+    assets: Assets<T>,
+    /// World-space placement of every mesh node as (mesh identifier, object-to-world transform).
+    instances: Vec<(String, Matrix4<T>)>,
+    /// Cameras of the file in node traversal order.
+    cameras: Vec<GltfCamera<T>>,
+}
+
+impl<T: Scalar> GltfImport<T> {
+    /// Load a glTF 2.0 file.
+    /// Both the JSON (.gltf) and binary (.glb) containers are supported, with buffers embedded as base64 data URIs,
+    /// stored in the binary chunk, or referenced as files relative to the glTF file.
+    /// Only triangle-list primitives are imported; primitives without normals use flat face normals.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The file or a referenced buffer cannot be read
+    /// - The document is not valid glTF JSON
+    /// - An index references a non-existent node, mesh, camera, accessor, buffer view or buffer
+    /// - Accessor data is out of range or of an unsupported type
+    /// - BVH construction fails for any mesh
+    #[cfg(feature = "fs")]
+    pub fn load<P: AsRef<Path>>(bvh_config: &BvhConfig<T>, path: P) -> Result<Self> {
+        Self::load_from(bvh_config, &FileSystemSource::new(), path)
+    }
+
+    /// Load a glTF 2.0 file as in `load`, preparing the `Triangle`s of every mesh with the given `MeshConfig`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The file or a referenced buffer cannot be read
+    /// - The document is not valid glTF JSON
+    /// - An index references a non-existent node, mesh, camera, accessor, buffer view or buffer
+    /// - Accessor data is out of range or of an unsupported type
+    /// - BVH construction fails for any mesh
+    #[cfg(feature = "fs")]
+    pub fn load_with_config<P: AsRef<Path>>(bvh_config: &BvhConfig<T>, mesh_config: MeshConfig, path: P) -> Result<Self> {
+        Self::load_from_with_config(bvh_config, mesh_config, &FileSystemSource::new(), path)
+    }
+
+    /// Load a glTF 2.0 file as in `load`, reading it and any referenced buffer files from the given `AssetSource`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The file or a referenced buffer cannot be read from the source
+    /// - The document is not valid glTF JSON
+    /// - An index references a non-existent node, mesh, camera, accessor, buffer view or buffer
+    /// - Accessor data is out of range or of an unsupported type
+    /// - BVH construction fails for any mesh
+    pub fn load_from<S: AssetSource + ?Sized, P: AsRef<Path>>(bvh_config: &BvhConfig<T>, source: &S, path: P) -> Result<Self> {
+        Self::load_from_with_config(bvh_config, MeshConfig::default(), source, path)
+    }
+
+    /// Load a glTF 2.0 file as in `load_from`, preparing the `Triangle`s of every mesh with the given `MeshConfig`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The file or a referenced buffer cannot be read from the source
+    /// - The document is not valid glTF JSON
+    /// - An index references a non-existent node, mesh, camera, accessor, buffer view or buffer
+    /// - Accessor data is out of range or of an unsupported type
+    /// - BVH construction fails for any mesh
+    pub fn load_from_with_config<S: AssetSource + ?Sized, P: AsRef<Path>>(
+        bvh_config: &BvhConfig<T>,
+        mesh_config: MeshConfig,
+        source: &S,
+        path: P,
+    ) -> Result<Self> {
+        let path = path.as_ref();
+        let bytes = source.read(path)?;
+        let (json, binary_chunk) = if bytes.starts_with(GLB_MAGIC) {
+            split_glb(&bytes)?
+        } else {
+            (bytes.as_slice(), None)
+        };
+        let document: Document = serde_json::from_slice(json)?;
+
+        let buffers = document
+            .buffers
+            .iter()
+            .enumerate()
+            .map(|(index, buffer)| load_buffer(source, path, index, buffer, binary_chunk))
+            .collect::<Result<Vec<_>>>()?;
+
+        let reader = Reader {
+            document: &document,
+            buffers: &buffers,
+        };
+
+        let mut assets = Assets::empty(bvh_config.clone()).with_mesh_config(mesh_config);
+        let mut mesh_ids = Vec::with_capacity(document.meshes.len());
+        for (index, mesh) in document.meshes.iter().enumerate() {
+            // Meshes without any triangle primitives (e.g. points or lines) are skipped
+            let Some(built) = reader.mesh(bvh_config, mesh_config, mesh)? else {
+                mesh_ids.push(None);
+                continue;
+            };
+            let id = match &mesh.name {
+                Some(name) if !name.is_empty() && !assets.contains(name) => name.clone(),
+                _ => format!("mesh_{index}"),
+            };
+            assets = assets.add_mesh(&id, built)?;
+            mesh_ids.push(Some(id));
+        }
+
+        let roots = match document.scene.or_else(|| (!document.scenes.is_empty()).then_some(0)) {
+            Some(scene) => document
+                .scenes
+                .get(scene)
+                .ok_or_else(|| gltf_error(format!("Scene {scene} does not exist")))?
+                .nodes
+                .clone(),
+            None => root_nodes(&document.nodes),
+        };
+
+        let mut instances = Vec::new();
+        let mut cameras = Vec::new();
+        let mut stack: Vec<(usize, Matrix4<T>, usize)> = roots.into_iter().rev().map(|n| (n, Matrix4::identity(), 0)).collect();
+        while let Some((node_index, parent_transform, depth)) = stack.pop() {
+            if depth > document.nodes.len() {
+                return Err(gltf_error("Node hierarchy contains a cycle".to_string()));
+            }
+            let node = document
+                .nodes
+                .get(node_index)
+                .ok_or_else(|| gltf_error(format!("Node {node_index} does not exist")))?;
+            let transform = parent_transform * node_transform(node)?;
+
+            if let Some(mesh) = node.mesh
+                && let Some(id) = mesh_ids
+                    .get(mesh)
+                    .ok_or_else(|| gltf_error(format!("Mesh {mesh} does not exist")))?
+            {
+                instances.push((id.clone(), transform));
+            }
+
+            if let Some(camera) = node.camera {
+                let camera = document
+                    .cameras
+                    .get(camera)
+                    .ok_or_else(|| gltf_error(format!("Camera {camera} does not exist")))?;
+                cameras.push(GltfCamera::new(camera, &transform)?);
+            }
+
+            // Push in reverse so children are visited in document order
+            stack.extend(node.children.iter().rev().map(|&child| (child, transform, depth + 1)));
+        }
+
+        Ok(Self {
+            assets,
+            instances,
+            cameras,
+        })
+    }
+
+    /// Get a reference to the imported mesh `Assets`.
+    #[must_use]
+    pub const fn assets(&self) -> &Assets<T> {
+        &self.assets
+    }
+
+    /// Get the world-space placements of the imported meshes as (mesh identifier, object-to-world transform).
+    #[must_use]
+    pub fn instances(&self) -> &[(String, Matrix4<T>)] {
+        &self.instances
+    }
+
+    /// Get the number of imported cameras.
+    #[must_use]
+    pub const fn num_cameras(&self) -> usize {
+        self.cameras.len()
+    }
+
+    /// Build a `Scene` containing an `Instance` of each placed mesh.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The file contains no mesh nodes
+    /// - Instance or BVH construction fails
+    pub fn build_scene(&self) -> Result<Scene<'_, T>> {
+        self.instances
+            .iter()
+            .try_fold(
+                Scene::builder().with_bvh_config(self.assets.bvh_config.clone()),
+                |builder, (id, transform)| builder.add_instance(self.assets.mesh(id)?, *transform),
+            )?
+            .build()
+    }
+
+    /// Build the `Camera` at the given index with the given resolution [height, width].
+    /// Perspective cameras without a fixed aspect ratio use the aspect ratio of the resolution.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The camera index is out of bounds
+    /// - The resolution is invalid
+    pub fn build_camera(&self, index: usize, resolution: [usize; 2]) -> Result<Camera<T>> {
+        let camera = self
+            .cameras
+            .get(index)
+            .ok_or_else(|| gltf_error(format!("Camera {index} does not exist")))?;
+        let projection = match camera.projection {
+            GltfProjection::Perspective(yfov, aspect_ratio) => {
+                let aspect_ratio = match aspect_ratio {
+                    Some(aspect_ratio) => aspect_ratio,
+                    None => T::try_from_usize(resolution[1])? / T::try_from_usize(resolution[0])?,
+                };
+                Projection::Perspective(yfov * aspect_ratio)
+            }
+            GltfProjection::Orthographic(view) => Projection::OrthographicView(view),
+        };
+        Camera::from_pose(camera.position, camera.orientation, projection, resolution)
+    }
+}
+
+impl<T: Scalar> GltfCamera<T> {
+    /// Place a glTF camera using the world transform of its node.
+    fn new(camera: &DocumentCamera, transform: &Matrix4<T>) -> Result<Self> {
+        let projection = match (&camera.perspective, &camera.orthographic) {
+            (Some(perspective), _) => GltfProjection::Perspective(
+                T::try_from_f64(perspective.yfov)?,
+                perspective.aspect_ratio.map(T::try_from_f64).transpose()?,
+            ),
+            (None, Some(orthographic)) => GltfProjection::Orthographic([
+                T::try_from_f64(2.0 * orthographic.ymag)?,
+                T::try_from_f64(2.0 * orthographic.xmag)?,
+            ]),
+            (None, None) => return Err(gltf_error("Camera has no projection".to_string())),
+        };
+
+        // glTF cameras look down their local -Z axis with +Y up, so their -Z, -X and +Y axes are forward, left and up
+        let position = transform.transform_point(&Point3::origin());
+        let basis = Matrix3::from_columns(&[
+            transform.transform_vector(&-Vector3::z()).normalize(),
+            transform.transform_vector(&-Vector3::x()).normalize(),
+            transform.transform_vector(&Vector3::y()).normalize(),
+        ]);
+        Ok(Self {
+            position,
+            orientation: UnitQuaternion::from_rotation_matrix(&Rotation3::from_matrix(&basis)),
+            projection,
+        })
+    }
+}
+
+/// Accessor data reader over the decoded buffers of a document.
+struct Reader<'a> {
+    /// Parsed glTF document.
+    document: &'a Document,
+    /// Decoded buffer contents.
+    buffers: &'a [Vec<u8>],
+}
+
+impl Reader<'_> {
+    /// Assemble the triangle-list primitives of a glTF mesh into a single `Mesh`.
+    /// Returns `None` if the mesh contains no triangles.
+    fn mesh<T: Scalar>(
+        &self,
+        bvh_config: &BvhConfig<T>,
+        mesh_config: MeshConfig,
+        mesh: &DocumentMesh,
+    ) -> Result<Option<Mesh<T>>> {
+        let mut triangles = Vec::new();
+        for primitive in &mesh.primitives {
+            if primitive.mode.unwrap_or(TRIANGLES_MODE) != TRIANGLES_MODE {
+                continue;
+            }
+
+            let positions = self.read_vec3::<T>(primitive.attributes.POSITION)?;
+            let normals = primitive.attributes.NORMAL.map(|n| self.read_vec3::<T>(n)).transpose()?;
+            let indices = match primitive.indices {
+                Some(accessor) => self.read_indices(accessor)?,
+                None => (0..positions.len()).collect(),
+            };
+
+            for face in indices.chunks_exact(3) {
+                if let Some(&index) = face.iter().find(|&&i| i >= positions.len()) {
+                    return Err(gltf_error(format!(
+                        "Index {index} out of bounds for {} vertices",
+                        positions.len()
+                    )));
+                }
+                let vertices = [0, 1, 2].map(|i| Point3::from(positions[face[i]]));
+                let normals = normals.as_ref().map_or_else(
+                    || [Unit::new_normalize((vertices[1] - vertices[0]).cross(&(vertices[2] - vertices[0]))); 3],
+                    |normals| [0, 1, 2].map(|i| Unit::new_normalize(normals[face[i]])),
+                );
+                triangles.push(Triangle::new(vertices, normals));
+            }
+        }
+        if triangles.is_empty() {
+            return Ok(None);
+        }
+        Mesh::new_with_config(bvh_config, mesh_config, triangles).map(Some)
+    }
+
+    /// Read a `VEC3` float accessor.
+    fn read_vec3<T: Scalar>(&self, accessor: usize) -> Result<Vec<Vector3<T>>> {
+        let (accessor, bytes, stride) = self.view(accessor, 12)?;
+        if accessor.kind != "VEC3" || accessor.component_type != 5126 {
+            return Err(gltf_error(format!(
+                "Expected a VEC3 float accessor, found {} of component type {}",
+                accessor.kind, accessor.component_type
+            )));
+        }
+        (0..accessor.count)
+            .map(|i| {
+                let element = &bytes[i * stride..i * stride + 12];
+                let component = |c: usize| {
+                    let value =
+                        f32::from_le_bytes([element[c * 4], element[c * 4 + 1], element[c * 4 + 2], element[c * 4 + 3]]);
+                    T::try_from_f32(value)
+                };
+                Ok(Vector3::new(component(0)?, component(1)?, component(2)?))
+            })
+            .collect()
+    }
+
+    /// Read a `SCALAR` unsigned integer index accessor.
+    fn read_indices(&self, accessor: usize) -> Result<Vec<usize>> {
+        let component_size = match self.accessor(accessor)?.component_type {
+            5121 => 1,
+            5123 => 2,
+            5125 => 4,
+            component_type => {
+                return Err(gltf_error(format!("Unsupported index component type {component_type}")));
+            }
+        };
+        let (accessor, bytes, stride) = self.view(accessor, component_size)?;
+        if accessor.kind != "SCALAR" {
+            return Err(gltf_error(format!(
+                "Expected a SCALAR index accessor, found {}",
+                accessor.kind
+            )));
+        }
+        Ok((0..accessor.count)
+            .map(|i| {
+                let element = &bytes[i * stride..i * stride + component_size];
+                match component_size {
+                    1 => usize::from(element[0]),
+                    2 => usize::from(u16::from_le_bytes([element[0], element[1]])),
+                    _ => u32::from_le_bytes([element[0], element[1], element[2], element[3]])
+                        .to_usize()
+                        .unwrap_or(usize::MAX),
+                }
+            })
+            .collect())
+    }
+
+    /// Get an accessor by index.
+    fn accessor(&self, index: usize) -> Result<&Accessor> {
+        self.document
+            .accessors
+            .get(index)
+            .ok_or_else(|| gltf_error(format!("Accessor {index} does not exist")))
+    }
+
+    /// Resolve an accessor to its bytes (starting at the first element) and element stride.
+    fn view(&self, index: usize, element_size: usize) -> Result<(&Accessor, &[u8], usize)> {
+        let accessor = self.accessor(index)?;
+        let view_index = accessor
+            .buffer_view
+            .ok_or_else(|| gltf_error(format!("Accessor {index} has no buffer view")))?;
+        let view = self
+            .document
+            .buffer_views
+            .get(view_index)
+            .ok_or_else(|| gltf_error(format!("Buffer view {view_index} does not exist")))?;
+        let buffer = self
+            .buffers
+            .get(view.buffer)
+            .ok_or_else(|| gltf_error(format!("Buffer {} does not exist", view.buffer)))?;
+
+        let stride = view.byte_stride.unwrap_or(element_size);
+        let range = accessor_range(accessor, view, element_size, stride, buffer.len())
+            .ok_or_else(|| gltf_error(format!("Accessor {index} data is out of range")))?;
+        Ok((accessor, &buffer[range], stride))
+    }
+}
+
+// == Utility functions ==
+
+/// Construct an invalid glTF error.
+fn gltf_error(message: String) -> GeodesicError {
+    FileParsingError::InvalidGltfFormat { message }.into()
+}
+
+/// Load the contents of a buffer from a data URI, a file relative to the glTF file, or the binary chunk.
+fn load_buffer<S: AssetSource + ?Sized>(
+    source: &S,
+    gltf_path: &Path,
+    index: usize,
+    buffer: &Buffer,
+    binary_chunk: Option<&[u8]>,
+) -> Result<Vec<u8>> {
+    let Some(uri) = &buffer.uri else {
+        return binary_chunk
+            .map(<[u8]>::to_vec)
+            .ok_or_else(|| gltf_error(format!("Buffer {index} has no URI and there is no binary chunk")));
+    };
+
+    if let Some(data) = uri.strip_prefix("data:") {
+        let (_, encoded) = data
+            .split_once(";base64,")
+            .ok_or_else(|| gltf_error(format!("Buffer {index} data URI is not base64 encoded")))?;
+        return decode_base64(encoded);
+    }
+
+    source.read(&gltf_path.parent().unwrap_or_else(|| Path::new("")).join(uri))
+}
+
+/// Find the byte range of the buffer view holding an accessor, starting at its first element.
+/// Returns `None` if the accessor is empty, or its elements do not fit within the buffer view and buffer.
+/// Offsets are added with checked arithmetic, so out of range values cannot overflow.
+fn accessor_range(
+    accessor: &Accessor,
+    view: &BufferView,
+    element_size: usize,
+    stride: usize,
+    buffer_length: usize,
+) -> Option<Range<usize>> {
+    let start = view.byte_offset.checked_add(accessor.byte_offset)?;
+    let end = view.byte_offset.checked_add(view.byte_length)?;
+    let required = accessor
+        .count
+        .checked_sub(1)?
+        .checked_mul(stride)?
+        .checked_add(element_size)?;
+    (start.checked_add(required)? <= end && end <= buffer_length).then_some(start..end)
+}
+
+/// Split a binary glTF container into its JSON chunk and optional binary chunk.
+fn split_glb(bytes: &[u8]) -> Result<(&[u8], Option<&[u8]>)> {
+    let read_u32 = |offset: usize| {
+        bytes
+            .get(offset..offset + 4)
+            .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+            .ok_or_else(|| gltf_error("Unexpected end of binary container".to_string()))
+    };
+
+    let mut chunks = Vec::new();
+    let mut offset = 12;
+    while offset < bytes.len() {
+        let length = read_u32(offset)?.to_usize().unwrap_or(usize::MAX);
+        let kind = read_u32(offset + 4)?;
+        let data = (offset + 8)
+            .checked_add(length)
+            .and_then(|end| bytes.get(offset + 8..end))
+            .ok_or_else(|| gltf_error("Chunk length exceeds file size".to_string()))?;
+        chunks.push((kind, data));
+        offset += 8 + length;
+    }
+
+    let json = chunks
+        .iter()
+        .find_map(|&(kind, data)| (kind == GLB_JSON_CHUNK).then_some(data))
+        .ok_or_else(|| gltf_error("Binary container has no JSON chunk".to_string()))?;
+    let binary = chunks
+        .iter()
+        .find_map(|&(kind, data)| (kind == GLB_BIN_CHUNK).then_some(data));
+    Ok((json, binary))
+}
+
+/// Find the nodes which are not the child of any other node.
+fn root_nodes(nodes: &[Node]) -> Vec<usize> {
+    (0..nodes.len())
+        .filter(|index| !nodes.iter().any(|node| node.children.contains(index)))
+        .collect()
+}
+
+/// Compute the local transformation matrix of a node.
+fn node_transform<T: Scalar>(node: &Node) -> Result<Matrix4<T>> {
+    if let Some(matrix) = node.matrix {
+        return Ok(Matrix4::from_column_slice(&convert(matrix)?));
+    }
+
+    let [tx, ty, tz]: [T; 3] = convert(node.translation.unwrap_or([0.0; 3]))?;
+    let [qx, qy, qz, qw]: [T; 4] = convert(node.rotation.unwrap_or([0.0, 0.0, 0.0, 1.0]))?;
+    let [sx, sy, sz]: [T; 3] = convert(node.scale.unwrap_or([1.0; 3]))?;
+
+    let translation = Translation3::new(tx, ty, tz);
+    let rotation = UnitQuaternion::from_quaternion(Quaternion::new(qw, qx, qy, qz));
+    let scale = Vector3::new(sx, sy, sz);
+
+    Ok(translation.to_homogeneous() * rotation.to_homogeneous() * Matrix4::new_nonuniform_scaling(&scale))
+}
+
+/// Convert an array of `f64` values to the target numeric type.
+fn convert<T: Scalar, const N: usize>(values: [f64; N]) -> Result<[T; N]> {
+    let mut converted = [T::zero(); N];
+    for (c, &value) in converted.iter_mut().zip(&values) {
+        *c = T::try_from_f64(value)?;
+    }
+    Ok(converted)
+}
+
+#[cfg(test)]
+mod tests {
+    use nalgebra::{Matrix4, Point3, Vector3};
+    use serde_json::{Value, json};
+
+    use super::GltfImport;
+    use crate::{bvh::BvhConfig, error::Result, serialization::base64::encode_base64, source::MemorySource};
+
+    /// Corners of a unit square in the z = 0 plane.
+    const SQUARE: [[f32; 3]; 4] = [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [1.0, 1.0, 0.0], [0.0, 1.0, 0.0]];
+    /// Corner indices of the two triangles of the square.
+    const SQUARE_INDICES: [u32; 6] = [0, 1, 2, 0, 2, 3];
+    /// glTF component types of unsigned byte, short and int indices.
+    const INDEX_TYPES: [u32; 3] = [5121, 5123, 5125];
+
+    /// Build the buffer and document of a scene placing a square mesh,
+    /// with `padding` bytes after each position and indices of the given component type.
+    fn square(index_type: u32, padding: usize, indices: &[u32]) -> (Vec<u8>, Value) {
+        let mut buffer = Vec::new();
+        for position in SQUARE {
+            buffer.extend(position.iter().flat_map(|coord| coord.to_le_bytes()));
+            buffer.resize(buffer.len() + padding, 0);
+        }
+        let positions_length = buffer.len();
+        for &index in indices {
+            match index_type {
+                5121 => buffer.push(u8::try_from(index).unwrap_or(u8::MAX)),
+                5123 => buffer.extend(u16::try_from(index).unwrap_or(u16::MAX).to_le_bytes()),
+                _ => buffer.extend(index.to_le_bytes()),
+            }
+        }
+
+        let mut positions_view = json!({ "buffer": 0, "byteLength": positions_length });
+        if padding > 0 {
+            positions_view["byteStride"] = json!(12 + padding);
+        }
+        let document = json!({
+            "asset": { "version": "2.0" },
+            "scene": 0,
+            "scenes": [{ "nodes": [0] }],
+            "nodes": [{ "mesh": 0, "translation": [0.0, 0.0, 2.0] }],
+            "meshes": [{ "name": "square", "primitives": [{ "attributes": { "POSITION": 0 }, "indices": 1 }] }],
+            "accessors": [
+                { "bufferView": 0, "componentType": 5126, "count": SQUARE.len(), "type": "VEC3" },
+                { "bufferView": 1, "componentType": index_type, "count": indices.len(), "type": "SCALAR" },
+            ],
+            "bufferViews": [
+                positions_view,
+                { "buffer": 0, "byteOffset": positions_length, "byteLength": buffer.len() - positions_length },
+            ],
+        });
+        (buffer, document)
+    }
+
+    /// Pack a buffer and document into a JSON (.gltf) file, embedding the buffer as a base64 data URI.
+    fn gltf(buffer: &[u8], mut document: Value) -> Vec<u8> {
+        document["buffers"] = json!([{
+            "byteLength": buffer.len(),
+            "uri": format!("data:application/octet-stream;base64,{}", encode_base64(buffer)),
+        }]);
+        document.to_string().into_bytes()
+    }
+
+    /// Pack a buffer and document into a binary (.glb) file, storing the buffer in the binary chunk.
+    fn glb(buffer: &[u8], mut document: Value) -> Vec<u8> {
+        document["buffers"] = json!([{ "byteLength": buffer.len() }]);
+        let mut json = document.to_string().into_bytes();
+        json.resize(json.len().next_multiple_of(4), b' ');
+        let mut binary = buffer.to_vec();
+        binary.resize(binary.len().next_multiple_of(4), 0);
+
+        let chunk_header = |data: &[u8], kind: &[u8; 4]| {
+            let mut header = u32::try_from(data.len()).unwrap_or(u32::MAX).to_le_bytes().to_vec();
+            header.extend(kind);
+            header
+        };
+        let length = 12 + 8 + json.len() + 8 + binary.len();
+        let mut bytes = b"glTF".to_vec();
+        bytes.extend(2_u32.to_le_bytes());
+        bytes.extend(u32::try_from(length).unwrap_or(u32::MAX).to_le_bytes());
+        bytes.extend(chunk_header(&json, b"JSON"));
+        bytes.extend(json);
+        bytes.extend(chunk_header(&binary, b"BIN\0"));
+        bytes.extend(binary);
+        bytes
+    }
+
+    /// Import a glTF file held in memory.
+    fn import(path: &str, bytes: Vec<u8>) -> Result<GltfImport<f64>> {
+        let source = MemorySource::new().with_file(path, bytes);
+        GltfImport::load_from(&BvhConfig::default(), &source, path)
+    }
+
+    /// Check that an import holds the placed square.
+    fn assert_square(import: &GltfImport<f64>) -> Result<()> {
+        let triangles = import.assets().mesh("square")?.triangles();
+        assert_eq!(triangles.len(), 2);
+        for (triangle, face) in triangles.iter().zip(SQUARE_INDICES.chunks_exact(3)) {
+            let expected = [0, 1, 2].map(|corner| Point3::from(SQUARE[face[corner] as usize].map(f64::from)));
+            assert_eq!(triangle.vertices(), expected);
+        }
+        assert_eq!(import.instances().len(), 1);
+        assert_eq!(import.instances()[0].0, "square");
+        assert_eq!(import.instances()[0].1, Matrix4::new_translation(&Vector3::z().scale(2.0)));
+        Ok(())
+    }
+
+    #[test]
+    fn imports_each_index_type_and_stride() -> Result<()> {
+        for index_type in INDEX_TYPES {
+            for padding in [0, 4, 12] {
+                let (buffer, document) = square(index_type, padding, &SQUARE_INDICES);
+                assert_square(&import("square.gltf", gltf(&buffer, document.clone()))?)?;
+                assert_square(&import("square.glb", glb(&buffer, document))?)?;
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn imports_unindexed_primitives() -> Result<()> {
+        let (_, mut document) = square(5125, 0, &[]);
+        // Unrolled corners of both triangles, drawn in order
+        let buffer: Vec<u8> = SQUARE_INDICES
+            .iter()
+            .flat_map(|&index| SQUARE[index as usize])
+            .flat_map(f32::to_le_bytes)
+            .collect();
+        document["meshes"][0]["primitives"][0] = json!({ "attributes": { "POSITION": 0 } });
+        document["accessors"][0]["count"] = json!(SQUARE_INDICES.len());
+        document["bufferViews"][0]["byteLength"] = json!(buffer.len());
+        assert_square(&import("square.gltf", gltf(&buffer, document))?)
+    }
+
+    #[test]
+    fn rejects_malformed_documents() {
+        // Each case sets a key of the object at a JSON pointer
+        let cases = [
+            ("position count beyond its view", "/accessors/0", "count", json!(5)),
+            ("empty position accessor", "/accessors/0", "count", json!(0)),
+            ("index count overflowing", "/accessors/1", "count", json!(u64::MAX)),
+            ("accessor offset overflowing", "/accessors/0", "byteOffset", json!(u64::MAX)),
+            ("view length overflowing", "/bufferViews/0", "byteLength", json!(u64::MAX)),
+            ("view stride overflowing", "/bufferViews/0", "byteStride", json!(u64::MAX)),
+            ("view beyond its buffer", "/bufferViews/1", "byteOffset", json!(1024)),
+            ("float indices", "/accessors/1", "componentType", json!(5126)),
+            ("two-component positions", "/accessors/0", "type", json!("VEC2")),
+            ("missing accessor", "/meshes/0/primitives/0", "indices", json!(7)),
+            ("missing buffer", "/bufferViews/1", "buffer", json!(3)),
+            ("missing mesh", "/nodes/0", "mesh", json!(4)),
+            ("missing scene", "", "scene", json!(2)),
+            ("cyclic hierarchy", "/nodes/0", "children", json!([0])),
+        ];
+        for (name, pointer, key, value) in cases {
+            let (buffer, mut document) = square(5123, 0, &SQUARE_INDICES);
+            let _previous = document
+                .pointer_mut(pointer)
+                .and_then(Value::as_object_mut)
+                .expect("fixture should contain the corrupted object")
+                .insert(key.to_string(), value);
+            assert!(
+                import("square.gltf", gltf(&buffer, document.clone())).is_err(),
+                "{name} (.gltf)"
+            );
+            assert!(import("square.glb", glb(&buffer, document)).is_err(), "{name} (.glb)");
+        }
+    }
+
+    #[test]
+    fn rejects_out_of_bounds_indices() {
+        for index_type in INDEX_TYPES {
+            let (buffer, document) = square(index_type, 0, &[0, 1, 2, 0, 2, 4]);
+            assert!(import("square.gltf", gltf(&buffer, document)).is_err());
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_data_uris() {
+        let (buffer, mut document) = square(5123, 0, &SQUARE_INDICES);
+        for uri in [
+            "data:application/octet-stream,AAAA".to_string(),
+            "data:application/octet-stream;base64,AA*A".to_string(),
+            format!(
+                "data:application/octet-stream;base64,{}",
+                encode_base64(&buffer[..buffer.len() - 1])
+            ),
+        ] {
+            document["buffers"] = json!([{ "byteLength": buffer.len(), "uri": uri }]);
+            assert!(import("square.gltf", document.to_string().into_bytes()).is_err(), "{uri}");
+        }
+    }
+
+    #[test]
+    fn rejects_truncated_files() {
+        let (buffer, document) = square(5125, 4, &SQUARE_INDICES);
+        for (path, file) in [
+            ("square.gltf", gltf(&buffer, document.clone())),
+            ("square.glb", glb(&buffer, document)),
+        ] {
+            for length in 0..file.len() {
+                assert!(
+                    import(path, file[..length].to_vec()).is_err(),
+                    "{path} truncated to {length} bytes"
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use nalgebra::{Matrix4, Translation3, Unit, UnitQuaternion, Vector3};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{Result, TransformationError},
+    rt::Pcg32,
+    serialization::SerializedTransform,
+    traits::{FallibleNumeric, Scalar, sample_unit},
+};
+
+/// Serialized placement of copies of a `Mesh` at the points of a regular lattice.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SerializedInstanceGrid<T: Scalar> {
+    /// Number of copies along each axis [x, y, z].
+    pub counts: [usize; 3],
+    /// Distance between neighbouring lattice points along each axis [x, y, z].
+    pub spacing: [T; 3],
+    /// Position of the first lattice point [x, y, z], the origin if omitted.
+    pub origin: Option<[T; 3]>,
+    /// Transformation applied to each copy before it is moved to its lattice point.
+    pub transform: Option<SerializedTransform<T>>,
+}
+
+/// Serialized placement of copies of a `Mesh` at random positions within a box.
+///
+/// The placement only depends on the seed, so a scene file always builds the same `Scene`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SerializedInstanceScatter<T: Scalar> {
+    /// Number of copies.
+    pub count: usize,
+    /// Minimum corner of the box of positions [x, y, z].
+    pub mins: [T; 3],
+    /// Maximum corner of the box of positions [x, y, z].
+    pub maxs: [T; 3],
+    /// Seed of the random placement.
+    pub seed: u64,
+    /// Axis [x, y, z] about which each copy is given a random rotation, unrotated if omitted.
+    pub rotation_axis: Option<[T; 3]>,
+    /// Range [min, max] from which each copy is given a random uniform scale, unscaled if omitted.
+    pub scale_range: Option<[T; 2]>,
+    /// Transformation applied to each copy before it is randomly rotated, scaled and moved.
+    pub transform: Option<SerializedTransform<T>>,
+}
+
+impl<T: Scalar> SerializedInstanceGrid<T> {
+    /// Construct the transformation `Matrix4` of every copy, with the x index varying fastest.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The base transformation fails to build
+    /// - Numeric conversion fails
+    pub fn build(self) -> Result<Vec<Matrix4<T>>> {
+        let base = self
+            .transform
+            .map_or_else(|| Ok(Matrix4::identity()), SerializedTransform::build)?;
+        let origin = self.origin.map_or_else(Vector3::zeros, Vector3::from);
+        let spacing = Vector3::from(self.spacing);
+        let [nx, ny, nz] = self.counts;
+
+        let mut transforms = Vec::with_capacity(nx * ny * nz);
+        for z in 0..nz {
+            for y in 0..ny {
+                for x in 0..nx {
+                    let index = Vector3::new(T::try_from_usize(x)?, T::try_from_usize(y)?, T::try_from_usize(z)?);
+                    let position = origin + index.component_mul(&spacing);
+                    transforms.push(Translation3::from(position).to_homogeneous() * base);
+                }
+            }
+        }
+        Ok(transforms)
+    }
+}
+
+impl<T: Scalar> SerializedInstanceScatter<T> {
+    /// Construct the transformation `Matrix4` of every copy.
+    /// Each copy draws from its own `Pcg32` stream, so changing the count does not move the other copies.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The rotation axis has zero length
+    /// - The base transformation fails to build
+    /// - Numeric conversion fails
+    pub fn build(self) -> Result<Vec<Matrix4<T>>> {
+        let base = self
+            .transform
+            .map_or_else(|| Ok(Matrix4::identity()), SerializedTransform::build)?;
+        let axis = self
+            .rotation_axis
+            .map(|axis| Unit::try_new(Vector3::from(axis), T::zero()).ok_or(TransformationError::DegenerateRotation))
+            .transpose()?;
+        let (mins, maxs) = (Vector3::from(self.mins), Vector3::from(self.maxs));
+
+        (0..self.count)
+            .map(|index| {
+                let mut rng = Pcg32::new(self.seed, index as u64);
+                let mut position = mins;
+                for (coord, max) in position.iter_mut().zip(maxs.iter()) {
+                    *coord += (*max - *coord) * sample_unit::<T, _>(&mut rng)?;
+                }
+                let rotation = match &axis {
+                    Some(axis) => UnitQuaternion::from_axis_angle(axis, T::two_pi() * sample_unit::<T, _>(&mut rng)?),
+                    None => UnitQuaternion::identity(),
+                };
+                let scale = match self.scale_range {
+                    Some([min, max]) => min + (max - min) * sample_unit::<T, _>(&mut rng)?,
+                    None => T::one(),
+                };
+                Ok(Translation3::from(position).to_homogeneous()
+                    * rotation.to_homogeneous()
+                    * Matrix4::new_scaling(scale)
+                    * base)
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,115 @@
+use serde::{Deserialize, Serialize};
+use std::{path::PathBuf, str::FromStr};
+
+use crate::{
+    bvh::BvhConfig,
+    error::Result,
+    geometry::{Mesh, MeshConfig, Triangle, parse_wavefront, triangles_from_buffers},
+    traits::{AssetSource, Scalar},
+};
+
+#[cfg(feature = "fs")]
+use crate::source::FileSystemSource;
+
+/// Serialized representation of a `Mesh` asset.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum SerializedMesh<T: Scalar> {
+    /// Path to a wavefront (.obj) file.
+    File(PathBuf),
+    /// Vertex and face data embedded directly in the asset file.
+    Inline {
+        /// Vertex positions.
+        positions: Vec<[T; 3]>,
+        /// Vertex normals, indexed as `positions`. Flat face normals are used if omitted.
+        normals: Option<Vec<[T; 3]>>,
+        /// Vertex texture coordinates [u, v], indexed as `positions`.
+        uvs: Option<Vec<[T; 2]>>,
+        /// Vertex colours [red, green, blue, alpha], indexed as `positions`.
+        colors: Option<Vec<[T; 4]>>,
+        /// Triangles as zero-based indices into `positions`.
+        faces: Vec<[usize; 3]>,
+    },
+}
+
+impl<T: Scalar + FromStr> SerializedMesh<T> {
+    /// Construct a `Mesh` instance, reading any referenced file from disk.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The referenced file cannot be loaded
+    /// - Inline normals, texture coordinates or colours are given but do not match the number of positions
+    /// - An inline face references a non-existent vertex
+    /// - BVH construction fails
+    #[cfg(feature = "fs")]
+    pub fn build(self, bvh_config: &BvhConfig<T>) -> Result<Mesh<T>> {
+        self.build_from(bvh_config, &FileSystemSource::new())
+    }
+
+    /// Construct a `Mesh` instance as in `build`, preparing its `Triangle`s with the given `MeshConfig`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The referenced file cannot be loaded
+    /// - Inline normals, texture coordinates or colours are given but do not match the number of positions
+    /// - An inline face references a non-existent vertex
+    /// - BVH construction fails
+    #[cfg(feature = "fs")]
+    pub fn build_with_config(self, bvh_config: &BvhConfig<T>, mesh_config: MeshConfig) -> Result<Mesh<T>> {
+        self.build_from_with_config(bvh_config, mesh_config, &FileSystemSource::new())
+    }
+
+    /// Construct a `Mesh` instance, reading any referenced file from the given `AssetSource`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The referenced file cannot be read from the source or parsed
+    /// - Inline normals, texture coordinates or colours are given but do not match the number of positions
+    /// - An inline face references a non-existent vertex
+    /// - BVH construction fails
+    pub fn build_from<S: AssetSource + ?Sized>(self, bvh_config: &BvhConfig<T>, source: &S) -> Result<Mesh<T>> {
+        self.build_from_with_config(bvh_config, MeshConfig::default(), source)
+    }
+
+    /// Construct a `Mesh` instance as in `build_from`, preparing its `Triangle`s with the given `MeshConfig`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The referenced file cannot be read from the source or parsed
+    /// - Inline normals, texture coordinates or colours are given but do not match the number of positions
+    /// - An inline face references a non-existent vertex
+    /// - BVH construction fails
+    pub fn build_from_with_config<S: AssetSource + ?Sized>(
+        self,
+        bvh_config: &BvhConfig<T>,
+        mesh_config: MeshConfig,
+        source: &S,
+    ) -> Result<Mesh<T>> {
+        Mesh::new_with_config(bvh_config, mesh_config, self.triangles_from(source)?)
+    }
+
+    /// Read the `Triangle`s of the mesh, without building a `Bvh`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The referenced file cannot be read from the source or parsed
+    /// - Inline normals, texture coordinates or colours are given but do not match the number of positions
+    /// - An inline face references a non-existent vertex
+    pub fn triangles_from<S: AssetSource + ?Sized>(self, source: &S) -> Result<Vec<Triangle<T>>> {
+        match self {
+            Self::File(path) => parse_wavefront(&source.read_to_string(&path)?),
+            Self::Inline {
+                positions,
+                normals,
+                uvs,
+                colors,
+                faces,
+            } => triangles_from_buffers(&positions, normals.as_deref(), uvs.as_deref(), colors.as_deref(), faces),
+        }
+    }
+}
@@ -1,13 +1,32 @@
 mod assets;
+mod base64;
+mod bvh_cache;
 mod camera;
+mod export;
+mod gltf;
+mod instance_layout;
+mod mesh;
 mod projection;
 mod scene;
 mod scene_object;
+mod scene_patch;
+#[cfg(feature = "watch")]
+mod scene_watcher;
 mod transform;
+mod wavefront_writer;
 
 pub use assets::SerializedAssets;
-pub use camera::SerializedCamera;
+pub use bvh_cache::BvhCache;
+pub use camera::{SerializedCamera, SerializedCameraMetadata, SerializedCameras};
+pub use export::{ExportConfig, SceneExport};
+pub use gltf::GltfImport;
+pub use instance_layout::{SerializedInstanceGrid, SerializedInstanceScatter};
+pub use mesh::SerializedMesh;
 pub use projection::SerializedProjection;
 pub use scene::SerializedScene;
 pub use scene_object::SerializedSceneObject;
-pub use transform::SerializedTransform;
+pub use scene_patch::ScenePatch;
+#[cfg(feature = "watch")]
+pub use scene_watcher::{SceneChange, SceneWatcher};
+pub use transform::{SerializedRotation, SerializedTransform};
+pub use wavefront_writer::WavefrontWriter;
@@ -1,20 +1,26 @@
-use nalgebra::RealField;
 use serde::{Deserialize, Serialize};
 
-use crate::{error::Result, scene::Projection, traits::FallibleNumeric};
+use crate::{
+    error::Result,
+    scene::Projection,
+    traits::{FallibleNumeric, Scalar},
+};
 
 const DEGREES_TO_RADIANS: f64 = std::f64::consts::PI / 180.0;
+const RADIANS_TO_DEGREES: f64 = 180.0 / std::f64::consts::PI;
 
 /// Serialized representation of a `Camera`'s `Projection`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub enum SerializedProjection<T: RealField + Copy> {
+pub enum SerializedProjection<T: Scalar> {
     /// Perspective projection with a field of view (degrees).
     Perspective(T),
     /// Orthographic projection with a specified width.
     Orthographic(T),
+    /// Orthographic projection with a specified view [height, width].
+    OrthographicView([T; 2]),
 }
 
-impl<T: RealField + Copy> SerializedProjection<T> {
+impl<T: Scalar> SerializedProjection<T> {
     /// Construct an `Projection` instance.
     ///
     /// # Errors
@@ -27,6 +33,23 @@ impl<T: RealField + Copy> SerializedProjection<T> {
                 Projection::Perspective(fov * to_rad)
             }
             Self::Orthographic(width) => Projection::Orthographic(width),
+            Self::OrthographicView(view) => Projection::OrthographicView(view),
+        })
+    }
+
+    /// Construct a `SerializedProjection` from a `Projection` instance.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if numeric type conversion fails when converting radians to degrees.
+    pub fn from_projection(projection: &Projection<T>) -> Result<Self> {
+        Ok(match *projection {
+            Projection::Perspective(fov) => {
+                let to_deg = T::try_from_f64(RADIANS_TO_DEGREES)?;
+                Self::Perspective(fov * to_deg)
+            }
+            Projection::Orthographic(width) => Self::Orthographic(width),
+            Projection::OrthographicView(view) => Self::OrthographicView(view),
         })
     }
 }
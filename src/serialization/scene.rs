@@ -1,21 +1,22 @@
-use nalgebra::RealField;
-use num_traits::ToPrimitive;
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 
 use crate::{
+    bvh::Bvh,
     error::{Result, SceneError},
     scene::{Assets, Scene, SceneObject},
-    serialization::SerializedSceneObject,
+    serialization::{BvhCache, SerializedSceneObject},
+    traits::Scalar,
 };
 
 /// Serialized representation of a `Scene`.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct SerializedScene<T: RealField + Copy> {
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SerializedScene<T: Scalar> {
     /// Objects within the `Scene`.
     pub objects: Vec<SerializedSceneObject<T>>,
 }
 
-impl<T: RealField + Copy + ToPrimitive> SerializedScene<T> {
+impl<T: Scalar> SerializedScene<T> {
     /// Construct a `Scene` instance.
     ///
     /// # Errors
@@ -29,11 +30,49 @@ impl<T: RealField + Copy + ToPrimitive> SerializedScene<T> {
             .objects
             .into_iter()
             .map(|obj| obj.build(assets))
-            .collect::<Result<Vec<_>>>()?;
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .flatten()
+            .collect();
 
         if objects.is_empty() {
             return Err(SceneError::EmptyScene.into());
         }
         Scene::new(&assets.bvh_config, objects)
     }
+
+    /// Construct a `Scene` instance, reusing the scene-level `Bvh` from the `BvhCache` if it still fits the objects.
+    /// Otherwise the `Bvh` is rebuilt. The cache is consumed, so its `Bvh` is moved into the `Scene` rather than copied.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - Any scene object fails to build
+    /// - The resulting objects vector is empty
+    /// - Scene construction fails
+    pub fn build_with_bvh(self, assets: &Assets<T>, bvhs: BvhCache<T>) -> Result<Scene<'_, T>> {
+        self.build_with_shared_bvh(assets, bvhs.scene.map(Arc::new))
+    }
+
+    /// Construct a `Scene` instance as in `build_with_bvh`, sharing the scene-level `Bvh` rather than taking it,
+    /// so that a `Scene` rebuilt repeatedly can reuse the `Bvh` of the last build without a copy.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - Any scene object fails to build
+    /// - The resulting objects vector is empty
+    /// - Scene construction fails
+    pub fn build_with_shared_bvh(self, assets: &Assets<T>, bvh: Option<Arc<Bvh<T>>>) -> Result<Scene<'_, T>> {
+        let objects: Vec<SceneObject<T>> = self
+            .objects
+            .into_iter()
+            .map(|obj| obj.build(assets))
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .flatten()
+            .collect();
+
+        Scene::with_shared_bvh(&assets.bvh_config, objects, bvh)
+    }
 }
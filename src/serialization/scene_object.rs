@@ -1,17 +1,18 @@
-use nalgebra::{Matrix4, Point3, RealField, Unit, Vector3};
-use num_traits::ToPrimitive;
+use nalgebra::{Matrix4, Point3, Unit, Vector3};
 use serde::{Deserialize, Serialize};
+use std::ptr;
 
 use crate::{
     error::{Result, SceneError},
     geometry::{Plane, Sphere, Triangle},
     scene::{Assets, Instance, SceneObject},
-    serialization::SerializedTransform,
+    serialization::{SerializedInstanceGrid, SerializedInstanceScatter, SerializedTransform},
+    traits::Scalar,
 };
 
 /// Enumeration of all `Traceable` objects that can be added to a `Scene`.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub enum SerializedSceneObject<T: RealField + Copy> {
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SerializedSceneObject<T: Scalar> {
     /// A sphere primitive.
     Sphere([T; 3], T), // Center and radius
     /// An infinite plane primitive.
@@ -20,10 +21,16 @@ pub enum SerializedSceneObject<T: RealField + Copy> {
     Triangle([[T; 3]; 3], [[T; 3]; 3]), // Vertex positions and normals
     /// A mesh instance with transformation.
     Instance(String, Option<SerializedTransform<T>>), // Mesh identifier and optional transformation
+    /// Many instances of one mesh.
+    InstanceArray(String, Vec<SerializedTransform<T>>), // Mesh identifier and transformation of each instance
+    /// Instances of one mesh on a regular lattice.
+    InstanceGrid(String, SerializedInstanceGrid<T>), // Mesh identifier and lattice
+    /// Instances of one mesh at random positions.
+    InstanceScatter(String, SerializedInstanceScatter<T>), // Mesh identifier and placement
 }
 
-impl<T: RealField + Copy + ToPrimitive> SerializedSceneObject<T> {
-    /// Construct a `SceneObject` instance.
+impl<T: Scalar> SerializedSceneObject<T> {
+    /// Construct the `SceneObject` instances described, which is more than one for instance arrays.
     ///
     /// # Errors
     ///
@@ -32,25 +39,81 @@ impl<T: RealField + Copy + ToPrimitive> SerializedSceneObject<T> {
     /// - Triangle creation fails
     /// - Mesh asset is not found in the provided assets
     /// - Instance creation fails due to invalid transformation
-    pub fn build(self, assets: &Assets<T>) -> Result<SceneObject<'_, T>> {
-        Ok(match self {
-            Self::Sphere(center, radius) => SceneObject::Sphere(Sphere::new(center.into(), radius)?),
+    pub fn build(self, assets: &Assets<T>) -> Result<Vec<SceneObject<'_, T>>> {
+        let (mesh_id, transforms) = match self {
+            Self::Sphere(center, radius) => return Ok(vec![SceneObject::Sphere(Sphere::new(center.into(), radius)?)]),
             Self::Plane(point, normal) => {
                 let point = Point3::new(point[0], point[1], point[2]);
                 let normal = Unit::new_normalize(Vector3::new(normal[0], normal[1], normal[2]));
-                SceneObject::Plane(Plane::new(point, normal))
+                return Ok(vec![SceneObject::Plane(Plane::new(point, normal))]);
             }
-            Self::Triangle(positions, normals) => SceneObject::Triangle(Triangle::new(
-                positions.map(|p| Point3::new(p[0], p[1], p[2])),
-                normals.map(|n| Unit::new_normalize(Vector3::new(n[0], n[1], n[2]))),
-            )),
-            Self::Instance(mesh_id, transform) => {
-                let mesh = assets
-                    .meshes
-                    .get(&mesh_id)
-                    .ok_or_else(|| SceneError::AssetNotFound { id: mesh_id.clone() })?;
-                let transform = transform.map_or_else(|| Ok(Matrix4::identity()), SerializedTransform::build)?;
-                SceneObject::Instance(Instance::new(mesh, transform)?)
+            Self::Triangle(positions, normals) => {
+                return Ok(vec![SceneObject::Triangle(Triangle::new(
+                    positions.map(|p| Point3::new(p[0], p[1], p[2])),
+                    normals.map(|n| Unit::new_normalize(Vector3::new(n[0], n[1], n[2]))),
+                ))]);
+            }
+            Self::Instance(mesh_id, transform) => (
+                mesh_id,
+                vec![transform.map_or_else(|| Ok(Matrix4::identity()), SerializedTransform::build)?],
+            ),
+            Self::InstanceArray(mesh_id, transforms) => (
+                mesh_id,
+                transforms
+                    .into_iter()
+                    .map(SerializedTransform::build)
+                    .collect::<Result<Vec<_>>>()?,
+            ),
+            Self::InstanceGrid(mesh_id, grid) => (mesh_id, grid.build()?),
+            Self::InstanceScatter(mesh_id, scatter) => (mesh_id, scatter.build()?),
+        };
+
+        let mesh = assets.mesh(&mesh_id)?;
+        transforms
+            .into_iter()
+            .map(|transform| Ok(SceneObject::Instance(Instance::new(mesh, transform)?)))
+            .collect()
+    }
+
+    /// Construct a `SerializedSceneObject` from a `SceneObject` instance.
+    /// `Instance`s are serialized individually, by the identifier under which their `Mesh` is registered in the `Assets`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The object is a bare `Mesh` or custom geometry, which have no serialized representation
+    /// - An `Instance` references a `Mesh` which is not registered in the `Assets`
+    pub fn from_scene_object(object: &SceneObject<'_, T>, assets: &Assets<T>) -> Result<Self> {
+        Ok(match object {
+            SceneObject::Sphere(sphere) => Self::Sphere(sphere.center.into(), sphere.radius),
+            SceneObject::Plane(plane) => Self::Plane((*plane.point()).into(), (**plane.normal()).into()),
+            SceneObject::Triangle(triangle) => Self::Triangle(
+                triangle.vertices().map(Into::into),
+                triangle.normals().map(|n| n.into_inner().into()),
+            ),
+            SceneObject::Mesh(_) => {
+                return Err(SceneError::UnserializableObject {
+                    reason: "meshes must be registered as assets and referenced by an instance".to_string(),
+                }
+                .into());
+            }
+            SceneObject::Custom(_) => {
+                return Err(SceneError::UnserializableObject {
+                    reason: "custom geometry has no serialized representation".to_string(),
+                }
+                .into());
+            }
+            SceneObject::Instance(instance) => {
+                let mesh_id = assets
+                    .iter()
+                    .find_map(|(id, mesh)| ptr::eq(mesh, instance.mesh()).then(|| id.to_string()))
+                    .ok_or(SceneError::UnregisteredMesh)?;
+                let transform = if *instance.transform() == Matrix4::identity() {
+                    None
+                } else {
+                    Some(SerializedTransform::from_matrix(instance.transform()))
+                };
+                Self::Instance(mesh_id, transform)
             }
         })
     }
@@ -0,0 +1,106 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{Result, SceneError},
+    serialization::{SerializedScene, SerializedSceneObject, SerializedTransform},
+    traits::Scalar,
+};
+
+/// Set of changes which turn one `SerializedScene` into another.
+///
+/// Object indices refer to positions in the base scene.
+/// Patches are applied by first replacing and retransforming objects in place,
+/// then removing objects, then appending the added objects.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScenePatch<T: Scalar> {
+    /// Instances whose transformation changes, and their new transformation.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub retransformed: Vec<(usize, Option<SerializedTransform<T>>)>,
+    /// Objects replaced by a different object.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub replaced: Vec<(usize, SerializedSceneObject<T>)>,
+    /// Objects removed from the scene.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub removed: Vec<usize>,
+    /// Objects appended to the end of the scene.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub added: Vec<SerializedSceneObject<T>>,
+}
+
+impl<T: Scalar> ScenePatch<T> {
+    /// Check if the patch makes no changes.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.retransformed.is_empty() && self.replaced.is_empty() && self.removed.is_empty() && self.added.is_empty()
+    }
+}
+
+impl<T: Scalar> SerializedScene<T> {
+    /// Compute the `ScenePatch` which turns `self` into `target`.
+    /// Objects are compared position by position.
+    #[must_use]
+    pub fn diff(&self, target: &Self) -> ScenePatch<T> {
+        let mut patch = ScenePatch {
+            retransformed: Vec::new(),
+            replaced: Vec::new(),
+            removed: (target.objects.len()..self.objects.len()).collect(),
+            added: target.objects.iter().skip(self.objects.len()).cloned().collect(),
+        };
+
+        for (index, (base, target)) in self.objects.iter().zip(&target.objects).enumerate() {
+            match (base, target) {
+                _ if base == target => {}
+                (SerializedSceneObject::Instance(base_id, _), SerializedSceneObject::Instance(target_id, transform))
+                    if base_id == target_id =>
+                {
+                    patch.retransformed.push((index, transform.clone()));
+                }
+                _ => patch.replaced.push((index, target.clone())),
+            }
+        }
+
+        patch
+    }
+
+    /// Apply a `ScenePatch` to produce a new `SerializedScene`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The patch references an object index outside of the scene
+    /// - The patch retransforms an object which is not an instance
+    pub fn apply(mut self, patch: &ScenePatch<T>) -> Result<Self> {
+        let count = self.objects.len();
+        let check = |index: usize| {
+            if index >= count {
+                return Err(SceneError::PatchIndexOutOfBounds { index, count });
+            }
+            Ok(index)
+        };
+
+        for (index, object) in &patch.replaced {
+            self.objects[check(*index)?] = object.clone();
+        }
+
+        for (index, transform) in &patch.retransformed {
+            match &mut self.objects[check(*index)?] {
+                SerializedSceneObject::Instance(_, current) => current.clone_from(transform),
+                _ => return Err(SceneError::NotAnInstance { index: *index }.into()),
+            }
+        }
+
+        let mut removed = patch
+            .removed
+            .iter()
+            .map(|&index| check(index))
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        removed.sort_unstable();
+        removed.dedup();
+        for index in removed.into_iter().rev() {
+            let _removed = self.objects.remove(index);
+        }
+
+        self.objects.extend(patch.added.iter().cloned());
+        Ok(self)
+    }
+}
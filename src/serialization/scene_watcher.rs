@@ -0,0 +1,280 @@
+//! Polling watcher which reloads edited scene files.
+
+use std::{
+    fs::metadata,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+        mpsc::{Receiver, Sender, channel},
+    },
+    thread::{JoinHandle, sleep, spawn},
+    time::{Duration, SystemTime},
+};
+
+use crate::{
+    bvh::Bvh,
+    error::Result,
+    scene::{Assets, Camera, MeshCache, Scene},
+    serialization::{SerializedAssets, SerializedCamera, SerializedScene},
+    traits::{Persistable, Scalar},
+};
+
+/// Watched file of a scene which has changed on disk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SceneChange {
+    /// The `SerializedAssets` file.
+    Assets,
+    /// The `SerializedScene` file.
+    Scene,
+    /// The `SerializedCamera` file.
+    Camera,
+}
+
+/// Watcher of the assets, scene and camera files of a scene, which reloads only the parts that change.
+///
+/// A background thread polls the modification times of the files, and sends a `SceneChange` down a channel
+/// whenever one is written. Reloaded assets reuse unchanged `Mesh`es from a `MeshCache`,
+/// and rebuilt `Scene`s reuse the previous scene-level `Bvh` if it still fits.
+/// Mesh files are only checked for changes when the assets file is reloaded.
+#[derive(Debug)]
+pub struct SceneWatcher<T: Scalar> {
+    /// Path of the `SerializedAssets` file.
+    assets_path: PathBuf,
+    /// Path of the `SerializedScene` file.
+    scene_path: PathBuf,
+    /// Path of the `SerializedCamera` file, if one is watched.
+    camera_path: Option<PathBuf>,
+    /// Parsed `Mesh`es, kept across reloads of the assets file.
+    mesh_cache: MeshCache<T>,
+    /// Most recently loaded `Assets`.
+    assets: Assets<T>,
+    /// Most recently loaded scene description.
+    scene: SerializedScene<T>,
+    /// Most recently loaded `Camera`, if one is watched.
+    camera: Option<Camera<T>>,
+    /// Scene-level `Bvh` of the most recently built `Scene`, shared with it.
+    scene_bvh: Option<Arc<Bvh<T>>>,
+    /// Receiving end of the changes reported by the polling thread.
+    changes: Receiver<SceneChange>,
+    /// Flag which keeps the polling thread running.
+    running: Arc<AtomicBool>,
+    /// Handle of the polling thread, joined on drop.
+    poller: Option<JoinHandle<()>>,
+}
+
+impl<T: Scalar + FromStr> SceneWatcher<T>
+where
+    SerializedAssets<T>: Persistable,
+    SerializedScene<T>: Persistable,
+    SerializedCamera<T>: Persistable,
+{
+    /// Load the assets, scene and optional camera files, then start polling them for changes every `interval`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of the files cannot be loaded or built.
+    pub fn new(assets_path: &Path, scene_path: &Path, camera_path: Option<&Path>, interval: Duration) -> Result<Self> {
+        let mut watched = vec![
+            (SceneChange::Assets, assets_path.to_path_buf()),
+            (SceneChange::Scene, scene_path.to_path_buf()),
+        ];
+        if let Some(camera_path) = camera_path {
+            watched.push((SceneChange::Camera, camera_path.to_path_buf()));
+        }
+
+        // Record modification times before loading, so edits made during the initial load are still reported
+        let stamps = watched.iter().map(|(_, path)| modified(path)).collect();
+
+        let mut mesh_cache = MeshCache::new();
+        let assets = SerializedAssets::load_and_build_cached(assets_path, &mut mesh_cache)?;
+        let scene = SerializedScene::load(scene_path)?;
+        let camera = camera_path.map(|path| SerializedCamera::load(path)?.build()).transpose()?;
+
+        let (sender, changes) = channel();
+        let running = Arc::new(AtomicBool::new(true));
+        let poller = {
+            let running = Arc::clone(&running);
+            spawn(move || poll(&watched, stamps, interval, &running, &sender))
+        };
+
+        Ok(Self {
+            assets_path: assets_path.to_path_buf(),
+            scene_path: scene_path.to_path_buf(),
+            camera_path: camera_path.map(Path::to_path_buf),
+            mesh_cache,
+            assets,
+            scene,
+            camera,
+            scene_bvh: None,
+            changes,
+            running,
+            poller: Some(poller),
+        })
+    }
+
+    /// Reload the part of the scene described by a changed file.
+    /// If loading fails, as when a file is read half-written, the previously loaded part is kept.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the changed file cannot be loaded or built.
+    pub fn reload(&mut self, change: SceneChange) -> Result<()> {
+        match change {
+            SceneChange::Assets => {
+                self.assets = SerializedAssets::load_and_build_cached(&self.assets_path, &mut self.mesh_cache)?;
+            }
+            SceneChange::Scene => self.scene = SerializedScene::load(&self.scene_path)?,
+            SceneChange::Camera => {
+                if let Some(path) = &self.camera_path {
+                    self.camera = Some(SerializedCamera::load(path)?.build()?);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Reload every part of the scene reported changed since the last update, without blocking.
+    /// Returns the parts which were reloaded.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a changed file cannot be loaded or built.
+    /// Later changes remain queued, and are reloaded by the next update.
+    pub fn update(&mut self) -> Result<Vec<SceneChange>> {
+        let mut reloaded = Vec::new();
+        while let Ok(change) = self.changes.try_recv() {
+            if !reloaded.contains(&change) {
+                self.reload(change)?;
+                reloaded.push(change);
+            }
+        }
+        Ok(reloaded)
+    }
+}
+
+impl<T: Scalar> SceneWatcher<T> {
+    /// Get the channel of reported changes, to block until a file changes.
+    /// Pass each received `SceneChange` to `reload`.
+    #[must_use]
+    pub const fn changes(&self) -> &Receiver<SceneChange> {
+        &self.changes
+    }
+
+    /// Get the most recently loaded `Assets`.
+    #[must_use]
+    pub const fn assets(&self) -> &Assets<T> {
+        &self.assets
+    }
+
+    /// Get the most recently loaded scene description.
+    #[must_use]
+    pub const fn serialized_scene(&self) -> &SerializedScene<T> {
+        &self.scene
+    }
+
+    /// Get the most recently loaded `Camera`, if one is watched.
+    #[must_use]
+    pub const fn camera(&self) -> Option<&Camera<T>> {
+        self.camera.as_ref()
+    }
+
+    /// Build the `Scene` from the most recently loaded files,
+    /// reusing the scene-level `Bvh` of the previous build if it still fits the objects.
+    /// The `Bvh` is shared with the `Scene` rather than copied, and a failed build keeps it for the next.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `Scene` construction fails.
+    pub fn build_scene(&mut self) -> Result<Scene<'_, T>> {
+        let scene = self
+            .scene
+            .clone()
+            .build_with_shared_bvh(&self.assets, self.scene_bvh.clone())?;
+        self.scene_bvh = scene.shared_bvh().cloned();
+        Ok(scene)
+    }
+}
+
+impl<T: Scalar> Drop for SceneWatcher<T> {
+    /// Stop the polling thread.
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::Relaxed);
+        if let Some(poller) = self.poller.take() {
+            let _joined = poller.join();
+        }
+    }
+}
+
+// == Utility functions ==
+
+/// Poll the modification times of the watched files until stopped, or the receiver is dropped,
+/// reporting each file whose modification time differs from the last seen.
+fn poll(
+    watched: &[(SceneChange, PathBuf)],
+    mut stamps: Vec<Option<SystemTime>>,
+    interval: Duration,
+    running: &AtomicBool,
+    sender: &Sender<SceneChange>,
+) {
+    while running.load(Ordering::Relaxed) {
+        sleep(interval);
+        for ((change, path), stamp) in watched.iter().zip(&mut stamps) {
+            let current = modified(path);
+            if current != *stamp {
+                *stamp = current;
+                if sender.send(*change).is_err() {
+                    return;
+                }
+            }
+        }
+    }
+}
+
+/// Get the modification time of a file, or `None` if it cannot be read, as while it is being replaced.
+fn modified(path: &Path) -> Option<SystemTime> {
+    metadata(path).and_then(|metadata| metadata.modified()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        env::temp_dir,
+        fs::{create_dir_all, remove_dir_all, write},
+        process,
+        sync::Arc,
+        time::Duration,
+    };
+
+    use crate::{error::Result, serialization::SceneWatcher};
+
+    #[test]
+    fn rebuilt_scenes_share_the_bvh_and_failed_builds_keep_it() -> Result<()> {
+        let directory = temp_dir().join(format!("geodesic-scene-watcher-{}", process::id()));
+        create_dir_all(&directory)?;
+        let [assets_path, scene_path] = ["assets.json", "scene.json"].map(|name| directory.join(name));
+        write(&assets_path, r#"{ "bvh_config": null, "meshes": [] }"#)?;
+        write(
+            &scene_path,
+            r#"{ "objects": [{ "Sphere": [[0.0, 0.0, 0.0], 1.0] }, { "Sphere": [[3.0, 0.0, 0.0], 1.0] }] }"#,
+        )?;
+        let mut watcher = SceneWatcher::<f64>::new(&assets_path, &scene_path, None, Duration::from_millis(10))?;
+
+        let first = watcher.build_scene()?.shared_bvh().cloned().expect("spheres are bounded");
+        let second = watcher.build_scene()?.shared_bvh().cloned().expect("spheres are bounded");
+        assert!(Arc::ptr_eq(&first, &second), "rebuild should reuse the shared Bvh");
+
+        // A build referencing a missing asset fails, leaving the cached `Bvh` for the next build
+        let scene = watcher.scene.clone();
+        watcher.scene = serde_json::from_str(r#"{ "objects": [{ "Instance": ["missing", null] }] }"#)?;
+        assert!(watcher.build_scene().is_err());
+        watcher.scene = scene;
+        let third = watcher.build_scene()?.shared_bvh().cloned().expect("spheres are bounded");
+        assert!(Arc::ptr_eq(&first, &third), "failed build should keep the shared Bvh");
+
+        drop(watcher);
+        remove_dir_all(&directory)?;
+        Ok(())
+    }
+}
@@ -1,39 +1,156 @@
-use nalgebra::{Matrix4, RealField, Rotation3, Translation3};
+use nalgebra::{Matrix3, Matrix4, Quaternion, Rotation3, Translation3, Unit, UnitQuaternion, Vector3};
 use serde::{Deserialize, Serialize};
 
-use crate::{error::Result, traits::FallibleNumeric};
+use crate::{
+    error::{Result, TransformationError},
+    traits::{FallibleNumeric, Scalar},
+};
 
 const DEGREES_TO_RADIANS: f64 = std::f64::consts::PI / 180.0;
 
+/// Serialized representation of a three-dimensional rotation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum SerializedRotation<T: Scalar> {
+    /// Euler rotation around axes [x, y, z] (degrees).
+    Euler([T; 3]),
+    /// Quaternion rotation.
+    Quaternion {
+        /// Quaternion components [x, y, z, w]. Normalised on construction.
+        quaternion: [T; 4],
+    },
+    /// Rotation around an arbitrary axis.
+    AxisAngle {
+        /// Rotation axis [x, y, z]. Normalised on construction.
+        axis: [T; 3],
+        /// Rotation angle (degrees).
+        angle: T,
+    },
+}
+
 /// Serialized representation of a three-dimensional transformation.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct SerializedTransform<T: RealField + Copy> {
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SerializedTransform<T: Scalar> {
     /// Translation vector [x, y, z].
     pub translation: Option<[T; 3]>,
-    /// Euler rotation around axes [x, y, z] (degrees).
-    pub rotation: Option<[T; 3]>,
+    /// Rotation as Euler angles, a quaternion, or an axis-angle pair.
+    pub rotation: Option<SerializedRotation<T>>,
     /// Uniform scaling factor.
     pub scale: Option<T>,
+    /// Raw row-major 4x4 transformation matrix, used instead of the translation, rotation and scale components.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub matrix: Option<[[T; 4]; 4]>,
 }
 
-impl<T: RealField + Copy> SerializedTransform<T> {
+impl<T: Scalar> SerializedRotation<T> {
+    /// Construct a `UnitQuaternion` instance.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - Numeric type conversion fails when converting degrees to radians
+    /// - A quaternion or axis has zero length
+    pub fn build(self) -> Result<UnitQuaternion<T>> {
+        Ok(match self {
+            Self::Euler(euler) => {
+                let to_rad = T::try_from_f64(DEGREES_TO_RADIANS)?;
+                UnitQuaternion::from_euler_angles(euler[0] * to_rad, euler[1] * to_rad, euler[2] * to_rad)
+            }
+            Self::Quaternion {
+                quaternion: [x, y, z, w],
+            } => UnitQuaternion::try_new(Quaternion::new(w, x, y, z), T::zero())
+                .ok_or(TransformationError::DegenerateRotation)?,
+            Self::AxisAngle { axis, angle } => {
+                let axis = Unit::try_new(Vector3::from(axis), T::zero()).ok_or(TransformationError::DegenerateRotation)?;
+                UnitQuaternion::from_axis_angle(&axis, angle * T::try_from_f64(DEGREES_TO_RADIANS)?)
+            }
+        })
+    }
+}
+
+impl<T: Scalar> SerializedTransform<T> {
     /// Construct a `Matrix4` instance.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - Numeric type conversion fails when converting degrees to radians
+    /// - A rotation quaternion or axis has zero length
+    /// - A raw `matrix` is given alongside translation, rotation or scale components
     pub fn build(self) -> Result<Matrix4<T>> {
+        if let Some(matrix) = self.matrix {
+            if self.translation.is_some() || self.rotation.is_some() || self.scale.is_some() {
+                return Err(TransformationError::ConflictingComponents.into());
+            }
+            return Ok(Matrix4::from_fn(|row, col| matrix[row][col]));
+        }
+
         let translation = self.translation.map_or_else(Translation3::identity, |translation| {
             Translation3::new(translation[0], translation[1], translation[2])
         });
 
-        let rotation = if let Some(euler) = self.rotation {
-            // Apply rotation (assuming Euler angles in radians)
-            let to_rad = T::try_from_f64(DEGREES_TO_RADIANS)?;
-            Rotation3::from_euler_angles(euler[0] * to_rad, euler[1] * to_rad, euler[2] * to_rad)
-        } else {
-            Rotation3::identity()
-        };
+        let rotation = self
+            .rotation
+            .map_or_else(|| Ok(UnitQuaternion::identity()), SerializedRotation::build)?;
 
         let scale_matrix = self.scale.map_or_else(Matrix4::identity, |scale| Matrix4::new_scaling(scale));
 
         // Combine transformations: Translation * Rotation * Scale
         Ok(translation.to_homogeneous() * rotation.to_homogeneous() * scale_matrix)
     }
+
+    /// Convert a `Matrix4` into a `SerializedTransform`.
+    /// Matrices composed solely of a translation, a rotation and a positive uniform scale are decomposed,
+    /// with the rotation stored as a quaternion; any other matrix is stored verbatim.
+    #[must_use]
+    pub fn from_matrix(matrix: &Matrix4<T>) -> Self {
+        Self::decompose(matrix).unwrap_or_else(|| Self {
+            translation: None,
+            rotation: None,
+            scale: None,
+            matrix: Some(std::array::from_fn(|row| std::array::from_fn(|col| matrix[(row, col)]))),
+        })
+    }
+
+    /// Decompose a `Matrix4` into translation, rotation and uniform scale components, if possible.
+    fn decompose(matrix: &Matrix4<T>) -> Option<Self> {
+        let tolerance = T::default_epsilon().sqrt();
+
+        // Bottom row must be that of an affine transformation
+        if matrix[(3, 0)].abs() > tolerance
+            || matrix[(3, 1)].abs() > tolerance
+            || matrix[(3, 2)].abs() > tolerance
+            || (matrix[(3, 3)] - T::one()).abs() > tolerance
+        {
+            return None;
+        }
+
+        let translation = matrix.fixed_view::<3, 1>(0, 3);
+        let linear: Matrix3<T> = matrix.fixed_view::<3, 3>(0, 0).into_owned();
+
+        // Uniform scale is the length of any basis vector
+        let scale = linear.column(0).norm();
+        if scale <= T::zero() {
+            return None;
+        }
+
+        // Remaining linear part must be a proper rotation
+        let rotation = linear / scale;
+        if (rotation.transpose() * rotation - Matrix3::identity()).amax() > tolerance
+            || (rotation.determinant() - T::one()).abs() > tolerance
+        {
+            return None;
+        }
+        let quaternion = UnitQuaternion::from_rotation_matrix(&Rotation3::from_matrix_unchecked(rotation));
+        let coords = quaternion.coords;
+
+        Some(Self {
+            translation: Some([translation[0], translation[1], translation[2]]),
+            rotation: Some(SerializedRotation::Quaternion {
+                quaternion: [coords[0], coords[1], coords[2], coords[3]],
+            }),
+            scale: Some(scale),
+            matrix: None,
+        })
+    }
 }
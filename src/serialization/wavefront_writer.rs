@@ -0,0 +1,106 @@
+//! Wavefront statement writer.
+
+use nalgebra::{Point3, Vector3};
+use std::fmt::Write as _;
+
+use crate::traits::Scalar;
+
+/// Writer of wavefront (.obj) statements, which keeps count of the vertices, texture coordinates and normals
+/// written so far, so elements can refer to them by their 1-based OBJ indices.
+#[derive(Debug, Clone, Default)]
+pub struct WavefrontWriter {
+    /// OBJ statements written so far.
+    contents: String,
+    /// Number of vertex positions written.
+    vertex_count: usize,
+    /// Number of texture coordinates written.
+    uv_count: usize,
+    /// Number of vertex normals written.
+    normal_count: usize,
+}
+
+impl WavefrontWriter {
+    /// Construct a new empty `WavefrontWriter`.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the number of vertex positions written so far.
+    #[must_use]
+    pub const fn vertex_count(&self) -> usize {
+        self.vertex_count
+    }
+
+    /// Get the OBJ statements written so far.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.contents
+    }
+
+    /// Take the OBJ statements written so far.
+    #[must_use]
+    pub fn into_string(self) -> String {
+        self.contents
+    }
+
+    /// Start a new named object.
+    pub fn object(&mut self, name: &str) {
+        let _unused = writeln!(self.contents, "o {name}");
+    }
+
+    /// Write a vertex position, optionally followed by its [red, green, blue] colour.
+    /// Returns its OBJ index.
+    pub fn vertex<T: Scalar>(&mut self, position: &Point3<T>, color: Option<[T; 3]>) -> usize {
+        let _unused = match color {
+            Some([r, g, b]) => writeln!(self.contents, "v {} {} {} {r} {g} {b}", position.x, position.y, position.z),
+            None => writeln!(self.contents, "v {} {} {}", position.x, position.y, position.z),
+        };
+        self.vertex_count += 1;
+        self.vertex_count
+    }
+
+    /// Write texture coordinates [u, v]. Returns their OBJ index.
+    pub fn uv<T: Scalar>(&mut self, [u, v]: [T; 2]) -> usize {
+        let _unused = writeln!(self.contents, "vt {u} {v}");
+        self.uv_count += 1;
+        self.uv_count
+    }
+
+    /// Write a vertex normal. Returns its OBJ index.
+    pub fn normal<T: Scalar>(&mut self, normal: &Vector3<T>) -> usize {
+        let _unused = writeln!(self.contents, "vn {} {} {}", normal.x, normal.y, normal.z);
+        self.normal_count += 1;
+        self.normal_count
+    }
+
+    /// Write a point element at a vertex.
+    pub fn point(&mut self, vertex: usize) {
+        let _unused = writeln!(self.contents, "p {vertex}");
+    }
+
+    /// Write a polyline element through the given vertices.
+    pub fn line(&mut self, vertices: &[usize]) {
+        self.contents.push('l');
+        for vertex in vertices {
+            let _unused = write!(self.contents, " {vertex}");
+        }
+        self.contents.push('\n');
+    }
+
+    /// Write a triangular face element, with optional texture coordinate and normal indices for each corner.
+    pub fn face(&mut self, vertices: [usize; 3], uvs: Option<[usize; 3]>, normals: Option<[usize; 3]>) {
+        self.contents.push('f');
+        for corner in 0..3 {
+            let _unused = match (uvs, normals) {
+                (Some(uvs), Some(normals)) => {
+                    write!(self.contents, " {}/{}/{}", vertices[corner], uvs[corner], normals[corner])
+                }
+                (Some(uvs), None) => write!(self.contents, " {}/{}", vertices[corner], uvs[corner]),
+                (None, Some(normals)) => write!(self.contents, " {}//{}", vertices[corner], normals[corner]),
+                (None, None) => write!(self.contents, " {}", vertices[corner]),
+            };
+        }
+        self.contents.push('\n');
+    }
+}
@@ -0,0 +1,69 @@
+//! Four-wide `Aabb` packet.
+
+use wide::{CmpLe, f32x4};
+
+use crate::{geometry::Aabb, rt::Ray};
+
+/// Up to four `Aabb`s stored in structure-of-arrays form, so a `Ray` can be slab tested against all of them at once.
+#[derive(Debug, Clone, Copy)]
+pub struct Aabb4 {
+    /// Minimum corner coordinates, one vector per axis.
+    mins: [f32x4; 3],
+    /// Maximum corner coordinates, one vector per axis.
+    maxs: [f32x4; 3],
+    /// Number of occupied lanes.
+    count: usize,
+}
+
+impl Aabb4 {
+    /// Pack up to four `Aabb`s. Any further boxes are ignored.
+    pub fn new(boxes: &[&Aabb<f32>]) -> Self {
+        let count = boxes.len().min(4);
+        let mut mins = [[0.0; 4]; 3];
+        let mut maxs = [[0.0; 4]; 3];
+        for (lane, aabb) in boxes.iter().take(count).enumerate() {
+            for axis in 0..3 {
+                mins[axis][lane] = aabb.mins[axis];
+                maxs[axis][lane] = aabb.maxs[axis];
+            }
+        }
+        Self {
+            mins: mins.map(f32x4::from),
+            maxs: maxs.map(f32x4::from),
+            count,
+        }
+    }
+
+    /// Slab test a `Ray` against every lane.
+    /// Returns the entry distance of each `Aabb` hit within `max_distance`, or infinity for misses and unused lanes.
+    pub fn intersect(&self, ray: &Ray<f32>, max_distance: f32) -> [f32; 4] {
+        let mut t_min = f32x4::ZERO;
+        let mut t_max = f32x4::splat(max_distance);
+        for axis in 0..3 {
+            let origin = f32x4::splat(ray.origin[axis]);
+            let inv_direction = ray.inv_direction[axis];
+
+            // Parallel rays only hit boxes whose slab contains the origin, matching the scalar `Aabb` test
+            if !inv_direction.is_finite() {
+                if inv_direction.is_nan() {
+                    return [f32::INFINITY; 4];
+                }
+                let inside = self.mins[axis].cmp_le(origin) & origin.cmp_le(self.maxs[axis]);
+                t_max = inside.blend(t_max, f32x4::splat(f32::NEG_INFINITY));
+                continue;
+            }
+            let inv_direction = f32x4::splat(inv_direction);
+
+            let t0 = (self.mins[axis] - origin) * inv_direction;
+            let t1 = (self.maxs[axis] - origin) * inv_direction;
+            t_min = t_min.max(t0.min(t1));
+            t_max = t_max.min(t0.max(t1));
+        }
+
+        let mut distances = t_min.cmp_le(t_max).blend(t_min, f32x4::splat(f32::INFINITY)).to_array();
+        for distance in &mut distances[self.count..] {
+            *distance = f32::INFINITY;
+        }
+        distances
+    }
+}
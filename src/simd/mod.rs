@@ -0,0 +1,7 @@
+mod aabb4;
+mod simd_mesh;
+mod triangle8;
+
+pub use aabb4::Aabb4;
+pub use simd_mesh::SimdMesh;
+pub use triangle8::Triangle8;
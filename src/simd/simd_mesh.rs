@@ -0,0 +1,126 @@
+//! Packed `Mesh` for SIMD traversal.
+
+use std::ops::Range;
+
+use crate::{
+    geometry::Mesh,
+    rt::Ray,
+    simd::{Aabb4, Triangle8},
+};
+
+/// Node of a `SimdMesh` hierarchy, mirroring a node of the source `Bvh`.
+#[derive(Debug, Clone)]
+enum SimdNode {
+    /// Internal node referencing the packed bounds of both children, held in lanes 0 and 1.
+    Internal { children: usize, left_child: usize },
+    /// Leaf node referencing a range of `Triangle8` packets.
+    Leaf { packets: Range<usize> },
+}
+
+/// Single-precision `Mesh` `Bvh` and `Triangle`s repacked for SIMD traversal.
+/// Sibling bounds are slab tested together, and leaf `Triangle`s are tested eight at a time.
+#[derive(Debug, Clone)]
+pub struct SimdMesh {
+    /// Bounds of the root node, in lane 0.
+    root: Aabb4,
+    /// Nodes, in the same order as the source `Bvh`.
+    nodes: Vec<SimdNode>,
+    /// Packed bounds of the children of internal nodes.
+    bounds: Vec<Aabb4>,
+    /// Packed leaf `Triangle`s.
+    packets: Vec<Triangle8>,
+}
+
+impl SimdMesh {
+    /// Pack the `Bvh` and `Triangle`s of a `Mesh`.
+    pub fn new(mesh: &Mesh<f32>) -> Self {
+        let bvh = mesh.bvh();
+        let mut bounds = Vec::new();
+        let mut packets = Vec::new();
+        let nodes = bvh
+            .nodes()
+            .iter()
+            .map(|node| {
+                if node.count > 0 {
+                    let start = packets.len();
+                    let indices = &bvh.indices()[node.left_child..node.left_child + node.count];
+                    packets.extend(indices.chunks(8).map(|chunk| Triangle8::new(mesh.triangles(), chunk)));
+                    SimdNode::Leaf {
+                        packets: start..packets.len(),
+                    }
+                } else {
+                    let nodes = bvh.nodes();
+                    bounds.push(Aabb4::new(&[
+                        &nodes[node.left_child].volume,
+                        &nodes[node.left_child + 1].volume,
+                    ]));
+                    SimdNode::Internal {
+                        children: bounds.len() - 1,
+                        left_child: node.left_child,
+                    }
+                }
+            })
+            .collect();
+
+        Self {
+            root: Aabb4::new(&[&bvh.nodes()[0].volume]),
+            nodes,
+            bounds,
+            packets,
+        }
+    }
+
+    /// Get the number of bytes held by the packed nodes, bounds and `Triangle`s.
+    pub const fn memory_bytes(&self) -> usize {
+        self.nodes.len() * size_of::<SimdNode>()
+            + self.bounds.len() * size_of::<Aabb4>()
+            + self.packets.len() * size_of::<Triangle8>()
+    }
+
+    /// Find the closest `Triangle` hit by a `Ray` within `max_distance`, returning its index and distance.
+    /// If `any_hit` is set, the first hit found is returned instead of the closest.
+    pub fn intersect(&self, ray: &Ray<f32>, max_distance: f32, any_hit: bool) -> Option<(usize, f32)> {
+        if self.root.intersect(ray, max_distance)[0].is_infinite() {
+            return None;
+        }
+
+        let mut closest: Option<(usize, f32)> = None;
+        let mut limit = max_distance;
+        let mut stack = Vec::with_capacity(64);
+        stack.push((0, 0.0));
+        while let Some((node_index, entry_distance)) = stack.pop() {
+            // Skip nodes which lie beyond a closer hit found since they were pushed
+            if entry_distance > limit {
+                continue;
+            }
+            match &self.nodes[node_index] {
+                SimdNode::Leaf { packets } => {
+                    for packet in &self.packets[packets.clone()] {
+                        if let Some(hit) = packet.closest(ray, limit, closest.is_none()) {
+                            if any_hit {
+                                return Some(hit);
+                            }
+                            closest = Some(hit);
+                            limit = hit.1;
+                        }
+                    }
+                }
+                SimdNode::Internal { children, left_child } => {
+                    let [left, right, _, _] = self.bounds[*children].intersect(ray, limit);
+                    // Push the farther child first, so the nearer child is visited first and tightens the limit
+                    let (near, far) = if left <= right {
+                        ((left, *left_child), (right, left_child + 1))
+                    } else {
+                        ((right, left_child + 1), (left, *left_child))
+                    };
+                    for (distance, child) in [far, near] {
+                        if distance.is_finite() {
+                            stack.push((child, distance));
+                        }
+                    }
+                }
+            }
+        }
+        closest
+    }
+}
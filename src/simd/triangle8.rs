@@ -0,0 +1,124 @@
+//! Eight-wide `Triangle` packet.
+
+use wide::{CmpGe, CmpGt, CmpLe, CmpLt, f32x8};
+
+use crate::{geometry::Triangle, rt::Ray};
+
+/// Up to eight `Triangle`s stored in structure-of-arrays form, so a `Ray` can be tested against all of them at once.
+#[derive(Debug, Clone, Copy)]
+pub struct Triangle8 {
+    /// First vertex positions, one vector per axis.
+    vertex0: [f32x8; 3],
+    /// Edges from vertex 0 to vertex 1, one vector per axis.
+    edge1: [f32x8; 3],
+    /// Edges from vertex 0 to vertex 2, one vector per axis.
+    edge2: [f32x8; 3],
+    /// Unnormalised normals, one vector per axis.
+    normal: [f32x8; 3],
+    /// Size-relative intersection tolerance of each `Triangle`.
+    epsilon: f32x8,
+    /// Mask of occupied lanes.
+    occupied: f32x8,
+    /// Index of the `Triangle` held in each lane.
+    indices: [usize; 8],
+}
+
+impl Triangle8 {
+    /// Pack up to eight `Triangle`s, selected from `triangles` by `indices`. Any further indices are ignored.
+    pub fn new(triangles: &[Triangle<f32>], indices: &[usize]) -> Self {
+        let count = indices.len().min(8);
+        let mut vertex0 = [[0.0; 8]; 3];
+        let mut edge1 = [[0.0; 8]; 3];
+        let mut edge2 = [[0.0; 8]; 3];
+        let mut normal = [[0.0; 8]; 3];
+        let mut epsilon = [0.0; 8];
+        let mut lane_indices = [0; 8];
+        for (lane, &index) in indices.iter().take(count).enumerate() {
+            let triangle = &triangles[index];
+            let [v0, _, _] = triangle.vertices();
+            let [e1, e2] = triangle.edges();
+            let n = e1.cross(&e2);
+            for axis in 0..3 {
+                vertex0[axis][lane] = v0[axis];
+                edge1[axis][lane] = e1[axis];
+                edge2[axis][lane] = e2[axis];
+                normal[axis][lane] = n[axis];
+            }
+            // Matches the tolerance of the scalar `Triangle` intersection test
+            epsilon[lane] = f32::EPSILON * e1.norm_squared().max(e2.norm_squared()).sqrt();
+            lane_indices[lane] = index;
+        }
+
+        #[expect(clippy::cast_precision_loss, reason = "Lane numbers are below eight.")]
+        let occupied = f32x8::from([0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0]).cmp_lt(f32x8::splat(count as f32));
+
+        Self {
+            vertex0: vertex0.map(f32x8::from),
+            edge1: edge1.map(f32x8::from),
+            edge2: edge2.map(f32x8::from),
+            normal: normal.map(f32x8::from),
+            epsilon: f32x8::from(epsilon),
+            occupied,
+            indices: lane_indices,
+        }
+    }
+
+    /// Moller-Trumbore intersection test of a `Ray` against every lane.
+    /// Returns the distance to each `Triangle` hit, or infinity for misses and unused lanes.
+    pub fn intersect(&self, ray: &Ray<f32>) -> [f32; 8] {
+        let origin = [0, 1, 2].map(|axis| f32x8::splat(ray.origin[axis]));
+        let direction = [0, 1, 2].map(|axis| f32x8::splat(ray.direction[axis]));
+
+        let denominator = dot(&direction, &self.normal);
+        let mut hit = self.occupied & denominator.abs().cmp_ge(self.epsilon);
+
+        let inv_denominator = f32x8::ONE / denominator;
+        let s = [0, 1, 2].map(|axis| origin[axis] - self.vertex0[axis]);
+        let t = -dot(&s, &self.normal) * inv_denominator;
+        hit &= t.cmp_gt(self.epsilon);
+
+        let q = cross(&s, &direction);
+        let u = -dot(&self.edge2, &q) * inv_denominator;
+        hit &= u.cmp_ge(f32x8::ZERO) & u.cmp_le(f32x8::ONE);
+
+        let v = dot(&self.edge1, &q) * inv_denominator;
+        hit &= v.cmp_ge(f32x8::ZERO) & (u + v).cmp_le(f32x8::ONE);
+
+        hit.blend(t, f32x8::splat(f32::INFINITY)).to_array()
+    }
+
+    /// Find the closest `Triangle` hit within `max_distance`, returning its index and distance.
+    /// Hits exactly at `max_distance` are only accepted if `inclusive` is set.
+    pub fn closest(&self, ray: &Ray<f32>, max_distance: f32, inclusive: bool) -> Option<(usize, f32)> {
+        let mut closest = None;
+        let mut limit = max_distance;
+        for (lane, distance) in self.intersect(ray).into_iter().enumerate() {
+            let within = if inclusive && closest.is_none() {
+                distance <= limit
+            } else {
+                distance < limit
+            };
+            if within && distance.is_finite() {
+                closest = Some((self.indices[lane], distance));
+                limit = distance;
+            }
+        }
+        closest
+    }
+}
+
+// == Utility functions ==
+
+/// Lane-wise cross product, matching the operation order of `nalgebra`.
+fn cross(a: &[f32x8; 3], b: &[f32x8; 3]) -> [f32x8; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+/// Lane-wise dot product, matching the operation order of `nalgebra`.
+fn dot(a: &[f32x8; 3], b: &[f32x8; 3]) -> f32x8 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
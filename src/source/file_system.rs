@@ -0,0 +1,67 @@
+//! Filesystem asset source.
+
+use std::{
+    fs::read,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    error::{FileParsingError, Result},
+    traits::AssetSource,
+};
+
+/// `AssetSource` reading files from disk.
+///
+/// Relative paths are tried against each root directory in turn, then the current working directory.
+/// Absolute paths are read as given.
+#[derive(Debug, Clone, Default)]
+pub struct FileSystemSource {
+    /// Root directories searched for relative paths, in priority order.
+    roots: Vec<PathBuf>,
+}
+
+impl FileSystemSource {
+    /// Construct a new `FileSystemSource` with no root directories.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { roots: Vec::new() }
+    }
+
+    /// Append a root directory to search for relative paths.
+    #[must_use]
+    pub fn with_root<P: Into<PathBuf>>(mut self, root: P) -> Self {
+        self.roots.push(root.into());
+        self
+    }
+
+    /// Get the root directories searched for relative paths.
+    #[must_use]
+    pub fn roots(&self) -> &[PathBuf] {
+        &self.roots
+    }
+
+    /// Resolve a path against the first root in which it exists.
+    /// Absolute paths, and paths not found under any root, are returned unchanged.
+    #[must_use]
+    pub fn resolve(&self, path: &Path) -> PathBuf {
+        if path.is_absolute() {
+            return path.to_path_buf();
+        }
+        self.roots
+            .iter()
+            .map(|root| root.join(path))
+            .find(|candidate| candidate.is_file())
+            .unwrap_or_else(|| path.to_path_buf())
+    }
+}
+
+impl AssetSource for FileSystemSource {
+    fn read(&self, path: &Path) -> Result<Vec<u8>> {
+        read(self.resolve(path)).map_err(|_| {
+            FileParsingError::FileNotFound {
+                path: path.display().to_string(),
+            }
+            .into()
+        })
+    }
+}
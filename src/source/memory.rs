@@ -0,0 +1,56 @@
+//! In-memory asset source.
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    error::{FileParsingError, Result},
+    traits::AssetSource,
+};
+
+/// `AssetSource` serving files from an in-memory map of paths to contents.
+/// Useful for packed archives that have been unpacked into memory, or for embedding assets in a binary.
+#[derive(Debug, Clone, Default)]
+pub struct MemorySource {
+    /// File contents keyed by path.
+    files: HashMap<PathBuf, Vec<u8>>,
+}
+
+impl MemorySource {
+    /// Construct a new empty `MemorySource`.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a file to the `MemorySource`, replacing any existing file at the same path.
+    #[must_use]
+    pub fn with_file<P: Into<PathBuf>, C: Into<Vec<u8>>>(mut self, path: P, contents: C) -> Self {
+        let _previous = self.insert(path, contents);
+        self
+    }
+
+    /// Insert a file into the `MemorySource`, returning the previous contents at that path if present.
+    pub fn insert<P: Into<PathBuf>, C: Into<Vec<u8>>>(&mut self, path: P, contents: C) -> Option<Vec<u8>> {
+        self.files.insert(path.into(), contents.into())
+    }
+
+    /// Check if a file exists at the given path.
+    #[must_use]
+    pub fn contains<P: AsRef<Path>>(&self, path: P) -> bool {
+        self.files.contains_key(path.as_ref())
+    }
+}
+
+impl AssetSource for MemorySource {
+    fn read(&self, path: &Path) -> Result<Vec<u8>> {
+        self.files.get(path).cloned().ok_or_else(|| {
+            FileParsingError::FileNotFound {
+                path: path.display().to_string(),
+            }
+            .into()
+        })
+    }
+}
@@ -0,0 +1,9 @@
+//! Implementations of the `AssetSource` trait.
+
+#[cfg(feature = "fs")]
+mod file_system;
+mod memory;
+
+#[cfg(feature = "fs")]
+pub use file_system::FileSystemSource;
+pub use memory::MemorySource;
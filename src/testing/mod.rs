@@ -0,0 +1,3 @@
+mod scene_generator;
+
+pub use scene_generator::SceneGenerator;
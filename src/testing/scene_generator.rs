@@ -0,0 +1,137 @@
+//! Seeded generator of synthetic scenes.
+
+use nalgebra::{Point3, Translation3, Unit, UnitQuaternion, Vector3};
+
+use crate::{
+    bvh::BvhConfig,
+    error::Result,
+    geometry::{Aabb, Mesh, Sphere, Triangle},
+    rt::{Pcg32, Ray},
+    scene::{Instance, Scene, SceneObject},
+    traits::{FallibleNumeric, Scalar, sample_sphere, sample_unit},
+};
+
+/// Generator of reproducible random scenes within a bounding box, for benchmarking and fuzzing `Bvh` traversal.
+///
+/// Every generated item draws from its own `Pcg32` stream, so identical seeds generate identical scenes,
+/// and changing a count does not move the other items.
+#[derive(Debug, Clone)]
+pub struct SceneGenerator<T: Scalar> {
+    /// Box within which items are placed.
+    pub bounds: Aabb<T>,
+    /// Seed of the random placement.
+    pub seed: u64,
+}
+
+impl<T: Scalar> SceneGenerator<T> {
+    /// Construct a new `SceneGenerator` instance.
+    #[must_use]
+    pub const fn new(bounds: Aabb<T>, seed: u64) -> Self {
+        Self { bounds, seed }
+    }
+
+    /// Generate a `Scene` of `count` `Sphere`s centred within the bounds, with radii drawn uniformly from `radius_range`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - `count` is zero
+    /// - A radius is not positive
+    /// - `Scene` construction fails
+    pub fn spheres(&self, bvh_config: &BvhConfig<T>, count: usize, radius_range: [T; 2]) -> Result<Scene<'static, T>> {
+        let objects = (0..count)
+            .map(|index| {
+                let mut rng = Pcg32::new(self.seed, index as u64);
+                let centre = self.sample_point(&mut rng)?;
+                let radius = radius_range[0] + (radius_range[1] - radius_range[0]) * sample_unit::<T, _>(&mut rng)?;
+                Ok(SceneObject::Sphere(Sphere::new(centre, radius)?))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Scene::new(bvh_config, objects)
+    }
+
+    /// Generate a `Mesh` of `count` unconnected `Triangle`s centred within the bounds,
+    /// each with its vertices at most `size` from its centre.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - `count` is zero
+    /// - `Mesh` construction fails
+    pub fn triangle_soup(&self, bvh_config: &BvhConfig<T>, count: usize, size: T) -> Result<Mesh<T>> {
+        let triangles = (0..count)
+            .map(|index| {
+                let mut rng = Pcg32::new(self.seed, index as u64);
+                let centre = self.sample_point(&mut rng)?;
+                let mut vertices = [centre; 3];
+                for vertex in &mut vertices {
+                    *vertex += sample_sphere::<T, _>(&mut rng)?.scale(size * sample_unit::<T, _>(&mut rng)?);
+                }
+                let normal = Unit::try_new((vertices[1] - vertices[0]).cross(&(vertices[2] - vertices[0])), T::zero())
+                    .unwrap_or_else(Vector3::z_axis);
+                Ok(Triangle::new(vertices, [normal; 3]))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Mesh::new(bvh_config, triangles)
+    }
+
+    /// Generate a `Scene` of `Instance`s of a `Mesh` at the centres of a regular lattice of [x, y, z] `counts` cells
+    /// filling the bounds, each randomly oriented.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - Any count is zero
+    /// - `Instance` or `Scene` construction fails
+    /// - Numeric conversion fails
+    pub fn instance_grid<'a>(&self, bvh_config: &BvhConfig<T>, mesh: &'a Mesh<T>, counts: [usize; 3]) -> Result<Scene<'a, T>> {
+        let [nx, ny, nz] = counts;
+        let cell = self.bounds.extents().component_div(&Vector3::new(
+            T::try_from_usize(nx)?,
+            T::try_from_usize(ny)?,
+            T::try_from_usize(nz)?,
+        ));
+        let half = T::try_from_f32(0.5)?;
+
+        let mut objects = Vec::with_capacity(nx * ny * nz);
+        for z in 0..nz {
+            for y in 0..ny {
+                for x in 0..nx {
+                    let mut rng = Pcg32::new(self.seed, objects.len() as u64);
+                    let index = Vector3::new(T::try_from_usize(x)?, T::try_from_usize(y)?, T::try_from_usize(z)?);
+                    let position = self.bounds.mins + index.add_scalar(half).component_mul(&cell);
+                    let rotation = UnitQuaternion::from_axis_angle(
+                        &sample_sphere(&mut rng)?,
+                        T::two_pi() * sample_unit::<T, _>(&mut rng)?,
+                    );
+                    let transform = Translation3::from(position.coords).to_homogeneous() * rotation.to_homogeneous();
+                    objects.push(SceneObject::Instance(Instance::new(mesh, transform)?));
+                }
+            }
+        }
+        Scene::new(bvh_config, objects)
+    }
+
+    /// Generate `count` `Ray`s with origins uniformly distributed within the bounds, and uniformly random directions.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if numeric conversion fails.
+    pub fn rays(&self, count: usize) -> Result<Vec<Ray<T>>> {
+        (0..count)
+            .map(|index| {
+                let mut rng = Pcg32::new(self.seed, index as u64);
+                Ok(Ray::new(self.sample_point(&mut rng)?, sample_sphere(&mut rng)?))
+            })
+            .collect()
+    }
+
+    /// Draw a point uniformly within the bounds.
+    fn sample_point(&self, rng: &mut Pcg32) -> Result<Point3<T>> {
+        let mut point = self.bounds.mins;
+        for (coord, max) in point.iter_mut().zip(self.bounds.maxs.iter()) {
+            *coord += (*max - *coord) * sample_unit::<T, _>(rng)?;
+        }
+        Ok(point)
+    }
+}
@@ -0,0 +1,29 @@
+use std::path::Path;
+
+use crate::error::{FileParsingError, Result};
+
+/// A provider of raw asset file contents, such as a directory on disk or an in-memory archive.
+pub trait AssetSource {
+    /// Read the full contents of the asset at the given path.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the asset does not exist or cannot be read.
+    fn read(&self, path: &Path) -> Result<Vec<u8>>;
+
+    /// Read the full contents of the asset at the given path as UTF-8 text.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The asset does not exist or cannot be read
+    /// - The contents are not valid UTF-8
+    fn read_to_string(&self, path: &Path) -> Result<String> {
+        String::from_utf8(self.read(path)?).map_err(|_| {
+            FileParsingError::InvalidUtf8 {
+                path: path.display().to_string(),
+            }
+            .into()
+        })
+    }
+}
@@ -1,17 +1,80 @@
 //! Bounded geometry trait.
 
-use nalgebra::RealField;
+use nalgebra::Vector3;
 use std::borrow::Cow;
 
-use crate::{error::Result, geometry::Aabb};
+use crate::{
+    error::{GeometryError, Result},
+    geometry::Aabb,
+    traits::Scalar,
+};
+
+/// Extent of a geometry: either a finite axis-aligned bounding box, or unbounded (such as an infinite `Plane`).
+#[derive(Debug, Clone)]
+pub enum Bounds<'a, T: Scalar> {
+    /// The geometry lies entirely within the `Aabb`.
+    Finite(Cow<'a, Aabb<T>>),
+    /// The geometry extends infinitely, so no `Aabb` can enclose it.
+    Unbounded,
+}
+
+impl<'a, T: Scalar> Bounds<'a, T> {
+    /// Check if the geometry is enclosed by a finite `Aabb`.
+    #[must_use]
+    pub const fn is_finite(&self) -> bool {
+        matches!(self, Self::Finite(_))
+    }
+
+    /// Get the `Aabb` of the geometry, or `None` if it is unbounded.
+    #[must_use]
+    pub const fn finite(self) -> Option<Cow<'a, Aabb<T>>> {
+        match self {
+            Self::Finite(aabb) => Some(aabb),
+            Self::Unbounded => None,
+        }
+    }
+
+    /// Get the `Aabb` of the geometry.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the geometry is unbounded.
+    pub fn try_finite(self) -> Result<Cow<'a, Aabb<T>>> {
+        Ok(self.finite().ok_or(GeometryError::UnboundedGeometry)?)
+    }
+
+    /// Combine the `Bounds` of two geometries. The result is unbounded if either is.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bounding boxes cannot be merged.
+    pub fn merge(&self, other: &Bounds<'_, T>) -> Result<Bounds<'static, T>> {
+        Ok(match (self, other) {
+            (Self::Finite(a), Bounds::Finite(b)) => Bounds::Finite(Cow::Owned(a.merge(b)?)),
+            _ => Bounds::Unbounded,
+        })
+    }
+}
 
 /// Types implementing this type can be bounded by an axis-aligned bounding box (`Aabb`).
-pub trait Bounded<T: RealField + Copy> {
-    /// Get the axis-aligned bounding box of the geometry.
+pub trait Bounded<T: Scalar> {
+    /// Get the `Bounds` of the geometry: its axis-aligned bounding box, or `Unbounded` if it extends infinitely.
     ///
     /// # Errors
     ///
     /// Returns an error if the bounding box calculation fails due to invalid
     /// geometry parameters or mathematical operations.
-    fn aabb(&self) -> Result<Cow<Aabb<T>>>;
+    fn aabb(&self) -> Result<Bounds<'_, T>>;
+
+    /// Get the interval [min, max] of the dot products of the points of the geometry with a direction,
+    /// or `None` if it is unbounded. Used to fit bounding volumes, such as a `Kdop`, with slabs along other directions.
+    ///
+    /// The default projects the `Aabb`, which is conservative; geometry with a tighter span should override it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bounding box calculation fails.
+    fn span(&self, direction: &Vector3<T>) -> Result<Option<[T; 2]>> {
+        Ok(self.aabb()?.finite().map(|aabb| aabb.span(direction)))
+    }
 }
@@ -0,0 +1,54 @@
+//! Bounding volume trait.
+
+use std::{borrow::Cow, fmt::Debug};
+
+use crate::{
+    error::Result,
+    geometry::Aabb,
+    rt::Ray,
+    traits::{Bounded, Scalar},
+};
+
+/// Convex volume enclosing geometry, stored in the nodes of a `Bvh`.
+///
+/// An `Aabb` is the cheapest to test, while a `Kdop` adds slabs along diagonal directions,
+/// fitting thin diagonal geometry more tightly so fewer `Ray`s enter nodes they miss everything in.
+pub trait BoundingVolume<T: Scalar>: Clone + Debug {
+    /// Construct the volume enclosing a shape, or `None` if the shape is unbounded.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the extent of the shape cannot be computed.
+    fn enclose<B: Bounded<T> + ?Sized>(shape: &B) -> Result<Option<Self>>;
+
+    /// Construct the volume enclosing this volume and another.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the merged volume has invalid bounds.
+    fn merge(&self, other: &Self) -> Result<Self>;
+
+    /// Grow the volume outwards by `margin` in every direction.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the expanded volume has invalid bounds.
+    fn expand(&self, margin: T) -> Result<Self>;
+
+    /// Check if this volume entirely encloses another.
+    fn contains(&self, other: &Self) -> bool;
+
+    /// Check if this volume may overlap another. Touching volumes overlap.
+    fn overlaps(&self, other: &Self) -> bool;
+
+    /// Distance along the `Ray` at which it enters the volume, if it does so within `max_distance`.
+    /// A `Ray` starting inside the volume enters at zero.
+    fn intersect_within(&self, ray: &Ray<T>, max_distance: T) -> Option<T>;
+
+    /// Get the axis-aligned bounding box of the volume.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the box has invalid bounds.
+    fn bounding_box(&self) -> Result<Cow<'_, Aabb<T>>>;
+}
@@ -0,0 +1,125 @@
+//! Debug visualisation logging trait.
+
+use nalgebra::Point3;
+
+use crate::{
+    bvh::Bvh,
+    error::Result,
+    geometry::{Aabb, Triangle},
+    rt::{Hit, Ray},
+    scene::Scene,
+    serialization::{ExportConfig, SceneExport},
+    traits::{Bounded, Scalar},
+};
+
+/// A sink for 3D debug geometry, such as a rerun.io recording stream or a file opened in an external viewer.
+///
+/// Only the primitive methods need implementing; `Scene`s, `Bvh`s, `Ray`s and `Hit`s are logged through them.
+/// Entities are named by `/`-separated paths, so related geometry can be grouped and toggled in the viewer.
+pub trait DebugLogger<T: Scalar> {
+    /// Log a set of points.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the logger fails to record the points.
+    fn log_points(&mut self, entity: &str, points: &[Point3<T>]) -> Result<()>;
+
+    /// Log a set of line segments, each given by its [start, end] points.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the logger fails to record the segments.
+    fn log_segments(&mut self, entity: &str, segments: &[[Point3<T>; 2]]) -> Result<()>;
+
+    /// Log a set of `Aabb`s as wireframe boxes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the logger fails to record the boxes.
+    fn log_boxes(&mut self, entity: &str, boxes: &[Aabb<T>]) -> Result<()>;
+
+    /// Log a triangle mesh.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the logger fails to record the triangles.
+    fn log_triangles(&mut self, entity: &str, triangles: &[Triangle<T>]) -> Result<()>;
+
+    /// Log `Ray`s as segments of the given length from their origins.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the logger fails to record the segments.
+    fn log_rays(&mut self, entity: &str, rays: &[Ray<T>], length: T) -> Result<()> {
+        let segments: Vec<_> = rays.iter().map(|ray| [ray.origin, ray.at(length)]).collect();
+        self.log_segments(entity, &segments)
+    }
+
+    /// Log the `Hit`s of `Ray`s: the segment from each origin to its hit at `{entity}/rays`,
+    /// the hit points at `{entity}/points`, and the interpolated normals, scaled by `normal_length`, at `{entity}/normals`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the logger fails to record the geometry.
+    fn log_hits(&mut self, entity: &str, hits: &[(Ray<T>, Hit<T>)], normal_length: T) -> Result<()> {
+        let points: Vec<_> = hits.iter().map(|(ray, hit)| ray.at(hit.distance)).collect();
+        let rays: Vec<_> = hits
+            .iter()
+            .zip(&points)
+            .map(|((ray, _), point)| [ray.origin, *point])
+            .collect();
+        let normals: Vec<_> = hits
+            .iter()
+            .zip(&points)
+            .map(|((_, hit), point)| [*point, point + hit.interpolated_normal.scale(normal_length)])
+            .collect();
+        self.log_segments(&format!("{entity}/rays"), &rays)?;
+        self.log_points(&format!("{entity}/points"), &points)?;
+        self.log_segments(&format!("{entity}/normals"), &normals)
+    }
+
+    /// Log the node boxes of a `Bvh` down to `max_depth`, grouped by depth at `{entity}/depth_{depth}`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the logger fails to record the boxes.
+    fn log_bvh(&mut self, entity: &str, bvh: &Bvh<T>, max_depth: usize) -> Result<()> {
+        let mut levels: Vec<Vec<Aabb<T>>> = Vec::new();
+        bvh.visit(|node, depth| {
+            if levels.len() <= depth {
+                levels.resize_with(depth + 1, Vec::new);
+            }
+            levels[depth].push(node.volume.clone());
+            depth < max_depth
+        });
+        for (depth, boxes) in levels.iter().enumerate() {
+            self.log_boxes(&format!("{entity}/depth_{depth}"), boxes)?;
+        }
+        Ok(())
+    }
+
+    /// Log a `Scene`: the world-space triangles of each object at `{entity}/{object}`, as exported by `SceneExport`,
+    /// and the bounding box of every finite object at `{entity}/bounds`.
+    /// Comparing the two shows whether an `Instance` transform moves its `Mesh` where its bounds say it is.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The scene cannot be exported with the given configuration
+    /// - An object bounding box cannot be computed
+    /// - The logger fails to record the geometry
+    fn log_scene(&mut self, entity: &str, scene: &Scene<'_, T>, config: &ExportConfig<T>) -> Result<()> {
+        let export = SceneExport::new(scene, config)?;
+        for (name, triangles) in export.groups() {
+            self.log_triangles(&format!("{entity}/{name}"), triangles)?;
+        }
+
+        let mut bounds = Vec::with_capacity(scene.objects().len());
+        for object in scene.objects() {
+            if let Some(aabb) = object.aabb()?.finite() {
+                bounds.push(aabb.into_owned());
+            }
+        }
+        self.log_boxes(&format!("{entity}/bounds"), &bounds)
+    }
+}
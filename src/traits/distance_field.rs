@@ -0,0 +1,17 @@
+//! Distance field trait.
+
+use nalgebra::Point3;
+
+use crate::{error::Result, traits::Scalar};
+
+/// Trait for geometry which can report the signed distance from a point to its surface,
+/// for proximity queries and sphere tracing.
+pub trait DistanceField<T: Scalar> {
+    /// Signed distance from `point` to the surface of the geometry: negative inside, zero on the surface,
+    /// and positive outside.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the distance calculation fails due to invalid geometry or numeric conversion.
+    fn signed_distance(&self, point: &Point3<T>) -> Result<T>;
+}
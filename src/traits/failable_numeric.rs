@@ -1,7 +1,6 @@
-use nalgebra::RealField;
 use std::any::type_name;
 
-use crate::error::NumericError;
+use crate::{error::NumericError, traits::Scalar};
 
 pub trait FallibleNumeric<T> {
     type Error;
@@ -14,7 +13,7 @@ pub trait FallibleNumeric<T> {
     fn try_from_usize(n: usize) -> Result<T, Self::Error>;
 }
 
-impl<T: RealField + Copy> FallibleNumeric<T> for T {
+impl<T: Scalar> FallibleNumeric<T> for T {
     type Error = NumericError;
 
     fn try_min_value() -> Result<T, Self::Error> {
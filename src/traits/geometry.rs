@@ -0,0 +1,216 @@
+//! Geometry trait.
+
+use nalgebra::Vector3;
+use std::{borrow::Cow, sync::Arc};
+
+use crate::{
+    error::Result,
+    geometry::Aabb,
+    rt::{Hit, Ray},
+    traits::{Bounded, Bounds, Scalar, Traceable},
+};
+
+/// Object-safe combination of `Bounded` and `Traceable`, allowing user-defined geometry to be stored as
+/// `Box<dyn Geometry<T>>` and traced alongside the built-in primitives, e.g. within a `Bvh`.
+pub trait Geometry<T: Scalar>: Bounded<T> + Traceable<T> {}
+
+// Automatic blanket implementation for any types satisfying the trait bounds.
+impl<T: Scalar, G: Bounded<T> + Traceable<T> + ?Sized> Geometry<T> for G {}
+
+impl<T: Scalar, G: Bounded<T> + ?Sized> Bounded<T> for Box<G> {
+    fn aabb(&self) -> Result<Bounds<'_, T>> {
+        (**self).aabb()
+    }
+
+    fn span(&self, direction: &Vector3<T>) -> Result<Option<[T; 2]>> {
+        (**self).span(direction)
+    }
+}
+
+impl<T: Scalar, G: Bounded<T> + ?Sized> Bounded<T> for &G {
+    fn aabb(&self) -> Result<Bounds<'_, T>> {
+        (**self).aabb()
+    }
+
+    fn span(&self, direction: &Vector3<T>) -> Result<Option<[T; 2]>> {
+        (**self).span(direction)
+    }
+}
+
+impl<T: Scalar, G: Bounded<T> + ?Sized> Bounded<T> for Arc<G> {
+    fn aabb(&self) -> Result<Bounds<'_, T>> {
+        (**self).aabb()
+    }
+
+    fn span(&self, direction: &Vector3<T>) -> Result<Option<[T; 2]>> {
+        (**self).span(direction)
+    }
+}
+
+impl<T: Scalar, G: Traceable<T> + ?Sized> Traceable<T> for Box<G> {
+    fn intersect(&self, ray: &Ray<T>) -> Result<Option<Hit<T>>> {
+        (**self).intersect(ray)
+    }
+
+    fn intersect_t(&self, ray: &Ray<T>) -> Result<Option<T>> {
+        (**self).intersect_t(ray)
+    }
+
+    fn intersect_within(&self, ray: &Ray<T>, max_distance: T) -> Result<Option<Hit<T>>> {
+        (**self).intersect_within(ray, max_distance)
+    }
+
+    fn intersect_any(&self, ray: &Ray<T>, max_distance: T) -> Result<bool> {
+        (**self).intersect_any(ray, max_distance)
+    }
+
+    fn sphere_cast(&self, ray: &Ray<T>, radius: T) -> Result<Option<Hit<T>>> {
+        (**self).sphere_cast(ray, radius)
+    }
+}
+
+impl<T: Scalar, G: Traceable<T> + ?Sized> Traceable<T> for &G {
+    fn intersect(&self, ray: &Ray<T>) -> Result<Option<Hit<T>>> {
+        (**self).intersect(ray)
+    }
+
+    fn intersect_t(&self, ray: &Ray<T>) -> Result<Option<T>> {
+        (**self).intersect_t(ray)
+    }
+
+    fn intersect_within(&self, ray: &Ray<T>, max_distance: T) -> Result<Option<Hit<T>>> {
+        (**self).intersect_within(ray, max_distance)
+    }
+
+    fn intersect_any(&self, ray: &Ray<T>, max_distance: T) -> Result<bool> {
+        (**self).intersect_any(ray, max_distance)
+    }
+
+    fn sphere_cast(&self, ray: &Ray<T>, radius: T) -> Result<Option<Hit<T>>> {
+        (**self).sphere_cast(ray, radius)
+    }
+}
+
+impl<T: Scalar, G: Traceable<T> + ?Sized> Traceable<T> for Arc<G> {
+    fn intersect(&self, ray: &Ray<T>) -> Result<Option<Hit<T>>> {
+        (**self).intersect(ray)
+    }
+
+    fn intersect_t(&self, ray: &Ray<T>) -> Result<Option<T>> {
+        (**self).intersect_t(ray)
+    }
+
+    fn intersect_within(&self, ray: &Ray<T>, max_distance: T) -> Result<Option<Hit<T>>> {
+        (**self).intersect_within(ray, max_distance)
+    }
+
+    fn intersect_any(&self, ray: &Ray<T>, max_distance: T) -> Result<bool> {
+        (**self).intersect_any(ray, max_distance)
+    }
+
+    fn sphere_cast(&self, ray: &Ray<T>, radius: T) -> Result<Option<Hit<T>>> {
+        (**self).sphere_cast(ray, radius)
+    }
+}
+
+/// The union of the element bounds, which is unbounded if any element is. An empty slice has an 'empty' `Aabb`.
+impl<T: Scalar, G: Bounded<T>> Bounded<T> for [G] {
+    fn aabb(&self) -> Result<Bounds<'_, T>> {
+        self.iter()
+            .try_fold(Bounds::Finite(Cow::Owned(Aabb::empty()?)), |bounds, geometry| {
+                bounds.merge(&geometry.aabb()?)
+            })
+    }
+}
+
+/// Linear search over the elements, returning the closest `Hit`, indexed by the element which was hit.
+/// Prefer a `Bvh` for more than a handful of elements.
+impl<T: Scalar, G: Traceable<T>> Traceable<T> for [G] {
+    fn intersect(&self, ray: &Ray<T>) -> Result<Option<Hit<T>>> {
+        let mut closest: Option<Hit<T>> = None;
+        for (index, geometry) in self.iter().enumerate() {
+            if let Some(mut hit) = geometry.intersect(ray)?
+                && closest.as_ref().is_none_or(|c| hit.distance < c.distance)
+            {
+                hit.index = index;
+                closest = Some(hit);
+            }
+        }
+        Ok(closest)
+    }
+
+    fn intersect_t(&self, ray: &Ray<T>) -> Result<Option<T>> {
+        let mut closest: Option<T> = None;
+        for geometry in self {
+            if let Some(distance) = geometry.intersect_t(ray)?
+                && closest.is_none_or(|c| distance < c)
+            {
+                closest = Some(distance);
+            }
+        }
+        Ok(closest)
+    }
+
+    fn intersect_within(&self, ray: &Ray<T>, max_distance: T) -> Result<Option<Hit<T>>> {
+        let mut closest: Option<Hit<T>> = None;
+        let mut limit = max_distance;
+        for (index, geometry) in self.iter().enumerate() {
+            if let Some(mut hit) = geometry.intersect_within(ray, limit)? {
+                hit.index = index;
+                limit = hit.distance;
+                closest = Some(hit);
+            }
+        }
+        Ok(closest)
+    }
+
+    fn intersect_any(&self, ray: &Ray<T>, max_distance: T) -> Result<bool> {
+        for geometry in self {
+            if geometry.intersect_any(ray, max_distance)? {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    fn sphere_cast(&self, ray: &Ray<T>, radius: T) -> Result<Option<Hit<T>>> {
+        let mut closest: Option<Hit<T>> = None;
+        for (index, geometry) in self.iter().enumerate() {
+            if let Some(mut hit) = geometry.sphere_cast(ray, radius)?
+                && closest.as_ref().is_none_or(|c| hit.distance < c.distance)
+            {
+                hit.index = index;
+                closest = Some(hit);
+            }
+        }
+        Ok(closest)
+    }
+}
+
+impl<T: Scalar, G: Bounded<T>> Bounded<T> for Vec<G> {
+    fn aabb(&self) -> Result<Bounds<'_, T>> {
+        self.as_slice().aabb()
+    }
+}
+
+impl<T: Scalar, G: Traceable<T>> Traceable<T> for Vec<G> {
+    fn intersect(&self, ray: &Ray<T>) -> Result<Option<Hit<T>>> {
+        self.as_slice().intersect(ray)
+    }
+
+    fn intersect_t(&self, ray: &Ray<T>) -> Result<Option<T>> {
+        self.as_slice().intersect_t(ray)
+    }
+
+    fn intersect_within(&self, ray: &Ray<T>, max_distance: T) -> Result<Option<Hit<T>>> {
+        self.as_slice().intersect_within(ray, max_distance)
+    }
+
+    fn intersect_any(&self, ray: &Ray<T>, max_distance: T) -> Result<bool> {
+        self.as_slice().intersect_any(ray, max_distance)
+    }
+
+    fn sphere_cast(&self, ray: &Ray<T>, radius: T) -> Result<Option<Hit<T>>> {
+        self.as_slice().sphere_cast(ray, radius)
+    }
+}
@@ -1,9 +1,29 @@
+mod asset_source;
 mod bounded;
+mod bounding_volume;
+mod debug_logger;
+mod distance_field;
 mod failable_numeric;
+mod geometry;
 mod persistable;
+mod scalar;
+mod solid_angle;
+mod split_strategy;
+mod surface_sample;
 mod traceable;
+mod transformable;
 
-pub use bounded::Bounded;
+pub use asset_source::AssetSource;
+pub use bounded::{Bounded, Bounds};
+pub use bounding_volume::BoundingVolume;
+pub use debug_logger::DebugLogger;
+pub use distance_field::DistanceField;
 pub use failable_numeric::FallibleNumeric;
+pub use geometry::Geometry;
 pub use persistable::Persistable;
+pub use scalar::Scalar;
+pub use solid_angle::SolidAngle;
+pub use split_strategy::SplitStrategy;
+pub use surface_sample::{SurfaceSample, sample_cosine_hemisphere, sample_sphere, sample_unit};
 pub use traceable::Traceable;
+pub use transformable::{Transformable, normal_matrix, uniform_scale};
@@ -1,4 +1,5 @@
 use serde::{Serialize, de::DeserializeOwned};
+#[cfg(feature = "fs")]
 use std::{
     fs::File,
     io::{self, Read, Write},
@@ -26,28 +27,90 @@ pub trait Persistable: Serialize + DeserializeOwned + Sized {
     }
 
     /// Save (serialize) `self` to the given file path (overwrites if exists).
+    /// Paths ending in `.gz` are gzip-compressed (requires the `gzip` feature).
+    /// Requires the `fs` feature.
     ///
     /// # Errors
     ///
-    /// Returns an error if the file cannot be created, written to, or the object cannot be serialized to JSON.
+    /// Returns an error if:
+    /// - The file cannot be created or written to
+    /// - The object cannot be serialized to JSON
+    /// - The path ends in `.gz` and the `gzip` feature is disabled
+    #[cfg(feature = "fs")]
     fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), io::Error> {
         let json = self.to_json().map_err(io::Error::other)?;
+        if is_gzip(path.as_ref()) {
+            return write_gzip(path.as_ref(), json.as_bytes());
+        }
         let mut file = File::create(path)?;
         file.write_all(json.as_bytes())
     }
 
     /// Load (deserialize) an instance from the given file path.
+    /// Paths ending in `.gz` are gzip-decompressed (requires the `gzip` feature).
+    /// Requires the `fs` feature.
     ///
     /// # Errors
     ///
-    /// Returns an error if the file does not exist, cannot be read, or the contents are not valid JSON.
+    /// Returns an error if:
+    /// - The file does not exist or cannot be read
+    /// - The contents are not valid JSON
+    /// - The path ends in `.gz` and the `gzip` feature is disabled
+    #[cfg(feature = "fs")]
     fn load<P: AsRef<Path>>(path: P) -> Result<Self, io::Error> {
-        let mut file = File::open(path)?;
-        let mut contents = String::new();
-        let _num_bytes_read = file.read_to_string(&mut contents)?;
+        let contents = if is_gzip(path.as_ref()) {
+            read_gzip(path.as_ref())?
+        } else {
+            let mut file = File::open(path)?;
+            let mut contents = String::new();
+            let _num_bytes_read = file.read_to_string(&mut contents)?;
+            contents
+        };
         Self::from_json(&contents).map_err(io::Error::other)
     }
 }
 
 // Automatic blanket implementation for any types satisfying the trait bounds.
 impl<T> Persistable for T where T: Serialize + DeserializeOwned + Sized {}
+
+// == Utility functions ==
+
+/// Check if a path refers to a gzip-compressed file.
+#[cfg(feature = "fs")]
+fn is_gzip(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("gz"))
+}
+
+/// Write gzip-compressed bytes to a file.
+#[cfg(feature = "gzip")]
+fn write_gzip(path: &Path, bytes: &[u8]) -> Result<(), io::Error> {
+    let mut encoder = flate2::write::GzEncoder::new(File::create(path)?, flate2::Compression::default());
+    encoder.write_all(bytes)?;
+    encoder.finish().map(drop)
+}
+
+/// Read and decompress a gzip-compressed UTF-8 file.
+#[cfg(feature = "gzip")]
+fn read_gzip(path: &Path) -> Result<String, io::Error> {
+    let mut contents = String::new();
+    let _num_bytes_read = flate2::read::GzDecoder::new(File::open(path)?).read_to_string(&mut contents)?;
+    Ok(contents)
+}
+
+/// Write gzip-compressed bytes to a file.
+#[cfg(all(feature = "fs", not(feature = "gzip")))]
+fn write_gzip(_path: &Path, _bytes: &[u8]) -> Result<(), io::Error> {
+    Err(gzip_unsupported())
+}
+
+/// Read and decompress a gzip-compressed UTF-8 file.
+#[cfg(all(feature = "fs", not(feature = "gzip")))]
+fn read_gzip(_path: &Path) -> Result<String, io::Error> {
+    Err(gzip_unsupported())
+}
+
+/// Error returned for compressed files when the `gzip` feature is disabled.
+#[cfg(all(feature = "fs", not(feature = "gzip")))]
+fn gzip_unsupported() -> io::Error {
+    io::Error::new(io::ErrorKind::Unsupported, "gzip-compressed files require the `gzip` feature")
+}
@@ -0,0 +1,16 @@
+//! Scalar trait.
+
+use nalgebra::RealField;
+use num_traits::ToPrimitive;
+
+/// Floating-point type over which geometry, `Scene`s and renders are generic, such as `f32` or `f64`.
+pub trait Scalar: RealField + Copy + ToPrimitive {
+    /// Check if the value is NaN, which is the only value unordered with itself.
+    #[must_use]
+    fn is_nan(self) -> bool {
+        self.partial_cmp(&self).is_none()
+    }
+}
+
+// Automatic blanket implementation for any types satisfying the trait bounds.
+impl<T: RealField + Copy + ToPrimitive> Scalar for T {}
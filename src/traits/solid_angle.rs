@@ -0,0 +1,50 @@
+//! Solid angle trait.
+
+use nalgebra::Point3;
+
+use crate::{
+    error::Result,
+    traits::{FallibleNumeric, Scalar},
+};
+
+/// Trait for geometry which can report the solid angle it subtends, as required by radiosity and daylighting analysis.
+pub trait SolidAngle<T: Scalar> {
+    /// Solid angle, in steradians, subtended by the geometry as seen from `point`.
+    /// Occlusion by other geometry is not considered.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if numeric conversion fails.
+    fn solid_angle(&self, point: &Point3<T>) -> Result<T>;
+}
+
+/// Solid angle subtended by a triangle given by its vertices.
+impl<T: Scalar> SolidAngle<T> for [Point3<T>; 3] {
+    fn solid_angle(&self, point: &Point3<T>) -> Result<T> {
+        Ok(signed_solid_angle(point, self)?.abs())
+    }
+}
+
+/// Solid angle subtended by a planar quad given by its vertices, in order around its boundary.
+impl<T: Scalar> SolidAngle<T> for [Point3<T>; 4] {
+    fn solid_angle(&self, point: &Point3<T>) -> Result<T> {
+        let [a, b, c, d] = *self;
+        Ok((signed_solid_angle(point, &[a, b, c])? + signed_solid_angle(point, &[a, c, d])?).abs())
+    }
+}
+
+// == Utility functions ==
+
+/// Signed solid angle subtended by a triangle, positive when its vertices wind anticlockwise as seen from `point`.
+/// Uses the formula of Van Oosterom and Strackee.
+///
+/// # Errors
+///
+/// Returns an error if numeric conversion fails.
+fn signed_solid_angle<T: Scalar>(point: &Point3<T>, vertices: &[Point3<T>; 3]) -> Result<T> {
+    let [a, b, c] = vertices.map(|vertex| vertex - point);
+    let (length_a, length_b, length_c) = (a.norm(), b.norm(), c.norm());
+    let numerator = a.dot(&b.cross(&c));
+    let denominator = length_a * length_b * length_c + a.dot(&b) * length_c + a.dot(&c) * length_b + b.dot(&c) * length_a;
+    Ok(T::try_from_u8(2)? * numerator.atan2(denominator))
+}
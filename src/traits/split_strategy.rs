@@ -0,0 +1,32 @@
+//! Bounding Volume Hierarchy split strategy trait.
+
+use crate::{
+    bvh::{BvhConfig, Split},
+    error::Result,
+    geometry::Aabb,
+    traits::Scalar,
+};
+
+/// Heuristic choosing the plane at which a `BvhBuilder` splits each node, such as `SahSplit` or `MedianSplit`.
+///
+/// The builder stops splitting nodes holding at most `max_shapes_per_node` shapes, or at `max_depth`,
+/// and also makes a leaf of any node whose chosen plane leaves one side empty.
+/// Closures taking the same arguments as `split` implement the trait.
+pub trait SplitStrategy<T: Scalar> {
+    /// Choose the plane splitting a node, or `None` to make it a leaf.
+    /// `bounds` encloses the node, and `shapes` holds the bounding boxes of its shapes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the split cannot be evaluated, which aborts the build.
+    fn split(&mut self, config: &BvhConfig<T>, bounds: &Aabb<T>, shapes: &[Aabb<T>]) -> Result<Option<Split<T>>>;
+}
+
+impl<T: Scalar, F> SplitStrategy<T> for F
+where
+    F: FnMut(&BvhConfig<T>, &Aabb<T>, &[Aabb<T>]) -> Result<Option<Split<T>>>,
+{
+    fn split(&mut self, config: &BvhConfig<T>, bounds: &Aabb<T>, shapes: &[Aabb<T>]) -> Result<Option<Split<T>>> {
+        self(config, bounds, shapes)
+    }
+}
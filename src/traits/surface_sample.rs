@@ -0,0 +1,74 @@
+//! Surface sampling trait.
+
+use nalgebra::{Point3, Unit, Vector3};
+use rand::Rng;
+
+use crate::{
+    error::Result,
+    traits::{FallibleNumeric, Scalar},
+};
+
+/// Trait for geometry whose surface can be sampled uniformly by area, as required by area lights and Monte Carlo integrators.
+pub trait SurfaceSample<T: Scalar> {
+    /// Total surface area of the geometry.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if numeric conversion fails.
+    fn area(&self) -> Result<T>;
+
+    /// Sample a point uniformly over the surface.
+    /// Returns the point, the outward geometric normal at that point, and the probability density with respect to area.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The surface has zero area
+    /// - Numeric conversion fails
+    fn sample_point<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<(Point3<T>, Unit<Vector3<T>>, T)>;
+}
+
+/// Draw a uniform random number in [0, 1).
+///
+/// # Errors
+///
+/// Returns an error if the number cannot be converted to the target type.
+pub fn sample_unit<T: Scalar, R: Rng + ?Sized>(rng: &mut R) -> Result<T> {
+    Ok(T::try_from_f64(rng.r#gen::<f64>())?)
+}
+
+/// Draw a direction uniformly over the unit sphere.
+///
+/// # Errors
+///
+/// Returns an error if numeric conversion fails.
+pub fn sample_sphere<T: Scalar, R: Rng + ?Sized>(rng: &mut R) -> Result<Unit<Vector3<T>>> {
+    let z = T::one() - T::try_from_u8(2)? * sample_unit::<T, R>(rng)?;
+    let phi = T::two_pi() * sample_unit::<T, R>(rng)?;
+    let r = (T::one() - z * z).max(T::zero()).sqrt();
+    Ok(Unit::new_normalize(Vector3::new(r * phi.cos(), r * phi.sin(), z)))
+}
+
+/// Draw a direction about a normal, with density proportional to the cosine of its angle to the normal.
+///
+/// # Errors
+///
+/// Returns an error if numeric conversion fails.
+pub fn sample_cosine_hemisphere<T: Scalar, R: Rng + ?Sized>(
+    normal: &Unit<Vector3<T>>,
+    rng: &mut R,
+) -> Result<Unit<Vector3<T>>> {
+    let radius = sample_unit::<T, R>(rng)?.sqrt();
+    let phi = T::two_pi() * sample_unit::<T, R>(rng)?;
+    let helper = if normal.x.abs() > T::try_from_f32(0.9)? {
+        Vector3::y()
+    } else {
+        Vector3::x()
+    };
+    let tangent = Unit::new_normalize(normal.cross(&helper));
+    let bitangent = normal.cross(&tangent);
+    let height = (T::one() - radius * radius).max(T::zero()).sqrt();
+    Ok(Unit::new_normalize(
+        tangent.scale(radius * phi.cos()) + bitangent * (radius * phi.sin()) + normal.scale(height),
+    ))
+}
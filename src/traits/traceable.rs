@@ -1,14 +1,15 @@
 //! Traceable trait.
 
-use nalgebra::RealField;
+use nalgebra::{Point3, Unit};
 
 use crate::{
-    error::Result,
+    error::{GeometryError, Result},
     rt::{Hit, Ray},
+    traits::Scalar,
 };
 
 /// Trait for types which can be tested for intersection by `Ray`s.
-pub trait Traceable<T: RealField + Copy> {
+pub trait Traceable<T: Scalar> {
     /// Test for an intersection between a `Ray` and this geometry.
     /// Returns the closest intersection if any, with the appropriate object index.
     ///
@@ -18,6 +19,28 @@ pub trait Traceable<T: RealField + Copy> {
     /// operations or invalid geometric configurations.
     fn intersect(&self, ray: &Ray<T>) -> Result<Option<Hit<T>>>;
 
+    /// Test for an intersection between a `Ray` and this geometry, returning only the distance to the closest hit.
+    /// Skips the normal interpolation and `Hit` construction of `intersect` when only the distance is needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the intersection calculation fails due to mathematical
+    /// operations or invalid geometric configurations.
+    fn intersect_t(&self, ray: &Ray<T>) -> Result<Option<T>> {
+        Ok(self.intersect(ray)?.map(|hit| hit.distance))
+    }
+
+    /// Test for an intersection between a `Ray` and this geometry nearer than `max_distance`.
+    /// Returns the closest intersection if it lies within the bound, letting hierarchies skip geometry beyond it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the intersection calculation fails due to mathematical
+    /// operations or invalid geometric configurations.
+    fn intersect_within(&self, ray: &Ray<T>, max_distance: T) -> Result<Option<Hit<T>>> {
+        Ok(self.intersect(ray)?.filter(|hit| hit.distance < max_distance))
+    }
+
     /// Test if a `Ray` intersects this geometry (shadow ray optimization).
     /// Returns true if there's any intersection within `max_distance`.
     ///
@@ -27,6 +50,37 @@ pub trait Traceable<T: RealField + Copy> {
     /// operations or invalid geometric configurations.
     fn intersect_any(&self, ray: &Ray<T>, max_distance: T) -> Result<bool> {
         // Default implementation: just check if there's a hit within range
-        (self.intersect(ray)?).map_or(Ok(false), |hit| Ok(hit.distance <= max_distance))
+        Ok(self.intersect_t(ray)?.is_some_and(|distance| distance <= max_distance))
+    }
+
+    /// Test for an intersection strictly between two points, such as for a visibility check or a cross-section probe.
+    /// Returns the intersection closest to `start` if any, with its distance measured from `start`.
+    /// A segment whose ends coincide, or are not finite, hits nothing.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the intersection calculation fails due to mathematical
+    /// operations or invalid geometric configurations.
+    fn intersect_segment(&self, start: Point3<T>, end: Point3<T>) -> Result<Option<Hit<T>>> {
+        let offset = end - start;
+        let length = offset.norm();
+        if length <= T::zero() || !length.is_finite() {
+            return Ok(None);
+        }
+        let ray = Ray::new(start, Unit::new_unchecked(offset / length));
+        Ok(self.intersect(&ray)?.filter(|hit| hit.distance < length))
+    }
+
+    /// Sweep a sphere of `radius` along a `Ray`, returning its first contact with this geometry if any.
+    /// The `Hit` distance is how far the sphere centre travels before touching, and its normals point from the
+    /// contact point towards the sphere centre. Contacts with geometry already overlapping the sphere at the `Ray`
+    /// origin are not reported.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the geometry does not support sphere casts, which is the default,
+    /// or if the calculation fails.
+    fn sphere_cast(&self, _ray: &Ray<T>, _radius: T) -> Result<Option<Hit<T>>> {
+        Err(GeometryError::UnsupportedSphereCast.into())
     }
 }
@@ -0,0 +1,46 @@
+//! Transformable geometry trait.
+
+use nalgebra::{Matrix3, Matrix4};
+
+use crate::{
+    error::{Result, TransformationError},
+    traits::Scalar,
+};
+
+/// Trait for geometry which can be mapped through an affine transformation matrix.
+pub trait Transformable<T: Scalar>: Sized {
+    /// Return a copy of the geometry with the transformation applied.
+    /// Positions are transformed as points and normals by the inverse transpose of the linear part.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transformation is not invertible,
+    /// or cannot be represented by this type of geometry.
+    fn transform(&self, transform: &Matrix4<T>) -> Result<Self>;
+}
+
+/// Compute the matrix which transforms normals under the given transformation (inverse transpose of the linear part).
+///
+/// # Errors
+///
+/// Returns an error if the transformation is not invertible.
+pub fn normal_matrix<T: Scalar>(transform: &Matrix4<T>) -> Result<Matrix3<T>> {
+    let inverse = transform.try_inverse().ok_or(TransformationError::NonInvertibleMatrix)?;
+    Ok(inverse.fixed_view::<3, 3>(0, 0).transpose())
+}
+
+/// Compute the scale factor of a transformation which preserves shape (rotation, translation and uniform scale only).
+///
+/// # Errors
+///
+/// Returns an error if the transformation scales non-uniformly, shears or collapses space.
+pub fn uniform_scale<T: Scalar>(transform: &Matrix4<T>) -> Result<T> {
+    let linear = transform.fixed_view::<3, 3>(0, 0);
+    let scale = linear.column(0).norm();
+    let tolerance = T::default_epsilon().sqrt() * scale.max(T::one());
+    let gram = linear.transpose() * linear;
+    if scale <= T::zero() || (gram - Matrix3::identity() * (scale * scale)).amax() > tolerance * scale {
+        return Err(TransformationError::NonUniformScale.into());
+    }
+    Ok(scale)
+}